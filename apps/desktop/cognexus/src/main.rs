@@ -3,13 +3,17 @@
 
 mod error;
 mod logger;
+mod progress;
 
 use crate::error::CognexusError;
 use crate::logger::initialize as LoggerInitialize;
+use crate::progress::TauriProgressSink;
 
+use cognexus_engine::ProgressSink;
 use cognexus_plugin_manager::{PluginManager, Registry};
 
 use std::fs::create_dir_all;
+use std::sync::Arc;
 
 use log::{error, info};
 use tauri::Manager;
@@ -75,6 +79,11 @@ fn main() {
             // Store registry in Tauri state for commands to access
             app.manage(registry);
 
+            // Shared by every run so the canvas can animate executing nodes
+            let progress: Arc<dyn ProgressSink> =
+                Arc::new(TauriProgressSink::new(app.handle().clone()));
+            app.manage(progress);
+
             Ok(())
         })
         .run(tauri::generate_context!())
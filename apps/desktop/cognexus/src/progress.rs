@@ -0,0 +1,58 @@
+use cognexus_engine::{ProgressEvent, ProgressEventKind, ProgressSink};
+
+use log::warn;
+use tauri::{AppHandle, Emitter};
+
+/// Tauri event the canvas listens on for live run progress.
+pub const EXECUTION_PROGRESS_EVENT: &str = "execution-progress";
+
+/// Forwards engine progress to the frontend as Tauri events.
+pub struct TauriProgressSink {
+    app: AppHandle,
+}
+
+impl TauriProgressSink {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl ProgressSink for TauriProgressSink {
+    fn on_progress(&self, event: &ProgressEvent) {
+        if let Err(e) = self.app.emit(EXECUTION_PROGRESS_EVENT, to_proto(event)) {
+            warn!("Failed to emit execution progress: {e}");
+        }
+    }
+}
+
+fn to_proto(event: &ProgressEvent) -> proto::ExecutionProgressEvent {
+    let (kind, message, success) = match event.kind() {
+        ProgressEventKind::RunStarted => (proto::ExecutionProgressKind::RunStarted, "", false),
+        ProgressEventKind::NodeQueued => (proto::ExecutionProgressKind::NodeQueued, "", false),
+        ProgressEventKind::NodeStarted => (proto::ExecutionProgressKind::NodeStarted, "", false),
+        ProgressEventKind::NodeFinished => (proto::ExecutionProgressKind::NodeFinished, "", false),
+        ProgressEventKind::NodeFailed { message } => (
+            proto::ExecutionProgressKind::NodeFailed,
+            message.as_str(),
+            false,
+        ),
+        ProgressEventKind::NodeSkipped => (proto::ExecutionProgressKind::NodeSkipped, "", false),
+        ProgressEventKind::NodeCancelled => {
+            (proto::ExecutionProgressKind::NodeCancelled, "", false)
+        }
+        ProgressEventKind::RunFinished { success } => {
+            (proto::ExecutionProgressKind::RunFinished, "", *success)
+        }
+    };
+
+    proto::ExecutionProgressEvent {
+        graph_id: event.graph_id().to_string(),
+        node_id: event.node_id().map(|id| id.to_string()).unwrap_or_default(),
+        kind: kind as i32,
+        message: String::from(message),
+        success,
+        completed: event.completed() as u32,
+        total: event.total() as u32,
+        percent_complete: event.percent_complete(),
+    }
+}
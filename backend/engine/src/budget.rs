@@ -0,0 +1,341 @@
+//! Run-level resource budgets, so a pathological graph can't consume the
+//! machine.
+
+use crate::trace::{InspectedValue, LogLevel, TraceEventKind};
+
+use cognexus_plugin_manager::{HostInputs, HostMode};
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+/// A resource a run can be budgeted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetResource {
+    WallClock,
+    /// WASM fuel, roughly proportional to instructions executed.
+    Fuel,
+    /// WASM linear memory.
+    Memory,
+}
+
+impl fmt::Display for BudgetResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetResource::WallClock => write!(f, "wall-clock"),
+            BudgetResource::Fuel => write!(f, "fuel"),
+            BudgetResource::Memory => write!(f, "memory"),
+        }
+    }
+}
+
+/// Limits on what a whole run may consume. Unset limits don't apply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunBudget {
+    wall_clock: Option<Duration>,
+    fuel: Option<u64>,
+    memory_bytes: Option<usize>,
+}
+
+impl RunBudget {
+    /// Stop the run once it has been going this long.
+    pub fn with_wall_clock(mut self, wall_clock: Duration) -> Self {
+        self.wall_clock = Some(wall_clock);
+        self
+    }
+
+    /// Stop the run once its nodes have consumed this much fuel in total.
+    pub fn with_fuel(mut self, fuel: u64) -> Self {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    /// Cap the linear memory the run's nodes hold at once. The budget is
+    /// split evenly across the nodes that may execute concurrently.
+    pub fn with_memory_bytes(mut self, memory_bytes: usize) -> Self {
+        self.memory_bytes = Some(memory_bytes);
+        self
+    }
+
+    pub fn wall_clock(&self) -> Option<Duration> {
+        self.wall_clock
+    }
+
+    pub fn fuel(&self) -> Option<u64> {
+        self.fuel
+    }
+
+    pub fn memory_bytes(&self) -> Option<usize> {
+        self.memory_bytes
+    }
+}
+
+/// Why a run was stopped for exceeding its budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    pub(crate) resource: BudgetResource,
+    pub(crate) node_id: Option<Uuid>,
+    pub(crate) message: String,
+}
+
+impl BudgetExceeded {
+    pub fn resource(&self) -> BudgetResource {
+        self.resource
+    }
+
+    /// The node that was executing when the budget ran out, if any.
+    pub fn node_id(&self) -> Option<Uuid> {
+        self.node_id
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Limits for one node execution attempt, from the node's timeout and what
+/// is left of the run's budget, and where the attempt reads clocks and
+/// randomness from when the run is recorded or replayed.
+///
+/// Executors that meter resources report back through `record_*`, which is
+/// how the engine keeps the run's totals and recordings; what a node logs is
+/// reported the same way and ends up in the run's trace.
+#[derive(Debug, Default)]
+pub struct ExecutionLimits {
+    timeout: Option<Duration>,
+    fuel: Option<u64>,
+    memory_bytes: Option<usize>,
+    host_mode: HostMode,
+    fuel_consumed: AtomicU64,
+    exhausted: Mutex<Option<BudgetResource>>,
+    host_inputs: Mutex<Option<HostInputs>>,
+    logs: Mutex<Vec<TraceEventKind>>,
+    deferral: Mutex<Option<Duration>>,
+}
+
+impl ExecutionLimits {
+    pub(crate) fn new(
+        timeout: Option<Duration>,
+        fuel: Option<u64>,
+        memory_bytes: Option<usize>,
+    ) -> Self {
+        Self {
+            timeout,
+            fuel,
+            memory_bytes,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn with_host_mode(mut self, host_mode: HostMode) -> Self {
+        self.host_mode = host_mode;
+        self
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn fuel(&self) -> Option<u64> {
+        self.fuel
+    }
+
+    pub fn memory_bytes(&self) -> Option<usize> {
+        self.memory_bytes
+    }
+
+    /// Whether to read host inputs live, record them, or replay them.
+    pub fn host_mode(&self) -> &HostMode {
+        &self.host_mode
+    }
+
+    /// Report what the attempt read from the host, when recording.
+    pub fn record_host_inputs(&self, host_inputs: HostInputs) {
+        *self
+            .host_inputs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(host_inputs);
+    }
+
+    /// Report a message the node logged during the attempt.
+    pub fn record_log(&self, level: LogLevel, message: String, value: Option<InspectedValue>) {
+        self.logs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(TraceEventKind::Logged {
+                level,
+                message,
+                value,
+            });
+    }
+
+    /// Report that the attempt's outputs are to be held back for `duration`
+    /// after it returns. The engine waits that out without holding up other
+    /// nodes, and counts it against the attempt's timeout.
+    pub fn record_deferral(&self, duration: Duration) {
+        *self.deferral.lock().unwrap_or_else(PoisonError::into_inner) = Some(duration);
+    }
+
+    /// Report fuel the attempt consumed.
+    pub fn record_fuel_consumed(&self, fuel: u64) {
+        self.fuel_consumed.fetch_add(fuel, Ordering::SeqCst);
+    }
+
+    /// Report that the attempt was stopped for running out of `resource`.
+    pub fn record_exhausted(&self, resource: BudgetResource) {
+        *self
+            .exhausted
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(resource);
+    }
+
+    pub(crate) fn fuel_consumed(&self) -> u64 {
+        self.fuel_consumed.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn exhausted(&self) -> Option<BudgetResource> {
+        *self
+            .exhausted
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn deferral(&self) -> Option<Duration> {
+        *self.deferral.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn take_host_inputs(&self) -> Option<HostInputs> {
+        self.host_inputs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    /// `Logged` trace events for what the node reported logging.
+    pub(crate) fn take_logs(&self) -> Vec<TraceEventKind> {
+        std::mem::take(&mut *self.logs.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Limits to hand an attempt run off the caller's thread, with the same
+    /// bounds but nothing reported yet.
+    pub(crate) fn detached(&self) -> Self {
+        Self::new(self.timeout, self.fuel, self.memory_bytes).with_host_mode(self.host_mode.clone())
+    }
+
+    /// Take over what an attempt reported to `detached` limits.
+    pub(crate) fn absorb(&self, detached: &ExecutionLimits) {
+        self.record_fuel_consumed(detached.fuel_consumed());
+        if let Some(resource) = detached.exhausted() {
+            self.record_exhausted(resource);
+        }
+        if let Some(host_inputs) = detached.take_host_inputs() {
+            self.record_host_inputs(host_inputs);
+        }
+        if let Some(deferral) = detached.deferral() {
+            self.record_deferral(deferral);
+        }
+        self.logs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend(detached.take_logs());
+    }
+}
+
+/// Tracks one run's consumption against its budget.
+pub(crate) struct BudgetTracker {
+    budget: RunBudget,
+    started: Instant,
+    fuel_consumed: AtomicU64,
+    exceeded: Mutex<Option<BudgetExceeded>>,
+}
+
+impl BudgetTracker {
+    pub(crate) fn new(budget: RunBudget) -> Self {
+        Self {
+            budget,
+            started: Instant::now(),
+            fuel_consumed: AtomicU64::new(0),
+            exceeded: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn remaining_time(&self) -> Option<Duration> {
+        self.budget
+            .wall_clock
+            .map(|limit| limit.saturating_sub(self.started.elapsed()))
+    }
+
+    pub(crate) fn remaining_fuel(&self) -> Option<u64> {
+        self.budget
+            .fuel
+            .map(|limit| limit.saturating_sub(self.fuel_consumed.load(Ordering::SeqCst)))
+    }
+
+    /// Memory one node may use when up to `max_parallelism` run at once.
+    pub(crate) fn memory_per_node(&self, max_parallelism: usize) -> Option<usize> {
+        self.budget
+            .memory_bytes
+            .map(|limit| limit / max_parallelism)
+    }
+
+    pub(crate) fn record_fuel(&self, fuel: u64) {
+        self.fuel_consumed.fetch_add(fuel, Ordering::SeqCst);
+    }
+
+    /// Mark the budget exceeded. The first resource to run out is the one
+    /// reported, so later calls return the original record.
+    pub(crate) fn exceed(&self, resource: BudgetResource, node_id: Option<Uuid>) -> BudgetExceeded {
+        let mut exceeded = self.exceeded.lock().unwrap_or_else(PoisonError::into_inner);
+
+        exceeded
+            .get_or_insert_with(|| {
+                let message = match resource {
+                    BudgetResource::WallClock => format!(
+                        "Run exceeded its wall-clock budget of {:?}",
+                        self.budget.wall_clock.unwrap_or_default()
+                    ),
+                    BudgetResource::Fuel => format!(
+                        "Run used up its fuel budget of {} unit(s)",
+                        self.budget.fuel.unwrap_or_default()
+                    ),
+                    BudgetResource::Memory => format!(
+                        "Run exceeded its memory budget of {} byte(s)",
+                        self.budget.memory_bytes.unwrap_or_default()
+                    ),
+                };
+
+                BudgetExceeded {
+                    resource,
+                    node_id,
+                    message,
+                }
+            })
+            .clone()
+    }
+
+    /// The exceeded budget, checking the wall clock and fuel totals first.
+    pub(crate) fn check(&self) -> Option<BudgetExceeded> {
+        if let Some(exceeded) = self.exceeded() {
+            return Some(exceeded);
+        }
+
+        if self.remaining_time() == Some(Duration::ZERO) {
+            return Some(self.exceed(BudgetResource::WallClock, None));
+        }
+        if self.remaining_fuel() == Some(0) {
+            return Some(self.exceed(BudgetResource::Fuel, None));
+        }
+
+        None
+    }
+
+    pub(crate) fn exceeded(&self) -> Option<BudgetExceeded> {
+        self.exceeded
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
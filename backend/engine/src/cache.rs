@@ -0,0 +1,333 @@
+//! Node output caching for incremental re-runs.
+
+use cognexus_model::graph::{Node, PortValues};
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use uuid::Uuid;
+
+/// Identifies one node execution by what determines its outputs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    definition_id: Uuid,
+    config_hash: u64,
+    /// (input port id, hash of its value), sorted by port id.
+    input_hashes: Vec<(Uuid, u64)>,
+}
+
+impl CacheKey {
+    pub(crate) fn new(node: &Node, inputs: &PortValues) -> Self {
+        let mut input_hashes: Vec<(Uuid, u64)> = inputs
+            .iter()
+            .map(|(port_id, bytes)| (*port_id, hash_bytes(bytes)))
+            .collect();
+        input_hashes.sort_unstable();
+
+        Self {
+            definition_id: node.definition_id(),
+            config_hash: config_hash(node),
+            input_hashes,
+        }
+    }
+}
+
+/// Instances of a definition with the same configuration hash the same.
+fn config_hash(node: &Node) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    node.config().hash(&mut hasher);
+    hasher.finish()
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hit and miss counts since the cache was created or last cleared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub size_bytes: usize,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    outputs: PortValues,
+    size_bytes: usize,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, CacheEntry>,
+    size_bytes: usize,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl CacheState {
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.size_bytes -= entry.size_bytes;
+        }
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone());
+
+        if let Some(key) = oldest {
+            self.remove(&key);
+        }
+    }
+}
+
+/// Node outputs from earlier runs, keyed by definition id, configuration, and
+/// input values.
+///
+/// Because keys depend only on what a node receives, re-running a graph after
+/// editing downstream nodes reuses every unchanged upstream result. The cache
+/// is bounded by total output size and evicts least-recently-used entries.
+/// Share one cache between engines (or runs) with an `Arc`.
+///
+/// Only deterministic nodes are cached, ones whose definition produces the
+/// same outputs from the same configuration and inputs. The engine always
+/// runs the others (`NodeDefinitionInfo::is_deterministic`).
+#[derive(Debug)]
+pub struct OutputCache {
+    max_bytes: usize,
+    state: Mutex<CacheState>,
+}
+
+impl OutputCache {
+    /// Create a cache holding at most `max_bytes` of serialized outputs.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            entries: state.entries.len(),
+            size_bytes: state.size_bytes,
+        }
+    }
+
+    /// Drop every cached output and reset the statistics.
+    pub fn clear(&self) {
+        *self.lock() = CacheState::default();
+    }
+
+    /// Drop every cached output of one node definition, e.g. after its
+    /// plugin was rebuilt.
+    pub fn invalidate_definition(&self, definition_id: Uuid) {
+        let mut state = self.lock();
+        let keys: Vec<CacheKey> = state
+            .entries
+            .keys()
+            .filter(|key| key.definition_id == definition_id)
+            .cloned()
+            .collect();
+
+        for key in &keys {
+            state.remove(key);
+        }
+    }
+
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<PortValues> {
+        let mut state = self.lock();
+        state.tick += 1;
+        let tick = state.tick;
+
+        match state.entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = tick;
+                let outputs = entry.outputs.clone();
+                state.hits += 1;
+                Some(outputs)
+            }
+            None => {
+                state.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn insert(&self, key: CacheKey, outputs: &PortValues) {
+        let size_bytes = outputs.iter().map(|(_, bytes)| bytes.len()).sum();
+        if size_bytes > self.max_bytes {
+            return;
+        }
+
+        let mut state = self.lock();
+        state.remove(&key);
+        while state.size_bytes + size_bytes > self.max_bytes {
+            state.evict_least_recently_used();
+        }
+
+        state.tick += 1;
+        let last_used = state.tick;
+        state.size_bytes += size_bytes;
+        state.entries.insert(
+            key,
+            CacheEntry {
+                outputs: outputs.clone(),
+                size_bytes,
+                last_used,
+            },
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        // Entry and size updates happen together under the lock without
+        // panicking in between, so a poisoned lock still holds valid state.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cognexus_model::graph::{NodeBuilder, NodeConfig};
+
+    fn node(definition_id: Uuid, config: NodeConfig) -> Node {
+        NodeBuilder::default()
+            .with_name("node")
+            .with_definition_id(definition_id)
+            .with_config(config)
+            .build()
+            .unwrap()
+    }
+
+    fn values(entries: &[(Uuid, &[u8])]) -> PortValues {
+        let mut values = PortValues::new();
+        for (port_id, bytes) in entries {
+            values.insert(*port_id, bytes.to_vec());
+        }
+        values
+    }
+
+    #[test]
+    fn keys_depend_only_on_definition_config_and_inputs() {
+        let definition_id = Uuid::new_v4();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut config = NodeConfig::new();
+        config.insert("value", vec![1]);
+
+        // Other instances with the same configuration share entries
+        let key = CacheKey::new(
+            &node(definition_id, config.clone()),
+            &values(&[(a, b"1"), (b, b"2")]),
+        );
+        let same = CacheKey::new(
+            &node(definition_id, config.clone()),
+            &values(&[(b, b"2"), (a, b"1")]),
+        );
+        assert_eq!(key, same);
+
+        let other_input = CacheKey::new(
+            &node(definition_id, config.clone()),
+            &values(&[(a, b"1"), (b, b"3")]),
+        );
+        assert_ne!(key, other_input);
+
+        let other_definition = CacheKey::new(
+            &node(Uuid::new_v4(), config.clone()),
+            &values(&[(a, b"1"), (b, b"2")]),
+        );
+        assert_ne!(key, other_definition);
+
+        config.insert("value", vec![2]);
+        let other_config = CacheKey::new(
+            &node(definition_id, config),
+            &values(&[(a, b"1"), (b, b"2")]),
+        );
+        assert_ne!(key, other_config);
+    }
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let cache = OutputCache::new(1024);
+        let key = CacheKey::new(&node(Uuid::new_v4(), NodeConfig::new()), &PortValues::new());
+        let outputs = values(&[(Uuid::new_v4(), b"out")]);
+
+        assert!(cache.get(&key).is_none());
+        cache.insert(key.clone(), &outputs);
+        assert_eq!(cache.get(&key).unwrap().iter().next().unwrap().1, b"out");
+
+        let stats = cache.stats();
+        assert_eq!(
+            (stats.hits, stats.misses, stats.entries, stats.size_bytes),
+            (1, 1, 1, 3)
+        );
+
+        cache.clear();
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+
+    #[test]
+    fn invalidates_one_definition() {
+        let cache = OutputCache::new(1024);
+        let (kept, dropped) = (Uuid::new_v4(), Uuid::new_v4());
+        let outputs = values(&[(Uuid::new_v4(), b"out")]);
+        let kept_key = CacheKey::new(&node(kept, NodeConfig::new()), &PortValues::new());
+        let dropped_key = CacheKey::new(&node(dropped, NodeConfig::new()), &PortValues::new());
+        cache.insert(kept_key.clone(), &outputs);
+        cache.insert(dropped_key.clone(), &outputs);
+
+        cache.invalidate_definition(dropped);
+
+        assert!(cache.get(&kept_key).is_some());
+        assert!(cache.get(&dropped_key).is_none());
+        assert_eq!(cache.stats().size_bytes, 3);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_by_size() {
+        let cache = OutputCache::new(8);
+        let port_id = Uuid::new_v4();
+        let key = |input: &[u8]| {
+            CacheKey::new(
+                &node(Uuid::nil(), NodeConfig::new()),
+                &values(&[(port_id, input)]),
+            )
+        };
+        let outputs = values(&[(port_id, b"four")]);
+
+        cache.insert(key(b"a"), &outputs);
+        cache.insert(key(b"b"), &outputs);
+        // Using "a" leaves "b" the least recently used
+        assert!(cache.get(&key(b"a")).is_some());
+        cache.insert(key(b"c"), &outputs);
+
+        assert!(cache.get(&key(b"a")).is_some());
+        assert!(cache.get(&key(b"b")).is_none());
+        assert!(cache.get(&key(b"c")).is_some());
+        assert_eq!(cache.stats().size_bytes, 8);
+
+        // Outputs larger than the whole cache aren't kept
+        cache.insert(key(b"d"), &values(&[(port_id, b"too large")]));
+        assert!(cache.get(&key(b"d")).is_none());
+        assert_eq!(cache.stats().entries, 2);
+    }
+}
@@ -0,0 +1,114 @@
+//! Cooperative cancellation of a running graph.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::Instant;
+
+type CancelCallback = Box<dyn Fn() + Send + Sync>;
+
+#[derive(Default)]
+struct Shared {
+    cancelled: AtomicBool,
+    callbacks: Mutex<Vec<CancelCallback>>,
+    /// Wakes waits on the token when it is cancelled.
+    cancelled_signal: Condvar,
+}
+
+/// A cloneable flag used to stop a run.
+///
+/// The engine checks the token before every stage and every node; nodes that
+/// had not started when `cancel` was called are reported as cancelled in the
+/// run's result. Work already inside an executor is only interrupted if the
+/// executor cooperates, typically by registering an `on_cancel` callback that
+/// trips its own interruption mechanism (e.g. bumping a wasmtime epoch).
+///
+/// Cancellation is permanent: every later run on an engine holding a
+/// cancelled token is cancelled immediately.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    shared: Arc<Shared>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. Callbacks run once, on the calling thread.
+    pub fn cancel(&self) {
+        if self.shared.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let callbacks = self
+            .shared
+            .callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for callback in callbacks.iter() {
+            callback();
+        }
+        self.shared.cancelled_signal.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.shared.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Block until `deadline`, returning early if the token is cancelled.
+    /// True if the wait was cut short.
+    pub(crate) fn wait_until(&self, deadline: Instant) -> bool {
+        let mut callbacks = self
+            .shared
+            .callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        loop {
+            if self.is_cancelled() {
+                return true;
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            callbacks = self
+                .shared
+                .cancelled_signal
+                .wait_timeout(callbacks, deadline - now)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Run `callback` when the token is cancelled, or immediately if it
+    /// already has been.
+    pub fn on_cancel<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let mut callbacks = self
+            .shared
+            .callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        // Checked under the lock so a concurrent `cancel` can't miss it
+        if self.is_cancelled() {
+            drop(callbacks);
+            callback();
+        } else {
+            callbacks.push(Box::new(callback));
+        }
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,316 @@
+//! Storage for the values flowing through a run.
+
+use crate::error::EngineError;
+
+use cognexus_model::error::ModelError;
+use cognexus_model::graph::{DataTypeRegistry, Edge, Node, Port, PortValues};
+
+use common::error::error_location::ErrorLocation;
+
+use std::collections::HashMap;
+use std::panic::Location;
+
+use uuid::Uuid;
+
+/// Values produced during a run, keyed by (node id, output port id).
+///
+/// The context is the single owner of in-flight data. Executors never see it
+/// directly: each node receives a `PortValues` scoped to its own input ports,
+/// and hands back a `PortValues` for its output ports.
+#[derive(Debug, Default)]
+pub struct ExecutionContext {
+    values: HashMap<(Uuid, Uuid), Vec<u8>>,
+    /// Values supplied from outside the graph, keyed by (node id, input
+    /// port id), e.g. the current item of a loop body.
+    seeded: HashMap<(Uuid, Uuid), Vec<u8>>,
+}
+
+impl ExecutionContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Raw serialized value a node produced on one of its output ports.
+    pub fn value(&self, node_id: Uuid, port_id: Uuid) -> Option<&[u8]> {
+        self.values.get(&(node_id, port_id)).map(Vec::as_slice)
+    }
+
+    /// Deserialize the value a node produced on `port` through the port's
+    /// type in `types`, and downcast it to `V`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ModelError` if the node produced no value on the
+    /// port, the port's type isn't registered with `register_value_type`, or
+    /// the value isn't a well-formed `V`.
+    #[track_caller]
+    pub fn read<V>(
+        &self,
+        node_id: Uuid,
+        port: &Port,
+        types: &DataTypeRegistry,
+    ) -> Result<V, EngineError>
+    where
+        V: 'static,
+    {
+        let data_type = types.get_value_type(&port.data_type_id())?;
+        let bytes = self
+            .value(node_id, port.id())
+            .ok_or_else(|| value_error(port, "No value produced"))?;
+
+        let value = data_type.deserialize_value(bytes).map_err(|e| {
+            value_error(
+                port,
+                &format!("Failed to deserialize {}: {e}", data_type.name()),
+            )
+        })?;
+
+        value.downcast::<V>().map(|boxed| *boxed).map_err(|_| {
+            value_error(
+                port,
+                &format!(
+                    "{} value is not a {}",
+                    data_type.name(),
+                    std::any::type_name::<V>()
+                ),
+            )
+        })
+    }
+
+    /// Serialize `value` through the type of `port` in `types` and store it as
+    /// what the node produced on the port.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ModelError` if the port's type isn't registered
+    /// with `register_value_type` or rejects the value.
+    #[track_caller]
+    pub fn write<V>(
+        &mut self,
+        node_id: Uuid,
+        port: &Port,
+        types: &DataTypeRegistry,
+        value: V,
+    ) -> Result<(), EngineError>
+    where
+        V: 'static,
+    {
+        let data_type = types.get_value_type(&port.data_type_id())?;
+        let bytes = data_type.serialize_value(Box::new(value)).map_err(|e| {
+            value_error(
+                port,
+                &format!("Failed to serialize {}: {e}", data_type.name()),
+            )
+        })?;
+
+        self.values.insert((node_id, port.id()), bytes);
+        Ok(())
+    }
+
+    /// Store already serialized `bytes` as what the node produced on `port`,
+    /// once the port's type in `types` accepts them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ModelError` if the port's type isn't registered
+    /// with `register_value_type` or the bytes aren't a value of it.
+    #[track_caller]
+    pub fn write_bytes(
+        &mut self,
+        node_id: Uuid,
+        port: &Port,
+        types: &DataTypeRegistry,
+        bytes: Vec<u8>,
+    ) -> Result<(), EngineError> {
+        let data_type = types.get_value_type(&port.data_type_id())?;
+        data_type
+            .validate_value(&bytes)
+            .map_err(|e| value_error(port, &format!("Invalid {} value: {e}", data_type.name())))?;
+
+        self.values.insert((node_id, port.id()), bytes);
+        Ok(())
+    }
+
+    /// Every value a node produced, keyed by output port id.
+    ///
+    /// Use `read` for typed access.
+    pub fn outputs_of(&self, node_id: Uuid) -> PortValues {
+        self.values
+            .iter()
+            .filter(|((owner, _), _)| *owner == node_id)
+            .map(|((_, port_id), bytes)| (*port_id, bytes.clone()))
+            .collect()
+    }
+
+    /// Record the outputs a node produced.
+    pub(crate) fn store_outputs(&mut self, node_id: Uuid, outputs: &PortValues) {
+        for (port_id, bytes) in outputs.iter() {
+            self.values.insert((node_id, *port_id), bytes.to_vec());
+        }
+    }
+
+    /// Feed `bytes` to an input port that has no incoming edge.
+    pub(crate) fn seed_input(&mut self, node_id: Uuid, port_id: Uuid, bytes: Vec<u8>) {
+        self.seeded.insert((node_id, port_id), bytes);
+    }
+
+    /// Build the input view for `node` by following its incoming edges back
+    /// to the values upstream nodes produced.
+    #[track_caller]
+    pub(crate) fn inputs_for(
+        &self,
+        node: &Node,
+        incoming: &[&Edge],
+    ) -> Result<PortValues, EngineError> {
+        let mut inputs: PortValues = self
+            .seeded
+            .iter()
+            .filter(|((owner, _), _)| *owner == node.id())
+            .map(|((_, port_id), bytes)| (*port_id, bytes.clone()))
+            .collect();
+
+        for edge in incoming {
+            let value = self
+                .value(edge.source_node_id(), edge.source_port_id())
+                .ok_or_else(|| EngineError::ExecutionError {
+                    message: format!(
+                        "No value for input port {}: node {} did not produce output port {}",
+                        edge.target_port_id(),
+                        edge.source_node_id(),
+                        edge.source_port_id()
+                    ),
+                    node_id: node.id(),
+                    location: ErrorLocation::from(Location::caller()),
+                    source: None,
+                })?;
+
+            inputs.insert(edge.target_port_id(), value.to_vec());
+        }
+
+        Ok(inputs)
+    }
+}
+
+#[track_caller]
+fn value_error(port: &Port, message: &str) -> EngineError {
+    EngineError::from_model(ModelError::ValueError {
+        message: String::from(message),
+        port_id: port.id(),
+        data_type_id: port.data_type_id(),
+        location: ErrorLocation::from(Location::caller()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cognexus_model::graph::{DataType, DataTypeInfo, PortBuilder};
+
+    use std::any::Any;
+    use std::io;
+
+    use semver::Version;
+
+    const NUMBER_ID: &str = "0b6f3c2e-8d41-4a7e-9c55-2f1d6e8a4b93";
+
+    /// Little-endian `f64`s.
+    struct Number;
+
+    impl DataTypeInfo for Number {
+        fn type_id(&self) -> Uuid {
+            Uuid::parse_str(NUMBER_ID).unwrap()
+        }
+
+        fn name(&self) -> &str {
+            "Number"
+        }
+
+        fn description(&self) -> &str {
+            "A number"
+        }
+
+        fn model_version(&self) -> Version {
+            Version::new(0, 1, 0)
+        }
+    }
+
+    impl DataType for Number {
+        type Error = io::Error;
+
+        fn serialize(&self, value: Box<dyn Any>) -> Result<Vec<u8>, io::Error> {
+            let value = value
+                .downcast::<f64>()
+                .map_err(|_| io::Error::other("not an f64"))?;
+            Ok(value.to_le_bytes().to_vec())
+        }
+
+        fn deserialize(&self, bytes: &[u8]) -> Result<Box<dyn Any>, io::Error> {
+            let bytes: [u8; 8] = bytes
+                .try_into()
+                .map_err(|_| io::Error::other("not 8 bytes"))?;
+            Ok(Box::new(f64::from_le_bytes(bytes)))
+        }
+    }
+
+    fn number_port() -> (DataTypeRegistry, Port) {
+        let mut types = DataTypeRegistry::new();
+        types.register_value_type(Number).unwrap();
+        let port = PortBuilder::default()
+            .with_name("value")
+            .with_data_type_id(Uuid::parse_str(NUMBER_ID).unwrap())
+            .build()
+            .unwrap();
+        (types, port)
+    }
+
+    #[test]
+    fn values_round_trip_through_the_port_type() {
+        let (types, port) = number_port();
+        let node_id = Uuid::new_v4();
+        let mut context = ExecutionContext::new();
+
+        context.write(node_id, &port, &types, 1.5_f64).unwrap();
+
+        assert_eq!(context.read::<f64>(node_id, &port, &types).unwrap(), 1.5);
+        assert_eq!(
+            context.value(node_id, port.id()),
+            Some(&1.5_f64.to_le_bytes()[..])
+        );
+    }
+
+    #[test]
+    fn writes_of_the_wrong_type_are_rejected() {
+        let (types, port) = number_port();
+        let node_id = Uuid::new_v4();
+        let mut context = ExecutionContext::new();
+
+        assert!(context.write(node_id, &port, &types, "1.5").is_err());
+        assert!(
+            context
+                .write_bytes(node_id, &port, &types, vec![1, 2, 3])
+                .is_err()
+        );
+        assert!(context.value(node_id, port.id()).is_none());
+        assert!(context.read::<f64>(node_id, &port, &types).is_err());
+    }
+
+    #[test]
+    fn ports_of_types_the_host_cannot_handle_are_rejected() {
+        let mut types = DataTypeRegistry::new();
+        types.register(Number).unwrap();
+        let port = PortBuilder::default()
+            .with_name("value")
+            .with_data_type_id(Uuid::parse_str(NUMBER_ID).unwrap())
+            .build()
+            .unwrap();
+
+        let mut context = ExecutionContext::new();
+
+        assert!(
+            context
+                .write(Uuid::new_v4(), &port, &types, 1.5_f64)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Attempts bounded by their timeout, for executors that can't stop their
+//! own work.
+
+use crate::budget::ExecutionLimits;
+use crate::error::DeadlineError;
+use crate::executor::NodeExecutor;
+
+use cognexus_model::graph::{ErrorClass, Node, PortValues};
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+
+use uuid::Uuid;
+
+/// Runs each attempt that has a timeout on its own thread and stops waiting
+/// for it at the deadline, so a node that hangs in native code can't hold
+/// up the run.
+///
+/// An abandoned attempt can't be stopped from outside: it keeps its thread
+/// until it returns, and whatever it returns is discarded. Attempts without
+/// a timeout run on the caller's thread.
+pub struct DeadlineExecutor<E> {
+    inner: Arc<E>,
+}
+
+impl<E> DeadlineExecutor<E> {
+    pub fn new(inner: E) -> Self {
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+}
+
+impl<E> NodeExecutor for DeadlineExecutor<E>
+where
+    E: NodeExecutor + Send + Sync + 'static,
+{
+    type Error = DeadlineError<E::Error>;
+
+    fn execute(&self, node: &Node, inputs: &PortValues) -> Result<PortValues, Self::Error> {
+        self.inner
+            .execute(node, inputs)
+            .map_err(DeadlineError::NodeError)
+    }
+
+    fn execute_with_limits(
+        &self,
+        node: &Node,
+        inputs: &PortValues,
+        limits: &ExecutionLimits,
+    ) -> Result<PortValues, Self::Error> {
+        let Some(timeout) = limits.timeout() else {
+            return self
+                .inner
+                .execute_with_limits(node, inputs, limits)
+                .map_err(DeadlineError::NodeError);
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let attempt_limits = Arc::new(limits.detached());
+        {
+            let inner = self.inner.clone();
+            let node = node.clone();
+            let inputs = inputs.clone();
+            let attempt_limits = attempt_limits.clone();
+            thread::spawn(move || {
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                    inner.execute_with_limits(&node, &inputs, &attempt_limits)
+                }));
+                // The caller has gone if the deadline passed
+                let _ = sender.send(outcome);
+            });
+        }
+
+        match receiver.recv_timeout(timeout) {
+            Ok(Ok(outcome)) => {
+                limits.absorb(&attempt_limits);
+                outcome.map_err(DeadlineError::NodeError)
+            }
+            Ok(Err(panic)) => panic::resume_unwind(panic),
+            Err(_) => Err(DeadlineError::TimeoutError {
+                message: format!("Node '{}' did not return within {timeout:?}", node.name()),
+                timeout,
+                location: ErrorLocation::from(Location::caller()),
+            }),
+        }
+    }
+
+    fn classify_error(&self, error: &Self::Error) -> ErrorClass {
+        match error {
+            DeadlineError::NodeError(error) => self.inner.classify_error(error),
+            DeadlineError::TimeoutError { .. } => ErrorClass::Timeout,
+        }
+    }
+
+    fn validate_value(&self, type_id: Uuid, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.inner
+            .validate_value(type_id, bytes)
+            .map_err(DeadlineError::NodeError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::engine_builder::ExecutionEngineBuilder;
+    use crate::error::EngineError;
+    use crate::result::NodeStatus;
+
+    use cognexus_model::error::ModelError;
+    use cognexus_model::graph::{GraphBuilder, NodeDefinitionInfo, NodeDefinitionRegistry, Port};
+
+    use std::io;
+    use std::time::{Duration, Instant};
+
+    use semver::Version;
+
+    const SLEEP_ID: &str = "6a0f7a52-3e0e-4d0a-9f56-5d8f3c1b2a71";
+
+    /// A definition without ports.
+    struct Sleep;
+
+    impl NodeDefinitionInfo for Sleep {
+        fn definition_id(&self) -> Uuid {
+            Uuid::parse_str(SLEEP_ID).unwrap()
+        }
+
+        fn name(&self) -> &str {
+            "Sleep"
+        }
+
+        fn description(&self) -> &str {
+            "Blocks its thread"
+        }
+
+        fn model_version(&self) -> Version {
+            Version::new(0, 1, 0)
+        }
+
+        fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+            Ok(Vec::new())
+        }
+
+        fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Blocks the calling thread, as native code that can't be interrupted.
+    struct SleepingExecutor(Duration);
+
+    impl NodeExecutor for SleepingExecutor {
+        type Error = io::Error;
+
+        fn execute(&self, _node: &Node, _inputs: &PortValues) -> Result<PortValues, io::Error> {
+            thread::sleep(self.0);
+            Ok(PortValues::new())
+        }
+    }
+
+    #[test]
+    fn run_returns_at_the_node_timeout() {
+        let mut definitions = NodeDefinitionRegistry::new();
+        definitions.register(Sleep).unwrap();
+        let mut graph = GraphBuilder::default().with_name("graph").build().unwrap();
+        let node_id = graph
+            .add_node(&definitions, None, "sleep", Sleep.definition_id())
+            .unwrap();
+
+        let engine = ExecutionEngineBuilder::default()
+            .with_node_timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let executor = DeadlineExecutor::new(SleepingExecutor(Duration::from_secs(10)));
+
+        let started = Instant::now();
+        let result = engine.run(&graph, &definitions, &executor).unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let node = result.node_result(node_id).unwrap();
+        assert_eq!(node.status(), NodeStatus::Failed);
+        assert!(matches!(
+            node.error(),
+            Some(EngineError::TimeoutError { node_id: id, .. }) if *id == node_id
+        ));
+    }
+
+    #[test]
+    fn attempts_within_the_timeout_return_their_outputs() {
+        let executor = DeadlineExecutor::new(SleepingExecutor(Duration::ZERO));
+        let mut definitions = NodeDefinitionRegistry::new();
+        definitions.register(Sleep).unwrap();
+        let mut graph = GraphBuilder::default().with_name("graph").build().unwrap();
+        graph
+            .add_node(&definitions, None, "sleep", Sleep.definition_id())
+            .unwrap();
+        let node = &graph.nodes()[0];
+        let limits = ExecutionLimits::new(Some(Duration::from_secs(5)), None, None);
+
+        let outputs = executor.execute_with_limits(node, &PortValues::new(), &limits);
+
+        assert_eq!(outputs.unwrap(), PortValues::new());
+    }
+}
@@ -0,0 +1,219 @@
+//! Breakpoints and step execution for interactive debugging of a run.
+
+use cognexus_model::graph::{Node, PortValues};
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+/// Where around a node execution pauses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PausePosition {
+    /// After the node's inputs are gathered, before its executor runs.
+    Before,
+    /// After the node's executor returned valid outputs.
+    After,
+}
+
+/// When a breakpoint on a node should trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    Before,
+    After,
+    Both,
+}
+
+impl Breakpoint {
+    fn triggers_at(self, position: PausePosition) -> bool {
+        matches!(
+            (self, position),
+            (Breakpoint::Both, _)
+                | (Breakpoint::Before, PausePosition::Before)
+                | (Breakpoint::After, PausePosition::After)
+        )
+    }
+}
+
+/// Snapshot of the node a run is currently paused at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PauseInfo {
+    pub(crate) node_id: Uuid,
+    pub(crate) node_name: String,
+    pub(crate) position: PausePosition,
+    pub(crate) values: PortValues,
+    sequence: u64,
+}
+
+impl PauseInfo {
+    pub fn node_id(&self) -> Uuid {
+        self.node_id
+    }
+
+    pub fn node_name(&self) -> &str {
+        &self.node_name
+    }
+
+    pub fn position(&self) -> PausePosition {
+        self.position
+    }
+
+    /// The node's inputs when paused before it, or its outputs when paused
+    /// after it, keyed by port id.
+    pub fn values(&self) -> &PortValues {
+        &self.values
+    }
+}
+
+#[derive(Debug, Default)]
+struct DebugState {
+    breakpoints: HashMap<Uuid, Breakpoint>,
+    pause_requested: bool,
+    paused: Option<PauseInfo>,
+    pauses: u64,
+    detached: bool,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    state: Mutex<DebugState>,
+    changed: Condvar,
+}
+
+/// Debug controls for a running engine.
+///
+/// A `Debugger` is a cheap, cloneable handle: give one clone to the engine
+/// through `ExecutionEngineBuilder::with_debugger` and keep another on the
+/// controlling side (e.g. the desktop app's command handlers). The engine
+/// checks in before and after every node; when a breakpoint or a pending
+/// step matches, the worker executing that node blocks until `resume` or
+/// `step` is called.
+///
+/// Only one node is paused at a time. In a parallel stage, other workers
+/// reaching a checkpoint wait until the current pause is released.
+#[derive(Debug, Clone, Default)]
+pub struct Debugger {
+    shared: Arc<Shared>,
+}
+
+impl Debugger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set (or replace) the breakpoint on a node.
+    pub fn set_breakpoint(&self, node_id: Uuid, breakpoint: Breakpoint) {
+        self.lock().breakpoints.insert(node_id, breakpoint);
+    }
+
+    /// Remove the breakpoint on a node. Returns the breakpoint if one was set.
+    pub fn clear_breakpoint(&self, node_id: Uuid) -> Option<Breakpoint> {
+        self.lock().breakpoints.remove(&node_id)
+    }
+
+    pub fn clear_breakpoints(&self) {
+        self.lock().breakpoints.clear();
+    }
+
+    pub fn breakpoints(&self) -> HashMap<Uuid, Breakpoint> {
+        self.lock().breakpoints.clone()
+    }
+
+    /// Pause at the next node checkpoint, whether or not it has a breakpoint.
+    pub fn pause(&self) {
+        self.lock().pause_requested = true;
+    }
+
+    /// Release the current pause and run until the next breakpoint.
+    pub fn resume(&self) {
+        let mut state = self.lock();
+        state.pause_requested = false;
+        state.paused = None;
+        self.shared.changed.notify_all();
+    }
+
+    /// Release the current pause and pause again at the next checkpoint.
+    pub fn step(&self) {
+        let mut state = self.lock();
+        state.pause_requested = true;
+        state.paused = None;
+        self.shared.changed.notify_all();
+    }
+
+    /// Release the current pause and ignore every checkpoint from now on.
+    /// The engine detaches its debugger when the run is cancelled.
+    pub fn detach(&self) {
+        let mut state = self.lock();
+        state.detached = true;
+        state.pause_requested = false;
+        state.paused = None;
+        self.shared.changed.notify_all();
+    }
+
+    /// The node the run is currently paused at, if any.
+    pub fn paused_at(&self) -> Option<PauseInfo> {
+        self.lock().paused.clone()
+    }
+
+    /// Block until the run pauses or `timeout` elapses.
+    pub fn wait_for_pause(&self, timeout: Duration) -> Option<PauseInfo> {
+        let (state, _) = self
+            .shared
+            .changed
+            .wait_timeout_while(self.lock(), timeout, |state| state.paused.is_none())
+            .unwrap_or_else(PoisonError::into_inner);
+        state.paused.clone()
+    }
+
+    /// Called by the engine at a node boundary. Blocks while paused here.
+    pub(crate) fn checkpoint(&self, node: &Node, position: PausePosition, values: &PortValues) {
+        // Let any pause held by another worker finish first
+        let mut state = self
+            .shared
+            .changed
+            .wait_while(self.lock(), |state| state.paused.is_some())
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let breakpoint_hit = state
+            .breakpoints
+            .get(&node.id())
+            .is_some_and(|bp| bp.triggers_at(position));
+
+        if state.detached || (!breakpoint_hit && !state.pause_requested) {
+            return;
+        }
+
+        state.pause_requested = false;
+        state.pauses += 1;
+        let sequence = state.pauses;
+        state.paused = Some(PauseInfo {
+            node_id: node.id(),
+            node_name: String::from(node.name()),
+            position,
+            values: values.clone(),
+            sequence,
+        });
+        self.shared.changed.notify_all();
+
+        let _released = self
+            .shared
+            .changed
+            .wait_while(state, |state| {
+                state
+                    .paused
+                    .as_ref()
+                    .is_some_and(|paused| paused.sequence == sequence)
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, DebugState> {
+        // Every update is a plain field assignment, so a poisoned lock still
+        // holds consistent state.
+        self.shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
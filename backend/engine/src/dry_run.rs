@@ -0,0 +1,259 @@
+//! Pre-flight checks: everything a run would verify, without executing.
+
+use crate::engine::ExecutionEngine;
+use crate::plan::ExecutionPlan;
+
+use cognexus_model::graph::{Graph, NodeDefinitionRegistry, Port};
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use uuid::Uuid;
+
+/// A problem that would stop a graph from running cleanly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanIssue {
+    /// The graph couldn't be ordered (a cycle or a dangling edge).
+    Unorderable { message: String },
+    /// The node's definition isn't registered or its ports can't be read.
+    InvalidDefinition { node_id: Uuid, message: String },
+    /// An input port has no incoming edge.
+    MissingInput {
+        node_id: Uuid,
+        port_id: Uuid,
+        port_name: String,
+    },
+    /// An input port has more than one incoming edge.
+    DuplicateInput { node_id: Uuid, port_id: Uuid },
+    /// An edge connects ports of different data types, with no conversion
+    /// between them.
+    TypeMismatch {
+        edge_id: Uuid,
+        source_type_id: Uuid,
+        target_type_id: Uuid,
+    },
+    /// An edge references a port its node doesn't declare.
+    UnknownPort { edge_id: Uuid, port_id: Uuid },
+    /// The node's configuration doesn't match its definition's parameters.
+    InvalidConfig { node_id: Uuid, message: String },
+}
+
+impl PlanIssue {
+    /// The node the issue is attributed to, if any.
+    pub fn node_id(&self) -> Option<Uuid> {
+        match self {
+            PlanIssue::InvalidDefinition { node_id, .. }
+            | PlanIssue::InvalidConfig { node_id, .. }
+            | PlanIssue::MissingInput { node_id, .. }
+            | PlanIssue::DuplicateInput { node_id, .. } => Some(*node_id),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PlanIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanIssue::Unorderable { message } => write!(f, "{message}"),
+            PlanIssue::InvalidDefinition { node_id, message } => {
+                write!(f, "Node {node_id} has an invalid definition: {message}")
+            }
+            PlanIssue::MissingInput {
+                node_id,
+                port_id,
+                port_name,
+            } => write!(
+                f,
+                "Input port '{port_name}' ({port_id}) on node {node_id} is not connected"
+            ),
+            PlanIssue::DuplicateInput { node_id, port_id } => write!(
+                f,
+                "Input port {port_id} on node {node_id} has more than one incoming edge"
+            ),
+            PlanIssue::TypeMismatch {
+                edge_id,
+                source_type_id,
+                target_type_id,
+            } => write!(
+                f,
+                "Edge {edge_id} connects type {source_type_id} to incompatible type {target_type_id}"
+            ),
+            PlanIssue::UnknownPort { edge_id, port_id } => {
+                write!(f, "Edge {edge_id} references unknown port {port_id}")
+            }
+            PlanIssue::InvalidConfig { node_id, message } => {
+                write!(f, "Node {node_id} has an invalid configuration: {message}")
+            }
+        }
+    }
+}
+
+/// What a run of a graph would do, and what would stop it.
+pub struct DryRunReport {
+    graph_id: Uuid,
+    plan: Option<ExecutionPlan>,
+    max_parallelism: usize,
+    issues: Vec<PlanIssue>,
+}
+
+impl DryRunReport {
+    pub fn graph_id(&self) -> Uuid {
+        self.graph_id
+    }
+
+    /// The resolved execution order, if the graph could be ordered.
+    pub fn plan(&self) -> Option<&ExecutionPlan> {
+        self.plan.as_ref()
+    }
+
+    pub fn issues(&self) -> &[PlanIssue] {
+        &self.issues
+    }
+
+    /// True if nothing found would stop the graph from running.
+    pub fn is_runnable(&self) -> bool {
+        self.plan.is_some() && self.issues.is_empty()
+    }
+
+    /// Batches of concurrent node executions the engine would need, given
+    /// its parallelism limit. Stages wider than the limit take several.
+    pub fn estimated_waves(&self) -> usize {
+        self.plan.as_ref().map_or(0, |plan| {
+            plan.stages()
+                .iter()
+                .map(|stage| stage.len().div_ceil(self.max_parallelism))
+                .sum()
+        })
+    }
+}
+
+impl ExecutionEngine {
+    /// Check `graph` the way `run` would, without executing any node.
+    ///
+    /// Unlike `run`, this doesn't stop at the first problem: every issue
+    /// found is collected into the report.
+    pub fn dry_run(&self, graph: &Graph, definitions: &NodeDefinitionRegistry) -> DryRunReport {
+        let mut issues = Vec::new();
+
+        let plan = match ExecutionPlan::build(graph) {
+            Ok(plan) => Some(plan),
+            Err(error) => {
+                issues.push(PlanIssue::Unorderable {
+                    message: error.to_string(),
+                });
+                None
+            }
+        };
+
+        // (input ports, output ports) of every node whose definition resolves
+        let mut ports: HashMap<Uuid, (Vec<Port>, Vec<Port>)> = HashMap::new();
+        for node in graph.nodes() {
+            let resolved = definitions
+                .get(&node.definition_id())
+                .and_then(|definition| {
+                    Ok((
+                        node.input_ports(definition)?,
+                        node.output_ports(definition)?,
+                    ))
+                });
+
+            match resolved {
+                Ok(node_ports) => {
+                    ports.insert(node.id(), node_ports);
+                }
+                Err(error) => issues.push(PlanIssue::InvalidDefinition {
+                    node_id: node.id(),
+                    message: error.to_string(),
+                }),
+            }
+
+            // An unresolved definition is already reported above
+            if let Ok(definition) = definitions.get(&node.definition_id()) {
+                let checked = definition
+                    .parameter_specs()
+                    .and_then(|parameters| node.config().validate(&parameters));
+                if let Err(error) = checked {
+                    issues.push(PlanIssue::InvalidConfig {
+                        node_id: node.id(),
+                        message: error.to_string(),
+                    });
+                }
+            }
+        }
+
+        let mut connected = HashSet::new();
+        for edge in graph.edges() {
+            if !connected.insert((edge.target_node_id(), edge.target_port_id())) {
+                issues.push(PlanIssue::DuplicateInput {
+                    node_id: edge.target_node_id(),
+                    port_id: edge.target_port_id(),
+                });
+            }
+
+            let source = ports.get(&edge.source_node_id()).map(|(_, outputs)| {
+                outputs
+                    .iter()
+                    .find(|port| port.id() == edge.source_port_id())
+            });
+            let target = ports.get(&edge.target_node_id()).map(|(inputs, _)| {
+                inputs
+                    .iter()
+                    .find(|port| port.id() == edge.target_port_id())
+            });
+
+            // Nodes without a resolved definition were reported above
+            match (source, target) {
+                (Some(None), _) => issues.push(PlanIssue::UnknownPort {
+                    edge_id: edge.id(),
+                    port_id: edge.source_port_id(),
+                }),
+                (_, Some(None)) => issues.push(PlanIssue::UnknownPort {
+                    edge_id: edge.id(),
+                    port_id: edge.target_port_id(),
+                }),
+                (Some(Some(source)), Some(Some(target))) if !self.can_convert(source, target) => {
+                    issues.push(PlanIssue::TypeMismatch {
+                        edge_id: edge.id(),
+                        source_type_id: source.data_type_id(),
+                        target_type_id: target.data_type_id(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        for node in graph.nodes() {
+            let Some((inputs, _)) = ports.get(&node.id()) else {
+                continue;
+            };
+
+            for port in inputs {
+                if !connected.contains(&(node.id(), port.id())) {
+                    issues.push(PlanIssue::MissingInput {
+                        node_id: node.id(),
+                        port_id: port.id(),
+                        port_name: String::from(port.name()),
+                    });
+                }
+            }
+        }
+
+        DryRunReport {
+            graph_id: graph.id(),
+            plan,
+            max_parallelism: self.max_parallelism,
+            issues,
+        }
+    }
+
+    /// Whether values of `source` can flow into `target`, as they are or
+    /// through the engine's type conversions.
+    fn can_convert(&self, source: &Port, target: &Port) -> bool {
+        match &self.conversions {
+            Some(conversions) => {
+                conversions.can_convert(&source.data_type_id(), &target.data_type_id())
+            }
+            None => source.data_type_id() == target.data_type_id(),
+        }
+    }
+}
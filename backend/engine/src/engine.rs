@@ -0,0 +1,1202 @@
+//! The execution engine: runs a graph stage by stage in dependency order.
+
+use crate::budget::{BudgetExceeded, BudgetResource, BudgetTracker, ExecutionLimits, RunBudget};
+use crate::cache::{CacheKey, OutputCache};
+use crate::cancellation::CancellationToken;
+use crate::context::ExecutionContext;
+use crate::debugger::{Debugger, PausePosition};
+use crate::error::EngineError;
+use crate::executor::NodeExecutor;
+use crate::history::{RunHistory, RunRecord};
+use crate::plan::ExecutionPlan;
+use crate::progress::{ProgressEventKind, ProgressReporter, ProgressSink};
+use crate::replay::ReplaySession;
+use crate::result::{ExecutionResult, NodeResult, NodeStatus};
+use crate::trace::{TraceEventKind, TraceSink, Tracer};
+
+use cognexus_model::graph::{
+    Edge, ErrorClass, Graph, Node, NodeDefinitionRegistry, Port, PortValues, TypeConversionRegistry,
+};
+
+use common::error::error_location::ErrorLocation;
+
+use std::collections::{HashMap, HashSet};
+use std::panic::{Location, resume_unwind};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use log::{debug, info, warn};
+use uuid::Uuid;
+
+/// Outputs of one node, or the reason it failed.
+type NodeOutcome = Result<PortValues, EngineError>;
+
+/// How far a node got by the time its worker moved on.
+enum NodeProgress {
+    /// It finished, successfully or not.
+    Done(NodeOutcome),
+    /// It succeeded, but its executor deferred its outputs until
+    /// `release_at`.
+    Deferred {
+        outputs: PortValues,
+        started: Instant,
+        release_at: Instant,
+    },
+}
+
+/// Everything needed to execute one node, resolved before a stage starts.
+struct NodeTask<'a> {
+    node: &'a Node,
+    /// Incoming edges that will carry a value, leaving out those from
+    /// branches that weren't taken.
+    incoming: Vec<&'a Edge>,
+    declared_outputs: HashSet<Uuid>,
+    branching: bool,
+    /// Whether its outputs may come from the cache.
+    deterministic: bool,
+    typed_incoming: Vec<TypedEdge>,
+}
+
+/// An edge into one of a node's input ports, with the data types of the
+/// ports at both ends.
+struct TypedEdge {
+    edge_id: Uuid,
+    port_id: Uuid,
+    source_type_id: Uuid,
+    target_type_id: Uuid,
+}
+
+/// Runs validated graphs.
+///
+/// The engine resolves a staged topological order, executes each node through
+/// a [`NodeExecutor`], and routes output values along edges (through an
+/// [`ExecutionContext`]) to the input ports of downstream nodes. Nodes within
+/// a stage don't depend on each other and run concurrently, up to the
+/// configured parallelism limit.
+///
+/// Execution is fail-fast: once a node fails, the rest of its stage still
+/// finishes but every later stage is reported as skipped.
+///
+/// Control-flow nodes (see `NodeDefinitionInfo::is_branching`) produce values
+/// only on the branches they take. Every node downstream of a branch that
+/// wasn't taken is reported as not taken, without failing the run, up to a
+/// merging node (`NodeDefinitionInfo::is_merging`) that another, taken branch
+/// also feeds.
+///
+/// Every node's start, finish, failure, or skip is recorded in the run's
+/// [`ExecutionTrace`](crate::ExecutionTrace), and forwarded live to the
+/// configured [`TraceSink`] if there is one; a [`ProgressSink`] receives a
+/// coarser queued/started/finished stream with percent complete. An attached
+/// [`Debugger`] can pause the run before or after any node, and a
+/// [`CancellationToken`] can stop it between nodes. With an [`OutputCache`],
+/// deterministic nodes (`NodeDefinitionInfo::is_deterministic`) whose
+/// definition and inputs match an earlier execution reuse its outputs
+/// instead of running. With a [`RunHistory`], every run is recorded
+/// so it can be reviewed later. A [`RunBudget`] stops a run gracefully once
+/// it has used up its wall clock, fuel, or memory: the node that ran out
+/// fails, later nodes are reported as cancelled, and the result says which
+/// budget was exceeded. [`ExecutionEngine::run_recorded`] captures what a run
+/// read from outside the graph so [`ExecutionEngine::replay`] can reproduce
+/// it. With a [`TypeConversionRegistry`], values crossing an edge between
+/// ports of different types are converted before the target node sees them,
+/// and with value validation, the executor checks each such value is
+/// well-formed for the target port's type.
+///
+/// For data too large to hand over in one piece, `run_streaming` executes
+/// every node concurrently and connects them with chunked channels instead.
+pub struct ExecutionEngine {
+    pub(crate) max_parallelism: usize,
+    pub(crate) trace_sink: Option<Arc<dyn TraceSink>>,
+    pub(crate) progress_sink: Option<Arc<dyn ProgressSink>>,
+    pub(crate) debugger: Option<Debugger>,
+    pub(crate) cancellation: Option<CancellationToken>,
+    pub(crate) cache: Option<Arc<OutputCache>>,
+    pub(crate) history: Option<Arc<RunHistory>>,
+    pub(crate) stream_buffer: usize,
+    pub(crate) node_timeout: Option<Duration>,
+    pub(crate) budget: Option<RunBudget>,
+    pub(crate) conversions: Option<Arc<TypeConversionRegistry>>,
+    pub(crate) validate_values: bool,
+}
+
+impl ExecutionEngine {
+    pub fn max_parallelism(&self) -> usize {
+        self.max_parallelism
+    }
+
+    pub fn debugger(&self) -> Option<&Debugger> {
+        self.debugger.as_ref()
+    }
+
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    pub fn output_cache(&self) -> Option<&Arc<OutputCache>> {
+        self.cache.as_ref()
+    }
+
+    pub fn run_history(&self) -> Option<&Arc<RunHistory>> {
+        self.history.as_ref()
+    }
+
+    /// Chunks buffered per edge in streaming runs.
+    pub fn stream_buffer(&self) -> usize {
+        self.stream_buffer
+    }
+
+    /// Time limit for each node execution attempt, unless the node sets its
+    /// own.
+    pub fn node_timeout(&self) -> Option<Duration> {
+        self.node_timeout
+    }
+
+    pub fn run_budget(&self) -> Option<&RunBudget> {
+        self.budget.as_ref()
+    }
+
+    pub fn type_conversions(&self) -> Option<&Arc<TypeConversionRegistry>> {
+        self.conversions.as_ref()
+    }
+
+    /// Whether values crossing edges are validated before nodes run.
+    pub fn validates_values(&self) -> bool {
+        self.validate_values
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Run `graph` to completion.
+    ///
+    /// Node failures are reported per node in the returned `ExecutionResult`.
+    /// A cancelled run still returns `Ok`, with every node that had not
+    /// started reported as cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the graph cannot be executed at all: it contains a
+    /// cycle, references an unregistered node definition, or wires more than
+    /// one edge into the same input port.
+    #[track_caller]
+    pub fn run<E>(
+        &self,
+        graph: &Graph,
+        definitions: &NodeDefinitionRegistry,
+        executor: &E,
+    ) -> Result<ExecutionResult, EngineError>
+    where
+        E: NodeExecutor + Sync,
+    {
+        self.run_with(graph, definitions, executor, ExecutionContext::new(), None)
+    }
+
+    /// Run `graph` starting from `context`, which may hold seeded inputs,
+    /// recording or replaying it if there's a `replay` session.
+    #[track_caller]
+    pub(crate) fn run_with<E>(
+        &self,
+        graph: &Graph,
+        definitions: &NodeDefinitionRegistry,
+        executor: &E,
+        mut context: ExecutionContext,
+        replay: Option<&ReplaySession<'_>>,
+    ) -> Result<ExecutionResult, EngineError>
+    where
+        E: NodeExecutor + Sync,
+    {
+        let plan = ExecutionPlan::build(graph)?;
+        let nodes: HashMap<Uuid, &Node> = graph.nodes().iter().map(|n| (n.id(), n)).collect();
+        let incoming = incoming_edges(graph)?;
+
+        info!(
+            "Running graph '{}' ({} node(s) in {} stage(s), parallelism {})",
+            graph.name(),
+            plan.order().len(),
+            plan.stages().len(),
+            self.max_parallelism
+        );
+
+        let started_at = SystemTime::now();
+        let tracer = self.tracer(graph, &plan);
+        tracer.report(None, ProgressEventKind::RunStarted);
+        let budget = self.budget.map(BudgetTracker::new);
+
+        let mut node_results = Vec::with_capacity(plan.order().len());
+        let mut failed = false;
+        // (node id, output port id) of every branch that wasn't taken
+        let mut untaken: HashSet<(Uuid, Uuid)> = HashSet::new();
+
+        for stage in plan.stages() {
+            let over_budget = budget.as_ref().is_some_and(|b| b.check().is_some());
+            if failed || over_budget || self.is_cancelled() {
+                let (status, kind) = if failed && !over_budget {
+                    (NodeStatus::Skipped, TraceEventKind::Skipped)
+                } else {
+                    (NodeStatus::Cancelled, TraceEventKind::Cancelled)
+                };
+
+                for node_id in stage {
+                    tracer.record(*node_id, nodes[node_id].name(), kind.clone());
+                    node_results.push(NodeResult {
+                        node_id: *node_id,
+                        status,
+                        outputs: PortValues::new(),
+                        error: None,
+                    });
+                }
+                continue;
+            }
+
+            // Resolve everything a worker needs up front
+            let mut tasks = Vec::with_capacity(stage.len());
+            for node_id in stage {
+                let node = nodes[node_id];
+                let node_incoming = incoming.get(node_id).map_or(&[][..], Vec::as_slice);
+                let definition = definitions.get(&node.definition_id())?;
+                let declared_outputs: HashSet<Uuid> = node
+                    .output_ports(definition)?
+                    .iter()
+                    .map(|port| port.id())
+                    .collect();
+
+                // None of a not-taken node's outputs will be produced either,
+                // so everything downstream of it is skipped too. A merging
+                // node is only skipped once all of its inputs are.
+                let (untaken_incoming, taken_incoming): (Vec<&Edge>, Vec<&Edge>) =
+                    node_incoming.iter().partition(|edge| {
+                        untaken.contains(&(edge.source_node_id(), edge.source_port_id()))
+                    });
+                let not_taken = if definition.is_merging() {
+                    !untaken_incoming.is_empty() && taken_incoming.is_empty()
+                } else {
+                    !untaken_incoming.is_empty()
+                };
+                if not_taken {
+                    untaken.extend(declared_outputs.iter().map(|port_id| (*node_id, *port_id)));
+                    tracer.record(*node_id, node.name(), TraceEventKind::NotTaken);
+                    node_results.push(NodeResult {
+                        node_id: *node_id,
+                        status: NodeStatus::NotTaken,
+                        outputs: PortValues::new(),
+                        error: None,
+                    });
+                    continue;
+                }
+
+                // Port types only matter for converting and validating
+                let typed_incoming = if self.conversions.is_some() || self.validate_values {
+                    typed_edges(
+                        &taken_incoming,
+                        &node.input_ports(definition)?,
+                        &nodes,
+                        definitions,
+                    )?
+                } else {
+                    Vec::new()
+                };
+
+                tracer.report(Some(*node_id), ProgressEventKind::NodeQueued);
+                tasks.push(NodeTask {
+                    node,
+                    incoming: taken_incoming,
+                    declared_outputs,
+                    branching: definition.is_branching(),
+                    deterministic: definition.is_deterministic(),
+                    typed_incoming,
+                });
+            }
+
+            let outcomes =
+                self.run_stage(&tasks, &context, executor, &tracer, budget.as_ref(), replay);
+
+            for (task, outcome) in tasks.iter().zip(outcomes) {
+                match outcome {
+                    Ok(outputs) => {
+                        context.store_outputs(task.node.id(), &outputs);
+
+                        if task.branching {
+                            untaken.extend(
+                                task.declared_outputs
+                                    .iter()
+                                    .filter(|port_id| !outputs.contains(port_id))
+                                    .map(|port_id| (task.node.id(), *port_id)),
+                            );
+                        }
+
+                        node_results.push(NodeResult {
+                            node_id: task.node.id(),
+                            status: NodeStatus::Succeeded,
+                            outputs,
+                            error: None,
+                        });
+                    }
+                    Err(EngineError::CancelledError { .. }) => {
+                        node_results.push(NodeResult {
+                            node_id: task.node.id(),
+                            status: NodeStatus::Cancelled,
+                            outputs: PortValues::new(),
+                            error: None,
+                        });
+                    }
+                    Err(error) => {
+                        warn!("{error}");
+                        failed = true;
+
+                        node_results.push(NodeResult {
+                            node_id: task.node.id(),
+                            status: NodeStatus::Failed,
+                            outputs: PortValues::new(),
+                            error: Some(error),
+                        });
+                    }
+                }
+            }
+        }
+
+        let budget_exceeded = budget.and_then(|b| b.exceeded());
+        Ok(self.finish_run(graph, started_at, node_results, tracer, budget_exceeded))
+    }
+
+    /// Report a run as finished and record it in the run history, if any.
+    pub(crate) fn finish_run(
+        &self,
+        graph: &Graph,
+        started_at: SystemTime,
+        node_results: Vec<NodeResult>,
+        tracer: Tracer<'_>,
+        budget_exceeded: Option<BudgetExceeded>,
+    ) -> ExecutionResult {
+        let success = node_results.iter().all(|r| r.status.is_success());
+        tracer.report(None, ProgressEventKind::RunFinished { success });
+
+        let result = ExecutionResult {
+            run_id: Uuid::new_v4(),
+            graph_id: graph.id(),
+            node_results,
+            trace: tracer.finish(),
+            budget_exceeded,
+        };
+
+        // A run that happened shouldn't be reported as failed just because
+        // it couldn't be written down
+        if let Some(history) = &self.history {
+            let record = RunRecord::new(graph, &result, started_at, SystemTime::now());
+            if let Err(e) = history.record(record) {
+                warn!("{e}");
+            }
+        }
+
+        result
+    }
+
+    /// Per-run recorder for trace and progress events.
+    pub(crate) fn tracer(&self, graph: &Graph, plan: &ExecutionPlan) -> Tracer<'_> {
+        let progress = self
+            .progress_sink
+            .as_deref()
+            .map(|sink| ProgressReporter::new(sink, graph.id(), plan.order().len()));
+
+        Tracer::new(self.trace_sink.as_deref(), progress)
+    }
+
+    /// Execute every task of one stage, returning outcomes in task order.
+    ///
+    /// Workers pull the next unclaimed task until the stage is drained, so a
+    /// slow node only holds up its own worker. Outputs a node's executor
+    /// deferred are released once they are due, after the workers have gone
+    /// on to the rest of the stage.
+    fn run_stage<E>(
+        &self,
+        tasks: &[NodeTask<'_>],
+        context: &ExecutionContext,
+        executor: &E,
+        tracer: &Tracer<'_>,
+        budget: Option<&BudgetTracker>,
+        replay: Option<&ReplaySession<'_>>,
+    ) -> Vec<NodeOutcome>
+    where
+        E: NodeExecutor + Sync,
+    {
+        let workers = self.max_parallelism.min(tasks.len());
+
+        let progress: Vec<NodeProgress> = if workers <= 1 {
+            tasks
+                .iter()
+                .map(|task| self.execute_node(task, context, executor, tracer, budget, replay))
+                .collect()
+        } else {
+            self.run_workers(tasks, context, executor, tracer, budget, replay)
+        };
+
+        tasks
+            .iter()
+            .zip(progress)
+            .map(|(task, progress)| match progress {
+                NodeProgress::Done(outcome) => outcome,
+                NodeProgress::Deferred {
+                    outputs,
+                    started,
+                    release_at,
+                } => self.release(task.node, outputs, started, release_at, tracer),
+            })
+            .collect()
+    }
+
+    /// Execute `tasks` on as many threads as may run at once, returning
+    /// progress in task order.
+    fn run_workers<E>(
+        &self,
+        tasks: &[NodeTask<'_>],
+        context: &ExecutionContext,
+        executor: &E,
+        tracer: &Tracer<'_>,
+        budget: Option<&BudgetTracker>,
+        replay: Option<&ReplaySession<'_>>,
+    ) -> Vec<NodeProgress>
+    where
+        E: NodeExecutor + Sync,
+    {
+        let workers = self.max_parallelism.min(tasks.len());
+        let next_task = AtomicUsize::new(0);
+        let mut progress: Vec<Option<NodeProgress>> = (0..tasks.len()).map(|_| None).collect();
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut completed = Vec::new();
+                        loop {
+                            let position = next_task.fetch_add(1, Ordering::Relaxed);
+                            let Some(task) = tasks.get(position) else {
+                                break;
+                            };
+                            completed.push((
+                                position,
+                                self.execute_node(task, context, executor, tracer, budget, replay),
+                            ));
+                        }
+                        completed
+                    })
+                })
+                .collect();
+
+            for handle in handles {
+                // Executor panics are not errors we can attribute; propagate
+                // them exactly as the serial path would.
+                let completed = handle.join().unwrap_or_else(|panic| resume_unwind(panic));
+                for (position, outcome) in completed {
+                    progress[position] = Some(outcome);
+                }
+            }
+        });
+
+        progress.into_iter().flatten().collect()
+    }
+
+    /// Hand on a node's deferred outputs once `release_at` comes, unless the
+    /// run is cancelled first.
+    fn release(
+        &self,
+        node: &Node,
+        outputs: PortValues,
+        started: Instant,
+        release_at: Instant,
+        tracer: &Tracer<'_>,
+    ) -> NodeOutcome {
+        let cancelled = match &self.cancellation {
+            Some(token) => token.wait_until(release_at),
+            None => {
+                thread::sleep(release_at.saturating_duration_since(Instant::now()));
+                false
+            }
+        };
+        if cancelled {
+            tracer.record(node.id(), node.name(), TraceEventKind::Cancelled);
+            return Err(EngineError::CancelledError {
+                node_id: node.id(),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        self.finish_node(node, Ok(outputs), started, None, tracer)
+    }
+
+    /// Gather inputs for, execute, and check the outputs of a single node,
+    /// recording its trace events and stopping at debugger checkpoints.
+    /// Cached outputs of deterministic nodes are returned without invoking
+    /// the executor, except in recorded and replayed runs, and the trace
+    /// duration of an executed node covers all of its attempts. Deferred
+    /// outputs are left for the stage to release, and aren't cached, since a
+    /// cache hit would skip the wait.
+    fn execute_node<E>(
+        &self,
+        task: &NodeTask<'_>,
+        context: &ExecutionContext,
+        executor: &E,
+        tracer: &Tracer<'_>,
+        budget: Option<&BudgetTracker>,
+        replay: Option<&ReplaySession<'_>>,
+    ) -> NodeProgress
+    where
+        E: NodeExecutor,
+    {
+        let node = task.node;
+
+        let inputs = match context
+            .inputs_for(node, &task.incoming)
+            .and_then(|inputs| self.convert_inputs(node, &task.typed_incoming, inputs))
+            .and_then(|inputs| {
+                self.validate_inputs(node, &task.typed_incoming, &inputs, executor)?;
+                Ok(inputs)
+            }) {
+            Ok(inputs) => inputs,
+            Err(error) => {
+                tracer.record(
+                    node.id(),
+                    node.name(),
+                    TraceEventKind::Failed {
+                        duration: Duration::ZERO,
+                        message: error.to_string(),
+                    },
+                );
+                return NodeProgress::Done(Err(error));
+            }
+        };
+
+        if let Some(debugger) = &self.debugger {
+            debugger.checkpoint(node, PausePosition::Before, &inputs);
+        }
+
+        // Stopping for the budget stops the remaining nodes just like a cancel
+        let over_budget = budget.is_some_and(|b| b.check().is_some());
+        if over_budget || self.is_cancelled() {
+            tracer.record(node.id(), node.name(), TraceEventKind::Cancelled);
+            return NodeProgress::Done(Err(EngineError::CancelledError {
+                node_id: node.id(),
+                location: ErrorLocation::from(Location::caller()),
+            }));
+        }
+
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|_| replay.is_none() && task.deterministic);
+        let cache_key = cache.map(|_| CacheKey::new(node, &inputs));
+        if let (Some(cache), Some(key)) = (cache, &cache_key)
+            && let Some(outputs) = cache.get(key)
+        {
+            debug!(
+                "Reusing cached outputs for node '{}' ({})",
+                node.name(),
+                node.id()
+            );
+
+            tracer.record(
+                node.id(),
+                node.name(),
+                TraceEventKind::Cached {
+                    output_count: outputs.len(),
+                    output_bytes: payload_bytes(&outputs),
+                },
+            );
+
+            if let Some(debugger) = &self.debugger {
+                debugger.checkpoint(node, PausePosition::After, &outputs);
+            }
+
+            return NodeProgress::Done(Ok(outputs));
+        }
+
+        tracer.record(
+            node.id(),
+            node.name(),
+            TraceEventKind::Started {
+                input_count: inputs.len(),
+                input_bytes: payload_bytes(&inputs),
+            },
+        );
+
+        debug!("Executing node '{}' ({})", node.name(), node.id());
+
+        let started = Instant::now();
+        let outcome = self
+            .invoke(node, &inputs, executor, tracer, budget, replay)
+            .and_then(|(outputs, release_at)| {
+                Ok((
+                    check_outputs(node, &task.declared_outputs, outputs)?,
+                    release_at,
+                ))
+            });
+
+        match outcome {
+            Ok((outputs, Some(release_at))) => NodeProgress::Deferred {
+                outputs,
+                started,
+                release_at,
+            },
+            Ok((outputs, None)) => NodeProgress::Done(self.finish_node(
+                node,
+                Ok(outputs),
+                started,
+                cache.zip(cache_key),
+                tracer,
+            )),
+            Err(error) => {
+                NodeProgress::Done(self.finish_node(node, Err(error), started, None, tracer))
+            }
+        }
+    }
+
+    /// Record how an executed node finished, caching its outputs under
+    /// `cache` and stopping at the debugger checkpoint after it.
+    fn finish_node(
+        &self,
+        node: &Node,
+        outcome: NodeOutcome,
+        started: Instant,
+        cache: Option<(&Arc<OutputCache>, CacheKey)>,
+        tracer: &Tracer<'_>,
+    ) -> NodeOutcome {
+        let duration = started.elapsed();
+        let kind = match &outcome {
+            Ok(outputs) => TraceEventKind::Finished {
+                duration,
+                output_count: outputs.len(),
+                output_bytes: payload_bytes(outputs),
+            },
+            Err(error) => TraceEventKind::Failed {
+                duration,
+                message: error.to_string(),
+            },
+        };
+        tracer.record(node.id(), node.name(), kind);
+
+        if let Ok(outputs) = &outcome {
+            if let Some((cache, key)) = cache {
+                cache.insert(key, outputs);
+            }
+
+            if let Some(debugger) = &self.debugger {
+                debugger.checkpoint(node, PausePosition::After, outputs);
+            }
+        }
+
+        outcome
+    }
+
+    /// Convert inputs fed from ports of another type. Edges between types
+    /// there's no conversion for pass values through unchanged, as `dry_run`
+    /// reports them.
+    #[track_caller]
+    fn convert_inputs(
+        &self,
+        node: &Node,
+        typed_incoming: &[TypedEdge],
+        mut inputs: PortValues,
+    ) -> Result<PortValues, EngineError> {
+        let Some(registry) = &self.conversions else {
+            return Ok(inputs);
+        };
+
+        for edge in typed_incoming {
+            if edge.source_type_id == edge.target_type_id
+                || !registry.can_convert(&edge.source_type_id, &edge.target_type_id)
+            {
+                continue;
+            }
+            let Some(bytes) = inputs.get(&edge.port_id) else {
+                continue;
+            };
+
+            let converted = registry
+                .convert(&edge.source_type_id, &edge.target_type_id, bytes)
+                .map_err(|e| EngineError::ExecutionError {
+                    message: format!(
+                        "Input port {} of node '{}' could not be converted: {e}",
+                        edge.port_id,
+                        node.name()
+                    ),
+                    node_id: node.id(),
+                    location: ErrorLocation::from(Location::caller()),
+                    source: Some(Box::new(e)),
+                })?;
+            inputs.insert(edge.port_id, converted);
+        }
+
+        Ok(inputs)
+    }
+
+    /// Have the executor check every value that arrived over an edge against
+    /// its input port's type, if the engine validates values.
+    #[track_caller]
+    fn validate_inputs<E>(
+        &self,
+        node: &Node,
+        typed_incoming: &[TypedEdge],
+        inputs: &PortValues,
+        executor: &E,
+    ) -> Result<(), EngineError>
+    where
+        E: NodeExecutor,
+    {
+        if !self.validate_values {
+            return Ok(());
+        }
+
+        for edge in typed_incoming {
+            let Some(bytes) = inputs.get(&edge.port_id) else {
+                continue;
+            };
+
+            executor
+                .validate_value(edge.target_type_id, bytes)
+                .map_err(|e| EngineError::InvalidValueError {
+                    message: format!(
+                        "Edge {} carried a malformed value into input port {} of node '{}': {e}",
+                        edge.edge_id,
+                        edge.port_id,
+                        node.name()
+                    ),
+                    node_id: node.id(),
+                    edge_id: edge.edge_id,
+                    location: ErrorLocation::from(Location::caller()),
+                    source: Some(Box::new(e)),
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Call the executor, retrying failures the node's retry policy covers.
+    /// Returns the outputs, and when to release them if the executor
+    /// deferred them. A deferral that would outlast the attempt's timeout
+    /// times the attempt out.
+    fn invoke<E>(
+        &self,
+        node: &Node,
+        inputs: &PortValues,
+        executor: &E,
+        tracer: &Tracer<'_>,
+        budget: Option<&BudgetTracker>,
+        replay: Option<&ReplaySession<'_>>,
+    ) -> Result<(PortValues, Option<Instant>), EngineError>
+    where
+        E: NodeExecutor,
+    {
+        let node_timeout = node.timeout().or(self.node_timeout);
+        let mut attempt = 1;
+
+        loop {
+            // No attempt may outlast what's left of the run's wall clock
+            let remaining = budget.and_then(BudgetTracker::remaining_time);
+            let timeout = match (node_timeout, remaining) {
+                (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+                (timeout, remaining) => timeout.or(remaining),
+            };
+            let limits = ExecutionLimits::new(
+                timeout,
+                budget.and_then(BudgetTracker::remaining_fuel),
+                budget.and_then(|b| b.memory_per_node(self.max_parallelism)),
+            );
+            let limits = match replay {
+                Some(replay) => replay.prepare(node, attempt, inputs, limits)?,
+                None => limits,
+            };
+
+            let started = Instant::now();
+            let outcome = executor.execute_with_limits(node, inputs, &limits);
+            let deferral = limits.deferral().filter(|_| outcome.is_ok());
+            let timed_out = timeout
+                .is_some_and(|timeout| started.elapsed() + deferral.unwrap_or_default() >= timeout);
+
+            for logged in limits.take_logs() {
+                tracer.record(node.id(), node.name(), logged);
+            }
+
+            if let Some(replay) = replay {
+                replay.finish(node, attempt, inputs, &limits);
+            }
+
+            if let Some(budget) = budget {
+                budget.record_fuel(limits.fuel_consumed());
+
+                let exhausted = limits.exhausted().or_else(|| {
+                    let out_of_time = budget
+                        .remaining_time()
+                        .is_some_and(|remaining| remaining <= deferral.unwrap_or_default());
+                    (timed_out && out_of_time).then_some(BudgetResource::WallClock)
+                });
+                if let Some(resource) = exhausted {
+                    let exceeded = budget.exceed(resource, Some(node.id()));
+                    return Err(EngineError::BudgetExceededError {
+                        message: format!(
+                            "Node '{}' was stopped: {}",
+                            node.name(),
+                            exceeded.message
+                        ),
+                        node_id: node.id(),
+                        location: ErrorLocation::from(Location::caller()),
+                    });
+                }
+            }
+
+            // Outputs that arrive after the deadline are discarded
+            let (class, error) = match outcome {
+                Ok(outputs) if !timed_out => {
+                    let release_at = deferral.map(|deferral| Instant::now() + deferral);
+                    return Ok((outputs, release_at));
+                }
+                Ok(_) => (ErrorClass::Timeout, None),
+                Err(_) if timed_out => (ErrorClass::Timeout, None),
+                Err(error) => (executor.classify_error(&error), Some(error)),
+            };
+            let message = match &error {
+                Some(error) => error.to_string(),
+                None => format!("timed out after {:?}", timeout.unwrap_or_default()),
+            };
+
+            let retry = node
+                .retry_policy()
+                .filter(|policy| policy.should_retry(attempt, class));
+
+            match retry {
+                Some(policy) if !self.is_cancelled() => {
+                    let delay = policy.delay_after(attempt);
+                    warn!(
+                        "Node '{}' attempt {attempt} failed, retrying in {delay:?}: {message}",
+                        node.name()
+                    );
+
+                    tracer.record(
+                        node.id(),
+                        node.name(),
+                        TraceEventKind::Retrying {
+                            attempt,
+                            delay,
+                            message,
+                        },
+                    );
+
+                    thread::sleep(delay);
+                    attempt += 1;
+                }
+                _ => {
+                    let message = if attempt > 1 {
+                        format!(
+                            "Node '{}' failed after {attempt} attempts: {message}",
+                            node.name()
+                        )
+                    } else {
+                        format!("Node '{}' failed: {message}", node.name())
+                    };
+
+                    let Some(error) = error else {
+                        return Err(EngineError::TimeoutError {
+                            message,
+                            node_id: node.id(),
+                            timeout: timeout.unwrap_or_default(),
+                            location: ErrorLocation::from(Location::caller()),
+                        });
+                    };
+
+                    return Err(EngineError::ExecutionError {
+                        message,
+                        node_id: node.id(),
+                        location: ErrorLocation::from(Location::caller()),
+                        source: Some(Box::new(error)),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Total serialized size of a set of port values.
+fn payload_bytes(values: &PortValues) -> usize {
+    values.iter().map(|(_, bytes)| bytes.len()).sum()
+}
+
+/// Index edges by target node, rejecting input ports with more than one edge.
+#[track_caller]
+pub(crate) fn incoming_edges(graph: &Graph) -> Result<HashMap<Uuid, Vec<&Edge>>, EngineError> {
+    let mut incoming: HashMap<Uuid, Vec<&Edge>> = HashMap::new();
+    let mut connected_ports = HashSet::new();
+
+    for edge in graph.edges() {
+        if !connected_ports.insert((edge.target_node_id(), edge.target_port_id())) {
+            return Err(EngineError::GraphError {
+                message: format!(
+                    "Input port {} on node {} has more than one incoming edge",
+                    edge.target_port_id(),
+                    edge.target_node_id()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        incoming
+            .entry(edge.target_node_id())
+            .or_default()
+            .push(edge);
+    }
+
+    Ok(incoming)
+}
+
+/// Resolve the port types at both ends of a node's incoming edges. Edges to
+/// or from undeclared ports are left out.
+#[track_caller]
+fn typed_edges(
+    incoming: &[&Edge],
+    input_ports: &[Port],
+    nodes: &HashMap<Uuid, &Node>,
+    definitions: &NodeDefinitionRegistry,
+) -> Result<Vec<TypedEdge>, EngineError> {
+    let mut typed = Vec::with_capacity(incoming.len());
+
+    for edge in incoming {
+        let Some(target) = input_ports.iter().find(|p| p.id() == edge.target_port_id()) else {
+            continue;
+        };
+
+        let source_node = nodes[&edge.source_node_id()];
+        let source_ports =
+            source_node.output_ports(definitions.get(&source_node.definition_id())?)?;
+        let Some(source) = source_ports
+            .iter()
+            .find(|p| p.id() == edge.source_port_id())
+        else {
+            continue;
+        };
+
+        typed.push(TypedEdge {
+            edge_id: edge.id(),
+            port_id: target.id(),
+            source_type_id: source.data_type_id(),
+            target_type_id: target.data_type_id(),
+        });
+    }
+
+    Ok(typed)
+}
+
+/// Reject outputs written to ports the node doesn't declare.
+#[track_caller]
+fn check_outputs(
+    node: &Node,
+    declared_outputs: &HashSet<Uuid>,
+    outputs: PortValues,
+) -> NodeOutcome {
+    if let Some(port_id) = outputs.port_ids().find(|id| !declared_outputs.contains(id)) {
+        return Err(EngineError::ExecutionError {
+            message: format!(
+                "Node '{}' produced a value for undeclared output port {port_id}",
+                node.name()
+            ),
+            node_id: node.id(),
+            location: ErrorLocation::from(Location::caller()),
+            source: None,
+        });
+    }
+
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::engine_builder::ExecutionEngineBuilder;
+
+    use cognexus_model::error::ModelError;
+    use cognexus_model::graph::{GraphBuilder, NodeDefinitionInfo};
+
+    use std::io;
+    use std::sync::Mutex;
+
+    use semver::Version;
+
+    /// A definition without ports, deterministic or not.
+    struct Source {
+        id: Uuid,
+        deterministic: bool,
+    }
+
+    impl NodeDefinitionInfo for Source {
+        fn definition_id(&self) -> Uuid {
+            self.id
+        }
+
+        fn name(&self) -> &str {
+            "Source"
+        }
+
+        fn description(&self) -> &str {
+            "Produces nothing"
+        }
+
+        fn model_version(&self) -> Version {
+            Version::new(0, 1, 0)
+        }
+
+        fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+            Ok(Vec::new())
+        }
+
+        fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+            Ok(Vec::new())
+        }
+
+        fn is_deterministic(&self) -> bool {
+            self.deterministic
+        }
+    }
+
+    /// Counts executions per definition.
+    #[derive(Default)]
+    struct CountingExecutor {
+        executions: Mutex<HashMap<Uuid, usize>>,
+    }
+
+    impl NodeExecutor for CountingExecutor {
+        type Error = io::Error;
+
+        fn execute(&self, node: &Node, _inputs: &PortValues) -> Result<PortValues, io::Error> {
+            *self
+                .executions
+                .lock()
+                .unwrap()
+                .entry(node.definition_id())
+                .or_default() += 1;
+            Ok(PortValues::new())
+        }
+    }
+
+    #[test]
+    fn caches_only_deterministic_nodes() {
+        let (deterministic, random) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut definitions = NodeDefinitionRegistry::new();
+        for (id, deterministic) in [(deterministic, true), (random, false)] {
+            definitions.register(Source { id, deterministic }).unwrap();
+        }
+
+        let mut graph = GraphBuilder::default().with_name("graph").build().unwrap();
+        graph
+            .add_node(&definitions, None, "deterministic", deterministic)
+            .unwrap();
+        graph
+            .add_node(&definitions, None, "random", random)
+            .unwrap();
+
+        let cache = Arc::new(OutputCache::new(1024));
+        let engine = ExecutionEngineBuilder::default()
+            .with_output_cache(cache.clone())
+            .build()
+            .unwrap();
+        let executor = CountingExecutor::default();
+        for _ in 0..2 {
+            engine.run(&graph, &definitions, &executor).unwrap();
+        }
+
+        let executions = executor.executions.lock().unwrap();
+        assert_eq!(executions[&deterministic], 1);
+        assert_eq!(executions[&random], 2);
+        assert_eq!(cache.stats().hits, 1);
+    }
+
+    /// Defers the outputs of nodes named "delay" by 500ms, and notes when
+    /// each node was executed.
+    #[derive(Default)]
+    struct DeferringExecutor {
+        executed: Mutex<HashMap<String, Instant>>,
+    }
+
+    impl NodeExecutor for DeferringExecutor {
+        type Error = io::Error;
+
+        fn execute(&self, node: &Node, _inputs: &PortValues) -> Result<PortValues, io::Error> {
+            self.executed
+                .lock()
+                .unwrap()
+                .insert(node.name().to_string(), Instant::now());
+            Ok(PortValues::new())
+        }
+
+        fn execute_with_limits(
+            &self,
+            node: &Node,
+            inputs: &PortValues,
+            limits: &ExecutionLimits,
+        ) -> Result<PortValues, io::Error> {
+            if node.name() == "delay" {
+                limits.record_deferral(Duration::from_millis(500));
+            }
+            self.execute(node, inputs)
+        }
+    }
+
+    fn delay_and_sibling() -> (Graph, NodeDefinitionRegistry) {
+        let source = Uuid::new_v4();
+        let mut definitions = NodeDefinitionRegistry::new();
+        definitions
+            .register(Source {
+                id: source,
+                deterministic: false,
+            })
+            .unwrap();
+
+        let mut graph = GraphBuilder::default().with_name("graph").build().unwrap();
+        for name in ["delay", "sibling"] {
+            graph.add_node(&definitions, None, name, source).unwrap();
+        }
+
+        (graph, definitions)
+    }
+
+    #[test]
+    fn deferred_outputs_do_not_hold_up_siblings() {
+        let (graph, definitions) = delay_and_sibling();
+        let engine = ExecutionEngineBuilder::default()
+            .with_max_parallelism(1)
+            .build()
+            .unwrap();
+        let executor = DeferringExecutor::default();
+
+        let started = Instant::now();
+        let result = engine.run(&graph, &definitions, &executor).unwrap();
+
+        assert!(result.is_success());
+        assert!(started.elapsed() >= Duration::from_millis(500));
+        let executed = executor.executed.lock().unwrap();
+        assert!(executed["delay"] <= executed["sibling"]);
+        assert!(executed["sibling"].duration_since(started) < Duration::from_millis(250));
+    }
+
+    #[test]
+    fn deferral_past_the_timeout_times_out() {
+        let (graph, definitions) = delay_and_sibling();
+        let engine = ExecutionEngineBuilder::default()
+            .with_node_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let started = Instant::now();
+        let result = engine
+            .run(&graph, &definitions, &DeferringExecutor::default())
+            .unwrap();
+
+        assert!(started.elapsed() < Duration::from_millis(500));
+        let delay = graph.nodes().iter().find(|node| node.name() == "delay");
+        let delay = result.node_result(delay.unwrap().id()).unwrap();
+        assert!(matches!(
+            delay.error(),
+            Some(EngineError::TimeoutError { .. })
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::budget::RunBudget;
+use crate::cache::OutputCache;
+use crate::cancellation::CancellationToken;
+use crate::debugger::Debugger;
+use crate::engine::ExecutionEngine;
+use crate::error::EngineError;
+use crate::history::RunHistory;
+use crate::progress::ProgressSink;
+use crate::trace::TraceSink;
+
+use cognexus_model::graph::TypeConversionRegistry;
+
+use common::error::error_location::ErrorLocation;
+
+use std::num::NonZeroUsize;
+use std::panic::Location;
+use std::sync::Arc;
+use std::thread::available_parallelism;
+use std::time::Duration;
+
+const DEFAULT_STREAM_BUFFER: usize = 16;
+
+#[derive(Default)]
+pub struct ExecutionEngineBuilder {
+    max_parallelism: Option<usize>,
+    trace_sink: Option<Arc<dyn TraceSink>>,
+    progress_sink: Option<Arc<dyn ProgressSink>>,
+    debugger: Option<Debugger>,
+    cancellation: Option<CancellationToken>,
+    cache: Option<Arc<OutputCache>>,
+    history: Option<Arc<RunHistory>>,
+    stream_buffer: Option<usize>,
+    node_timeout: Option<Duration>,
+    budget: Option<RunBudget>,
+    conversions: Option<Arc<TypeConversionRegistry>>,
+    validate_values: bool,
+}
+
+impl ExecutionEngineBuilder {
+    /// Limit how many nodes of one stage may execute at the same time.
+    /// Defaults to the number of available CPU cores; 1 runs strictly serially.
+    pub fn with_max_parallelism(mut self, max_parallelism: usize) -> Self {
+        self.max_parallelism = Some(max_parallelism);
+        self
+    }
+
+    /// Stream trace events live to `sink` as nodes start and finish.
+    /// Events are always collected into the run's trace regardless.
+    pub fn with_trace_sink(mut self, sink: Arc<dyn TraceSink>) -> Self {
+        self.trace_sink = Some(sink);
+        self
+    }
+
+    /// Report run progress (queued, started, finished, percent complete) to
+    /// `sink`, e.g. to forward to the frontend.
+    pub fn with_progress_sink(mut self, sink: Arc<dyn ProgressSink>) -> Self {
+        self.progress_sink = Some(sink);
+        self
+    }
+
+    /// Attach debug controls. Keep a clone of `debugger` to set breakpoints
+    /// and step the run from another thread.
+    pub fn with_debugger(mut self, debugger: Debugger) -> Self {
+        self.debugger = Some(debugger);
+        self
+    }
+
+    /// Stop runs when `token` is cancelled. Keep a clone to cancel from
+    /// another thread.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Reuse node outputs across runs. Share the same cache between engines
+    /// to carry results over when an engine is rebuilt.
+    pub fn with_output_cache(mut self, cache: Arc<OutputCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Record every run, batch or streaming, into `history`.
+    pub fn with_run_history(mut self, history: Arc<RunHistory>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Chunks buffered per edge in streaming runs before a sender blocks.
+    /// Defaults to 16; 0 makes every send wait for its receiver.
+    pub fn with_stream_buffer(mut self, stream_buffer: usize) -> Self {
+        self.stream_buffer = Some(stream_buffer);
+        self
+    }
+
+    /// Time limit for each node execution attempt, for nodes that don't set
+    /// their own. Defaults to no limit.
+    pub fn with_node_timeout(mut self, timeout: Duration) -> Self {
+        self.node_timeout = Some(timeout);
+        self
+    }
+
+    /// Limit what each run may consume in total. Defaults to no limits.
+    pub fn with_run_budget(mut self, budget: RunBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Convert values crossing edges between ports of different types.
+    /// Without conversions, values are passed on as they were produced.
+    pub fn with_type_conversions(mut self, conversions: Arc<TypeConversionRegistry>) -> Self {
+        self.conversions = Some(conversions);
+        self
+    }
+
+    /// Have the executor validate every value crossing an edge before the
+    /// target node runs, failing the node with the edge that carried a
+    /// malformed value. Defaults to off.
+    pub fn with_value_validation(mut self, validate_values: bool) -> Self {
+        self.validate_values = validate_values;
+        self
+    }
+
+    #[track_caller]
+    pub fn build(self) -> Result<ExecutionEngine, EngineError> {
+        let max_parallelism = match self.max_parallelism {
+            Some(0) => {
+                return Err(EngineError::ConfigurationError {
+                    message: String::from("Maximum parallelism must be at least 1"),
+                    location: ErrorLocation::from(Location::caller()),
+                });
+            }
+            Some(max) => max,
+            None => available_parallelism().map_or(1, NonZeroUsize::get),
+        };
+
+        if self.node_timeout == Some(Duration::ZERO) {
+            return Err(EngineError::ConfigurationError {
+                message: String::from("Node timeout must be greater than zero"),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        // A run paused at a breakpoint would otherwise never see the cancel
+        if let (Some(token), Some(debugger)) = (&self.cancellation, &self.debugger) {
+            let debugger = debugger.clone();
+            token.on_cancel(move || debugger.detach());
+        }
+
+        Ok(ExecutionEngine {
+            max_parallelism,
+            trace_sink: self.trace_sink,
+            progress_sink: self.progress_sink,
+            debugger: self.debugger,
+            cancellation: self.cancellation,
+            cache: self.cache,
+            history: self.history,
+            stream_buffer: self.stream_buffer.unwrap_or(DEFAULT_STREAM_BUFFER),
+            node_timeout: self.node_timeout,
+            budget: self.budget,
+            conversions: self.conversions,
+            validate_values: self.validate_values,
+        })
+    }
+}
@@ -0,0 +1,164 @@
+use common::error::error_location::ErrorLocation;
+
+use cognexus_model::error::ModelError;
+
+use std::time::Duration;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Errors that can occur while planning or running a graph.
+#[derive(Error, Debug)]
+pub enum EngineError {
+    #[error("Configuration error: {message} {location}")]
+    ConfigurationError {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("Graph error: {message} {location}")]
+    GraphError {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("Model error: {message} {location}")]
+    ModelError {
+        message: String,
+        location: ErrorLocation,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    #[error("Execution error: {message} (node: {node_id}) {location}")]
+    ExecutionError {
+        message: String,
+        node_id: Uuid,
+        location: ErrorLocation,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    #[error("Loop error: {message} {location}")]
+    LoopError {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("Replay error: {message} {location}")]
+    ReplayError {
+        message: String,
+        /// The node that diverged from the recording, if any.
+        node_id: Option<Uuid>,
+        location: ErrorLocation,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    #[error("History error: {message} {location}")]
+    HistoryError {
+        message: String,
+        location: ErrorLocation,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    #[error("Timeout error: {message} (node: {node_id}) {location}")]
+    TimeoutError {
+        message: String,
+        node_id: Uuid,
+        timeout: Duration,
+        location: ErrorLocation,
+    },
+
+    #[error("Budget exceeded: {message} (node: {node_id}) {location}")]
+    BudgetExceededError {
+        message: String,
+        node_id: Uuid,
+        location: ErrorLocation,
+    },
+
+    #[error("Invalid value: {message} (node: {node_id}, edge: {edge_id}) {location}")]
+    InvalidValueError {
+        message: String,
+        node_id: Uuid,
+        edge_id: Uuid,
+        location: ErrorLocation,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    #[error("Cancelled: node {node_id} did not run {location}")]
+    CancelledError {
+        node_id: Uuid,
+        location: ErrorLocation,
+    },
+}
+
+/// Why a node run through a [`SubGraphExecutor`](crate::SubGraphExecutor)
+/// failed.
+#[derive(Error, Debug)]
+pub enum SubGraphError<E> {
+    /// A node that isn't a sub-graph failed in the wrapped executor.
+    #[error(transparent)]
+    NodeError(E),
+
+    /// A sub-graph node's graph couldn't be resolved or run, or failed.
+    #[error("Sub-graph error: {message} {location}")]
+    GraphError {
+        message: String,
+        /// The referenced graph, if the node's configuration names one.
+        graph_id: Option<Uuid>,
+        location: ErrorLocation,
+        #[source]
+        source: Option<Box<EngineError>>,
+    },
+}
+
+/// Why a node run through a [`DeadlineExecutor`](crate::DeadlineExecutor)
+/// failed.
+#[derive(Error, Debug)]
+pub enum DeadlineError<E> {
+    /// The wrapped executor failed before the deadline.
+    #[error(transparent)]
+    NodeError(E),
+
+    /// The deadline passed before the wrapped executor returned.
+    #[error("Timeout error: {message} {location}")]
+    TimeoutError {
+        message: String,
+        timeout: Duration,
+        location: ErrorLocation,
+    },
+}
+
+impl EngineError {
+    #[track_caller]
+    pub fn from_model(error: ModelError) -> Self {
+        EngineError::ModelError {
+            message: error.to_string(),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+            source: Some(Box::new(error)),
+        }
+    }
+
+    /// The node this error is attributed to, if any.
+    pub fn node_id(&self) -> Option<Uuid> {
+        match self {
+            EngineError::ExecutionError { node_id, .. }
+            | EngineError::TimeoutError { node_id, .. }
+            | EngineError::BudgetExceededError { node_id, .. }
+            | EngineError::InvalidValueError { node_id, .. }
+            | EngineError::CancelledError { node_id, .. } => Some(*node_id),
+            EngineError::ReplayError { node_id, .. } => *node_id,
+            _ => None,
+        }
+    }
+}
+
+impl From<ModelError> for EngineError {
+    #[track_caller]
+    fn from(error: ModelError) -> Self {
+        EngineError::from_model(error)
+    }
+}
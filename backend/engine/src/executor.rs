@@ -0,0 +1,57 @@
+//! Extension point the engine uses to run individual nodes.
+
+use crate::budget::ExecutionLimits;
+
+use cognexus_model::graph::{ErrorClass, Node, PortValues};
+
+use std::error::Error;
+
+use uuid::Uuid;
+
+/// Executes a single node instance on behalf of the engine.
+///
+/// The engine owns ordering and value routing; an executor only knows how to
+/// turn one node's inputs into its outputs. Implementations bridge to wherever
+/// node logic actually lives (in-process definitions, WASM plugin components).
+pub trait NodeExecutor {
+    /// The error type for execution failures.
+    type Error: Error + Send + Sync + 'static;
+
+    /// Execute `node` with its inputs keyed by input port id.
+    /// Returns outputs keyed by output port id.
+    fn execute(&self, node: &Node, inputs: &PortValues) -> Result<PortValues, Self::Error>;
+
+    /// Execute `node` within `limits`: a time limit and what is left of the
+    /// run's fuel and memory budgets.
+    ///
+    /// The engine reports an attempt that returns after the timeout as timed
+    /// out either way, discarding its outputs. Executors that can stop work
+    /// in progress should override this to give up at the deadline, and
+    /// those that meter fuel or memory should enforce those limits and
+    /// report back through `limits`. The default just calls `execute`, so
+    /// wrap executors that can't stop their work in a
+    /// [`DeadlineExecutor`](crate::DeadlineExecutor) for the run to move on
+    /// at the deadline.
+    fn execute_with_limits(
+        &self,
+        node: &Node,
+        inputs: &PortValues,
+        _limits: &ExecutionLimits,
+    ) -> Result<PortValues, Self::Error> {
+        self.execute(node, inputs)
+    }
+
+    /// Categorize a failure so a node's retry policy can decide whether to
+    /// try again.
+    fn classify_error(&self, _error: &Self::Error) -> ErrorClass {
+        ErrorClass::Unclassified
+    }
+
+    /// Check that `bytes` is a well-formed value of the data type `type_id`,
+    /// for engines that validate the values crossing edges. Executors that
+    /// can't check a type should accept its values, as the default does for
+    /// every type.
+    fn validate_value(&self, _type_id: Uuid, _bytes: &[u8]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+//! Iteration: run a body graph once per item of a list.
+
+use crate::context::ExecutionContext;
+use crate::engine::ExecutionEngine;
+use crate::error::EngineError;
+use crate::executor::NodeExecutor;
+use crate::result::ExecutionResult;
+
+use cognexus_model::graph::{Graph, NodeDefinitionRegistry};
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+use log::info;
+use uuid::Uuid;
+
+/// A loop body and where items enter and results leave it.
+pub struct ForEachLoop {
+    pub(crate) body: Graph,
+    pub(crate) item_input: (Uuid, Uuid),
+    pub(crate) result_output: (Uuid, Uuid),
+    pub(crate) max_iterations: usize,
+}
+
+impl ForEachLoop {
+    pub fn body(&self) -> &Graph {
+        &self.body
+    }
+
+    /// (node id, input port id) receiving each item.
+    pub fn item_input(&self) -> (Uuid, Uuid) {
+        self.item_input
+    }
+
+    /// (node id, output port id) collected from each iteration.
+    pub fn result_output(&self) -> (Uuid, Uuid) {
+        self.result_output
+    }
+
+    pub fn max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+}
+
+/// Outcome of running a loop over a list of items.
+#[derive(Debug)]
+pub struct LoopResult {
+    pub(crate) iterations: Vec<ExecutionResult>,
+    pub(crate) outputs: Vec<Vec<u8>>,
+    pub(crate) item_count: usize,
+}
+
+impl LoopResult {
+    /// One result per iteration that ran, in item order.
+    pub fn iterations(&self) -> &[ExecutionResult] {
+        &self.iterations
+    }
+
+    /// The result output's value from every successful iteration, in item
+    /// order. Iterations that didn't produce it contribute nothing.
+    pub fn outputs(&self) -> &[Vec<u8>] {
+        &self.outputs
+    }
+
+    /// True if every item was processed and every iteration succeeded.
+    pub fn is_success(&self) -> bool {
+        self.iterations.len() == self.item_count
+            && self.iterations.iter().all(ExecutionResult::is_success)
+    }
+}
+
+impl ExecutionEngine {
+    /// Run `body` once per item, feeding each item to its item input and
+    /// collecting its result output.
+    ///
+    /// Iterations run one after another, each as a full run of the body, so
+    /// the engine's parallelism, tracing, caching, and history apply within
+    /// every iteration. The loop stops at the first iteration that doesn't
+    /// succeed, and before starting an iteration once the run is cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are more items than the loop's maximum
+    /// iterations, or if an iteration can't be executed at all (see `run`).
+    #[track_caller]
+    pub fn run_for_each<E>(
+        &self,
+        body: &ForEachLoop,
+        definitions: &NodeDefinitionRegistry,
+        executor: &E,
+        items: &[Vec<u8>],
+    ) -> Result<LoopResult, EngineError>
+    where
+        E: NodeExecutor + Sync,
+    {
+        if items.len() > body.max_iterations {
+            return Err(EngineError::LoopError {
+                message: format!(
+                    "Loop over '{}' has {} item(s), more than its limit of {} iteration(s)",
+                    body.body.name(),
+                    items.len(),
+                    body.max_iterations
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        info!(
+            "Looping graph '{}' over {} item(s)",
+            body.body.name(),
+            items.len()
+        );
+
+        let (item_node, item_port) = body.item_input;
+        let (result_node, result_port) = body.result_output;
+
+        let mut iterations = Vec::with_capacity(items.len());
+        let mut outputs = Vec::with_capacity(items.len());
+
+        for item in items {
+            if self.is_cancelled() {
+                break;
+            }
+
+            let mut context = ExecutionContext::new();
+            context.seed_input(item_node, item_port, item.clone());
+
+            let result = self.run_with(&body.body, definitions, executor, context, None)?;
+
+            if let Some(value) = result
+                .node_result(result_node)
+                .and_then(|r| r.outputs().get(&result_port))
+            {
+                outputs.push(value.to_vec());
+            }
+
+            let succeeded = result.is_success();
+            iterations.push(result);
+            if !succeeded {
+                break;
+            }
+        }
+
+        Ok(LoopResult {
+            iterations,
+            outputs,
+            item_count: items.len(),
+        })
+    }
+}
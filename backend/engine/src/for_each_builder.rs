@@ -0,0 +1,110 @@
+use crate::error::EngineError;
+use crate::for_each::ForEachLoop;
+
+use cognexus_model::graph::Graph;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+use uuid::Uuid;
+
+const DEFAULT_MAX_ITERATIONS: usize = 10_000;
+
+#[derive(Default)]
+pub struct ForEachLoopBuilder {
+    body: Option<Graph>,
+    item_input: Option<(Uuid, Uuid)>,
+    result_output: Option<(Uuid, Uuid)>,
+    max_iterations: Option<usize>,
+}
+
+impl ForEachLoopBuilder {
+    /// The graph executed once per item.
+    pub fn with_body(mut self, body: Graph) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// The unconnected input port of a body node that receives each item.
+    pub fn with_item_input(mut self, node_id: Uuid, port_id: Uuid) -> Self {
+        self.item_input = Some((node_id, port_id));
+        self
+    }
+
+    /// The output port of a body node whose value is collected from every
+    /// iteration.
+    pub fn with_result_output(mut self, node_id: Uuid, port_id: Uuid) -> Self {
+        self.result_output = Some((node_id, port_id));
+        self
+    }
+
+    /// Refuse to run more than `max_iterations` items. Defaults to 10,000.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+
+    #[track_caller]
+    pub fn build(self) -> Result<ForEachLoop, EngineError> {
+        let body = self.body.ok_or_else(|| EngineError::ConfigurationError {
+            message: String::from("Loop body is required"),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let item_input = self
+            .item_input
+            .ok_or_else(|| EngineError::ConfigurationError {
+                message: String::from("Loop item input port is required"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        let result_output = self
+            .result_output
+            .ok_or_else(|| EngineError::ConfigurationError {
+                message: String::from("Loop result output port is required"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        for (role, (node_id, _)) in [("item input", item_input), ("result output", result_output)] {
+            if !body.nodes().iter().any(|node| node.id() == node_id) {
+                return Err(EngineError::ConfigurationError {
+                    message: format!(
+                        "Loop {role} node {node_id} is not in body graph '{}'",
+                        body.name()
+                    ),
+                    location: ErrorLocation::from(Location::caller()),
+                });
+            }
+        }
+
+        if body
+            .edges()
+            .iter()
+            .any(|edge| (edge.target_node_id(), edge.target_port_id()) == item_input)
+        {
+            return Err(EngineError::ConfigurationError {
+                message: format!(
+                    "Loop item input port {} already has an incoming edge",
+                    item_input.1
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        let max_iterations = self.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS);
+        if max_iterations == 0 {
+            return Err(EngineError::ConfigurationError {
+                message: String::from("Loop max iterations must be at least 1"),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        Ok(ForEachLoop {
+            body,
+            item_input,
+            result_output,
+            max_iterations,
+        })
+    }
+}
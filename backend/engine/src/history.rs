@@ -0,0 +1,460 @@
+//! A store of past runs, so executions can be reviewed and their traces
+//! re-opened later.
+
+use crate::error::EngineError;
+use crate::result::{ExecutionResult, NodeStatus};
+use crate::trace::ExecutionTrace;
+
+use cognexus_model::graph::Graph;
+
+use common::error::error_location::ErrorLocation;
+
+use std::cmp::Reverse;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::panic::Location;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Overall outcome of a recorded run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunStatus {
+    /// Every node succeeded or was on a branch that wasn't taken.
+    Succeeded,
+    /// At least one node failed.
+    Failed,
+    /// The run was cancelled before every node could execute.
+    Cancelled,
+}
+
+/// What happened to one node in a recorded run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeRunRecord {
+    pub(crate) node_id: Uuid,
+    pub(crate) status: NodeStatus,
+    pub(crate) error: Option<String>,
+}
+
+impl NodeRunRecord {
+    pub fn node_id(&self) -> Uuid {
+        self.node_id
+    }
+
+    pub fn status(&self) -> NodeStatus {
+        self.status
+    }
+
+    /// The node's error message, if it failed.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+/// One run of a graph, as kept in a [`RunHistory`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub(crate) run_id: Uuid,
+    pub(crate) graph_id: Uuid,
+    pub(crate) graph_name: String,
+    pub(crate) snapshot_hash: u64,
+    pub(crate) started_at: SystemTime,
+    pub(crate) finished_at: SystemTime,
+    pub(crate) nodes: Vec<NodeRunRecord>,
+    pub(crate) trace: ExecutionTrace,
+}
+
+impl RunRecord {
+    /// Capture the outcome of a run of `graph`.
+    pub fn new(
+        graph: &Graph,
+        result: &ExecutionResult,
+        started_at: SystemTime,
+        finished_at: SystemTime,
+    ) -> Self {
+        let nodes = result
+            .node_results()
+            .iter()
+            .map(|r| NodeRunRecord {
+                node_id: r.node_id(),
+                status: r.status(),
+                error: r.error().map(ToString::to_string),
+            })
+            .collect();
+
+        Self {
+            run_id: result.run_id(),
+            graph_id: graph.id(),
+            graph_name: String::from(graph.name()),
+            snapshot_hash: snapshot_hash(graph),
+            started_at,
+            finished_at,
+            nodes,
+            trace: result.trace().clone(),
+        }
+    }
+
+    pub fn run_id(&self) -> Uuid {
+        self.run_id
+    }
+
+    pub fn graph_id(&self) -> Uuid {
+        self.graph_id
+    }
+
+    /// The graph's name at the time of the run.
+    pub fn graph_name(&self) -> &str {
+        &self.graph_name
+    }
+
+    /// Hash of the graph's structure when it ran; see [`snapshot_hash`].
+    pub fn snapshot_hash(&self) -> u64 {
+        self.snapshot_hash
+    }
+
+    pub fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    pub fn finished_at(&self) -> SystemTime {
+        self.finished_at
+    }
+
+    /// Wall-clock duration of the run.
+    pub fn duration(&self) -> Duration {
+        self.finished_at
+            .duration_since(self.started_at)
+            .unwrap_or_default()
+    }
+
+    /// Per-node outcomes in execution order.
+    pub fn nodes(&self) -> &[NodeRunRecord] {
+        &self.nodes
+    }
+
+    /// The trace recorded during the run.
+    pub fn trace(&self) -> &ExecutionTrace {
+        &self.trace
+    }
+
+    pub fn status(&self) -> RunStatus {
+        if self.nodes.iter().any(|n| n.status == NodeStatus::Cancelled) {
+            RunStatus::Cancelled
+        } else if self.nodes.iter().all(|n| n.status.is_success()) {
+            RunStatus::Succeeded
+        } else {
+            RunStatus::Failed
+        }
+    }
+}
+
+/// Hash of a graph's structure: its nodes' ids and definitions, and its edges.
+///
+/// Names and layout don't contribute, so two runs share a hash exactly when
+/// they executed the same graph shape. It's 64-bit FNV-1a over the graph's
+/// id, then the sorted nodes and edges, each list as its length followed by
+/// the bytes of its UUIDs, so it's the same on every build and platform and
+/// hashes kept in history files and recordings go on matching.
+pub fn snapshot_hash(graph: &Graph) -> u64 {
+    let mut nodes: Vec<(Uuid, Uuid)> = graph
+        .nodes()
+        .iter()
+        .map(|n| (n.id(), n.definition_id()))
+        .collect();
+    nodes.sort_unstable();
+
+    let mut edges: Vec<(Uuid, Uuid, Uuid, Uuid)> = graph
+        .edges()
+        .iter()
+        .map(|e| {
+            (
+                e.source_node_id(),
+                e.source_port_id(),
+                e.target_node_id(),
+                e.target_port_id(),
+            )
+        })
+        .collect();
+    edges.sort_unstable();
+
+    let mut hasher = Fnv1a::new();
+    hasher.write(graph.id().as_bytes());
+    hasher.write(&(nodes.len() as u64).to_le_bytes());
+    for (node_id, definition_id) in &nodes {
+        hasher.write(node_id.as_bytes());
+        hasher.write(definition_id.as_bytes());
+    }
+    hasher.write(&(edges.len() as u64).to_le_bytes());
+    for (source_node, source_port, target_node, target_port) in &edges {
+        for id in [source_node, source_port, target_node, target_port] {
+            hasher.write(id.as_bytes());
+        }
+    }
+    hasher.finish()
+}
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher` its output is fixed, which hashes
+/// that are persisted need.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Which runs a [`RunHistory`] query returns. Unset criteria match any run.
+#[derive(Debug, Clone, Default)]
+pub struct RunQuery {
+    graph_id: Option<Uuid>,
+    snapshot_hash: Option<u64>,
+    status: Option<RunStatus>,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    limit: Option<usize>,
+}
+
+impl RunQuery {
+    pub fn with_graph_id(mut self, graph_id: Uuid) -> Self {
+        self.graph_id = Some(graph_id);
+        self
+    }
+
+    pub fn with_snapshot_hash(mut self, snapshot_hash: u64) -> Self {
+        self.snapshot_hash = Some(snapshot_hash);
+        self
+    }
+
+    pub fn with_status(mut self, status: RunStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only runs started at or after `since`.
+    pub fn with_since(mut self, since: SystemTime) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Only runs started before `until`.
+    pub fn with_until(mut self, until: SystemTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Return at most `limit` runs, newest first.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn matches(&self, record: &RunRecord) -> bool {
+        self.graph_id.is_none_or(|id| record.graph_id == id)
+            && self
+                .snapshot_hash
+                .is_none_or(|hash| record.snapshot_hash == hash)
+            && self.status.is_none_or(|status| record.status() == status)
+            && self.since.is_none_or(|since| record.started_at >= since)
+            && self.until.is_none_or(|until| record.started_at < until)
+    }
+}
+
+/// Recorded runs, optionally persisted to a file.
+///
+/// A persistent history appends one JSON line per run to its file, so it
+/// survives restarts and can be shared by every engine in the process.
+#[derive(Debug)]
+pub struct RunHistory {
+    path: Option<PathBuf>,
+    records: Mutex<Vec<RunRecord>>,
+}
+
+impl RunHistory {
+    /// A history kept only for the lifetime of the process.
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Open the history persisted at `path`, creating it on the first
+    /// recorded run if it doesn't exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but can't be read or contains a
+    /// line that isn't a run record.
+    #[track_caller]
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, EngineError> {
+        let path = path.into();
+
+        let records = match File::open(&path) {
+            Ok(file) => read_records(&path, file)?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(history_error(&path, "could not be opened", e)),
+        };
+
+        Ok(Self {
+            path: Some(path),
+            records: Mutex::new(records),
+        })
+    }
+
+    /// The file the history is persisted to, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Add a run to the history, persisting it if the history has a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record can't be written; it is then not kept
+    /// in memory either.
+    #[track_caller]
+    pub fn record(&self, record: RunRecord) -> Result<(), EngineError> {
+        let mut records = self.lock();
+
+        if let Some(path) = &self.path {
+            let mut line = serde_json::to_string(&record)
+                .map_err(|e| history_error(path, "could not be serialized", e))?;
+            line.push('\n');
+
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| file.write_all(line.as_bytes()))
+                .map_err(|e| history_error(path, "could not be written", e))?;
+        }
+
+        records.push(record);
+        Ok(())
+    }
+
+    pub fn get(&self, run_id: Uuid) -> Option<RunRecord> {
+        self.lock().iter().find(|r| r.run_id == run_id).cloned()
+    }
+
+    /// Runs matching `query`, newest first.
+    pub fn query(&self, query: &RunQuery) -> Vec<RunRecord> {
+        let records = self.lock();
+        let mut matching: Vec<RunRecord> = records
+            .iter()
+            .filter(|r| query.matches(r))
+            .cloned()
+            .collect();
+
+        matching.sort_by_key(|r| Reverse(r.started_at));
+        if let Some(limit) = query.limit {
+            matching.truncate(limit);
+        }
+        matching
+    }
+
+    /// The most recent run of a graph.
+    pub fn latest(&self, graph_id: Uuid) -> Option<RunRecord> {
+        self.query(&RunQuery::default().with_graph_id(graph_id).with_limit(1))
+            .pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<RunRecord>> {
+        self.records.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[track_caller]
+fn read_records(path: &Path, file: File) -> Result<Vec<RunRecord>, EngineError> {
+    let mut records = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| history_error(path, "could not be read", e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record = serde_json::from_str(&line)
+            .map_err(|e| history_error(path, "contains an invalid record", e))?;
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[track_caller]
+fn history_error(
+    path: &Path,
+    problem: &str,
+    error: impl std::error::Error + Send + Sync + 'static,
+) -> EngineError {
+    EngineError::HistoryError {
+        message: format!("Run history '{}' {problem}: {error}", path.display()),
+        location: ErrorLocation::from(Location::caller()),
+        source: Some(Box::new(error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cognexus_model::graph::GraphBuilder;
+
+    fn fnv1a(bytes: &[u8]) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    #[test]
+    fn fnv1a_matches_reference_values() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn snapshot_hash_is_stable() {
+        let id = Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+        let graph = |name: &str| {
+            GraphBuilder::default()
+                .with_id(id)
+                .with_name(name)
+                .build()
+                .unwrap()
+        };
+
+        // Pinned, since history files and recordings keep it
+        assert_eq!(snapshot_hash(&graph("graph")), 0x742e_e419_b6fa_81e5);
+        assert_eq!(
+            snapshot_hash(&graph("graph")),
+            snapshot_hash(&graph("renamed"))
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! Execution engine for running node graphs.
+
+mod budget;
+mod cache;
+mod cancellation;
+mod context;
+mod deadline_executor;
+mod debugger;
+mod dry_run;
+mod engine;
+mod engine_builder;
+mod error;
+mod executor;
+mod for_each;
+mod for_each_builder;
+mod history;
+mod plan;
+mod progress;
+mod replay;
+mod result;
+mod streaming;
+mod sub_graph;
+mod sub_graph_builder;
+mod trace;
+mod wasm_executor;
+
+pub use budget::{BudgetExceeded, BudgetResource, ExecutionLimits, RunBudget};
+pub use cache::{CacheStats, OutputCache};
+pub use cancellation::CancellationToken;
+pub use context::ExecutionContext;
+pub use deadline_executor::DeadlineExecutor;
+pub use debugger::{Breakpoint, Debugger, PauseInfo, PausePosition};
+pub use dry_run::{DryRunReport, PlanIssue};
+pub use engine::ExecutionEngine;
+pub use engine_builder::ExecutionEngineBuilder;
+pub use error::{DeadlineError, EngineError, SubGraphError};
+pub use executor::NodeExecutor;
+pub use for_each::{ForEachLoop, LoopResult};
+pub use for_each_builder::ForEachLoopBuilder;
+pub use history::{NodeRunRecord, RunHistory, RunQuery, RunRecord, RunStatus, snapshot_hash};
+pub use plan::ExecutionPlan;
+pub use progress::{ProgressEvent, ProgressEventKind, ProgressSink};
+pub use replay::{RECORDING_FORMAT_VERSION, RecordedAttempt, RunRecording};
+pub use result::{ExecutionResult, NodeResult, NodeStatus};
+pub use streaming::{InputStream, InputStreams, OutputStreams, StreamingNodeExecutor};
+pub use sub_graph::{GraphLibrary, SubGraphExecutor, SubGraphNode};
+pub use sub_graph_builder::SubGraphNodeBuilder;
+pub use trace::{ExecutionTrace, InspectedValue, LogLevel, TraceEvent, TraceEventKind, TraceSink};
+pub use wasm_executor::WasmNodeExecutor;
@@ -0,0 +1,128 @@
+//! Execution planning: resolving the order in which graph nodes run.
+
+use crate::error::EngineError;
+
+use cognexus_model::graph::Graph;
+
+use common::error::error_location::ErrorLocation;
+
+use std::collections::HashMap;
+use std::panic::Location;
+
+use uuid::Uuid;
+
+/// A resolved execution order for a graph, grouped into stages.
+///
+/// Every node appears in a later stage than all of the nodes feeding its
+/// inputs, so nodes within one stage are independent of each other and may
+/// run concurrently. Within a stage, nodes keep the order they were added to
+/// the graph, so plans are deterministic for a given graph.
+pub struct ExecutionPlan {
+    stages: Vec<Vec<Uuid>>,
+    order: Vec<Uuid>,
+}
+
+impl ExecutionPlan {
+    /// Resolve staged topological order for `graph` using Kahn's algorithm.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::GraphError` if an edge references a node that is
+    /// not in the graph, or if the graph contains a cycle.
+    #[track_caller]
+    pub fn build(graph: &Graph) -> Result<Self, EngineError> {
+        let index: HashMap<Uuid, usize> = graph
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(position, node)| (node.id(), position))
+            .collect();
+
+        let mut in_degree = vec![0usize; graph.nodes().len()];
+        let mut downstream: Vec<Vec<usize>> = vec![Vec::new(); graph.nodes().len()];
+
+        for edge in graph.edges() {
+            let source =
+                *index
+                    .get(&edge.source_node_id())
+                    .ok_or_else(|| EngineError::GraphError {
+                        message: format!(
+                            "Edge {} references unknown source node {}",
+                            edge.id(),
+                            edge.source_node_id()
+                        ),
+                        location: ErrorLocation::from(Location::caller()),
+                    })?;
+
+            let target =
+                *index
+                    .get(&edge.target_node_id())
+                    .ok_or_else(|| EngineError::GraphError {
+                        message: format!(
+                            "Edge {} references unknown target node {}",
+                            edge.id(),
+                            edge.target_node_id()
+                        ),
+                        location: ErrorLocation::from(Location::caller()),
+                    })?;
+
+            downstream[source].push(target);
+            in_degree[target] += 1;
+        }
+
+        let mut ready: Vec<usize> = (0..graph.nodes().len())
+            .filter(|&position| in_degree[position] == 0)
+            .collect();
+        let mut stages = Vec::new();
+        let mut ordered = 0;
+
+        while !ready.is_empty() {
+            ready.sort_unstable();
+            ordered += ready.len();
+
+            let mut next = Vec::new();
+            for &position in &ready {
+                for &target in &downstream[position] {
+                    in_degree[target] -= 1;
+                    if in_degree[target] == 0 {
+                        next.push(target);
+                    }
+                }
+            }
+
+            stages.push(
+                ready
+                    .iter()
+                    .map(|&position| graph.nodes()[position].id())
+                    .collect::<Vec<_>>(),
+            );
+            ready = next;
+        }
+
+        if ordered != graph.nodes().len() {
+            return Err(EngineError::GraphError {
+                message: format!(
+                    "Graph '{}' contains a cycle ({} of {} node(s) could not be ordered)",
+                    graph.name(),
+                    graph.nodes().len() - ordered,
+                    graph.nodes().len()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        let order = stages.iter().flatten().copied().collect();
+
+        Ok(Self { stages, order })
+    }
+
+    /// Groups of mutually independent node ids, in execution order.
+    pub fn stages(&self) -> &[Vec<Uuid>] {
+        &self.stages
+    }
+
+    /// Node ids in execution order (all stages flattened).
+    pub fn order(&self) -> &[Uuid] {
+        &self.order
+    }
+}
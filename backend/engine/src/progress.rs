@@ -0,0 +1,147 @@
+//! Coarse run progress for driving a UI: what is queued, running, and done.
+
+use crate::trace::TraceEventKind;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+
+use uuid::Uuid;
+
+/// What changed in a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressEventKind {
+    RunStarted,
+    /// The node's stage was reached; it will start once a worker is free.
+    NodeQueued,
+    NodeStarted,
+    /// The node produced its outputs (executed or reused from the cache).
+    NodeFinished,
+    NodeFailed {
+        message: String,
+    },
+    /// The node won't run, because an earlier node failed or a branch
+    /// feeding it was not taken.
+    NodeSkipped,
+    NodeCancelled,
+    RunFinished {
+        success: bool,
+    },
+}
+
+/// One progress update, with how many nodes have reached a final state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressEvent {
+    pub(crate) graph_id: Uuid,
+    pub(crate) node_id: Option<Uuid>,
+    pub(crate) kind: ProgressEventKind,
+    pub(crate) completed: usize,
+    pub(crate) total: usize,
+}
+
+impl ProgressEvent {
+    pub fn graph_id(&self) -> Uuid {
+        self.graph_id
+    }
+
+    /// The node the event is about; `None` for run-level events.
+    pub fn node_id(&self) -> Option<Uuid> {
+        self.node_id
+    }
+
+    pub fn kind(&self) -> &ProgressEventKind {
+        &self.kind
+    }
+
+    /// Nodes that have finished, failed, been skipped, or been cancelled.
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn percent_complete(&self) -> f32 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.completed as f32 * 100.0 / self.total as f32
+        }
+    }
+}
+
+/// Receives progress events live, as the run advances.
+///
+/// Like trace sinks, progress sinks are called from worker threads and
+/// should hand events off quickly (e.g. to a UI event loop).
+pub trait ProgressSink: Send + Sync {
+    fn on_progress(&self, event: &ProgressEvent);
+}
+
+impl ProgressSink for Sender<ProgressEvent> {
+    fn on_progress(&self, event: &ProgressEvent) {
+        // A dropped receiver just means nobody is watching any more
+        let _ = self.send(event.clone());
+    }
+}
+
+/// Turns one run's node activity into progress events for a sink.
+pub(crate) struct ProgressReporter<'a> {
+    sink: &'a dyn ProgressSink,
+    graph_id: Uuid,
+    total: usize,
+    completed: AtomicUsize,
+}
+
+impl<'a> ProgressReporter<'a> {
+    pub(crate) fn new(sink: &'a dyn ProgressSink, graph_id: Uuid, total: usize) -> Self {
+        Self {
+            sink,
+            graph_id,
+            total,
+            completed: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn report(&self, node_id: Option<Uuid>, kind: ProgressEventKind) {
+        let completes = matches!(
+            kind,
+            ProgressEventKind::NodeFinished
+                | ProgressEventKind::NodeFailed { .. }
+                | ProgressEventKind::NodeSkipped
+                | ProgressEventKind::NodeCancelled
+        );
+
+        let completed = if completes {
+            self.completed.fetch_add(1, Ordering::SeqCst) + 1
+        } else {
+            self.completed.load(Ordering::SeqCst)
+        };
+
+        self.sink.on_progress(&ProgressEvent {
+            graph_id: self.graph_id,
+            node_id,
+            kind,
+            completed,
+            total: self.total,
+        });
+    }
+
+    /// Derive the progress event, if any, for a node's trace event.
+    pub(crate) fn on_trace(&self, node_id: Uuid, kind: &TraceEventKind) {
+        let kind = match kind {
+            TraceEventKind::Started { .. } => ProgressEventKind::NodeStarted,
+            TraceEventKind::Finished { .. } | TraceEventKind::Cached { .. } => {
+                ProgressEventKind::NodeFinished
+            }
+            TraceEventKind::Failed { message, .. } => ProgressEventKind::NodeFailed {
+                message: message.clone(),
+            },
+            TraceEventKind::Skipped | TraceEventKind::NotTaken => ProgressEventKind::NodeSkipped,
+            TraceEventKind::Cancelled => ProgressEventKind::NodeCancelled,
+            TraceEventKind::Retrying { .. } | TraceEventKind::Logged { .. } => return,
+        };
+
+        self.report(Some(node_id), kind);
+    }
+}
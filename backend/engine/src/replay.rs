@@ -0,0 +1,351 @@
+//! Deterministic record and replay: capture everything a run read from
+//! outside the graph, then run it again against the same reads to reproduce
+//! the execution exactly while debugging.
+
+use crate::budget::ExecutionLimits;
+use crate::context::ExecutionContext;
+use crate::engine::ExecutionEngine;
+use crate::error::EngineError;
+use crate::executor::NodeExecutor;
+use crate::history::snapshot_hash;
+use crate::result::ExecutionResult;
+
+use cognexus_model::graph::{Graph, Node, NodeDefinitionRegistry, PortValues};
+use cognexus_plugin_manager::{HostInputs, HostMode};
+
+use common::error::error_location::ErrorLocation;
+
+use std::fs;
+use std::panic::Location;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What one execution attempt of a node received.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedAttempt {
+    pub(crate) node_id: Uuid,
+    pub(crate) attempt: u32,
+    /// Input values sorted by port id, so equal inputs compare equal.
+    pub(crate) inputs: Vec<(Uuid, Vec<u8>)>,
+    pub(crate) host_inputs: Option<HostInputs>,
+}
+
+impl RecordedAttempt {
+    fn new(node: &Node, attempt: u32, inputs: &PortValues) -> Self {
+        Self {
+            node_id: node.id(),
+            attempt,
+            inputs: sorted_inputs(inputs),
+            host_inputs: None,
+        }
+    }
+
+    pub fn node_id(&self) -> Uuid {
+        self.node_id
+    }
+
+    /// Which attempt this was, starting at 1.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    pub fn inputs(&self) -> PortValues {
+        self.inputs.iter().cloned().collect()
+    }
+
+    /// Clock readings and randomness the executor reported, if it records
+    /// them. The WASM executor does for attempts that don't fail.
+    pub fn host_inputs(&self) -> Option<&HostInputs> {
+        self.host_inputs.as_ref()
+    }
+}
+
+/// Version of the recording format, bumped whenever what a recording holds
+/// changes meaning.
+pub const RECORDING_FORMAT_VERSION: u32 = 1;
+
+/// Everything one run read from outside the graph, for replaying it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecording {
+    pub(crate) format_version: u32,
+    pub(crate) graph_id: Uuid,
+    pub(crate) snapshot_hash: u64,
+    pub(crate) attempts: Vec<RecordedAttempt>,
+}
+
+impl RunRecording {
+    /// The [`RECORDING_FORMAT_VERSION`] the recording was made with.
+    pub fn format_version(&self) -> u32 {
+        self.format_version
+    }
+
+    pub fn graph_id(&self) -> Uuid {
+        self.graph_id
+    }
+
+    /// Structure hash of the recorded graph; see
+    /// [`snapshot_hash`](crate::snapshot_hash).
+    pub fn snapshot_hash(&self) -> u64 {
+        self.snapshot_hash
+    }
+
+    /// Every recorded attempt, in the order they finished.
+    pub fn attempts(&self) -> &[RecordedAttempt] {
+        &self.attempts
+    }
+
+    pub fn attempt(&self, node_id: Uuid, attempt: u32) -> Option<&RecordedAttempt> {
+        self.attempts
+            .iter()
+            .find(|a| a.node_id == node_id && a.attempt == attempt)
+    }
+
+    /// Write the recording to `path` as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    #[track_caller]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), EngineError> {
+        let path = path.as_ref();
+
+        let json = serde_json::to_vec(self)
+            .map_err(|e| replay_error(path, "could not be serialized", e))?;
+        fs::write(path, json).map_err(|e| replay_error(path, "could not be written", e))
+    }
+
+    /// Read a recording written by [`RunRecording::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a recording.
+    #[track_caller]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EngineError> {
+        let path = path.as_ref();
+
+        let json = fs::read(path).map_err(|e| replay_error(path, "could not be read", e))?;
+        serde_json::from_slice(&json).map_err(|e| replay_error(path, "is not a run recording", e))
+    }
+}
+
+/// Records or replays the attempts of one run.
+pub(crate) enum ReplaySession<'a> {
+    Recording(Mutex<Vec<RecordedAttempt>>),
+    Replaying(&'a RunRecording),
+}
+
+impl ReplaySession<'_> {
+    /// Set `limits` up to record or replay the attempt's host inputs.
+    ///
+    /// # Errors
+    ///
+    /// When replaying, returns an error if the recording has no such attempt
+    /// or the node received different inputs than it did then.
+    #[track_caller]
+    pub(crate) fn prepare(
+        &self,
+        node: &Node,
+        attempt: u32,
+        inputs: &PortValues,
+        limits: ExecutionLimits,
+    ) -> Result<ExecutionLimits, EngineError> {
+        let recording = match self {
+            ReplaySession::Recording(_) => return Ok(limits.with_host_mode(HostMode::Record)),
+            ReplaySession::Replaying(recording) => recording,
+        };
+
+        let recorded = recording.attempt(node.id(), attempt).ok_or_else(|| {
+            diverged(
+                node,
+                format!(
+                    "attempt {attempt} of node '{}' was not recorded",
+                    node.name()
+                ),
+            )
+        })?;
+
+        if recorded.inputs != sorted_inputs(inputs) {
+            return Err(diverged(
+                node,
+                format!(
+                    "node '{}' received different inputs on attempt {attempt}",
+                    node.name()
+                ),
+            ));
+        }
+
+        let host = recorded
+            .host_inputs
+            .clone()
+            .map_or(HostMode::Live, HostMode::Replay);
+        Ok(limits.with_host_mode(host))
+    }
+
+    /// Keep the attempt, if recording.
+    pub(crate) fn finish(
+        &self,
+        node: &Node,
+        attempt: u32,
+        inputs: &PortValues,
+        limits: &ExecutionLimits,
+    ) {
+        if let ReplaySession::Recording(attempts) = self {
+            let mut recorded = RecordedAttempt::new(node, attempt, inputs);
+            recorded.host_inputs = limits.take_host_inputs();
+
+            attempts
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(recorded);
+        }
+    }
+}
+
+impl ExecutionEngine {
+    /// Run `graph` like [`ExecutionEngine::run`], recording every node's
+    /// inputs and what its executor reports reading from the host (clock
+    /// readings and randomness), so the run can be replayed later.
+    ///
+    /// The output cache is bypassed so every node actually executes.
+    ///
+    /// # Errors
+    ///
+    /// As for [`ExecutionEngine::run`].
+    #[track_caller]
+    pub fn run_recorded<E>(
+        &self,
+        graph: &Graph,
+        definitions: &NodeDefinitionRegistry,
+        executor: &E,
+    ) -> Result<(ExecutionResult, RunRecording), EngineError>
+    where
+        E: NodeExecutor + Sync,
+    {
+        let session = ReplaySession::Recording(Mutex::new(Vec::new()));
+        let result = self.run_with(
+            graph,
+            definitions,
+            executor,
+            ExecutionContext::new(),
+            Some(&session),
+        )?;
+
+        let ReplaySession::Recording(attempts) = session else {
+            unreachable!("session was created recording");
+        };
+        let recording = RunRecording {
+            format_version: RECORDING_FORMAT_VERSION,
+            graph_id: graph.id(),
+            snapshot_hash: snapshot_hash(graph),
+            attempts: attempts
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner),
+        };
+
+        Ok((result, recording))
+    }
+
+    /// Run `graph` again, feeding each attempt the host inputs recorded for
+    /// it, to reproduce a recorded run.
+    ///
+    /// A node whose inputs differ from the recording fails, as does an
+    /// attempt the recording doesn't have (e.g. a retry that didn't happen
+    /// originally): from that point on the run is no longer a replay. Nodes
+    /// whose executor didn't record host inputs run live. The output cache
+    /// is bypassed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the recording is of another format version, or of
+    /// a different graph or graph structure, or as for
+    /// [`ExecutionEngine::run`].
+    #[track_caller]
+    pub fn replay<E>(
+        &self,
+        graph: &Graph,
+        definitions: &NodeDefinitionRegistry,
+        executor: &E,
+        recording: &RunRecording,
+    ) -> Result<ExecutionResult, EngineError>
+    where
+        E: NodeExecutor + Sync,
+    {
+        if recording.format_version != RECORDING_FORMAT_VERSION {
+            return Err(EngineError::ReplayError {
+                message: format!(
+                    "Recording is in format version {} and can't be replayed by this engine, \
+                     which reads version {RECORDING_FORMAT_VERSION}; record the run again",
+                    recording.format_version
+                ),
+                node_id: None,
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            });
+        }
+
+        if recording.graph_id != graph.id() || recording.snapshot_hash != snapshot_hash(graph) {
+            return Err(EngineError::ReplayError {
+                message: format!(
+                    "Recording is of graph {} and can't replay graph '{}' ({}) as it is now",
+                    recording.graph_id,
+                    graph.name(),
+                    graph.id()
+                ),
+                node_id: None,
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            });
+        }
+
+        info!(
+            "Replaying graph '{}' from {} recorded attempt(s)",
+            graph.name(),
+            recording.attempts.len()
+        );
+
+        self.run_with(
+            graph,
+            definitions,
+            executor,
+            ExecutionContext::new(),
+            Some(&ReplaySession::Replaying(recording)),
+        )
+    }
+}
+
+fn sorted_inputs(inputs: &PortValues) -> Vec<(Uuid, Vec<u8>)> {
+    let mut sorted: Vec<(Uuid, Vec<u8>)> = inputs
+        .iter()
+        .map(|(port_id, bytes)| (*port_id, bytes.to_vec()))
+        .collect();
+    sorted.sort_unstable();
+    sorted
+}
+
+#[track_caller]
+fn diverged(node: &Node, problem: String) -> EngineError {
+    EngineError::ReplayError {
+        message: format!("Replay diverged from the recording: {problem}"),
+        node_id: Some(node.id()),
+        location: ErrorLocation::from(Location::caller()),
+        source: None,
+    }
+}
+
+#[track_caller]
+fn replay_error(
+    path: &Path,
+    problem: &str,
+    error: impl std::error::Error + Send + Sync + 'static,
+) -> EngineError {
+    EngineError::ReplayError {
+        message: format!("Run recording '{}' {problem}: {error}", path.display()),
+        node_id: None,
+        location: ErrorLocation::from(Location::caller()),
+        source: Some(Box::new(error)),
+    }
+}
@@ -0,0 +1,115 @@
+//! Results produced by running a graph.
+
+use crate::budget::BudgetExceeded;
+use crate::error::EngineError;
+use crate::trace::ExecutionTrace;
+
+use cognexus_model::graph::PortValues;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Final state of a single node after a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeStatus {
+    /// The node executed and produced its outputs.
+    Succeeded,
+    /// The node's executor returned an error.
+    Failed,
+    /// The node never ran because an earlier node failed.
+    Skipped,
+    /// The node never ran because the run was cancelled or ran out of
+    /// budget.
+    Cancelled,
+    /// The node never ran because a branch feeding it was not taken.
+    NotTaken,
+}
+
+impl NodeStatus {
+    /// True if the node ended the way a successful run allows: it either
+    /// ran, or sat on a branch that wasn't taken.
+    pub fn is_success(self) -> bool {
+        matches!(self, NodeStatus::Succeeded | NodeStatus::NotTaken)
+    }
+}
+
+/// Outcome of executing one node.
+#[derive(Debug)]
+pub struct NodeResult {
+    pub(crate) node_id: Uuid,
+    pub(crate) status: NodeStatus,
+    pub(crate) outputs: PortValues,
+    pub(crate) error: Option<EngineError>,
+}
+
+impl NodeResult {
+    pub fn node_id(&self) -> Uuid {
+        self.node_id
+    }
+
+    pub fn status(&self) -> NodeStatus {
+        self.status
+    }
+
+    /// Outputs keyed by output port id. Empty unless the node succeeded.
+    pub fn outputs(&self) -> &PortValues {
+        &self.outputs
+    }
+
+    pub fn error(&self) -> Option<&EngineError> {
+        self.error.as_ref()
+    }
+}
+
+/// Outcome of running a whole graph.
+#[derive(Debug)]
+pub struct ExecutionResult {
+    pub(crate) run_id: Uuid,
+    pub(crate) graph_id: Uuid,
+    pub(crate) node_results: Vec<NodeResult>,
+    pub(crate) trace: ExecutionTrace,
+    pub(crate) budget_exceeded: Option<BudgetExceeded>,
+}
+
+impl ExecutionResult {
+    /// Identifies this run, e.g. in a [`RunHistory`](crate::RunHistory).
+    pub fn run_id(&self) -> Uuid {
+        self.run_id
+    }
+
+    pub fn graph_id(&self) -> Uuid {
+        self.graph_id
+    }
+
+    /// Per-node results in execution order.
+    pub fn node_results(&self) -> &[NodeResult] {
+        &self.node_results
+    }
+
+    /// Find the result for a specific node.
+    pub fn node_result(&self, node_id: Uuid) -> Option<&NodeResult> {
+        self.node_results.iter().find(|r| r.node_id == node_id)
+    }
+
+    /// Per-node trace events recorded during the run.
+    pub fn trace(&self) -> &ExecutionTrace {
+        &self.trace
+    }
+
+    /// Which budget stopped the run, if one did.
+    pub fn budget_exceeded(&self) -> Option<&BudgetExceeded> {
+        self.budget_exceeded.as_ref()
+    }
+
+    /// True if the run was cancelled before every node could execute.
+    pub fn is_cancelled(&self) -> bool {
+        self.node_results
+            .iter()
+            .any(|r| r.status == NodeStatus::Cancelled)
+    }
+
+    /// True if every node succeeded or was on a branch that wasn't taken.
+    pub fn is_success(&self) -> bool {
+        self.node_results.iter().all(|r| r.status.is_success())
+    }
+}
@@ -0,0 +1,333 @@
+//! Streaming execution: nodes exchange chunks over channels instead of
+//! handing over a single value per port.
+
+use crate::engine::{ExecutionEngine, incoming_edges};
+use crate::error::EngineError;
+use crate::plan::ExecutionPlan;
+use crate::progress::ProgressEventKind;
+use crate::result::{ExecutionResult, NodeResult, NodeStatus};
+use crate::trace::{TraceEventKind, Tracer};
+
+use cognexus_model::graph::{Graph, Node, NodeDefinitionRegistry, PortValues};
+
+use common::error::error_location::ErrorLocation;
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::panic::{Location, resume_unwind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+use std::thread;
+use std::time::{Instant, SystemTime};
+
+use log::{debug, info, warn};
+use uuid::Uuid;
+
+/// Chunks arriving on one input port, ending when the upstream node finishes.
+pub struct InputStream {
+    receiver: Receiver<Vec<u8>>,
+}
+
+impl InputStream {
+    /// Block for the next chunk. Returns `None` once the stream has ended.
+    pub fn recv(&self) -> Option<Vec<u8>> {
+        self.receiver.recv().ok()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        self.receiver.iter()
+    }
+}
+
+/// The connected input streams of one node, keyed by input port id.
+/// Unconnected input ports have no stream.
+#[derive(Default)]
+pub struct InputStreams {
+    streams: HashMap<Uuid, InputStream>,
+}
+
+impl InputStreams {
+    pub fn get(&self, port_id: &Uuid) -> Option<&InputStream> {
+        self.streams.get(port_id)
+    }
+
+    pub fn port_ids(&self) -> impl Iterator<Item = &Uuid> {
+        self.streams.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.streams.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.streams.is_empty()
+    }
+}
+
+/// Where one node sends chunks, keyed by output port id.
+///
+/// A chunk sent on a port is delivered to every edge leaving it. Sending
+/// blocks while a downstream buffer is full, which is what keeps a fast
+/// producer from running ahead of a slow consumer.
+pub struct OutputStreams {
+    node_id: Uuid,
+    node_name: String,
+    declared: HashSet<Uuid>,
+    senders: HashMap<Uuid, Vec<SyncSender<Vec<u8>>>>,
+    chunks_sent: AtomicUsize,
+    bytes_sent: AtomicUsize,
+}
+
+impl OutputStreams {
+    /// Send one chunk on `port_id`.
+    ///
+    /// Downstream nodes that already stopped reading are silently skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `port_id` isn't an output port of the node.
+    #[track_caller]
+    pub fn send(&self, port_id: Uuid, chunk: Vec<u8>) -> Result<(), EngineError> {
+        if !self.declared.contains(&port_id) {
+            return Err(EngineError::ExecutionError {
+                message: format!(
+                    "Node '{}' sent a chunk on undeclared output port {port_id}",
+                    self.node_name
+                ),
+                node_id: self.node_id,
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            });
+        }
+
+        self.chunks_sent.fetch_add(1, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(chunk.len(), Ordering::Relaxed);
+
+        if let Some(senders) = self.senders.get(&port_id) {
+            for sender in senders {
+                let _ = sender.send(chunk.clone());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes a single node in streaming mode.
+///
+/// The executor reads chunks from its input streams as they arrive and sends
+/// output chunks as it produces them. Returning ends every output stream.
+pub trait StreamingNodeExecutor {
+    /// The error type for execution failures.
+    type Error: Error + Send + Sync + 'static;
+
+    fn execute_stream(
+        &self,
+        node: &Node,
+        inputs: InputStreams,
+        outputs: &OutputStreams,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Everything one streaming node needs, built before any node starts.
+struct StreamTask<'a> {
+    node: &'a Node,
+    inputs: InputStreams,
+    outputs: OutputStreams,
+}
+
+impl ExecutionEngine {
+    /// Run `graph` in streaming mode.
+    ///
+    /// Every node gets its own thread and all of them run at once, connected
+    /// by bounded channels of `stream_buffer` chunks per edge, so the
+    /// parallelism limit does not apply. A failed node ends its output
+    /// streams early; downstream nodes see the end of their input and carry
+    /// on, but the run is reported as failed. A node joining several streams
+    /// should interleave its reads, or a full buffer on one edge can stall the
+    /// producers feeding the others. Output caching, retries, timeouts,
+    /// budgets, branch skipping, recording, type conversions, value
+    /// validation, and debugger checkpoints only apply to `run` (a branch
+    /// that isn't taken here is just a stream that ends without chunks);
+    /// cancellation is checked before each node starts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the graph cannot be executed at all: it contains a
+    /// cycle, references an unregistered node definition, or wires more than
+    /// one edge into the same input port.
+    #[track_caller]
+    pub fn run_streaming<E>(
+        &self,
+        graph: &Graph,
+        definitions: &NodeDefinitionRegistry,
+        executor: &E,
+    ) -> Result<ExecutionResult, EngineError>
+    where
+        E: StreamingNodeExecutor + Sync,
+    {
+        let plan = ExecutionPlan::build(graph)?;
+        let nodes: HashMap<Uuid, &Node> = graph.nodes().iter().map(|n| (n.id(), n)).collect();
+
+        // Rejects fan-in to a single port, same as batch runs
+        incoming_edges(graph)?;
+
+        info!(
+            "Streaming graph '{}' ({} node(s), buffer {} chunk(s) per edge)",
+            graph.name(),
+            plan.order().len(),
+            self.stream_buffer
+        );
+
+        let mut tasks: HashMap<Uuid, StreamTask> = HashMap::new();
+        for node_id in plan.order() {
+            let node = nodes[node_id];
+            let declared = node
+                .output_ports(definitions.get(&node.definition_id())?)?
+                .iter()
+                .map(|port| port.id())
+                .collect();
+
+            tasks.insert(
+                *node_id,
+                StreamTask {
+                    node,
+                    inputs: InputStreams::default(),
+                    outputs: OutputStreams {
+                        node_id: *node_id,
+                        node_name: String::from(node.name()),
+                        declared,
+                        senders: HashMap::new(),
+                        chunks_sent: AtomicUsize::new(0),
+                        bytes_sent: AtomicUsize::new(0),
+                    },
+                },
+            );
+        }
+
+        for edge in graph.edges() {
+            let (sender, receiver) = sync_channel(self.stream_buffer);
+
+            if let Some(source) = tasks.get_mut(&edge.source_node_id()) {
+                source
+                    .outputs
+                    .senders
+                    .entry(edge.source_port_id())
+                    .or_default()
+                    .push(sender);
+            }
+            if let Some(target) = tasks.get_mut(&edge.target_node_id()) {
+                target
+                    .inputs
+                    .streams
+                    .insert(edge.target_port_id(), InputStream { receiver });
+            }
+        }
+
+        let started_at = SystemTime::now();
+        let tracer = self.tracer(graph, &plan);
+        tracer.report(None, ProgressEventKind::RunStarted);
+        let ordered: Vec<StreamTask> = plan
+            .order()
+            .iter()
+            .filter_map(|node_id| tasks.remove(node_id))
+            .collect();
+
+        let outcomes: Vec<(Uuid, NodeStatus, Option<EngineError>)> = thread::scope(|scope| {
+            let handles: Vec<_> = ordered
+                .into_iter()
+                .map(|task| {
+                    let tracer = &tracer;
+                    scope.spawn(move || self.execute_stream_node(task, executor, tracer))
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|panic| resume_unwind(panic)))
+                .collect()
+        });
+
+        let node_results = outcomes
+            .into_iter()
+            .map(|(node_id, status, error)| NodeResult {
+                node_id,
+                status,
+                outputs: PortValues::new(),
+                error,
+            })
+            .collect();
+
+        Ok(self.finish_run(graph, started_at, node_results, tracer, None))
+    }
+
+    fn execute_stream_node<E>(
+        &self,
+        task: StreamTask<'_>,
+        executor: &E,
+        tracer: &Tracer<'_>,
+    ) -> (Uuid, NodeStatus, Option<EngineError>)
+    where
+        E: StreamingNodeExecutor,
+    {
+        let StreamTask {
+            node,
+            inputs,
+            outputs,
+        } = task;
+
+        if self.is_cancelled() {
+            tracer.record(node.id(), node.name(), TraceEventKind::Cancelled);
+            return (node.id(), NodeStatus::Cancelled, None);
+        }
+
+        tracer.record(
+            node.id(),
+            node.name(),
+            TraceEventKind::Started {
+                input_count: inputs.len(),
+                input_bytes: 0,
+            },
+        );
+
+        debug!("Streaming node '{}' ({})", node.name(), node.id());
+
+        let started = Instant::now();
+        let outcome = executor.execute_stream(node, inputs, &outputs);
+        let duration = started.elapsed();
+
+        match outcome {
+            Ok(()) => {
+                tracer.record(
+                    node.id(),
+                    node.name(),
+                    TraceEventKind::Finished {
+                        duration,
+                        output_count: outputs.chunks_sent.load(Ordering::Relaxed),
+                        output_bytes: outputs.bytes_sent.load(Ordering::Relaxed),
+                    },
+                );
+                (node.id(), NodeStatus::Succeeded, None)
+            }
+            Err(e) => {
+                let error = EngineError::ExecutionError {
+                    message: format!("Node '{}' failed: {e}", node.name()),
+                    node_id: node.id(),
+                    location: ErrorLocation::from(Location::caller()),
+                    source: Some(Box::new(e)),
+                };
+                warn!("{error}");
+
+                tracer.record(
+                    node.id(),
+                    node.name(),
+                    TraceEventKind::Failed {
+                        duration,
+                        message: error.to_string(),
+                    },
+                );
+                (node.id(), NodeStatus::Failed, Some(error))
+            }
+        }
+    }
+}
@@ -0,0 +1,514 @@
+//! Sub-graphs: a node that runs another saved graph as a single step, so
+//! workflows can be reused as components of other workflows.
+
+use crate::budget::ExecutionLimits;
+use crate::engine::ExecutionEngine;
+use crate::error::SubGraphError;
+use crate::executor::NodeExecutor;
+
+use cognexus_model::error::ModelError;
+use cognexus_model::graph::{
+    ErrorClass, Graph, Node, NodeConfig, NodeDefinitionInfo, NodeDefinitionRegistry, NodeIcon,
+    ParameterSpec, ParameterSpecBuilder, Port, PortBuilder, PortValues,
+};
+
+use common::error::error_location::ErrorLocation;
+
+use std::collections::HashMap;
+use std::panic::Location;
+use std::sync::Arc;
+
+use log::info;
+use semver::Version;
+use uuid::Uuid;
+
+const ID: &str = "0b5f7d0e-5a0c-4d61-9d3e-2f6c8a41b7e9";
+const NAME: &str = "SubGraph";
+const DESCRIPTION: &str = "Runs another saved graph as a single step";
+
+/// Saved graphs that sub-graph nodes can reference, keyed by graph id.
+#[derive(Default)]
+pub struct GraphLibrary {
+    graphs: HashMap<Uuid, Graph>,
+}
+
+impl GraphLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Save `graph`, replacing a saved graph with the same id.
+    pub fn insert(&mut self, graph: Graph) {
+        self.graphs.insert(graph.id(), graph);
+    }
+
+    pub fn get(&self, graph_id: &Uuid) -> Option<&Graph> {
+        self.graphs.get(graph_id)
+    }
+}
+
+/// The SubGraph node definition.
+///
+/// An instance's `graph` parameter holds the id of a graph in the library,
+/// as UTF-8 text. Its ports mirror that graph's boundary: an input for every
+/// Start node and an output for every End node, each with the boundary
+/// node's id and name, so the ports are resolved from the configuration.
+/// Executing it is left to a [`SubGraphExecutor`].
+///
+/// Register a clone in the `NodeDefinitionRegistry` and hand another to the
+/// executor; clones share the library.
+#[derive(Clone)]
+pub struct SubGraphNode {
+    pub(crate) library: Arc<GraphLibrary>,
+    pub(crate) start_definition_id: Uuid,
+    pub(crate) start_type_id: Uuid,
+    pub(crate) end_definition_id: Uuid,
+    pub(crate) end_type_id: Uuid,
+    pub(crate) reference_type_id: Uuid,
+}
+
+impl SubGraphNode {
+    /// Name of the parameter holding the referenced graph's id.
+    pub const GRAPH_PARAMETER: &str = "graph";
+
+    pub fn library(&self) -> &GraphLibrary {
+        &self.library
+    }
+
+    /// The graph an instance's configuration references.
+    fn graph(&self, config: &NodeConfig) -> Result<&Graph, (String, Option<Uuid>)> {
+        let graph_id = config
+            .get(Self::GRAPH_PARAMETER)
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .and_then(|text| Uuid::parse_str(text.trim()).ok())
+            .ok_or_else(|| {
+                (
+                    format!(
+                        "Parameter '{}' must be the id of a saved graph",
+                        Self::GRAPH_PARAMETER
+                    ),
+                    None,
+                )
+            })?;
+
+        self.library
+            .get(&graph_id)
+            .ok_or_else(|| (format!("No saved graph with id {graph_id}"), Some(graph_id)))
+    }
+
+    /// Nodes of `graph` created from `definition_id`.
+    fn boundary(graph: &Graph, definition_id: Uuid) -> impl Iterator<Item = &Node> {
+        graph
+            .nodes()
+            .iter()
+            .filter(move |node| node.definition_id() == definition_id)
+    }
+
+    fn boundary_ports(
+        graph: &Graph,
+        definition_id: Uuid,
+        type_id: Uuid,
+    ) -> Result<Vec<Port>, ModelError> {
+        Self::boundary(graph, definition_id)
+            .map(|node| {
+                PortBuilder::default()
+                    .with_id(node.id())
+                    .with_name(node.name())
+                    .with_data_type_id(type_id)
+                    .build()
+            })
+            .collect()
+    }
+}
+
+impl NodeDefinitionInfo for SubGraphNode {
+    fn definition_id(&self) -> Uuid {
+        Uuid::parse_str(ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> &str {
+        DESCRIPTION
+    }
+
+    fn category(&self) -> &str {
+        "Flow"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![
+            String::from("subgraph"),
+            String::from("nested"),
+            String::from("reuse"),
+        ]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("layers")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        let parameter = ParameterSpecBuilder::default()
+            .with_name(Self::GRAPH_PARAMETER)
+            .with_description("Id of the saved graph to run")
+            .with_data_type_id(self.reference_type_id)
+            .with_required(true)
+            .build()?;
+
+        Ok(vec![parameter])
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        // Without a graph there's no boundary to mirror
+        Ok(vec![])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        Ok(vec![])
+    }
+
+    /// End nodes the inner graph doesn't reach produce no value, so whatever
+    /// their outputs feed is skipped.
+    fn is_branching(&self) -> bool {
+        true
+    }
+
+    fn has_configured_ports(&self) -> bool {
+        true
+    }
+
+    /// The inner nodes are cached one by one, as far as they're
+    /// deterministic themselves.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    #[track_caller]
+    fn configured_ports(&self, config: &NodeConfig) -> Result<(Vec<Port>, Vec<Port>), ModelError> {
+        let graph = self
+            .graph(config)
+            .map_err(|(message, _)| ModelError::ConfigError {
+                message,
+                parameter: String::from(Self::GRAPH_PARAMETER),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok((
+            Self::boundary_ports(graph, self.start_definition_id, self.start_type_id)?,
+            Self::boundary_ports(graph, self.end_definition_id, self.end_type_id)?,
+        ))
+    }
+}
+
+/// Executes SubGraph nodes by running their graphs through the engine, and
+/// every other node through the executor it wraps.
+///
+/// An inner graph runs as a run of its own on the same engine, with the same
+/// definitions, and through this executor, so sub-graphs can nest. Its trace,
+/// history, and budgets are separate from the outer run's; cancelling the
+/// engine stops both. The inner Start nodes produce their own values, so a
+/// SubGraph node's inputs only decide when it runs. Each output carries the
+/// value its End node received, and a graph that includes itself, directly
+/// or through other sub-graphs, fails instead of recursing forever.
+pub struct SubGraphExecutor<'a, E> {
+    engine: &'a ExecutionEngine,
+    definitions: &'a NodeDefinitionRegistry,
+    sub_graph: &'a SubGraphNode,
+    executor: &'a E,
+    /// The outer graph, and those being run by the SubGraph nodes this
+    /// executor is nested in.
+    running: Vec<Uuid>,
+}
+
+impl<'a, E> SubGraphExecutor<'a, E>
+where
+    E: NodeExecutor + Sync,
+{
+    /// An executor for running `graph`, which its SubGraph nodes may not
+    /// run again.
+    pub fn new(
+        engine: &'a ExecutionEngine,
+        definitions: &'a NodeDefinitionRegistry,
+        sub_graph: &'a SubGraphNode,
+        executor: &'a E,
+        graph: &Graph,
+    ) -> Self {
+        Self {
+            engine,
+            definitions,
+            sub_graph,
+            executor,
+            running: vec![graph.id()],
+        }
+    }
+
+    #[track_caller]
+    fn run_sub_graph(&self, node: &Node) -> Result<PortValues, SubGraphError<E::Error>> {
+        let graph = self
+            .sub_graph
+            .graph(node.config())
+            .map_err(|(message, graph_id)| SubGraphError::GraphError {
+                message,
+                graph_id,
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            })?;
+
+        if self.running.contains(&graph.id()) {
+            return Err(SubGraphError::GraphError {
+                message: format!("Graph '{}' runs itself as a sub-graph", graph.name()),
+                graph_id: Some(graph.id()),
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            });
+        }
+
+        info!(
+            "Running sub-graph '{}' for node '{}' ({})",
+            graph.name(),
+            node.name(),
+            node.id()
+        );
+
+        let mut running = self.running.clone();
+        running.push(graph.id());
+        let nested = Self { running, ..*self };
+
+        let result = self
+            .engine
+            .run(graph, self.definitions, &nested)
+            .map_err(|e| SubGraphError::GraphError {
+                message: format!("Graph '{}' could not be run", graph.name()),
+                graph_id: Some(graph.id()),
+                location: ErrorLocation::from(Location::caller()),
+                source: Some(Box::new(e)),
+            })?;
+
+        if !result.is_success() {
+            let reason = result
+                .node_results()
+                .iter()
+                .find_map(|r| r.error())
+                .map_or_else(|| String::from("it was cancelled"), ToString::to_string);
+
+            return Err(SubGraphError::GraphError {
+                message: format!("Graph '{}' failed: {reason}", graph.name()),
+                graph_id: Some(graph.id()),
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            });
+        }
+
+        let mut outputs = PortValues::new();
+        for end in SubGraphNode::boundary(graph, self.sub_graph.end_definition_id) {
+            let value = graph
+                .edges()
+                .iter()
+                .filter(|edge| edge.target_node_id() == end.id())
+                .find_map(|edge| {
+                    result
+                        .node_result(edge.source_node_id())
+                        .and_then(|r| r.outputs().get(&edge.source_port_id()))
+                });
+
+            if let Some(value) = value {
+                outputs.insert(end.id(), value.to_vec());
+            }
+        }
+
+        Ok(outputs)
+    }
+}
+
+impl<E> NodeExecutor for SubGraphExecutor<'_, E>
+where
+    E: NodeExecutor + Sync,
+{
+    type Error = SubGraphError<E::Error>;
+
+    fn execute(&self, node: &Node, inputs: &PortValues) -> Result<PortValues, Self::Error> {
+        self.execute_with_limits(node, inputs, &ExecutionLimits::default())
+    }
+
+    fn execute_with_limits(
+        &self,
+        node: &Node,
+        inputs: &PortValues,
+        limits: &ExecutionLimits,
+    ) -> Result<PortValues, Self::Error> {
+        if node.definition_id() == self.sub_graph.definition_id() {
+            return self.run_sub_graph(node);
+        }
+
+        self.executor
+            .execute_with_limits(node, inputs, limits)
+            .map_err(SubGraphError::NodeError)
+    }
+
+    fn classify_error(&self, error: &Self::Error) -> ErrorClass {
+        match error {
+            SubGraphError::NodeError(e) => self.executor.classify_error(e),
+            SubGraphError::GraphError { .. } => ErrorClass::Unclassified,
+        }
+    }
+
+    fn validate_value(&self, type_id: Uuid, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.executor
+            .validate_value(type_id, bytes)
+            .map_err(SubGraphError::NodeError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::engine_builder::ExecutionEngineBuilder;
+    use crate::result::NodeStatus;
+    use crate::sub_graph_builder::SubGraphNodeBuilder;
+
+    use cognexus_model::graph::GraphBuilder;
+
+    use std::io;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const VALUE_TYPE_ID: &str = "5c1e9a47-2b83-4f60-8d2a-7e4b9f0c3d18";
+
+    /// A definition with the given ports and nothing else.
+    struct Stub {
+        id: Uuid,
+        inputs: usize,
+        outputs: usize,
+    }
+
+    impl Stub {
+        const START: &str = "3f2d8c61-9a4e-4b07-8e15-6c0a7d2b9f43";
+        const END: &str = "8e47b2d0-1c5f-4a93-b6e8-0d9f3a5c7e21";
+        const COUNTED: &str = "c6a03e9b-7d12-4f85-a4c7-5b1e8d6f2a90";
+
+        fn new(id: &str, inputs: usize, outputs: usize) -> Self {
+            Self {
+                id: Uuid::parse_str(id).unwrap(),
+                inputs,
+                outputs,
+            }
+        }
+
+        fn ports(count: usize) -> Result<Vec<Port>, ModelError> {
+            (0..count)
+                .map(|_| {
+                    PortBuilder::default()
+                        .with_name("value")
+                        .with_data_type_id(Uuid::parse_str(VALUE_TYPE_ID).unwrap())
+                        .build()
+                })
+                .collect()
+        }
+    }
+
+    impl NodeDefinitionInfo for Stub {
+        fn definition_id(&self) -> Uuid {
+            self.id
+        }
+
+        fn name(&self) -> &str {
+            "Stub"
+        }
+
+        fn description(&self) -> &str {
+            "A stub"
+        }
+
+        fn model_version(&self) -> Version {
+            Version::new(0, 1, 0)
+        }
+
+        fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+            Self::ports(self.inputs)
+        }
+
+        fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+            Self::ports(self.outputs)
+        }
+    }
+
+    /// Counts the nodes it runs.
+    #[derive(Default)]
+    struct CountingExecutor(AtomicUsize);
+
+    impl NodeExecutor for CountingExecutor {
+        type Error = io::Error;
+
+        fn execute(&self, _node: &Node, _inputs: &PortValues) -> Result<PortValues, io::Error> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(PortValues::new())
+        }
+    }
+
+    fn sub_graph_node(library: GraphLibrary) -> SubGraphNode {
+        SubGraphNodeBuilder::default()
+            .with_library(Arc::new(library))
+            .with_start_definition(&Stub::new(Stub::START, 0, 1))
+            .with_end_definition(&Stub::new(Stub::END, 1, 0))
+            .with_reference_type_id(Uuid::parse_str(VALUE_TYPE_ID).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn a_graph_that_includes_itself_fails_without_running_again() {
+        let mut definitions = NodeDefinitionRegistry::new();
+        definitions
+            .register(Stub::new(Stub::COUNTED, 0, 0))
+            .unwrap();
+        definitions
+            .register(sub_graph_node(GraphLibrary::new()))
+            .unwrap();
+
+        let mut graph = GraphBuilder::default()
+            .with_name("recursive")
+            .build()
+            .unwrap();
+        let graph_id = graph.id();
+        graph
+            .add_node(
+                &definitions,
+                None,
+                "counted",
+                Uuid::parse_str(Stub::COUNTED).unwrap(),
+            )
+            .unwrap();
+        let itself = graph
+            .add_node(&definitions, None, "itself", Uuid::parse_str(ID).unwrap())
+            .unwrap();
+        let config = [(
+            String::from(SubGraphNode::GRAPH_PARAMETER),
+            graph_id.to_string().into_bytes(),
+        )]
+        .into_iter()
+        .collect();
+        graph.set_node_config(itself, config).unwrap();
+
+        let mut library = GraphLibrary::new();
+        library.insert(graph);
+        let sub_graph = sub_graph_node(library);
+        let graph = sub_graph.library().get(&graph_id).unwrap();
+
+        let engine = ExecutionEngineBuilder::default().build().unwrap();
+        let counting = CountingExecutor::default();
+        let executor = SubGraphExecutor::new(&engine, &definitions, &sub_graph, &counting, graph);
+
+        let result = engine.run(graph, &definitions, &executor).unwrap();
+
+        assert_eq!(counting.0.load(Ordering::SeqCst), 1);
+        let node = result.node_result(itself).unwrap();
+        assert_eq!(node.status(), NodeStatus::Failed);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::EngineError;
+use crate::sub_graph::{GraphLibrary, SubGraphNode};
+
+use cognexus_model::graph::NodeDefinitionInfo;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct SubGraphNodeBuilder {
+    library: Option<Arc<GraphLibrary>>,
+    start: Option<(Uuid, Option<Uuid>)>,
+    end: Option<(Uuid, Option<Uuid>)>,
+    reference_type_id: Option<Uuid>,
+}
+
+impl SubGraphNodeBuilder {
+    /// The saved graphs instances can reference.
+    pub fn with_library(mut self, library: Arc<GraphLibrary>) -> Self {
+        self.library = Some(library);
+        self
+    }
+
+    /// The definition of the nodes where a graph starts. Each becomes an
+    /// input, typed like the definition's first output port.
+    pub fn with_start_definition(mut self, definition: &dyn NodeDefinitionInfo) -> Self {
+        let type_id = definition
+            .output_port_specs()
+            .ok()
+            .and_then(|ports| ports.first().map(|port| port.data_type_id()));
+        self.start = Some((definition.definition_id(), type_id));
+        self
+    }
+
+    /// The definition of the nodes where a graph ends. Each becomes an
+    /// output, typed like the definition's first input port.
+    pub fn with_end_definition(mut self, definition: &dyn NodeDefinitionInfo) -> Self {
+        let type_id = definition
+            .input_port_specs()
+            .ok()
+            .and_then(|ports| ports.first().map(|port| port.data_type_id()));
+        self.end = Some((definition.definition_id(), type_id));
+        self
+    }
+
+    /// The data type of the `graph` parameter, text holding a graph id
+    /// (String).
+    pub fn with_reference_type_id(mut self, type_id: Uuid) -> Self {
+        self.reference_type_id = Some(type_id);
+        self
+    }
+
+    #[track_caller]
+    pub fn build(self) -> Result<SubGraphNode, EngineError> {
+        let library = self
+            .library
+            .ok_or_else(|| EngineError::ConfigurationError {
+                message: String::from("Sub-graph library is required"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        let (start_definition_id, start_type_id) =
+            self.start.ok_or_else(|| EngineError::ConfigurationError {
+                message: String::from("Sub-graph start definition is required"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+        let start_type_id = start_type_id.ok_or_else(|| EngineError::ConfigurationError {
+            message: format!("Sub-graph start definition {start_definition_id} has no output port"),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let (end_definition_id, end_type_id) =
+            self.end.ok_or_else(|| EngineError::ConfigurationError {
+                message: String::from("Sub-graph end definition is required"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+        let end_type_id = end_type_id.ok_or_else(|| EngineError::ConfigurationError {
+            message: format!("Sub-graph end definition {end_definition_id} has no input port"),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let reference_type_id =
+            self.reference_type_id
+                .ok_or_else(|| EngineError::ConfigurationError {
+                    message: String::from("Sub-graph reference type is required"),
+                    location: ErrorLocation::from(Location::caller()),
+                })?;
+
+        Ok(SubGraphNode {
+            library,
+            start_definition_id,
+            start_type_id,
+            end_definition_id,
+            end_type_id,
+            reference_type_id,
+        })
+    }
+}
@@ -0,0 +1,242 @@
+//! Structured per-node trace events recorded during a run.
+
+use crate::progress::{ProgressEventKind, ProgressReporter};
+
+use std::cmp::Reverse;
+use std::sync::mpsc::Sender;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What happened to a node at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TraceEventKind {
+    /// The node's inputs were gathered and its executor was invoked.
+    Started {
+        input_count: usize,
+        input_bytes: usize,
+    },
+    /// The executor returned outputs. In streaming runs the output count is
+    /// the number of chunks sent.
+    Finished {
+        duration: Duration,
+        output_count: usize,
+        output_bytes: usize,
+    },
+    /// Outputs were reused from the output cache; the executor didn't run.
+    Cached {
+        output_count: usize,
+        output_bytes: usize,
+    },
+    /// An attempt failed and will be retried after `delay`.
+    Retrying {
+        attempt: u32,
+        delay: Duration,
+        message: String,
+    },
+    /// The node failed, either gathering inputs or executing.
+    Failed { duration: Duration, message: String },
+    /// The node never ran because an earlier node failed.
+    Skipped,
+    /// The node never ran because the run was cancelled or ran out of
+    /// budget.
+    Cancelled,
+    /// The node never ran because a branch feeding it was not taken.
+    NotTaken,
+    /// The node logged a message while executing, possibly with a value kept
+    /// for inspection.
+    Logged {
+        level: LogLevel,
+        message: String,
+        value: Option<InspectedValue>,
+    },
+}
+
+/// Severity of a message a node logged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// A serialized value a node logged, kept in the trace so it can be
+/// inspected after the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InspectedValue {
+    pub(crate) data_type_id: Uuid,
+    pub(crate) bytes: Vec<u8>,
+}
+
+impl InspectedValue {
+    pub fn new(data_type_id: Uuid, bytes: Vec<u8>) -> Self {
+        Self {
+            data_type_id,
+            bytes,
+        }
+    }
+
+    pub fn data_type_id(&self) -> Uuid {
+        self.data_type_id
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// A single trace event for one node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEvent {
+    pub(crate) node_id: Uuid,
+    pub(crate) node_name: String,
+    pub(crate) timestamp: SystemTime,
+    pub(crate) kind: TraceEventKind,
+}
+
+impl TraceEvent {
+    pub fn node_id(&self) -> Uuid {
+        self.node_id
+    }
+
+    pub fn node_name(&self) -> &str {
+        &self.node_name
+    }
+
+    /// Wall-clock time the event was recorded.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    pub fn kind(&self) -> &TraceEventKind {
+        &self.kind
+    }
+
+    /// Time spent executing, for `Finished` and `Failed` events.
+    pub fn duration(&self) -> Option<Duration> {
+        match &self.kind {
+            TraceEventKind::Finished { duration, .. } | TraceEventKind::Failed { duration, .. } => {
+                Some(*duration)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Every trace event recorded during a run, in the order they happened.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    events: Vec<TraceEvent>,
+}
+
+impl ExecutionTrace {
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Events recorded for a single node.
+    pub fn node_events(&self, node_id: Uuid) -> impl Iterator<Item = &TraceEvent> {
+        self.events.iter().filter(move |e| e.node_id == node_id)
+    }
+
+    /// Time a node spent executing, if it ran.
+    pub fn node_duration(&self, node_id: Uuid) -> Option<Duration> {
+        self.node_events(node_id).find_map(TraceEvent::duration)
+    }
+
+    /// Sum of every node's execution time. With parallel stages this can
+    /// exceed the wall-clock time of the run.
+    pub fn total_node_time(&self) -> Duration {
+        self.events.iter().filter_map(TraceEvent::duration).sum()
+    }
+
+    /// Nodes ordered by execution time, slowest first.
+    pub fn slowest_nodes(&self) -> Vec<(Uuid, Duration)> {
+        let mut durations: Vec<(Uuid, Duration)> = self
+            .events
+            .iter()
+            .filter_map(|e| e.duration().map(|d| (e.node_id, d)))
+            .collect();
+        durations.sort_by_key(|&(_, duration)| Reverse(duration));
+        durations
+    }
+}
+
+/// Receives trace events live, as the engine records them.
+///
+/// Sinks are called from worker threads while a stage is running, so they
+/// should hand events off quickly rather than doing heavy work inline.
+pub trait TraceSink: Send + Sync {
+    fn record(&self, event: &TraceEvent);
+}
+
+impl TraceSink for Sender<TraceEvent> {
+    fn record(&self, event: &TraceEvent) {
+        // A dropped receiver just means nobody is watching any more
+        let _ = self.send(event.clone());
+    }
+}
+
+/// Collects events for one run and forwards them to an optional live sink,
+/// deriving progress events along the way if a run is being watched.
+pub(crate) struct Tracer<'a> {
+    events: Mutex<Vec<TraceEvent>>,
+    sink: Option<&'a dyn TraceSink>,
+    progress: Option<ProgressReporter<'a>>,
+}
+
+impl<'a> Tracer<'a> {
+    pub(crate) fn new(
+        sink: Option<&'a dyn TraceSink>,
+        progress: Option<ProgressReporter<'a>>,
+    ) -> Self {
+        Self {
+            events: Mutex::new(Vec::new()),
+            sink,
+            progress,
+        }
+    }
+
+    /// Report a progress event that has no trace counterpart.
+    pub(crate) fn report(&self, node_id: Option<Uuid>, kind: ProgressEventKind) {
+        if let Some(progress) = &self.progress {
+            progress.report(node_id, kind);
+        }
+    }
+
+    pub(crate) fn record(&self, node_id: Uuid, node_name: &str, kind: TraceEventKind) {
+        let event = TraceEvent {
+            node_id,
+            node_name: String::from(node_name),
+            timestamp: SystemTime::now(),
+            kind,
+        };
+
+        if let Some(sink) = self.sink {
+            sink.record(&event);
+        }
+        if let Some(progress) = &self.progress {
+            progress.on_trace(node_id, &event.kind);
+        }
+
+        // Pushing to a Vec can't leave it half-updated, so a poisoned lock
+        // still holds a usable trace.
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(event);
+    }
+
+    pub(crate) fn finish(self) -> ExecutionTrace {
+        ExecutionTrace {
+            events: self
+                .events
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Node executor backed by WASM plugin components.
+
+use crate::budget::{BudgetResource, ExecutionLimits};
+use crate::cancellation::CancellationToken;
+use crate::executor::NodeExecutor;
+use crate::trace::{InspectedValue, LogLevel};
+
+use cognexus_model::graph::{ErrorClass, Node, NodeIsolation, PortValues};
+use cognexus_plugin_manager::{
+    CallLimits, CallUsage, HostMode, InterruptHandle, PluginLog, PluginManager, PluginManagerError,
+};
+
+use uuid::Uuid;
+
+/// Executes nodes through the plugin components they were discovered in.
+///
+/// Each execution instantiates the node's component afresh, in its own store,
+/// reusing the component compiled at discovery time. Nodes set to
+/// [`NodeIsolation::SharedInstance`] instead keep one instance per graph node
+/// across executions, so stateful plugins see their earlier state; their clock
+/// readings and randomness are never recorded or replayed. Traps and
+/// plugin-reported failures surface as `PluginManagerError`s, which the engine
+/// attributes to the node.
+pub struct WasmNodeExecutor<'a> {
+    plugins: &'a PluginManager,
+    interrupt: Option<InterruptHandle>,
+}
+
+impl<'a> WasmNodeExecutor<'a> {
+    pub fn new(plugins: &'a PluginManager) -> Self {
+        Self {
+            plugins,
+            interrupt: None,
+        }
+    }
+
+    /// Interrupt this executor's plugin code that is still running when
+    /// `token` is cancelled, instead of waiting for the current node to
+    /// return on its own. Executions other executors make through the same
+    /// plugin manager carry on.
+    pub fn with_cancellation(mut self, token: &CancellationToken) -> Self {
+        let interrupt = self.plugins.interrupt_handle();
+        let cancelled = interrupt.clone();
+        token.on_cancel(move || cancelled.interrupt());
+        self.interrupt = Some(interrupt);
+        self
+    }
+
+    fn call(
+        &self,
+        node: &Node,
+        inputs: &PortValues,
+        limits: &CallLimits,
+        host: HostMode,
+    ) -> Result<(PortValues, CallUsage), PluginManagerError> {
+        let definition_id = node.definition_id().to_string();
+
+        match node.isolation() {
+            NodeIsolation::Isolated => {
+                self.plugins
+                    .execute_node(&definition_id, node.config(), inputs, limits, host)
+            }
+            NodeIsolation::SharedInstance => self.plugins.execute_node_shared(
+                node.id(),
+                &definition_id,
+                node.config(),
+                inputs,
+                limits,
+            ),
+        }
+    }
+}
+
+/// Report a plugin's log message, keeping its value only if the value's
+/// type id is a UUID.
+fn record_log(limits: &ExecutionLimits, log: &PluginLog) {
+    let level = match log.level {
+        log::Level::Error => LogLevel::Error,
+        log::Level::Warn => LogLevel::Warn,
+        log::Level::Info => LogLevel::Info,
+        log::Level::Debug => LogLevel::Debug,
+        log::Level::Trace => LogLevel::Trace,
+    };
+    let value = log.value.as_ref().and_then(|value| {
+        Uuid::parse_str(&value.type_id)
+            .ok()
+            .map(|type_id| InspectedValue::new(type_id, value.bytes.clone()))
+    });
+
+    limits.record_log(level, log.message.clone(), value);
+}
+
+impl NodeExecutor for WasmNodeExecutor<'_> {
+    type Error = PluginManagerError;
+
+    fn execute(&self, node: &Node, inputs: &PortValues) -> Result<PortValues, Self::Error> {
+        let call_limits = CallLimits {
+            interrupt: self.interrupt.clone(),
+            ..CallLimits::default()
+        };
+
+        self.call(node, inputs, &call_limits, HostMode::Live)
+            .map(|(outputs, _)| outputs)
+    }
+
+    /// Traps the plugin call through an epoch deadline once the timeout
+    /// passes, holds it to the fuel and memory it is given, records or
+    /// replays its host inputs, and reports what it logged and how long it
+    /// deferred its outputs.
+    fn execute_with_limits(
+        &self,
+        node: &Node,
+        inputs: &PortValues,
+        limits: &ExecutionLimits,
+    ) -> Result<PortValues, Self::Error> {
+        let call_limits = CallLimits {
+            timeout: limits.timeout(),
+            fuel: limits.fuel(),
+            memory_bytes: limits.memory_bytes(),
+            interrupt: self.interrupt.clone(),
+        };
+
+        let outcome = self.call(node, inputs, &call_limits, limits.host_mode().clone());
+
+        match &outcome {
+            Ok((_, usage)) => {
+                limits.record_fuel_consumed(usage.fuel_consumed);
+                if let Some(host_inputs) = &usage.host_inputs {
+                    limits.record_host_inputs(host_inputs.clone());
+                }
+                for log in &usage.logs {
+                    record_log(limits, log);
+                }
+                if let Some(deferral) = usage.deferred {
+                    limits.record_deferral(deferral);
+                }
+            }
+            Err(PluginManagerError::FuelExhaustedError { .. }) => {
+                limits.record_fuel_consumed(limits.fuel().unwrap_or_default());
+                limits.record_exhausted(BudgetResource::Fuel);
+            }
+            Err(PluginManagerError::MemoryLimitError { .. }) => {
+                limits.record_exhausted(BudgetResource::Memory);
+            }
+            Err(_) => {}
+        }
+
+        outcome.map(|(outputs, _)| outputs)
+    }
+
+    fn classify_error(&self, error: &Self::Error) -> ErrorClass {
+        match error {
+            PluginManagerError::TimeoutError { .. } => ErrorClass::Timeout,
+            PluginManagerError::FuelExhaustedError { .. }
+            | PluginManagerError::MemoryLimitError { .. } => ErrorClass::Permanent,
+            _ => ErrorClass::Unclassified,
+        }
+    }
+
+    /// Validates through the types plugin, accepting values of types no
+    /// plugin provides.
+    fn validate_value(&self, type_id: Uuid, bytes: &[u8]) -> Result<(), Self::Error> {
+        let type_id = type_id.to_string();
+
+        if !self.plugins.provides_type(&type_id) {
+            return Ok(());
+        }
+
+        self.plugins.validate_value(&type_id, bytes)
+    }
+}
@@ -9,11 +9,17 @@ pub const MIN_ZOOM_MAX: f32 = 0.01;
 pub const MAX_ZOOM: f32 = 1000.0;
 pub const DEFAULT_MAX_ZOOM: f32 = 100.0;
 pub const DEFAULT_ZOOM_RATE: f32 = 0.001;
+pub const DEFAULT_PIXEL_RATIO: f32 = 1.0;
 
+/// A 2D camera over a viewport measured in logical pixels, the size and
+/// coordinates input arrives in. The pixel ratio maps them to the physical
+/// pixels the viewport is drawn at, e.g. 2 on most high-density displays.
+#[derive(Debug, Clone)]
 pub struct Camera2D {
     position: Vec2,
     zoom: f32,
     viewport_size: (u32, u32),
+    pixel_ratio: f32,
     zoom_min: f32,
     zoom_max: f32,
 }
@@ -31,6 +37,18 @@ impl Camera2D {
         self.viewport_size
     }
 
+    pub fn pixel_ratio(&self) -> f32 {
+        self.pixel_ratio
+    }
+
+    /// The viewport size in physical pixels, at least one by one.
+    pub fn physical_viewport_size(&self) -> (u32, u32) {
+        let (width, height) = self.viewport_size;
+        let scale = |logical: u32| ((logical as f32 * self.pixel_ratio).round() as u32).max(1);
+
+        (scale(width), scale(height))
+    }
+
     pub fn zoom_min(&self) -> f32 {
         self.zoom_min
     }
@@ -63,6 +81,53 @@ impl Camera2D {
         self.projection_matrix() * self.view_matrix()
     }
 
+    /// Change the viewport size, keeping the position and zoom, so resizing
+    /// grows or shrinks the view around its center instead of stretching it.
+    pub fn set_viewport_size(&mut self, width: u32, height: u32) {
+        self.viewport_size = (width, height);
+    }
+
+    /// Change the number of physical pixels per logical pixel. The view
+    /// stays the same, only drawn at a different resolution.
+    #[track_caller]
+    pub fn set_pixel_ratio(&mut self, pixel_ratio: f32) -> Result<(), ModelError> {
+        self.pixel_ratio = validate_pixel_ratio(pixel_ratio)?;
+        Ok(())
+    }
+
+    /// Move the view to `position` at `zoom`, clamped to the zoom limits.
+    pub fn set_view(&mut self, position: Vec2, zoom: f32) {
+        self.position = position;
+        self.zoom = zoom.clamp(self.zoom_min, self.zoom_max);
+    }
+
+    /// Center the view on the box from `min` to `max`, in world units, and
+    /// zoom so it fills the viewport with `padding` logical pixels to spare
+    /// on every side. The zoom stays within its limits, and is kept as it
+    /// is for a box with no area.
+    pub fn fit_to_bounds(&mut self, min: Vec2, max: Vec2, padding: f32) {
+        let size = (max - min).abs();
+        self.position = (min + max) / 2.0;
+
+        let (width, height) = (self.viewport_size.0 as f32, self.viewport_size.1 as f32);
+        // What's left of the viewport inside the padding, at least a pixel
+        let inner_width = (width - padding.max(0.0) * 2.0).max(1.0);
+        let inner_height = (height - padding.max(0.0) * 2.0).max(1.0);
+
+        // The view is 2 / zoom world units tall, so the box's height fits at
+        // zoom 2 / height, scaled down to the inner part of the viewport
+        let fit_height = (size.y > 0.0).then(|| 2.0 / size.y * inner_height / height);
+        let fit_width = (size.x > 0.0).then(|| 2.0 / size.x * inner_width / height);
+
+        let zoom = match (fit_width, fit_height) {
+            (Some(fit_width), Some(fit_height)) => fit_width.min(fit_height),
+            (Some(fit), None) | (None, Some(fit)) => fit,
+            (None, None) => return,
+        };
+
+        self.zoom = zoom.clamp(self.zoom_min, self.zoom_max);
+    }
+
     pub fn pan_by_screen_delta(&mut self, delta_x: f32, delta_y: f32) {
         let aspect_ratio = self.viewport_size.0 as f32 / self.viewport_size.1 as f32;
         let height = 2.0 / self.zoom;
@@ -116,6 +181,7 @@ pub struct Camera2DBuilder {
     viewport_size: Option<(u32, u32)>,
     zoom_min: Option<f32>,
     zoom_max: Option<f32>,
+    pixel_ratio: Option<f32>,
 }
 
 impl Camera2DBuilder {
@@ -134,6 +200,11 @@ impl Camera2DBuilder {
         self
     }
 
+    pub fn with_pixel_ratio(mut self, pixel_ratio: f32) -> Self {
+        self.pixel_ratio = Some(pixel_ratio);
+        self
+    }
+
     pub fn build(self) -> Result<Camera2D, ModelError> {
         let viewport_size = self.viewport_size.ok_or(ModelError::CameraError {
             message: String::from("Missing Viewport Size!"),
@@ -181,12 +252,30 @@ impl Camera2DBuilder {
             None => DEFAULT_MIN_ZOOM,
         };
 
+        let pixel_ratio = match self.pixel_ratio {
+            Some(pixel_ratio) => validate_pixel_ratio(pixel_ratio)?,
+            None => DEFAULT_PIXEL_RATIO,
+        };
+
         Ok(Camera2D {
             position: Vec2::ZERO,
             zoom: 1.0,
             viewport_size,
+            pixel_ratio,
             zoom_min,
             zoom_max,
         })
     }
 }
+
+#[track_caller]
+fn validate_pixel_ratio(pixel_ratio: f32) -> Result<f32, ModelError> {
+    if !pixel_ratio.is_finite() || pixel_ratio <= 0.0 {
+        return Err(ModelError::CameraError {
+            message: format!("Pixel ratio must be a positive number: {pixel_ratio}"),
+            location: ErrorLocation::from(PanicLocation::caller()),
+        });
+    }
+
+    Ok(pixel_ratio)
+}
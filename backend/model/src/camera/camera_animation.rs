@@ -0,0 +1,78 @@
+use glam::Vec2;
+
+/// How an animation's progress speeds up and slows down over its duration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    /// Starts fast and settles into the target.
+    EaseOut,
+    /// Starts and ends slowly.
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// The eased progress for a linear `t` between 0 and 1.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+        }
+    }
+}
+
+/// A camera move from one position and zoom to another over a fixed time.
+///
+/// It holds no clock: whoever drives it samples it with the time elapsed
+/// since it started, once per frame.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraAnimation {
+    from_position: Vec2,
+    from_zoom: f32,
+    to_position: Vec2,
+    to_zoom: f32,
+    /// In seconds.
+    duration: f32,
+    easing: Easing,
+}
+
+impl CameraAnimation {
+    pub fn new(from: (Vec2, f32), to: (Vec2, f32), duration: f32, easing: Easing) -> Self {
+        Self {
+            from_position: from.0,
+            from_zoom: from.1,
+            to_position: to.0,
+            to_zoom: to.1,
+            duration: duration.max(0.0),
+            easing,
+        }
+    }
+
+    pub fn target(&self) -> (Vec2, f32) {
+        (self.to_position, self.to_zoom)
+    }
+
+    pub fn is_finished(&self, elapsed: f32) -> bool {
+        elapsed >= self.duration
+    }
+
+    /// Position and zoom `elapsed` seconds in, the target once it's over.
+    ///
+    /// Zoom is interpolated geometrically, so zooming in or out by the same
+    /// factor takes the same time at every scale.
+    pub fn sample(&self, elapsed: f32) -> (Vec2, f32) {
+        if self.is_finished(elapsed) {
+            return self.target();
+        }
+
+        let t = self.easing.apply(elapsed / self.duration);
+        let position = self.from_position.lerp(self.to_position, t);
+        let zoom = self.from_zoom * (self.to_zoom / self.from_zoom).powf(t);
+
+        (position, zoom)
+    }
+}
@@ -1 +1,2 @@
 pub mod camera_2d;
+pub mod camera_animation;
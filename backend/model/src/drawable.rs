@@ -1,4 +1,6 @@
-use glam::Mat4;
+use glam::{Mat4, Vec2};
+use std::fmt;
+use uuid::Uuid;
 
 /// Trait for objects that can be rendered in the scene
 pub trait Drawable: Send {
@@ -8,3 +10,67 @@ pub trait Drawable: Send {
     /// Returns the color as [r, g, b, a]
     fn color(&self) -> [f32; 4];
 }
+
+/// Layers of the scene, from the bottom up: everything in a layer is drawn
+/// over everything in the layers before it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    /// Just above the grid, like canvas backdrops
+    Background,
+    /// Group frames around sets of nodes
+    Groups,
+    Edges,
+    #[default]
+    Nodes,
+    /// Selection boxes, previews, and other interaction feedback
+    Overlay,
+}
+
+impl Layer {
+    pub const COUNT: usize = 5;
+
+    /// Every layer, from the bottom up
+    pub const ALL: [Layer; Layer::COUNT] = [
+        Layer::Background,
+        Layer::Groups,
+        Layer::Edges,
+        Layer::Nodes,
+        Layer::Overlay,
+    ];
+}
+
+/// Where a drawable goes in the draw order: its layer, then its z-index
+/// within the layer. Whatever is ordered higher stacks on top, over any kind
+/// of drawable ordered lower; drawables with the same order are drawn in the
+/// order they were added
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DrawOrder {
+    pub layer: Layer,
+    pub z_index: i32,
+}
+
+impl DrawOrder {
+    pub fn new(layer: Layer, z_index: i32) -> Self {
+        Self { layer, z_index }
+    }
+}
+
+/// Identifies a drawable in the retained scene, like the graph node, port,
+/// or edge it represents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DrawableId(pub Uuid);
+
+impl fmt::Display for DrawableId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Trait for drawables that can be picked with the pointer
+pub trait Pickable {
+    /// Returns the id picking resolves to
+    fn id(&self) -> DrawableId;
+
+    /// Returns whether the world-space `point` is over the drawable
+    fn contains(&self, point: Vec2) -> bool;
+}
@@ -22,4 +22,19 @@ pub enum ModelError {
         data_type_id: uuid::Uuid,
         location: ErrorLocation,
     },
+
+    #[error("Value Error: {message} (port: {port_id}, type: {data_type_id}) {location}")]
+    ValueError {
+        message: String,
+        port_id: uuid::Uuid,
+        data_type_id: uuid::Uuid,
+        location: ErrorLocation,
+    },
+
+    #[error("Config Error: {message} (parameter: '{parameter}') {location}")]
+    ConfigError {
+        message: String,
+        parameter: String,
+        location: ErrorLocation,
+    },
 }
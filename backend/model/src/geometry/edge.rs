@@ -0,0 +1,24 @@
+use crate::drawable::{DrawOrder, DrawableId};
+use glam::Vec2;
+
+/// Where one end of an edge is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeEnd {
+    /// On a port anchor, wherever its node is.
+    Port(DrawableId),
+    /// At a fixed point, in world units.
+    Point(Vec2),
+}
+
+/// A connection between two ports (or fixed points), drawn as a curve
+/// leaving the source and arriving at the target.
+#[derive(Debug, Clone, Copy)]
+pub struct Edge {
+    pub id: DrawableId,
+    pub source: EdgeEnd,
+    pub target: EdgeEnd,
+    pub width: f32,
+    pub color: [f32; 4], // r, g, b, a
+    pub dashed: bool,
+    pub order: DrawOrder,
+}
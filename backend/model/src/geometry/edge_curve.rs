@@ -0,0 +1,74 @@
+use crate::geometry::port_anchor::PortDirection;
+use glam::Vec2;
+
+/// The cubic bezier an edge is drawn along, leaving its first port
+/// horizontally, away from the node, and arriving at the other end the same
+/// way from the opposite side.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeCurve {
+    pub start: Vec2,
+    pub start_control: Vec2,
+    pub end_control: Vec2,
+    pub end: Vec2,
+}
+
+impl EdgeCurve {
+    /// A curve from the port at `start`, on the `direction` side of its node,
+    /// to `end`.
+    pub fn from_port(start: Vec2, direction: PortDirection, end: Vec2) -> Self {
+        // Far enough out that the curve bends smoothly, even when the ends
+        // are level or the end is behind the start
+        let reach = ((end.x - start.x).abs() / 2.0).max(start.distance(end) / 4.0);
+        let outward = match direction {
+            PortDirection::Input => Vec2::new(-reach, 0.0),
+            PortDirection::Output => Vec2::new(reach, 0.0),
+        };
+
+        Self {
+            start,
+            start_control: start + outward,
+            end_control: end - outward,
+            end,
+        }
+    }
+
+    /// The point `t` of the way along the curve, from 0 at the start to 1
+    /// at the end.
+    pub fn point(&self, t: f32) -> Vec2 {
+        let u = 1.0 - t;
+
+        self.start * (u * u * u)
+            + self.start_control * (3.0 * u * u * t)
+            + self.end_control * (3.0 * u * t * t)
+            + self.end * (t * t * t)
+    }
+
+    /// The curve as `segments` straight lines, the `segments + 1` points
+    /// from the start to the end.
+    pub fn flatten(&self, segments: usize) -> Vec<Vec2> {
+        let segments = segments.max(1);
+
+        (0..=segments)
+            .map(|i| self.point(i as f32 / segments as f32))
+            .collect()
+    }
+
+    /// How far `point` is from the curve as flattened into `segments`
+    /// straight lines, the way it's drawn.
+    pub fn distance_to(&self, point: Vec2, segments: usize) -> f32 {
+        self.flatten(segments)
+            .windows(2)
+            .map(|segment| {
+                let (a, b) = (segment[0], segment[1]);
+                let along = b - a;
+                let t = if along == Vec2::ZERO {
+                    0.0
+                } else {
+                    ((point - a).dot(along) / along.length_squared()).clamp(0.0, 1.0)
+                };
+
+                point.distance(a + along * t)
+            })
+            .fold(f32::INFINITY, f32::min)
+    }
+}
@@ -1 +1,8 @@
+pub mod edge;
+pub mod edge_curve;
+pub mod node_widget;
+pub mod port_anchor;
 pub mod quad;
+pub mod sprite;
+pub mod text_label;
+pub mod transform_animation;
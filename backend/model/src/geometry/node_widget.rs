@@ -0,0 +1,53 @@
+use crate::drawable::{DrawOrder, Drawable, DrawableId, Pickable};
+use glam::{Mat4, Vec2, Vec3};
+
+/// The visual representation of a graph node: a rounded rectangle with a
+/// border and a header band along its top edge.
+#[derive(Debug, Clone, Copy)]
+pub struct NodeWidget {
+    pub id: DrawableId,
+    pub position: [f32; 3],     // x, y, z of the center
+    pub size: [f32; 2],         // width, height
+    pub fill_color: [f32; 4],   // r, g, b, a of the body
+    pub border_color: [f32; 4], // r, g, b, a
+    pub header_color: [f32; 4], // r, g, b, a
+    pub border_width: f32,
+    pub corner_radius: f32,
+    pub header_height: f32,
+    pub selected: bool,
+    pub hovered: bool,
+    pub order: DrawOrder,
+}
+
+impl Drawable for NodeWidget {
+    fn model_matrix(&self) -> Mat4 {
+        let transform = Mat4::from_translation(Vec3::from(self.position));
+        let scale = Mat4::from_scale(Vec3::new(self.size[0], self.size[1], 1.0));
+
+        transform * scale
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.fill_color
+    }
+}
+
+impl Pickable for NodeWidget {
+    fn id(&self) -> DrawableId {
+        self.id
+    }
+
+    /// Matches the rounded outline the node shader draws, so clicks just
+    /// outside a corner miss.
+    fn contains(&self, point: Vec2) -> bool {
+        let half_size = Vec2::from(self.size) / 2.0;
+        let radius = self
+            .corner_radius
+            .clamp(0.0, half_size.x.min(half_size.y).max(0.0));
+
+        let offset = (point - Vec2::new(self.position[0], self.position[1])).abs();
+        let corner = offset - half_size + Vec2::splat(radius);
+
+        corner.max(Vec2::ZERO).length() + corner.x.max(corner.y).min(0.0) <= radius
+    }
+}
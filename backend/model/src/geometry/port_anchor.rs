@@ -0,0 +1,123 @@
+use crate::drawable::{DrawOrder, Drawable, DrawableId, Pickable};
+use crate::geometry::node_widget::NodeWidget;
+use glam::{Mat4, Vec2, Vec3};
+use uuid::Uuid;
+
+/// Saturation and brightness of the colors derived from data types, so every
+/// type reads clearly against the node body.
+const DATA_TYPE_SATURATION: f32 = 0.65;
+const DATA_TYPE_VALUE: f32 = 0.9;
+
+/// Which side of its node a port sits on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortDirection {
+    /// Along the left edge.
+    Input,
+    /// Along the right edge.
+    Output,
+}
+
+/// The point a connection attaches to on a node: a circle, or a pill when
+/// its width and height differ, filled when the port is connected and drawn
+/// as a ring when it isn't.
+#[derive(Debug, Clone, Copy)]
+pub struct PortAnchor {
+    pub id: DrawableId,
+    /// The node widget the port is on, whose stacking it follows.
+    pub node: Option<DrawableId>,
+    pub position: [f32; 3], // x, y, z of the center
+    pub size: [f32; 2],     // width, height
+    pub color: [f32; 4],    // r, g, b, a
+    pub direction: PortDirection,
+    pub connected: bool,
+    pub hovered: bool,
+    pub order: DrawOrder,
+}
+
+impl PortAnchor {
+    /// A color for ports of the data type `type_id`, the same one every time,
+    /// so ports that can connect look alike.
+    pub fn data_type_color(type_id: Uuid) -> [f32; 4] {
+        // The hue comes from the id, spread around the color wheel
+        let hue = (type_id.as_u128() % 360) as f32;
+        let [r, g, b] = hsv_to_rgb(hue, DATA_TYPE_SATURATION, DATA_TYPE_VALUE);
+
+        [r, g, b, 1.0]
+    }
+}
+
+impl NodeWidget {
+    /// Where the port at `index` of `count` on the `direction` side of the
+    /// node goes: on its left or right edge, spread evenly down the body
+    /// below the header, and just in front of the node.
+    pub fn port_anchor_position(
+        &self,
+        direction: PortDirection,
+        index: usize,
+        count: usize,
+    ) -> [f32; 3] {
+        let [x, y, z] = self.position;
+        let [width, height] = self.size;
+
+        let edge = match direction {
+            PortDirection::Input => x - width / 2.0,
+            PortDirection::Output => x + width / 2.0,
+        };
+
+        let body_top = y + height / 2.0 - self.header_height.clamp(0.0, height);
+        let body_height = body_top - (y - height / 2.0);
+        let spacing = body_height / (count.max(1) + 1) as f32;
+
+        [edge, body_top - spacing * (index + 1) as f32, z]
+    }
+}
+
+impl Drawable for PortAnchor {
+    fn model_matrix(&self) -> Mat4 {
+        let transform = Mat4::from_translation(Vec3::from(self.position));
+        let scale = Mat4::from_scale(Vec3::new(self.size[0], self.size[1], 1.0));
+
+        transform * scale
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+}
+
+impl Pickable for PortAnchor {
+    fn id(&self) -> DrawableId {
+        self.id
+    }
+
+    /// Matches the pill the port shader draws.
+    fn contains(&self, point: Vec2) -> bool {
+        let half_size = Vec2::from(self.size).abs() / 2.0;
+        let radius = half_size.x.min(half_size.y);
+
+        let offset = (point - Vec2::new(self.position[0], self.position[1])).abs();
+        let corner = offset - half_size + Vec2::splat(radius);
+
+        corner.max(Vec2::ZERO).length() + corner.x.max(corner.y).min(0.0) <= radius
+    }
+}
+
+/// Red, green, and blue for a hue in degrees and a saturation and value
+/// between 0 and 1.
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> [f32; 3] {
+    let chroma = value * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+
+    [r + m, g + m, b + m]
+}
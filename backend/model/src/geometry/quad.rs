@@ -1,4 +1,4 @@
-use crate::drawable::Drawable;
+use crate::drawable::{DrawOrder, Drawable};
 use glam::{Mat4, Vec3};
 
 #[derive(Debug, Clone, Copy)]
@@ -6,6 +6,7 @@ pub struct Quad {
     pub position: [f32; 3], // x, y, z
     pub size: [f32; 2],     // width, height
     pub color: [f32; 4],    // r, g, b, a
+    pub order: DrawOrder,
 }
 
 impl Drawable for Quad {
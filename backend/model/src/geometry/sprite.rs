@@ -0,0 +1,29 @@
+use crate::drawable::{DrawOrder, Drawable};
+use glam::{Mat4, Vec3};
+
+/// An image from the renderer's icon atlas, such as a node icon, a port-type
+/// glyph, or a status badge.
+///
+/// The icon is referenced by the name it was uploaded under. The image is
+/// multiplied by the tint, so white leaves it as it is.
+#[derive(Debug, Clone)]
+pub struct Sprite {
+    pub icon: String,
+    pub position: [f32; 3], // x, y, z of the center
+    pub size: [f32; 2],     // width, height
+    pub tint: [f32; 4],     // r, g, b, a
+    pub order: DrawOrder,
+}
+
+impl Drawable for Sprite {
+    fn model_matrix(&self) -> Mat4 {
+        let transform = Mat4::from_translation(Vec3::from(self.position));
+        let scale = Mat4::from_scale(Vec3::new(self.size[0], self.size[1], 1.0));
+
+        transform * scale
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.tint
+    }
+}
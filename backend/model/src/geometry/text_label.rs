@@ -0,0 +1,26 @@
+use crate::drawable::DrawOrder;
+
+/// Which point of a line of text its label's position marks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    /// The position is where the text starts.
+    #[default]
+    Left,
+    /// The position is the middle of the text.
+    Center,
+    /// The position is where the text ends.
+    Right,
+}
+
+/// Text drawn on the canvas, such as a node, port, or comment name.
+///
+/// Everything is in world units, so the text pans and zooms with the graph.
+#[derive(Debug, Clone)]
+pub struct TextLabel {
+    pub text: String,
+    pub position: [f32; 3], // x, y, z of the first line's vertical middle
+    pub size: f32,          // font size
+    pub color: [f32; 4],    // r, g, b, a
+    pub align: TextAlign,
+    pub order: DrawOrder,
+}
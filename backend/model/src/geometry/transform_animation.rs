@@ -0,0 +1,53 @@
+use crate::camera::camera_animation::Easing;
+use glam::Vec2;
+
+/// A drawable's move from one position and size to another over a fixed
+/// time, both in world units.
+///
+/// Like `CameraAnimation`, it holds no clock: whoever drives it samples it
+/// with the time elapsed since it started, once per frame.
+#[derive(Debug, Clone, Copy)]
+pub struct TransformAnimation {
+    from_position: Vec2,
+    from_size: Vec2,
+    to_position: Vec2,
+    to_size: Vec2,
+    /// In seconds.
+    duration: f32,
+    easing: Easing,
+}
+
+impl TransformAnimation {
+    pub fn new(from: (Vec2, Vec2), to: (Vec2, Vec2), duration: f32, easing: Easing) -> Self {
+        Self {
+            from_position: from.0,
+            from_size: from.1,
+            to_position: to.0,
+            to_size: to.1,
+            duration: duration.max(0.0),
+            easing,
+        }
+    }
+
+    pub fn target(&self) -> (Vec2, Vec2) {
+        (self.to_position, self.to_size)
+    }
+
+    pub fn is_finished(&self, elapsed: f32) -> bool {
+        elapsed >= self.duration
+    }
+
+    /// Position and size `elapsed` seconds in, the target once it's over.
+    pub fn sample(&self, elapsed: f32) -> (Vec2, Vec2) {
+        if self.is_finished(elapsed) {
+            return self.target();
+        }
+
+        let t = self.easing.apply(elapsed / self.duration);
+
+        (
+            self.from_position.lerp(self.to_position, t),
+            self.from_size.lerp(self.to_size, t),
+        )
+    }
+}
@@ -1,3 +1,5 @@
+use crate::graph::TypeField;
+
 use std::any::Any;
 use std::error::Error;
 
@@ -18,6 +20,18 @@ pub trait DataTypeInfo {
 
     /// Model version this type was built against.
     fn model_version(&self) -> Version;
+
+    /// For collection types, the type of their elements.
+    fn element_type_id(&self) -> Option<Uuid> {
+        None
+    }
+
+    /// For structured types, their fields in order. A structured value is
+    /// its fields' values in the same order, each prefixed by its length as
+    /// a little-endian `u32`.
+    fn fields(&self) -> Vec<TypeField> {
+        Vec::new()
+    }
 }
 
 pub trait DataType: DataTypeInfo {
@@ -28,4 +42,46 @@ pub trait DataType: DataTypeInfo {
 
     /// Deserialize bytes back to a value of this type.
     fn deserialize(&self, bytes: &[u8]) -> Result<Box<dyn Any>, Self::Error>;
+
+    /// Check that bytes are a well-formed value of this type. By default,
+    /// anything that deserializes is.
+    fn validate(&self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.deserialize(bytes).map(|_| ())
+    }
+}
+
+/// A `DataType` with its error type erased, so types with different error
+/// types can be looked up by id from one registry.
+pub trait DynDataType: DataTypeInfo {
+    fn serialize_value(&self, value: Box<dyn Any>)
+    -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>;
+
+    fn deserialize_value(&self, bytes: &[u8])
+    -> Result<Box<dyn Any>, Box<dyn Error + Send + Sync>>;
+
+    fn validate_value(&self, bytes: &[u8]) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+impl<T> DynDataType for T
+where
+    T: DataType,
+    T::Error: Send + Sync + 'static,
+{
+    fn serialize_value(
+        &self,
+        value: Box<dyn Any>,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        Ok(self.serialize(value)?)
+    }
+
+    fn deserialize_value(
+        &self,
+        bytes: &[u8],
+    ) -> Result<Box<dyn Any>, Box<dyn Error + Send + Sync>> {
+        Ok(self.deserialize(bytes)?)
+    }
+
+    fn validate_value(&self, bytes: &[u8]) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(self.validate(bytes)?)
+    }
 }
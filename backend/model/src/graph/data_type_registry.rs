@@ -1,15 +1,32 @@
 use crate::error::ModelError;
-use crate::graph::DataTypeInfo;
+use crate::graph::{DataType, DataTypeInfo, DynDataType};
 
 use common::error::error_location::ErrorLocation;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::panic::Location;
 
 use uuid::Uuid;
 
 pub struct DataTypeRegistry {
-    types: HashMap<Uuid, Box<dyn DataTypeInfo>>,
+    types: HashMap<Uuid, RegisteredType>,
+}
+
+/// A registered type, and whether the host can handle its values itself.
+enum RegisteredType {
+    /// Only described, like a type a plugin provides.
+    Info(Box<dyn DataTypeInfo>),
+    /// Serialized and validated in the host.
+    Value(Box<dyn DynDataType>),
+}
+
+impl RegisteredType {
+    fn info(&self) -> &dyn DataTypeInfo {
+        match self {
+            RegisteredType::Info(data_type) => data_type.as_ref(),
+            RegisteredType::Value(data_type) => data_type.as_ref(),
+        }
+    }
 }
 
 impl DataTypeRegistry {
@@ -21,11 +38,32 @@ impl DataTypeRegistry {
     }
 
     /// Register a data type
+    ///
+    /// A collection type, like a list of numbers, can only be registered once
+    /// its element type is, and a structured type once the types of all its
+    /// fields are.
     #[track_caller]
     pub fn register<T>(&mut self, data_type: T) -> Result<(), ModelError>
     where
         T: DataTypeInfo + 'static,
     {
+        self.insert(RegisteredType::Info(Box::new(data_type)))
+    }
+
+    /// Register a data type whose values the host serializes and validates
+    /// itself. Otherwise the same as `register`.
+    #[track_caller]
+    pub fn register_value_type<T>(&mut self, data_type: T) -> Result<(), ModelError>
+    where
+        T: DataType + 'static,
+        T::Error: Send + Sync + 'static,
+    {
+        self.insert(RegisteredType::Value(Box::new(data_type)))
+    }
+
+    #[track_caller]
+    fn insert(&mut self, registered: RegisteredType) -> Result<(), ModelError> {
+        let data_type = registered.info();
         let id = data_type.type_id();
 
         if self.types.contains_key(&id) {
@@ -34,7 +72,45 @@ impl DataTypeRegistry {
             return Ok(());
         }
 
-        self.types.insert(id, Box::new(data_type));
+        if let Some(element_type_id) = data_type.element_type_id()
+            && !self.types.contains_key(&element_type_id)
+        {
+            return Err(ModelError::ModelError {
+                message: format!(
+                    "Element type {element_type_id} of {} must be registered first",
+                    data_type.name()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        let mut field_names = HashSet::new();
+        for field in data_type.fields() {
+            if !field_names.insert(String::from(field.name())) {
+                return Err(ModelError::ModelError {
+                    message: format!(
+                        "Field '{}' of {} is declared more than once",
+                        field.name(),
+                        data_type.name()
+                    ),
+                    location: ErrorLocation::from(Location::caller()),
+                });
+            }
+
+            if !self.types.contains_key(&field.type_id()) {
+                return Err(ModelError::ModelError {
+                    message: format!(
+                        "Type {} of field '{}' of {} must be registered first",
+                        field.type_id(),
+                        field.name(),
+                        data_type.name()
+                    ),
+                    location: ErrorLocation::from(Location::caller()),
+                });
+            }
+        }
+
+        self.types.insert(id, registered);
         Ok(())
     }
 
@@ -42,10 +118,36 @@ impl DataTypeRegistry {
     pub fn get(&self, type_id: &Uuid) -> Result<&dyn DataTypeInfo, ModelError> {
         self.types
             .get(type_id)
-            .map(|boxed| boxed.as_ref())
+            .map(RegisteredType::info)
             .ok_or_else(|| ModelError::ModelError {
                 message: format!("Data type not found: {type_id}"),
                 location: ErrorLocation::from(Location::caller()),
             })
     }
+
+    /// A type registered with `register_value_type`, to serialize or
+    /// validate its values through.
+    #[track_caller]
+    pub fn get_value_type(&self, type_id: &Uuid) -> Result<&dyn DynDataType, ModelError> {
+        match self.types.get(type_id) {
+            Some(RegisteredType::Value(data_type)) => Ok(data_type.as_ref()),
+            Some(RegisteredType::Info(data_type)) => Err(ModelError::ModelError {
+                message: format!("Values of {} are not handled by the host", data_type.name()),
+                location: ErrorLocation::from(Location::caller()),
+            }),
+            None => Err(ModelError::ModelError {
+                message: format!("Data type not found: {type_id}"),
+                location: ErrorLocation::from(Location::caller()),
+            }),
+        }
+    }
+
+    /// The registered collection type whose elements are `element_type_id`
+    /// values, if there is one.
+    pub fn collection_of(&self, element_type_id: &Uuid) -> Option<&dyn DataTypeInfo> {
+        self.types
+            .values()
+            .map(RegisteredType::info)
+            .find(|t| t.element_type_id() == Some(*element_type_id))
+    }
 }
@@ -0,0 +1,11 @@
+use crate::graph::{NodeDefinitionRegistry, TypeConversionRegistry};
+
+/// What [`Graph::add_edge_with_conversions`](crate::graph::Graph::add_edge_with_conversions)
+/// validates a new edge against.
+#[derive(Clone, Copy)]
+pub struct EdgeRegistries<'a> {
+    /// Definitions of the connected nodes, for their ports.
+    pub definitions: &'a NodeDefinitionRegistry,
+    /// Conversions between the types of ports that don't match.
+    pub conversions: &'a TypeConversionRegistry,
+}
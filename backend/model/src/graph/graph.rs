@@ -1,11 +1,13 @@
 use crate::error::ModelError;
 use crate::graph::{
-    DataTypeRegistry, Edge, EdgeBuilder, Node, NodeBuilder, NodeDefinitionRegistry,
+    DataTypeRegistry, Edge, EdgeBuilder, EdgeRegistries, Node, NodeBuilder, NodeConfig,
+    NodeDefinitionRegistry, NodeIsolation, Port, RetryPolicy, TypeConversionRegistry,
 };
 
 use common::error::error_location::ErrorLocation;
 
 use std::panic::Location;
+use std::time::Duration;
 
 use uuid::Uuid;
 
@@ -64,9 +66,91 @@ impl Graph {
         Ok(node_id)
     }
 
+    /// Set or clear the retry policy of a node in the graph.
+    #[track_caller]
+    pub fn set_node_retry_policy(
+        &mut self,
+        node_id: Uuid,
+        retry_policy: Option<RetryPolicy>,
+    ) -> Result<(), ModelError> {
+        self.node_mut(node_id)?.retry_policy = retry_policy;
+
+        Ok(())
+    }
+
+    /// Set or clear the per-attempt timeout of a node in the graph.
+    /// Fails for a zero timeout.
+    #[track_caller]
+    pub fn set_node_timeout(
+        &mut self,
+        node_id: Uuid,
+        timeout: Option<Duration>,
+    ) -> Result<(), ModelError> {
+        if timeout == Some(Duration::ZERO) {
+            return Err(ModelError::ModelError {
+                message: String::from("Node timeout must be greater than zero"),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        self.node_mut(node_id)?.timeout = timeout;
+
+        Ok(())
+    }
+
+    /// Set how a node in the graph is isolated between executions.
+    #[track_caller]
+    pub fn set_node_isolation(
+        &mut self,
+        node_id: Uuid,
+        isolation: NodeIsolation,
+    ) -> Result<(), ModelError> {
+        self.node_mut(node_id)?.isolation = isolation;
+
+        Ok(())
+    }
+
+    /// Replace the configuration of a node in the graph.
+    #[track_caller]
+    pub fn set_node_config(&mut self, node_id: Uuid, config: NodeConfig) -> Result<(), ModelError> {
+        self.node_mut(node_id)?.config = config;
+
+        Ok(())
+    }
+
+    /// Set the ports of a node in the graph, for a definition whose ports
+    /// depend on the node's configuration.
+    /// Fails if an edge of the node is connected to a port that isn't kept.
+    #[track_caller]
+    pub fn set_node_ports(
+        &mut self,
+        node_id: Uuid,
+        inputs: Vec<Port>,
+        outputs: Vec<Port>,
+    ) -> Result<(), ModelError> {
+        if let Some(edge) = self.edges.iter().find(|e| {
+            (e.target_node_id() == node_id && !inputs.iter().any(|p| p.id() == e.target_port_id()))
+                || (e.source_node_id() == node_id
+                    && !outputs.iter().any(|p| p.id() == e.source_port_id()))
+        }) {
+            return Err(ModelError::ModelError {
+                message: format!(
+                    "Edge {} is connected to a port node {node_id} would no longer have",
+                    edge.id()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        self.node_mut(node_id)?.ports = Some((inputs, outputs));
+
+        Ok(())
+    }
+
     /// Add an edge to the graph.
     /// If id is None, a new UUID will be generated.
-    /// Validates that source and target nodes exist and ports are valid.
+    /// Validates that source and target nodes exist and ports are valid, and
+    /// that the ports carry the same type.
     /// Returns the edge's UUID.
     #[track_caller]
     pub fn add_edge(
@@ -78,6 +162,39 @@ impl Graph {
         target_node_id: Uuid,
         target_port_id: Uuid,
     ) -> Result<Uuid, ModelError> {
+        let registries = EdgeRegistries {
+            definitions: registry,
+            conversions: &TypeConversionRegistry::new(),
+        };
+
+        self.add_edge_with_conversions(
+            registries,
+            id,
+            source_node_id,
+            source_port_id,
+            target_node_id,
+            target_port_id,
+        )
+    }
+
+    /// Add an edge to the graph, like [`Graph::add_edge`], between ports that
+    /// carry the same type or types the registries' conversions can convert
+    /// between.
+    #[track_caller]
+    pub fn add_edge_with_conversions(
+        &mut self,
+        registries: EdgeRegistries<'_>,
+        id: Option<Uuid>,
+        source_node_id: Uuid,
+        source_port_id: Uuid,
+        target_node_id: Uuid,
+        target_port_id: Uuid,
+    ) -> Result<Uuid, ModelError> {
+        let EdgeRegistries {
+            definitions: registry,
+            conversions,
+        } = registries;
+
         // Validate source node exists
         let source_node = self
             .nodes
@@ -98,23 +215,38 @@ impl Graph {
                 location: ErrorLocation::from(Location::caller()),
             })?;
 
-        // Validate source port exists on source node definition
+        // Validate source port exists on source node
         let source_definition = registry.get(&source_node.definition_id())?;
-        let source_ports = source_definition.output_port_specs()?;
-        if !source_ports.iter().any(|p| p.id() == source_port_id) {
-            return Err(ModelError::ModelError {
+        let source_ports = source_node.output_ports(source_definition)?;
+        let source_port = source_ports
+            .iter()
+            .find(|p| p.id() == source_port_id)
+            .ok_or_else(|| ModelError::ModelError {
                 message: format!("Source port {source_port_id} not found on node {source_node_id}"),
                 location: ErrorLocation::from(Location::caller()),
-            });
-        }
+            })?;
 
-        // Validate target port exists on target node definition
+        // Validate target port exists on target node
         let target_definition = registry.get(&target_node.definition_id())?;
-        let target_ports = target_definition.input_port_specs()?;
-        if !target_ports.iter().any(|p| p.id() == target_port_id) {
-            return Err(ModelError::ModelError {
+        let target_ports = target_node.input_ports(target_definition)?;
+        let target_port = target_ports
+            .iter()
+            .find(|p| p.id() == target_port_id)
+            .ok_or_else(|| ModelError::ModelError {
                 message: format!("Target port {target_port_id} not found on node {target_node_id}"),
                 location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        // Validate the ports carry the same type, or one that converts
+        if !conversions.can_convert(&source_port.data_type_id(), &target_port.data_type_id()) {
+            return Err(ModelError::PortError {
+                message: format!(
+                    "Cannot connect a port of type {} without a conversion",
+                    source_port.data_type_id()
+                ),
+                port_name: String::from(target_port.name()),
+                data_type_id: target_port.data_type_id(),
+                location: ErrorLocation::from(Location::caller()),
             });
         }
 
@@ -136,4 +268,16 @@ impl Graph {
 
         Ok(edge_id)
     }
+
+    /// The node with the given id, for changing it in place.
+    #[track_caller]
+    fn node_mut(&mut self, node_id: Uuid) -> Result<&mut Node, ModelError> {
+        self.nodes
+            .iter_mut()
+            .find(|n| n.id() == node_id)
+            .ok_or_else(|| ModelError::ModelError {
+                message: format!("Node {node_id} not found in graph"),
+                location: ErrorLocation::from(Location::caller()),
+            })
+    }
 }
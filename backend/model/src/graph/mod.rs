@@ -2,26 +2,48 @@ mod data_type;
 mod data_type_registry;
 mod edge;
 mod edge_builder;
+mod edge_registries;
 mod graph;
 mod graph_builder;
 mod node;
 mod node_builder;
+mod node_config;
 mod node_definition;
 mod node_definition_registry;
+mod node_icon;
+mod parameter_spec;
+mod parameter_spec_builder;
 mod port;
 mod port_builder;
+mod port_values;
+mod retry_policy;
+mod retry_policy_builder;
+mod type_conversion_registry;
+mod type_field;
 
 pub use data_type::DataType;
 pub use data_type::DataTypeInfo;
+pub use data_type::DynDataType;
 pub use data_type_registry::DataTypeRegistry;
 pub use edge::Edge;
 pub use edge_builder::EdgeBuilder;
+pub use edge_registries::EdgeRegistries;
 pub use graph::Graph;
 pub use graph_builder::GraphBuilder;
 pub use node::Node;
+pub use node::NodeIsolation;
 pub use node_builder::NodeBuilder;
+pub use node_config::NodeConfig;
 pub use node_definition::NodeDefinition;
 pub use node_definition::NodeDefinitionInfo;
 pub use node_definition_registry::NodeDefinitionRegistry;
+pub use node_icon::NodeIcon;
+pub use parameter_spec::{ParameterConstraint, ParameterSpec};
+pub use parameter_spec_builder::ParameterSpecBuilder;
 pub use port::Port;
 pub use port_builder::PortBuilder;
+pub use port_values::PortValues;
+pub use retry_policy::{Backoff, ErrorClass, RetryOn, RetryPolicy};
+pub use retry_policy_builder::RetryPolicyBuilder;
+pub use type_conversion_registry::{Conversion, TypeConversionRegistry};
+pub use type_field::TypeField;
@@ -1,9 +1,32 @@
+use crate::error::ModelError;
+use crate::graph::{NodeConfig, NodeDefinitionInfo, Port, RetryPolicy};
+
+use std::time::Duration;
+
 use uuid::Uuid;
 
+/// How a node's executions are separated from one another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NodeIsolation {
+    /// Every execution starts from clean state, so a crash or runaway
+    /// memory use can't affect any other execution.
+    #[default]
+    Isolated,
+    /// Executions of this node reuse one long-lived instance, for nodes that
+    /// keep state between executions. Only this node shares the instance.
+    SharedInstance,
+}
+
+#[derive(Clone)]
 pub struct Node {
     pub(crate) id: Uuid,
     pub(crate) name: String,
     pub(crate) definition_id: Uuid,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) isolation: NodeIsolation,
+    pub(crate) config: NodeConfig,
+    pub(crate) ports: Option<(Vec<Port>, Vec<Port>)>,
 }
 
 impl Node {
@@ -18,4 +41,50 @@ impl Node {
     pub fn definition_id(&self) -> Uuid {
         self.definition_id
     }
+
+    /// How the engine retries this node when it fails. `None` means a
+    /// single attempt.
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    /// Time limit for each execution attempt of this node. `None` leaves it
+    /// to the engine's default.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn isolation(&self) -> NodeIsolation {
+        self.isolation
+    }
+
+    /// Parameters of this instance, passed to its definition on every
+    /// execution.
+    pub fn config(&self) -> &NodeConfig {
+        &self.config
+    }
+
+    /// Input ports of this instance: the ones set for its configuration, if
+    /// any, otherwise its definition's.
+    pub fn input_ports(
+        &self,
+        definition: &dyn NodeDefinitionInfo,
+    ) -> Result<Vec<Port>, ModelError> {
+        match &self.ports {
+            Some((inputs, _)) => Ok(inputs.clone()),
+            None => definition.input_port_specs(),
+        }
+    }
+
+    /// Output ports of this instance: the ones set for its configuration, if
+    /// any, otherwise its definition's.
+    pub fn output_ports(
+        &self,
+        definition: &dyn NodeDefinitionInfo,
+    ) -> Result<Vec<Port>, ModelError> {
+        match &self.ports {
+            Some((_, outputs)) => Ok(outputs.clone()),
+            None => definition.output_port_specs(),
+        }
+    }
 }
@@ -1,9 +1,10 @@
 use crate::error::ModelError;
-use crate::graph::Node;
+use crate::graph::{Node, NodeConfig, NodeIsolation, Port, RetryPolicy};
 
 use common::error::error_location::ErrorLocation;
 
 use std::panic::Location;
+use std::time::Duration;
 
 use uuid::Uuid;
 
@@ -12,6 +13,11 @@ pub struct NodeBuilder {
     id: Option<Uuid>,
     name: Option<String>,
     definition_id: Option<Uuid>,
+    retry_policy: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+    isolation: NodeIsolation,
+    config: NodeConfig,
+    ports: Option<(Vec<Port>, Vec<Port>)>,
 }
 
 impl NodeBuilder {
@@ -30,6 +36,35 @@ impl NodeBuilder {
         self
     }
 
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Must be greater than zero.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Defaults to [`NodeIsolation::Isolated`].
+    pub fn with_isolation(mut self, isolation: NodeIsolation) -> Self {
+        self.isolation = isolation;
+        self
+    }
+
+    pub fn with_config(mut self, config: NodeConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Ports for an instance of a definition whose ports depend on its
+    /// configuration. Defaults to the definition's ports.
+    pub fn with_ports(mut self, inputs: Vec<Port>, outputs: Vec<Port>) -> Self {
+        self.ports = Some((inputs, outputs));
+        self
+    }
+
     #[track_caller]
     pub fn build(self) -> Result<Node, ModelError> {
         let id = self.id.unwrap_or_else(Uuid::new_v4);
@@ -44,10 +79,22 @@ impl NodeBuilder {
             location: ErrorLocation::from(Location::caller()),
         })?;
 
+        if self.timeout == Some(Duration::ZERO) {
+            return Err(ModelError::ModelError {
+                message: String::from("Node timeout must be greater than zero"),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
         Ok(Node {
             id,
             name,
             definition_id,
+            retry_policy: self.retry_policy,
+            timeout: self.timeout,
+            isolation: self.isolation,
+            config: self.config,
+            ports: self.ports,
         })
     }
 }
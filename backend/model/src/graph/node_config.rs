@@ -0,0 +1,106 @@
+use crate::error::ModelError;
+use crate::graph::ParameterSpec;
+
+use common::error::error_location::ErrorLocation;
+
+use std::collections::BTreeMap;
+use std::panic::Location;
+
+/// Per-instance configuration of a node, as serialized values keyed by
+/// parameter name.
+///
+/// Values use the same wire format as port values, so a definition reads a
+/// parameter through the data type it expects. Parameters are kept sorted by
+/// name, which keeps iteration and hashing stable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct NodeConfig {
+    values: BTreeMap<String, Vec<u8>>,
+}
+
+impl NodeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Raw serialized value of a parameter, if it is set.
+    pub fn get(&self, name: &str) -> Option<&[u8]> {
+        self.values.get(name).map(Vec::as_slice)
+    }
+
+    /// Set the raw serialized value of a parameter, replacing any previous
+    /// value.
+    pub fn insert(&mut self, name: &str, bytes: Vec<u8>) {
+        self.values.insert(String::from(name), bytes);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Vec<u8>> {
+        self.values.remove(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.values
+            .iter()
+            .map(|(name, bytes)| (name.as_str(), bytes.as_slice()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Check the configuration against a definition's parameters: every
+    /// parameter set is declared and meets its constraints, and every
+    /// required parameter is set.
+    #[track_caller]
+    pub fn validate(&self, parameters: &[ParameterSpec]) -> Result<(), ModelError> {
+        let error = |parameter: &str, message: String| ModelError::ConfigError {
+            message,
+            parameter: String::from(parameter),
+            location: ErrorLocation::from(Location::caller()),
+        };
+
+        for (name, value) in self.iter() {
+            let spec = parameters
+                .iter()
+                .find(|spec| spec.name() == name)
+                .ok_or_else(|| error(name, String::from("Unknown parameter")))?;
+
+            for constraint in spec.constraints() {
+                constraint
+                    .check(value)
+                    .map_err(|problem| error(name, format!("Value {problem}")))?;
+            }
+        }
+
+        if let Some(spec) = parameters
+            .iter()
+            .find(|spec| spec.is_required() && !self.contains(spec.name()))
+        {
+            return Err(error(
+                spec.name(),
+                String::from("Required parameter is not set"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl FromIterator<(String, Vec<u8>)> for NodeConfig {
+    fn from_iter<I: IntoIterator<Item = (String, Vec<u8>)>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::graph::Port;
+use crate::graph::{NodeConfig, NodeIcon, ParameterSpec, Port, PortValues};
 
 use std::error::Error;
 
@@ -18,6 +18,23 @@ pub trait NodeDefinitionInfo {
     /// Description of what this node does.
     fn description(&self) -> &str;
 
+    /// Group this node type is listed under, such as Math, Logic, IO, or
+    /// Flow. Empty for an ungrouped node.
+    fn category(&self) -> &str {
+        ""
+    }
+
+    /// Extra terms this node type can be found by, besides its name.
+    fn tags(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// How this node type is drawn. `None` leaves it to the frontend's
+    /// default.
+    fn icon(&self) -> Option<NodeIcon> {
+        None
+    }
+
     /// Model version this node definition was built against.
     fn model_version(&self) -> Version;
 
@@ -26,6 +43,48 @@ pub trait NodeDefinitionInfo {
 
     /// Specifications for output ports: (name, data_type_id).
     fn output_port_specs(&self) -> Result<Vec<Port>, ModelError>;
+
+    /// The parameters of an instance's configuration, for editing them and
+    /// validating them with `NodeConfig::validate`.
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        Ok(Vec::new())
+    }
+
+    /// Whether this is a control-flow node (If, Switch) whose executions
+    /// produce values only on the output ports of the branches they take.
+    /// Nodes fed by a branch that wasn't taken are skipped. For any other
+    /// node, an output port left without a value fails whatever reads it.
+    fn is_branching(&self) -> bool {
+        false
+    }
+
+    /// Whether this node re-joins branches (Merge). It runs if any of its
+    /// inputs is fed by a branch that was taken, seeing values only on
+    /// those, and is skipped only when none of them are.
+    fn is_merging(&self) -> bool {
+        false
+    }
+
+    /// Whether an instance's ports depend on its configuration (Expression),
+    /// so they have to be resolved with `configured_ports` and set on the
+    /// node rather than taken from `input_port_specs`/`output_port_specs`.
+    fn has_configured_ports(&self) -> bool {
+        false
+    }
+
+    /// Whether an execution's outputs depend only on the instance's
+    /// configuration and inputs, so an output cache can reuse them for the
+    /// same ones. Nodes that draw randomness or have side effects aren't,
+    /// and always run.
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    /// (input ports, output ports) of an instance configured with `config`.
+    /// Defaults to the definition's ports.
+    fn configured_ports(&self, _config: &NodeConfig) -> Result<(Vec<Port>, Vec<Port>), ModelError> {
+        Ok((self.input_port_specs()?, self.output_port_specs()?))
+    }
 }
 
 /// Trait for defining node types that can be instantiated in the graph.
@@ -34,7 +93,10 @@ pub trait NodeDefinition: NodeDefinitionInfo {
     /// The error type for execution operations.
     type Error: Error;
 
-    /// Execute this node with the given inputs (serialized as bytes for WASM compatibility).
-    /// Returns serialized outputs.
-    fn execute(&self, inputs: Vec<u8>) -> Result<Vec<u8>, Self::Error>;
+    /// Execute this node with its instance's configuration and its inputs
+    /// keyed by input port id.
+    /// Values stay serialized as bytes for WASM compatibility; use
+    /// `PortValues::read`/`write` with the port's `DataType` for typed access.
+    /// Returns outputs keyed by output port id.
+    fn execute(&self, config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, Self::Error>;
 }
@@ -9,7 +9,7 @@ use std::panic::Location;
 use uuid::Uuid;
 
 pub struct NodeDefinitionRegistry {
-    definitions: HashMap<Uuid, Box<dyn NodeDefinitionInfo>>,
+    definitions: HashMap<Uuid, Box<dyn NodeDefinitionInfo + Send + Sync>>,
 }
 
 impl NodeDefinitionRegistry {
@@ -20,11 +20,12 @@ impl NodeDefinitionRegistry {
         }
     }
 
-    /// Register a node definition
+    /// Register a node definition.
+    /// Definitions are shared with the threads a run executes on.
     #[track_caller]
     pub fn register<T>(&mut self, definition: T) -> Result<(), ModelError>
     where
-        T: NodeDefinitionInfo + 'static,
+        T: NodeDefinitionInfo + Send + Sync + 'static,
     {
         let id = definition.definition_id();
 
@@ -42,7 +43,7 @@ impl NodeDefinitionRegistry {
     pub fn get(&self, definition_id: &Uuid) -> Result<&dyn NodeDefinitionInfo, ModelError> {
         self.definitions
             .get(definition_id)
-            .map(|boxed| boxed.as_ref())
+            .map(|boxed| boxed.as_ref() as &dyn NodeDefinitionInfo)
             .ok_or_else(|| ModelError::ModelError {
                 message: format!("Node definition not found: {definition_id} "),
                 location: ErrorLocation::from(Location::caller()),
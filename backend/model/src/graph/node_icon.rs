@@ -0,0 +1,8 @@
+/// How a node type is drawn on the canvas and in the palette.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NodeIcon {
+    /// A glyph from the frontend's icon set, by name.
+    Glyph(String),
+    /// An SVG document.
+    Svg(Vec<u8>),
+}
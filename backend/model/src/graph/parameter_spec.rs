@@ -0,0 +1,100 @@
+use uuid::Uuid;
+
+/// A restriction on the values a node parameter may take.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterConstraint {
+    /// Smallest allowed value of a Number parameter.
+    Minimum(f64),
+    /// Largest allowed value of a Number parameter.
+    Maximum(f64),
+    /// The serialized values the parameter may take.
+    OneOf(Vec<Vec<u8>>),
+    /// Longest allowed serialized value, in bytes.
+    MaxLength(usize),
+}
+
+impl ParameterConstraint {
+    /// Check a serialized value against the constraint. Returns why it
+    /// doesn't satisfy it.
+    ///
+    /// Number bounds read the value in the Number wire format, a
+    /// little-endian `f64`.
+    pub fn check(&self, value: &[u8]) -> Result<(), String> {
+        let number = || {
+            <[u8; 8]>::try_from(value)
+                .map(f64::from_le_bytes)
+                .map_err(|_| String::from("must be a Number"))
+        };
+
+        match self {
+            ParameterConstraint::Minimum(minimum) => {
+                let number = number()?;
+                if number < *minimum {
+                    return Err(format!("must be at least {minimum}, got {number}"));
+                }
+            }
+            ParameterConstraint::Maximum(maximum) => {
+                let number = number()?;
+                if number > *maximum {
+                    return Err(format!("must be at most {maximum}, got {number}"));
+                }
+            }
+            ParameterConstraint::OneOf(values) => {
+                if !values.iter().any(|allowed| allowed == value) {
+                    return Err(String::from("must be one of the allowed values"));
+                }
+            }
+            ParameterConstraint::MaxLength(max_length) => {
+                if value.len() > *max_length {
+                    return Err(format!(
+                        "must be at most {max_length} byte(s), got {}",
+                        value.len()
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Describes one parameter of a node definition's configuration, so
+/// instances can be configured without knowing the definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterSpec {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) data_type_id: Uuid,
+    pub(crate) default: Option<Vec<u8>>,
+    pub(crate) required: bool,
+    pub(crate) constraints: Vec<ParameterConstraint>,
+}
+
+impl ParameterSpec {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Data type of the parameter's values, in that type's wire format.
+    pub fn data_type_id(&self) -> Uuid {
+        self.data_type_id
+    }
+
+    /// Serialized value the definition uses when the parameter isn't set.
+    pub fn default(&self) -> Option<&[u8]> {
+        self.default.as_deref()
+    }
+
+    /// Whether every instance has to set the parameter.
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    pub fn constraints(&self) -> &[ParameterConstraint] {
+        &self.constraints
+    }
+}
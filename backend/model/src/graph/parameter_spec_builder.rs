@@ -0,0 +1,76 @@
+use crate::error::ModelError;
+use crate::graph::{ParameterConstraint, ParameterSpec};
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct ParameterSpecBuilder {
+    name: Option<String>,
+    description: String,
+    data_type_id: Option<Uuid>,
+    default: Option<Vec<u8>>,
+    required: bool,
+    constraints: Vec<ParameterConstraint>,
+}
+
+impl ParameterSpecBuilder {
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(String::from(name));
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = String::from(description);
+        self
+    }
+
+    pub fn with_data_type_id(mut self, data_type_id: Uuid) -> Self {
+        self.data_type_id = Some(data_type_id);
+        self
+    }
+
+    pub fn with_default(mut self, default: Vec<u8>) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    /// Defaults to optional.
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Add a constraint; values have to satisfy all of them.
+    pub fn with_constraint(mut self, constraint: ParameterConstraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    #[track_caller]
+    pub fn build(self) -> Result<ParameterSpec, ModelError> {
+        let name = self.name.ok_or_else(|| ModelError::ConfigError {
+            message: String::from("Parameter name is required"),
+            parameter: String::from("<unnamed>"),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let data_type_id = self.data_type_id.ok_or_else(|| ModelError::ConfigError {
+            message: String::from("Parameter data type id is required"),
+            parameter: name.clone(),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        Ok(ParameterSpec {
+            name,
+            description: self.description,
+            data_type_id,
+            default: self.default,
+            required: self.required,
+            constraints: self.constraints,
+        })
+    }
+}
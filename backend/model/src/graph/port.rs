@@ -1,5 +1,6 @@
 use uuid::Uuid;
 
+#[derive(Debug, Clone)]
 pub struct Port {
     pub(crate) id: Uuid,
     pub(crate) name: String,
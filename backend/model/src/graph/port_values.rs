@@ -0,0 +1,135 @@
+use crate::error::ModelError;
+use crate::graph::DataType;
+
+use common::error::error_location::ErrorLocation;
+
+use std::collections::HashMap;
+use std::panic::Location;
+
+use uuid::Uuid;
+
+/// Serialized values for one node's ports, keyed by port id.
+///
+/// This is what crosses the execution boundary: a node receives its inputs
+/// as `PortValues` and returns its outputs the same way. Typed access goes
+/// through the port's `DataType` so serialization stays with the type that
+/// owns the format.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortValues {
+    values: HashMap<Uuid, Vec<u8>>,
+}
+
+impl PortValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Raw serialized value for a port, if one was provided.
+    pub fn get(&self, port_id: &Uuid) -> Option<&[u8]> {
+        self.values.get(port_id).map(Vec::as_slice)
+    }
+
+    /// Set the raw serialized value for a port, replacing any previous value.
+    pub fn insert(&mut self, port_id: Uuid, bytes: Vec<u8>) {
+        self.values.insert(port_id, bytes);
+    }
+
+    pub fn contains(&self, port_id: &Uuid) -> bool {
+        self.values.contains_key(port_id)
+    }
+
+    pub fn remove(&mut self, port_id: &Uuid) -> Option<Vec<u8>> {
+        self.values.remove(port_id)
+    }
+
+    pub fn port_ids(&self) -> impl Iterator<Item = &Uuid> {
+        self.values.keys()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Uuid, &[u8])> {
+        self.values.iter().map(|(id, bytes)| (id, bytes.as_slice()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Deserialize a port's value through `data_type` and downcast it to `V`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModelError::ValueError` if the port has no value, the bytes
+    /// don't deserialize, or the deserialized value is not a `V`.
+    #[track_caller]
+    pub fn read<T, V>(&self, port_id: &Uuid, data_type: &T) -> Result<V, ModelError>
+    where
+        T: DataType,
+        V: 'static,
+    {
+        let bytes = self.get(port_id).ok_or_else(|| ModelError::ValueError {
+            message: String::from("No value provided"),
+            port_id: *port_id,
+            data_type_id: data_type.type_id(),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let value = data_type
+            .deserialize(bytes)
+            .map_err(|e| ModelError::ValueError {
+                message: format!("Failed to deserialize {}: {e}", data_type.name()),
+                port_id: *port_id,
+                data_type_id: data_type.type_id(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        value
+            .downcast::<V>()
+            .map(|boxed| *boxed)
+            .map_err(|_| ModelError::ValueError {
+                message: format!(
+                    "{} value is not a {}",
+                    data_type.name(),
+                    std::any::type_name::<V>()
+                ),
+                port_id: *port_id,
+                data_type_id: data_type.type_id(),
+                location: ErrorLocation::from(Location::caller()),
+            })
+    }
+
+    /// Serialize `value` through `data_type` and store it for a port.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModelError::ValueError` if the type rejects the value.
+    #[track_caller]
+    pub fn write<T, V>(&mut self, port_id: Uuid, data_type: &T, value: V) -> Result<(), ModelError>
+    where
+        T: DataType,
+        V: 'static,
+    {
+        let bytes = data_type
+            .serialize(Box::new(value))
+            .map_err(|e| ModelError::ValueError {
+                message: format!("Failed to serialize {}: {e}", data_type.name()),
+                port_id,
+                data_type_id: data_type.type_id(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        self.insert(port_id, bytes);
+        Ok(())
+    }
+}
+
+impl FromIterator<(Uuid, Vec<u8>)> for PortValues {
+    fn from_iter<I: IntoIterator<Item = (Uuid, Vec<u8>)>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
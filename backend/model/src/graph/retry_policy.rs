@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+/// Broad category of an execution failure, used to decide whether retrying
+/// could help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// A temporary condition (network blip, busy resource) that may clear.
+    Transient,
+    /// The operation ran out of time.
+    Timeout,
+    /// Retrying with the same inputs will fail the same way.
+    Permanent,
+    /// The executor didn't say.
+    Unclassified,
+}
+
+/// How long to wait between attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// Retry immediately.
+    None,
+    /// Wait the same delay before every retry.
+    Fixed(Duration),
+    /// Start at `initial` and multiply by `factor` after every retry, capped
+    /// at `max`.
+    Exponential {
+        initial: Duration,
+        factor: u32,
+        max: Duration,
+    },
+}
+
+/// Which failures are retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryOn {
+    AnyError,
+    Classes(Vec<ErrorClass>),
+}
+
+/// Per-node retry configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub(crate) max_attempts: u32,
+    pub(crate) backoff: Backoff,
+    pub(crate) retry_on: RetryOn,
+}
+
+impl RetryPolicy {
+    /// Total attempts including the first, always at least 1.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+
+    pub fn retry_on(&self) -> &RetryOn {
+        &self.retry_on
+    }
+
+    /// Whether a failure of `class` on attempt number `attempt` (1-based)
+    /// should be retried.
+    pub fn should_retry(&self, attempt: u32, class: ErrorClass) -> bool {
+        if attempt >= self.max_attempts {
+            return false;
+        }
+
+        match &self.retry_on {
+            RetryOn::AnyError => true,
+            RetryOn::Classes(classes) => classes.contains(&class),
+        }
+    }
+
+    /// Delay before retrying after attempt number `attempt` (1-based) failed.
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        match self.backoff {
+            Backoff::None => Duration::ZERO,
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential {
+                initial,
+                factor,
+                max,
+            } => {
+                let multiplier = factor.saturating_pow(attempt.saturating_sub(1));
+                initial.saturating_mul(multiplier).min(max)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::graph::RetryPolicyBuilder;
+
+    fn policy(max_attempts: u32, backoff: Backoff, retry_on: RetryOn) -> RetryPolicy {
+        RetryPolicyBuilder::default()
+            .with_max_attempts(max_attempts)
+            .with_backoff(backoff)
+            .with_retry_on(retry_on)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn retries_listed_classes_until_out_of_attempts() {
+        let retry_on = RetryOn::Classes(vec![ErrorClass::Transient, ErrorClass::Timeout]);
+        let policy = policy(3, Backoff::None, retry_on);
+
+        assert!(policy.should_retry(1, ErrorClass::Transient));
+        assert!(policy.should_retry(2, ErrorClass::Timeout));
+        assert!(!policy.should_retry(3, ErrorClass::Transient));
+        assert!(!policy.should_retry(1, ErrorClass::Permanent));
+        assert!(!policy.should_retry(1, ErrorClass::Unclassified));
+    }
+
+    #[test]
+    fn retries_any_error() {
+        let policy = policy(2, Backoff::None, RetryOn::AnyError);
+
+        assert!(policy.should_retry(1, ErrorClass::Permanent));
+        assert!(!policy.should_retry(2, ErrorClass::Permanent));
+    }
+
+    #[test]
+    fn backs_off() {
+        let fixed = policy(
+            5,
+            Backoff::Fixed(Duration::from_millis(50)),
+            RetryOn::AnyError,
+        );
+        assert_eq!(fixed.delay_after(1), Duration::from_millis(50));
+        assert_eq!(fixed.delay_after(4), Duration::from_millis(50));
+
+        let exponential = Backoff::Exponential {
+            initial: Duration::from_millis(100),
+            factor: 3,
+            max: Duration::from_secs(1),
+        };
+        let exponential = policy(u32::MAX, exponential, RetryOn::AnyError);
+        assert_eq!(exponential.delay_after(1), Duration::from_millis(100));
+        assert_eq!(exponential.delay_after(2), Duration::from_millis(300));
+        assert_eq!(exponential.delay_after(3), Duration::from_millis(900));
+        assert_eq!(exponential.delay_after(4), Duration::from_secs(1));
+        // The multiplier saturates rather than overflowing
+        assert_eq!(exponential.delay_after(1000), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn rejects_invalid_policies() {
+        assert!(RetryPolicyBuilder::default().build().is_err());
+        assert!(
+            RetryPolicyBuilder::default()
+                .with_max_attempts(0)
+                .build()
+                .is_err()
+        );
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_millis(1),
+            factor: 0,
+            max: Duration::from_secs(1),
+        };
+        assert!(
+            RetryPolicyBuilder::default()
+                .with_max_attempts(2)
+                .with_backoff(backoff)
+                .build()
+                .is_err()
+        );
+    }
+}
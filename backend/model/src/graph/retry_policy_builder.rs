@@ -0,0 +1,63 @@
+use crate::error::ModelError;
+use crate::graph::{Backoff, RetryOn, RetryPolicy};
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+#[derive(Default)]
+pub struct RetryPolicyBuilder {
+    max_attempts: Option<u32>,
+    backoff: Option<Backoff>,
+    retry_on: Option<RetryOn>,
+}
+
+impl RetryPolicyBuilder {
+    /// Total attempts including the first.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Defaults to retrying immediately.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = Some(backoff);
+        self
+    }
+
+    /// Defaults to retrying on any error.
+    pub fn with_retry_on(mut self, retry_on: RetryOn) -> Self {
+        self.retry_on = Some(retry_on);
+        self
+    }
+
+    #[track_caller]
+    pub fn build(self) -> Result<RetryPolicy, ModelError> {
+        let max_attempts = self.max_attempts.ok_or_else(|| ModelError::ModelError {
+            message: String::from("Retry policy max attempts is required"),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        if max_attempts == 0 {
+            return Err(ModelError::ModelError {
+                message: String::from("Retry policy max attempts must be at least 1"),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        let backoff = self.backoff.unwrap_or(Backoff::None);
+
+        if let Backoff::Exponential { factor: 0, .. } = backoff {
+            return Err(ModelError::ModelError {
+                message: String::from("Exponential backoff factor must be at least 1"),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        Ok(RetryPolicy {
+            max_attempts,
+            backoff,
+            retry_on: self.retry_on.unwrap_or(RetryOn::AnyError),
+        })
+    }
+}
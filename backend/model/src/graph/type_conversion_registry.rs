@@ -0,0 +1,87 @@
+use crate::error::ModelError;
+
+use common::error::error_location::ErrorLocation;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::panic::Location;
+
+use uuid::Uuid;
+
+/// Turns a serialized value of one data type into a serialized value of
+/// another.
+pub type Conversion =
+    Box<dyn Fn(&[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> + Send + Sync>;
+
+/// Conversions between data types, keyed by (from type id, to type id).
+///
+/// Edges may connect ports of different types when a conversion between them
+/// is registered; the engine applies it to every value crossing the edge.
+#[derive(Default)]
+pub struct TypeConversionRegistry {
+    conversions: HashMap<(Uuid, Uuid), Conversion>,
+}
+
+impl TypeConversionRegistry {
+    /// Create a new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register how to convert `from_type_id` values into `to_type_id`
+    /// values, replacing any earlier conversion between the two.
+    #[track_caller]
+    pub fn register<F>(
+        &mut self,
+        from_type_id: Uuid,
+        to_type_id: Uuid,
+        conversion: F,
+    ) -> Result<(), ModelError>
+    where
+        F: Fn(&[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> + Send + Sync + 'static,
+    {
+        if from_type_id == to_type_id {
+            return Err(ModelError::ModelError {
+                message: format!("Cannot register a conversion from type {from_type_id} to itself"),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        self.conversions
+            .insert((from_type_id, to_type_id), Box::new(conversion));
+        Ok(())
+    }
+
+    /// Whether values of `from_type_id` can flow into a `to_type_id` port,
+    /// either because the types match or through a registered conversion.
+    pub fn can_convert(&self, from_type_id: &Uuid, to_type_id: &Uuid) -> bool {
+        from_type_id == to_type_id || self.conversions.contains_key(&(*from_type_id, *to_type_id))
+    }
+
+    /// Convert a serialized `from_type_id` value to `to_type_id`. Values
+    /// already of the right type are returned unchanged.
+    #[track_caller]
+    pub fn convert(
+        &self,
+        from_type_id: &Uuid,
+        to_type_id: &Uuid,
+        bytes: &[u8],
+    ) -> Result<Vec<u8>, ModelError> {
+        if from_type_id == to_type_id {
+            return Ok(bytes.to_vec());
+        }
+
+        let conversion = self
+            .conversions
+            .get(&(*from_type_id, *to_type_id))
+            .ok_or_else(|| ModelError::ModelError {
+                message: format!("No conversion from type {from_type_id} to {to_type_id}"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        conversion(bytes).map_err(|e| ModelError::ModelError {
+            message: format!("Converting type {from_type_id} to {to_type_id} failed: {e}"),
+            location: ErrorLocation::from(Location::caller()),
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use uuid::Uuid;
+
+/// One named field of a structured data type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeField {
+    pub(crate) name: String,
+    pub(crate) type_id: Uuid,
+}
+
+impl TypeField {
+    pub fn new(name: impl Into<String>, type_id: Uuid) -> Self {
+        Self {
+            name: name.into(),
+            type_id,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The data type of the field's values.
+    pub fn type_id(&self) -> Uuid {
+        self.type_id
+    }
+}
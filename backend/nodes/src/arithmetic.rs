@@ -0,0 +1,180 @@
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, Port, PortBuilder,
+    PortValues,
+};
+use cognexus_types::NumberType;
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+/// The operation an [`ArithmeticNode`] applies to its two inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticOperation {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+/// Combines two Numbers, `a` and `b`, into one: `a + b`, `a - b`, `a * b`,
+/// or `a / b`. Each operation is its own node definition.
+pub struct ArithmeticNode {
+    operation: ArithmeticOperation,
+}
+
+const ADD_ID: &str = "9ef23987-3038-4ff0-a68c-c7cb1fbbd79e";
+const SUBTRACT_ID: &str = "abb7622a-34d5-4ad0-9734-d7ffd3a7fffa";
+const MULTIPLY_ID: &str = "1e591a16-446e-43a8-adbe-b029fc4c1aac";
+const DIVIDE_ID: &str = "e9815cd5-95cf-4d7a-8475-a8782d60da06";
+const A_PORT_ID: &str = "1722d9a8-626c-43f4-99ff-c8c62c3f9e44";
+const B_PORT_ID: &str = "4ed65351-eb01-4fb2-977f-524dd3ee2e3e";
+const RESULT_PORT_ID: &str = "f00aa156-f08c-4cb9-a193-818aca343048";
+
+impl ArithmeticNode {
+    pub fn new(operation: ArithmeticOperation) -> Self {
+        Self { operation }
+    }
+
+    pub fn operation(&self) -> ArithmeticOperation {
+        self.operation
+    }
+}
+
+impl NodeDefinitionInfo for ArithmeticNode {
+    fn definition_id(&self) -> Uuid {
+        let id = match self.operation {
+            ArithmeticOperation::Add => ADD_ID,
+            ArithmeticOperation::Subtract => SUBTRACT_ID,
+            ArithmeticOperation::Multiply => MULTIPLY_ID,
+            ArithmeticOperation::Divide => DIVIDE_ID,
+        };
+
+        Uuid::parse_str(id).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        match self.operation {
+            ArithmeticOperation::Add => "Add",
+            ArithmeticOperation::Subtract => "Subtract",
+            ArithmeticOperation::Multiply => "Multiply",
+            ArithmeticOperation::Divide => "Divide",
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self.operation {
+            ArithmeticOperation::Add => "Adds two numbers",
+            ArithmeticOperation::Subtract => "Subtracts b from a",
+            ArithmeticOperation::Multiply => "Multiplies two numbers",
+            ArithmeticOperation::Divide => "Divides a by b",
+        }
+    }
+
+    fn category(&self) -> &str {
+        "Math"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        match self.operation {
+            ArithmeticOperation::Add => vec![String::from("sum"), String::from("plus")],
+            ArithmeticOperation::Subtract => {
+                vec![String::from("difference"), String::from("minus")]
+            }
+            ArithmeticOperation::Multiply => vec![String::from("product"), String::from("times")],
+            ArithmeticOperation::Divide => vec![String::from("quotient"), String::from("ratio")],
+        }
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        let glyph = match self.operation {
+            ArithmeticOperation::Add => "plus",
+            ArithmeticOperation::Subtract => "minus",
+            ArithmeticOperation::Multiply => "times",
+            ArithmeticOperation::Divide => "divide",
+        };
+
+        Some(NodeIcon::Glyph(String::from(glyph)))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let number_type = NumberType;
+        let a = PortBuilder::default()
+            .with_id(Uuid::parse_str(A_PORT_ID).unwrap())
+            .with_name("a")
+            .with_data_type_id(number_type.type_id())
+            .build()?;
+        let b = PortBuilder::default()
+            .with_id(Uuid::parse_str(B_PORT_ID).unwrap())
+            .with_name("b")
+            .with_data_type_id(number_type.type_id())
+            .build()?;
+
+        Ok(vec![a, b])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let number_type = NumberType;
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(RESULT_PORT_ID).unwrap())
+            .with_name("result")
+            .with_data_type_id(number_type.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+}
+
+impl NodeDefinition for ArithmeticNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, _config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let read = |port_id: &str| -> Result<f64, NodeError> {
+            inputs
+                .read(&Uuid::parse_str(port_id).unwrap(), &NumberType)
+                .map_err(|e| NodeError::InvalidInput {
+                    message: e.to_string(),
+                    location: ErrorLocation::from(Location::caller()),
+                })
+        };
+        let (a, b) = (read(A_PORT_ID)?, read(B_PORT_ID)?);
+
+        let result = match self.operation {
+            ArithmeticOperation::Add => a + b,
+            ArithmeticOperation::Subtract => a - b,
+            ArithmeticOperation::Multiply => a * b,
+            ArithmeticOperation::Divide if b == 0.0 => {
+                return Err(NodeError::ExecutionError {
+                    message: format!("Cannot divide {a} by zero"),
+                    location: ErrorLocation::from(Location::caller()),
+                });
+            }
+            ArithmeticOperation::Divide => a / b,
+        };
+
+        let mut outputs = PortValues::new();
+        outputs
+            .write(
+                Uuid::parse_str(RESULT_PORT_ID).unwrap(),
+                &NumberType,
+                result,
+            )
+            .map_err(|e| NodeError::SerializationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(outputs)
+    }
+}
@@ -3,6 +3,246 @@
 //   * runtime_path: "wit_bindgen_rt"
 #[rustfmt::skip]
 #[allow(dead_code, clippy::all)]
+pub mod cognexus {
+    pub mod plugin {
+        /// Timekeeping the host provides to node plugins.
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod clock {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            #[allow(unused_unsafe, clippy::all)]
+            /// Wait `nanoseconds` before returning. The host ends the wait early,
+            /// trapping the call, if the call is interrupted or runs out of time, and
+            /// skips it when a recorded run is being replayed.
+            pub fn sleep(nanoseconds: u64) -> () {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "cognexus:plugin/clock")]
+                    unsafe extern "C" {
+                        #[link_name = "sleep"]
+                        fn wit_import0(_: i64);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0(_: i64) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import0(_rt::as_i64(&nanoseconds)) };
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Hold the outputs of the call back for `nanoseconds` after it returns,
+            /// without waiting in the call. The host waits it out while other nodes
+            /// run, fails the call if the wait would outlast its time limit, and
+            /// skips it when a recorded run is being replayed.
+            pub fn defer(nanoseconds: u64) -> () {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "cognexus:plugin/clock")]
+                    unsafe extern "C" {
+                        #[link_name = "defer"]
+                        fn wit_import0(_: i64);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0(_: i64) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import0(_rt::as_i64(&nanoseconds)) };
+                }
+            }
+        }
+        /// Randomness the host provides to node plugins. Each call draws from a
+        /// source seeded by the host, and the seed is kept when the call is recorded,
+        /// so replaying the call draws the same values.
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod random {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            #[allow(unused_unsafe, clippy::all)]
+            /// `len` random bytes. The host traps calls asking for more than 1 MiB.
+            pub fn random_bytes(len: u32) -> _rt::Vec<u8> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 2
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "cognexus:plugin/random")]
+                    unsafe extern "C" {
+                        #[link_name = "random-bytes"]
+                        fn wit_import1(_: i32, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import1(_rt::as_i32(&len), ptr0) };
+                    let l2 = *ptr0.add(0).cast::<*mut u8>();
+                    let l3 = *ptr0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len4 = l3;
+                    let result5 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
+                    result5
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn random_u64() -> u64 {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "cognexus:plugin/random")]
+                    unsafe extern "C" {
+                        #[link_name = "random-u64"]
+                        fn wit_import0() -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0() -> i64 {
+                        unreachable!()
+                    }
+                    let ret = unsafe { wit_import0() };
+                    ret as u64
+                }
+            }
+        }
+        /// Logging the host provides to node plugins. Messages are attributed to
+        /// the node execution in progress.
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod logging {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            pub enum Level {
+                Error,
+                Warn,
+                Info,
+                Debug,
+                Trace,
+            }
+            impl ::core::fmt::Debug for Level {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        Level::Error => f.debug_tuple("Level::Error").finish(),
+                        Level::Warn => f.debug_tuple("Level::Warn").finish(),
+                        Level::Info => f.debug_tuple("Level::Info").finish(),
+                        Level::Debug => f.debug_tuple("Level::Debug").finish(),
+                        Level::Trace => f.debug_tuple("Level::Trace").finish(),
+                    }
+                }
+            }
+            impl Level {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> Level {
+                    if !cfg!(debug_assertions) {
+                        return ::core::mem::transmute(val);
+                    }
+                    match val {
+                        0 => Level::Error,
+                        1 => Level::Warn,
+                        2 => Level::Info,
+                        3 => Level::Debug,
+                        4 => Level::Trace,
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn log(level: Level, message: &str) -> () {
+                unsafe {
+                    let vec0 = message;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "cognexus:plugin/logging")]
+                    unsafe extern "C" {
+                        #[link_name = "log"]
+                        fn wit_import1(_: i32, _: *mut u8, _: usize);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: i32, _: *mut u8, _: usize) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import1(level.clone() as i32, ptr0.cast_mut(), len0) };
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Log `message` with a serialized value of the type `type-id`, which the
+            /// host keeps so it can be inspected after the run.
+            pub fn log_value(
+                level: Level,
+                message: &str,
+                type_id: &str,
+                value: &[u8],
+            ) -> () {
+                unsafe {
+                    let vec0 = message;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = type_id;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let vec2 = value;
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "cognexus:plugin/logging")]
+                    unsafe extern "C" {
+                        #[link_name = "log-value"]
+                        fn wit_import3(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(
+                        _: i32,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import3(
+                            level.clone() as i32,
+                            ptr0.cast_mut(),
+                            len0,
+                            ptr1.cast_mut(),
+                            len1,
+                            ptr2.cast_mut(),
+                            len2,
+                        )
+                    };
+                }
+            }
+        }
+    }
+}
+#[rustfmt::skip]
+#[allow(dead_code, clippy::all)]
 pub mod exports {
     pub mod cognexus {
         pub mod plugin {
@@ -66,14 +306,127 @@ pub mod exports {
                             .finish()
                     }
                 }
+                /// How a node is drawn on the canvas and in the palette.
+                #[derive(Clone)]
+                pub enum Icon {
+                    /// A glyph from the frontend's icon set, by name.
+                    Glyph(_rt::String),
+                    /// An SVG document.
+                    Svg(_rt::Vec<u8>),
+                }
+                impl ::core::fmt::Debug for Icon {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            Icon::Glyph(e) => {
+                                f.debug_tuple("Icon::Glyph").field(e).finish()
+                            }
+                            Icon::Svg(e) => f.debug_tuple("Icon::Svg").field(e).finish(),
+                        }
+                    }
+                }
+                /// A restriction on the values a parameter may take.
+                #[derive(Clone)]
+                pub enum ParameterConstraint {
+                    /// Smallest allowed value of a Number parameter.
+                    Minimum(f64),
+                    /// Largest allowed value of a Number parameter.
+                    Maximum(f64),
+                    /// The serialized values the parameter may take.
+                    OneOf(_rt::Vec<_rt::Vec<u8>>),
+                    /// Longest allowed serialized value, in bytes.
+                    MaxLength(u64),
+                }
+                impl ::core::fmt::Debug for ParameterConstraint {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            ParameterConstraint::Minimum(e) => {
+                                f.debug_tuple("ParameterConstraint::Minimum")
+                                    .field(e)
+                                    .finish()
+                            }
+                            ParameterConstraint::Maximum(e) => {
+                                f.debug_tuple("ParameterConstraint::Maximum")
+                                    .field(e)
+                                    .finish()
+                            }
+                            ParameterConstraint::OneOf(e) => {
+                                f.debug_tuple("ParameterConstraint::OneOf")
+                                    .field(e)
+                                    .finish()
+                            }
+                            ParameterConstraint::MaxLength(e) => {
+                                f.debug_tuple("ParameterConstraint::MaxLength")
+                                    .field(e)
+                                    .finish()
+                            }
+                        }
+                    }
+                }
+                /// One parameter of a node's configuration.
+                #[derive(Clone)]
+                pub struct ParameterSpec {
+                    pub name: _rt::String,
+                    pub description: _rt::String,
+                    /// Data type of the parameter's values, in that type's wire format.
+                    pub data_type_id: _rt::String,
+                    /// Serialized value the node uses when the parameter isn't set.
+                    pub default_value: Option<_rt::Vec<u8>>,
+                    /// Whether every instance has to set the parameter.
+                    pub required: bool,
+                    pub constraints: _rt::Vec<ParameterConstraint>,
+                }
+                impl ::core::fmt::Debug for ParameterSpec {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("ParameterSpec")
+                            .field("name", &self.name)
+                            .field("description", &self.description)
+                            .field("data-type-id", &self.data_type_id)
+                            .field("default-value", &self.default_value)
+                            .field("required", &self.required)
+                            .field("constraints", &self.constraints)
+                            .finish()
+                    }
+                }
                 #[derive(Clone)]
                 pub struct NodeInfo {
                     pub id: _rt::String,
                     pub name: _rt::String,
                     pub description: _rt::String,
+                    /// Group the node is listed under, such as Math, Logic, IO, or Flow;
+                    /// empty for an ungrouped node.
+                    pub category: _rt::String,
+                    /// Extra terms the node can be found by, besides its name.
+                    pub tags: _rt::Vec<_rt::String>,
+                    /// How the node is drawn; none leaves it to the frontend's default.
+                    pub icon: Option<Icon>,
                     pub version: _rt::String,
                     pub input_ports: _rt::Vec<PortSpec>,
                     pub output_ports: _rt::Vec<PortSpec>,
+                    /// Parameters of an instance's configuration.
+                    pub parameters: _rt::Vec<ParameterSpec>,
+                    /// Whether executions produce values only on the output ports of the
+                    /// branch they take, like an If node's.
+                    pub branching: bool,
+                    /// Whether the node re-joins branches, running when any of its
+                    /// inputs is fed by a branch that was taken, like a Merge node.
+                    pub merging: bool,
+                    /// Whether an instance's ports depend on its configuration, in which
+                    /// case `resolve-ports` gives them and the ports above are only the
+                    /// ones an unconfigured instance has.
+                    pub configured_ports: bool,
+                    /// Whether an execution's outputs depend only on the instance's
+                    /// configuration and inputs, so they can be reused for the same ones.
+                    /// False for nodes that draw randomness or have side effects.
+                    pub deterministic: bool,
                 }
                 impl ::core::fmt::Debug for NodeInfo {
                     fn fmt(
@@ -84,9 +437,67 @@ pub mod exports {
                             .field("id", &self.id)
                             .field("name", &self.name)
                             .field("description", &self.description)
+                            .field("category", &self.category)
+                            .field("tags", &self.tags)
+                            .field("icon", &self.icon)
                             .field("version", &self.version)
                             .field("input-ports", &self.input_ports)
                             .field("output-ports", &self.output_ports)
+                            .field("parameters", &self.parameters)
+                            .field("branching", &self.branching)
+                            .field("merging", &self.merging)
+                            .field("configured-ports", &self.configured_ports)
+                            .field("deterministic", &self.deterministic)
+                            .finish()
+                    }
+                }
+                /// The ports of one node instance.
+                #[derive(Clone)]
+                pub struct InstancePorts {
+                    pub input_ports: _rt::Vec<PortSpec>,
+                    pub output_ports: _rt::Vec<PortSpec>,
+                }
+                impl ::core::fmt::Debug for InstancePorts {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("InstancePorts")
+                            .field("input-ports", &self.input_ports)
+                            .field("output-ports", &self.output_ports)
+                            .finish()
+                    }
+                }
+                #[derive(Clone)]
+                pub struct PortValue {
+                    pub port_id: _rt::String,
+                    pub value: _rt::Vec<u8>,
+                }
+                impl ::core::fmt::Debug for PortValue {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("PortValue")
+                            .field("port-id", &self.port_id)
+                            .field("value", &self.value)
+                            .finish()
+                    }
+                }
+                /// One parameter of a node instance's configuration.
+                #[derive(Clone)]
+                pub struct ConfigValue {
+                    pub name: _rt::String,
+                    pub value: _rt::Vec<u8>,
+                }
+                impl ::core::fmt::Debug for ConfigValue {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("ConfigValue")
+                            .field("name", &self.name)
+                            .field("value", &self.value)
                             .finish()
                     }
                 }
@@ -96,32 +507,40 @@ pub mod exports {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
                     let result0 = T::list_nodes();
                     let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
-                    let vec17 = result0;
-                    let len17 = vec17.len();
-                    let layout17 = _rt::alloc::Layout::from_size_align_unchecked(
-                        vec17.len() * (12 * ::core::mem::size_of::<*const u8>()),
+                    let vec31 = result0;
+                    let len31 = vec31.len();
+                    let layout31 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec31.len() * (23 * ::core::mem::size_of::<*const u8>()),
                         ::core::mem::size_of::<*const u8>(),
                     );
-                    let result17 = if layout17.size() != 0 {
-                        let ptr = _rt::alloc::alloc(layout17).cast::<u8>();
+                    let result31 = if layout31.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout31).cast::<u8>();
                         if ptr.is_null() {
-                            _rt::alloc::handle_alloc_error(layout17);
+                            _rt::alloc::handle_alloc_error(layout31);
                         }
                         ptr
                     } else {
                         ::core::ptr::null_mut()
                     };
-                    for (i, e) in vec17.into_iter().enumerate() {
-                        let base = result17
-                            .add(i * (12 * ::core::mem::size_of::<*const u8>()));
+                    for (i, e) in vec31.into_iter().enumerate() {
+                        let base = result31
+                            .add(i * (23 * ::core::mem::size_of::<*const u8>()));
                         {
                             let NodeInfo {
                                 id: id2,
                                 name: name2,
                                 description: description2,
+                                category: category2,
+                                tags: tags2,
+                                icon: icon2,
                                 version: version2,
                                 input_ports: input_ports2,
                                 output_ports: output_ports2,
+                                parameters: parameters2,
+                                branching: branching2,
+                                merging: merging2,
+                                configured_ports: configured_ports2,
+                                deterministic: deterministic2,
                             } = e;
                             let vec3 = (id2.into_bytes()).into_boxed_slice();
                             let ptr3 = vec3.as_ptr().cast::<u8>();
@@ -151,7 +570,7 @@ pub mod exports {
                             *base
                                 .add(4 * ::core::mem::size_of::<*const u8>())
                                 .cast::<*mut u8>() = ptr5.cast_mut();
-                            let vec6 = (version2.into_bytes()).into_boxed_slice();
+                            let vec6 = (category2.into_bytes()).into_boxed_slice();
                             let ptr6 = vec6.as_ptr().cast::<u8>();
                             let len6 = vec6.len();
                             ::core::mem::forget(vec6);
@@ -161,71 +580,96 @@ pub mod exports {
                             *base
                                 .add(6 * ::core::mem::size_of::<*const u8>())
                                 .cast::<*mut u8>() = ptr6.cast_mut();
-                            let vec11 = input_ports2;
-                            let len11 = vec11.len();
-                            let layout11 = _rt::alloc::Layout::from_size_align_unchecked(
-                                vec11.len() * (7 * ::core::mem::size_of::<*const u8>()),
+                            let vec8 = tags2;
+                            let len8 = vec8.len();
+                            let layout8 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec8.len() * (2 * ::core::mem::size_of::<*const u8>()),
                                 ::core::mem::size_of::<*const u8>(),
                             );
-                            let result11 = if layout11.size() != 0 {
-                                let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
+                            let result8 = if layout8.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout8).cast::<u8>();
                                 if ptr.is_null() {
-                                    _rt::alloc::handle_alloc_error(layout11);
+                                    _rt::alloc::handle_alloc_error(layout8);
                                 }
                                 ptr
                             } else {
                                 ::core::ptr::null_mut()
                             };
-                            for (i, e) in vec11.into_iter().enumerate() {
-                                let base = result11
-                                    .add(i * (7 * ::core::mem::size_of::<*const u8>()));
+                            for (i, e) in vec8.into_iter().enumerate() {
+                                let base = result8
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
                                 {
-                                    let PortSpec {
-                                        id: id7,
-                                        name: name7,
-                                        direction: direction7,
-                                        data_type_id: data_type_id7,
-                                    } = e;
-                                    let vec8 = (id7.into_bytes()).into_boxed_slice();
-                                    let ptr8 = vec8.as_ptr().cast::<u8>();
-                                    let len8 = vec8.len();
-                                    ::core::mem::forget(vec8);
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
                                     *base
                                         .add(::core::mem::size_of::<*const u8>())
-                                        .cast::<usize>() = len8;
-                                    *base.add(0).cast::<*mut u8>() = ptr8.cast_mut();
-                                    let vec9 = (name7.into_bytes()).into_boxed_slice();
-                                    let ptr9 = vec9.as_ptr().cast::<u8>();
-                                    let len9 = vec9.len();
-                                    ::core::mem::forget(vec9);
-                                    *base
-                                        .add(3 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<usize>() = len9;
-                                    *base
-                                        .add(2 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<*mut u8>() = ptr9.cast_mut();
-                                    *base
-                                        .add(4 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<u8>() = (direction7.clone() as i32) as u8;
-                                    let vec10 = (data_type_id7.into_bytes()).into_boxed_slice();
-                                    let ptr10 = vec10.as_ptr().cast::<u8>();
-                                    let len10 = vec10.len();
-                                    ::core::mem::forget(vec10);
-                                    *base
-                                        .add(6 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<usize>() = len10;
-                                    *base
-                                        .add(5 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<*mut u8>() = ptr10.cast_mut();
+                                        .cast::<usize>() = len7;
+                                    *base.add(0).cast::<*mut u8>() = ptr7.cast_mut();
                                 }
                             }
                             *base
                                 .add(9 * ::core::mem::size_of::<*const u8>())
-                                .cast::<usize>() = len11;
+                                .cast::<usize>() = len8;
                             *base
                                 .add(8 * ::core::mem::size_of::<*const u8>())
-                                .cast::<*mut u8>() = result11;
-                            let vec16 = output_ports2;
+                                .cast::<*mut u8>() = result8;
+                            match icon2 {
+                                Some(e) => {
+                                    *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    match e {
+                                        Icon::Glyph(e) => {
+                                            *base
+                                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                            let vec9 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr9 = vec9.as_ptr().cast::<u8>();
+                                            let len9 = vec9.len();
+                                            ::core::mem::forget(vec9);
+                                            *base
+                                                .add(13 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len9;
+                                            *base
+                                                .add(12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr9.cast_mut();
+                                        }
+                                        Icon::Svg(e) => {
+                                            *base
+                                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let vec10 = (e).into_boxed_slice();
+                                            let ptr10 = vec10.as_ptr().cast::<u8>();
+                                            let len10 = vec10.len();
+                                            ::core::mem::forget(vec10);
+                                            *base
+                                                .add(13 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len10;
+                                            *base
+                                                .add(12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr10.cast_mut();
+                                        }
+                                    }
+                                }
+                                None => {
+                                    *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            let vec11 = (version2.into_bytes()).into_boxed_slice();
+                            let ptr11 = vec11.as_ptr().cast::<u8>();
+                            let len11 = vec11.len();
+                            ::core::mem::forget(vec11);
+                            *base
+                                .add(15 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len11;
+                            *base
+                                .add(14 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr11.cast_mut();
+                            let vec16 = input_ports2;
                             let len16 = vec16.len();
                             let layout16 = _rt::alloc::Layout::from_size_align_unchecked(
                                 vec16.len() * (7 * ::core::mem::size_of::<*const u8>()),
@@ -285,15 +729,273 @@ pub mod exports {
                                 }
                             }
                             *base
-                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                .add(17 * ::core::mem::size_of::<*const u8>())
                                 .cast::<usize>() = len16;
                             *base
-                                .add(10 * ::core::mem::size_of::<*const u8>())
+                                .add(16 * ::core::mem::size_of::<*const u8>())
                                 .cast::<*mut u8>() = result16;
+                            let vec21 = output_ports2;
+                            let len21 = vec21.len();
+                            let layout21 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec21.len() * (7 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let result21 = if layout21.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout21).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout21);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec21.into_iter().enumerate() {
+                                let base = result21
+                                    .add(i * (7 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let PortSpec {
+                                        id: id17,
+                                        name: name17,
+                                        direction: direction17,
+                                        data_type_id: data_type_id17,
+                                    } = e;
+                                    let vec18 = (id17.into_bytes()).into_boxed_slice();
+                                    let ptr18 = vec18.as_ptr().cast::<u8>();
+                                    let len18 = vec18.len();
+                                    ::core::mem::forget(vec18);
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len18;
+                                    *base.add(0).cast::<*mut u8>() = ptr18.cast_mut();
+                                    let vec19 = (name17.into_bytes()).into_boxed_slice();
+                                    let ptr19 = vec19.as_ptr().cast::<u8>();
+                                    let len19 = vec19.len();
+                                    ::core::mem::forget(vec19);
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len19;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr19.cast_mut();
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (direction17.clone() as i32) as u8;
+                                    let vec20 = (data_type_id17.into_bytes())
+                                        .into_boxed_slice();
+                                    let ptr20 = vec20.as_ptr().cast::<u8>();
+                                    let len20 = vec20.len();
+                                    ::core::mem::forget(vec20);
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len20;
+                                    *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr20.cast_mut();
+                                }
+                            }
+                            *base
+                                .add(19 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len21;
+                            *base
+                                .add(18 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result21;
+                            let vec30 = parameters2;
+                            let len30 = vec30.len();
+                            let layout30 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec30.len() * (12 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let result30 = if layout30.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout30).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout30);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec30.into_iter().enumerate() {
+                                let base = result30
+                                    .add(i * (12 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let ParameterSpec {
+                                        name: name22,
+                                        description: description22,
+                                        data_type_id: data_type_id22,
+                                        default_value: default_value22,
+                                        required: required22,
+                                        constraints: constraints22,
+                                    } = e;
+                                    let vec23 = (name22.into_bytes()).into_boxed_slice();
+                                    let ptr23 = vec23.as_ptr().cast::<u8>();
+                                    let len23 = vec23.len();
+                                    ::core::mem::forget(vec23);
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len23;
+                                    *base.add(0).cast::<*mut u8>() = ptr23.cast_mut();
+                                    let vec24 = (description22.into_bytes()).into_boxed_slice();
+                                    let ptr24 = vec24.as_ptr().cast::<u8>();
+                                    let len24 = vec24.len();
+                                    ::core::mem::forget(vec24);
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len24;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr24.cast_mut();
+                                    let vec25 = (data_type_id22.into_bytes())
+                                        .into_boxed_slice();
+                                    let ptr25 = vec25.as_ptr().cast::<u8>();
+                                    let len25 = vec25.len();
+                                    ::core::mem::forget(vec25);
+                                    *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len25;
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr25.cast_mut();
+                                    match default_value22 {
+                                        Some(e) => {
+                                            *base
+                                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let vec26 = (e).into_boxed_slice();
+                                            let ptr26 = vec26.as_ptr().cast::<u8>();
+                                            let len26 = vec26.len();
+                                            ::core::mem::forget(vec26);
+                                            *base
+                                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len26;
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr26.cast_mut();
+                                        }
+                                        None => {
+                                            *base
+                                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (match required22 {
+                                        true => 1,
+                                        false => 0,
+                                    }) as u8;
+                                    let vec29 = constraints22;
+                                    let len29 = vec29.len();
+                                    let layout29 = _rt::alloc::Layout::from_size_align_unchecked(
+                                        vec29.len() * (8 + 2 * ::core::mem::size_of::<*const u8>()),
+                                        8,
+                                    );
+                                    let result29 = if layout29.size() != 0 {
+                                        let ptr = _rt::alloc::alloc(layout29).cast::<u8>();
+                                        if ptr.is_null() {
+                                            _rt::alloc::handle_alloc_error(layout29);
+                                        }
+                                        ptr
+                                    } else {
+                                        ::core::ptr::null_mut()
+                                    };
+                                    for (i, e) in vec29.into_iter().enumerate() {
+                                        let base = result29
+                                            .add(i * (8 + 2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            match e {
+                                                ParameterConstraint::Minimum(e) => {
+                                                    *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                    *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                                }
+                                                ParameterConstraint::Maximum(e) => {
+                                                    *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                    *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                                }
+                                                ParameterConstraint::OneOf(e) => {
+                                                    *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                    let vec28 = e;
+                                                    let len28 = vec28.len();
+                                                    let layout28 = _rt::alloc::Layout::from_size_align_unchecked(
+                                                        vec28.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                                        ::core::mem::size_of::<*const u8>(),
+                                                    );
+                                                    let result28 = if layout28.size() != 0 {
+                                                        let ptr = _rt::alloc::alloc(layout28).cast::<u8>();
+                                                        if ptr.is_null() {
+                                                            _rt::alloc::handle_alloc_error(layout28);
+                                                        }
+                                                        ptr
+                                                    } else {
+                                                        ::core::ptr::null_mut()
+                                                    };
+                                                    for (i, e) in vec28.into_iter().enumerate() {
+                                                        let base = result28
+                                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                                        {
+                                                            let vec27 = (e).into_boxed_slice();
+                                                            let ptr27 = vec27.as_ptr().cast::<u8>();
+                                                            let len27 = vec27.len();
+                                                            ::core::mem::forget(vec27);
+                                                            *base
+                                                                .add(::core::mem::size_of::<*const u8>())
+                                                                .cast::<usize>() = len27;
+                                                            *base.add(0).cast::<*mut u8>() = ptr27.cast_mut();
+                                                        }
+                                                    }
+                                                    *base
+                                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<usize>() = len28;
+                                                    *base.add(8).cast::<*mut u8>() = result28;
+                                                }
+                                                ParameterConstraint::MaxLength(e) => {
+                                                    *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                    *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                                }
+                                            }
+                                        }
+                                    }
+                                    *base
+                                        .add(11 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len29;
+                                    *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = result29;
+                                }
+                            }
+                            *base
+                                .add(21 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len30;
+                            *base
+                                .add(20 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result30;
+                            *base
+                                .add(22 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (match branching2 {
+                                true => 1,
+                                false => 0,
+                            }) as u8;
+                            *base
+                                .add(1 + 22 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (match merging2 {
+                                true => 1,
+                                false => 0,
+                            }) as u8;
+                            *base
+                                .add(2 + 22 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (match configured_ports2 {
+                                true => 1,
+                                false => 0,
+                            }) as u8;
+                            *base
+                                .add(3 + 22 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (match deterministic2 {
+                                true => 1,
+                                false => 0,
+                            }) as u8;
                         }
                     }
-                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len17;
-                    *ptr1.add(0).cast::<*mut u8>() = result17;
+                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len31;
+                    *ptr1.add(0).cast::<*mut u8>() = result31;
                     ptr1
                 }
                 #[doc(hidden)]
@@ -303,11 +1005,11 @@ pub mod exports {
                     let l1 = *arg0
                         .add(::core::mem::size_of::<*const u8>())
                         .cast::<usize>();
-                    let base28 = l0;
-                    let len28 = l1;
-                    for i in 0..len28 {
-                        let base = base28
-                            .add(i * (12 * ::core::mem::size_of::<*const u8>()));
+                    let base65 = l0;
+                    let len65 = l1;
+                    for i in 0..len65 {
+                        let base = base65
+                            .add(i * (23 * ::core::mem::size_of::<*const u8>()));
                         {
                             let l2 = *base.add(0).cast::<*mut u8>();
                             let l3 = *base
@@ -341,89 +1043,776 @@ pub mod exports {
                             let l11 = *base
                                 .add(9 * ::core::mem::size_of::<*const u8>())
                                 .cast::<usize>();
-                            let base18 = l10;
-                            let len18 = l11;
-                            for i in 0..len18 {
-                                let base = base18
-                                    .add(i * (7 * ::core::mem::size_of::<*const u8>()));
+                            let base14 = l10;
+                            let len14 = l11;
+                            for i in 0..len14 {
+                                let base = base14
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let l12 = *base.add(0).cast::<*mut u8>();
                                     let l13 = *base
                                         .add(::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
                                     _rt::cabi_dealloc(l12, l13, 1);
-                                    let l14 = *base
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base14,
+                                len14 * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let l15 = i32::from(
+                                *base
+                                    .add(10 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<u8>(),
+                            );
+                            match l15 {
+                                0 => {}
+                                _ => {
+                                    let l16 = i32::from(
+                                        *base
+                                            .add(11 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l16 {
+                                        0 => {
+                                            let l17 = *base
+                                                .add(12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l18 = *base
+                                                .add(13 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l17, l18, 1);
+                                        }
+                                        _ => {
+                                            let l19 = *base
+                                                .add(12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l20 = *base
+                                                .add(13 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let base21 = l19;
+                                            let len21 = l20;
+                                            _rt::cabi_dealloc(base21, len21 * 1, 1);
+                                        }
+                                    }
+                                }
+                            }
+                            let l22 = *base
+                                .add(14 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l23 = *base
+                                .add(15 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l22, l23, 1);
+                            let l24 = *base
+                                .add(16 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l25 = *base
+                                .add(17 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base32 = l24;
+                            let len32 = l25;
+                            for i in 0..len32 {
+                                let base = base32
+                                    .add(i * (7 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let l26 = *base.add(0).cast::<*mut u8>();
+                                    let l27 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l26, l27, 1);
+                                    let l28 = *base
                                         .add(2 * ::core::mem::size_of::<*const u8>())
                                         .cast::<*mut u8>();
-                                    let l15 = *base
+                                    let l29 = *base
                                         .add(3 * ::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
-                                    _rt::cabi_dealloc(l14, l15, 1);
-                                    let l16 = *base
+                                    _rt::cabi_dealloc(l28, l29, 1);
+                                    let l30 = *base
                                         .add(5 * ::core::mem::size_of::<*const u8>())
                                         .cast::<*mut u8>();
-                                    let l17 = *base
+                                    let l31 = *base
                                         .add(6 * ::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
-                                    _rt::cabi_dealloc(l16, l17, 1);
+                                    _rt::cabi_dealloc(l30, l31, 1);
                                 }
                             }
                             _rt::cabi_dealloc(
-                                base18,
-                                len18 * (7 * ::core::mem::size_of::<*const u8>()),
+                                base32,
+                                len32 * (7 * ::core::mem::size_of::<*const u8>()),
                                 ::core::mem::size_of::<*const u8>(),
                             );
-                            let l19 = *base
-                                .add(10 * ::core::mem::size_of::<*const u8>())
+                            let l33 = *base
+                                .add(18 * ::core::mem::size_of::<*const u8>())
                                 .cast::<*mut u8>();
-                            let l20 = *base
-                                .add(11 * ::core::mem::size_of::<*const u8>())
+                            let l34 = *base
+                                .add(19 * ::core::mem::size_of::<*const u8>())
                                 .cast::<usize>();
-                            let base27 = l19;
-                            let len27 = l20;
-                            for i in 0..len27 {
-                                let base = base27
+                            let base41 = l33;
+                            let len41 = l34;
+                            for i in 0..len41 {
+                                let base = base41
                                     .add(i * (7 * ::core::mem::size_of::<*const u8>()));
                                 {
-                                    let l21 = *base.add(0).cast::<*mut u8>();
-                                    let l22 = *base
+                                    let l35 = *base.add(0).cast::<*mut u8>();
+                                    let l36 = *base
                                         .add(::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
-                                    _rt::cabi_dealloc(l21, l22, 1);
-                                    let l23 = *base
+                                    _rt::cabi_dealloc(l35, l36, 1);
+                                    let l37 = *base
                                         .add(2 * ::core::mem::size_of::<*const u8>())
                                         .cast::<*mut u8>();
-                                    let l24 = *base
+                                    let l38 = *base
                                         .add(3 * ::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
-                                    _rt::cabi_dealloc(l23, l24, 1);
-                                    let l25 = *base
+                                    _rt::cabi_dealloc(l37, l38, 1);
+                                    let l39 = *base
                                         .add(5 * ::core::mem::size_of::<*const u8>())
                                         .cast::<*mut u8>();
-                                    let l26 = *base
+                                    let l40 = *base
                                         .add(6 * ::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
-                                    _rt::cabi_dealloc(l25, l26, 1);
+                                    _rt::cabi_dealloc(l39, l40, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base41,
+                                len41 * (7 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let l42 = *base
+                                .add(20 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l43 = *base
+                                .add(21 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base64 = l42;
+                            let len64 = l43;
+                            for i in 0..len64 {
+                                let base = base64
+                                    .add(i * (12 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let l44 = *base.add(0).cast::<*mut u8>();
+                                    let l45 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l44, l45, 1);
+                                    let l46 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l47 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l46, l47, 1);
+                                    let l48 = *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l49 = *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l48, l49, 1);
+                                    let l50 = i32::from(
+                                        *base
+                                            .add(6 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l50 {
+                                        0 => {}
+                                        _ => {
+                                            let l51 = *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l52 = *base
+                                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let base53 = l51;
+                                            let len53 = l52;
+                                            _rt::cabi_dealloc(base53, len53 * 1, 1);
+                                        }
+                                    }
+                                    let l54 = *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l55 = *base
+                                        .add(11 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base63 = l54;
+                                    let len63 = l55;
+                                    for i in 0..len63 {
+                                        let base = base63
+                                            .add(i * (8 + 2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let l56 = i32::from(*base.add(0).cast::<u8>());
+                                            match l56 {
+                                                0 => {}
+                                                1 => {}
+                                                2 => {
+                                                    let l57 = *base.add(8).cast::<*mut u8>();
+                                                    let l58 = *base
+                                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<usize>();
+                                                    let base62 = l57;
+                                                    let len62 = l58;
+                                                    for i in 0..len62 {
+                                                        let base = base62
+                                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                                        {
+                                                            let l59 = *base.add(0).cast::<*mut u8>();
+                                                            let l60 = *base
+                                                                .add(::core::mem::size_of::<*const u8>())
+                                                                .cast::<usize>();
+                                                            let base61 = l59;
+                                                            let len61 = l60;
+                                                            _rt::cabi_dealloc(base61, len61 * 1, 1);
+                                                        }
+                                                    }
+                                                    _rt::cabi_dealloc(
+                                                        base62,
+                                                        len62 * (2 * ::core::mem::size_of::<*const u8>()),
+                                                        ::core::mem::size_of::<*const u8>(),
+                                                    );
+                                                }
+                                                _ => {}
+                                            }
+                                        }
+                                    }
+                                    _rt::cabi_dealloc(
+                                        base63,
+                                        len63 * (8 + 2 * ::core::mem::size_of::<*const u8>()),
+                                        8,
+                                    );
                                 }
                             }
                             _rt::cabi_dealloc(
-                                base27,
-                                len27 * (7 * ::core::mem::size_of::<*const u8>()),
+                                base64,
+                                len64 * (12 * ::core::mem::size_of::<*const u8>()),
                                 ::core::mem::size_of::<*const u8>(),
                             );
                         }
                     }
                     _rt::cabi_dealloc(
-                        base28,
-                        len28 * (12 * ::core::mem::size_of::<*const u8>()),
+                        base65,
+                        len65 * (23 * ::core::mem::size_of::<*const u8>()),
                         ::core::mem::size_of::<*const u8>(),
                     );
                 }
-                pub trait Guest {
-                    fn list_nodes() -> _rt::Vec<NodeInfo>;
-                }
                 #[doc(hidden)]
-                macro_rules! __export_cognexus_plugin_nodes_cabi {
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_execute_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                    arg4: *mut u8,
+                    arg5: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let base7 = arg2;
+                    let len7 = arg3;
+                    let mut result7 = _rt::Vec::with_capacity(len7);
+                    for i in 0..len7 {
+                        let base = base7
+                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                        let e7 = {
+                            let l1 = *base.add(0).cast::<*mut u8>();
+                            let l2 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len3 = l2;
+                            let bytes3 = _rt::Vec::from_raw_parts(l1.cast(), len3, len3);
+                            let l4 = *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l5 = *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len6 = l5;
+                            ConfigValue {
+                                name: _rt::string_lift(bytes3),
+                                value: _rt::Vec::from_raw_parts(l4.cast(), len6, len6),
+                            }
+                        };
+                        result7.push(e7);
+                    }
+                    _rt::cabi_dealloc(
+                        base7,
+                        len7 * (4 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let base14 = arg4;
+                    let len14 = arg5;
+                    let mut result14 = _rt::Vec::with_capacity(len14);
+                    for i in 0..len14 {
+                        let base = base14
+                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                        let e14 = {
+                            let l8 = *base.add(0).cast::<*mut u8>();
+                            let l9 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len10 = l9;
+                            let bytes10 = _rt::Vec::from_raw_parts(
+                                l8.cast(),
+                                len10,
+                                len10,
+                            );
+                            let l11 = *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l12 = *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len13 = l12;
+                            PortValue {
+                                port_id: _rt::string_lift(bytes10),
+                                value: _rt::Vec::from_raw_parts(l11.cast(), len13, len13),
+                            }
+                        };
+                        result14.push(e14);
+                    }
+                    _rt::cabi_dealloc(
+                        base14,
+                        len14 * (4 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result15 = T::execute(
+                        _rt::string_lift(bytes0),
+                        result7,
+                        result14,
+                    );
+                    let ptr16 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result15 {
+                        Ok(e) => {
+                            *ptr16.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec20 = e;
+                            let len20 = vec20.len();
+                            let layout20 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec20.len() * (4 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let result20 = if layout20.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout20).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout20);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec20.into_iter().enumerate() {
+                                let base = result20
+                                    .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let PortValue { port_id: port_id17, value: value17 } = e;
+                                    let vec18 = (port_id17.into_bytes()).into_boxed_slice();
+                                    let ptr18 = vec18.as_ptr().cast::<u8>();
+                                    let len18 = vec18.len();
+                                    ::core::mem::forget(vec18);
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len18;
+                                    *base.add(0).cast::<*mut u8>() = ptr18.cast_mut();
+                                    let vec19 = (value17).into_boxed_slice();
+                                    let ptr19 = vec19.as_ptr().cast::<u8>();
+                                    let len19 = vec19.len();
+                                    ::core::mem::forget(vec19);
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len19;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr19.cast_mut();
+                                }
+                            }
+                            *ptr16
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len20;
+                            *ptr16
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result20;
+                        }
+                        Err(e) => {
+                            *ptr16.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec21 = (e.into_bytes()).into_boxed_slice();
+                            let ptr21 = vec21.as_ptr().cast::<u8>();
+                            let len21 = vec21.len();
+                            ::core::mem::forget(vec21);
+                            *ptr16
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len21;
+                            *ptr16
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr21.cast_mut();
+                        }
+                    };
+                    ptr16
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_execute<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base8 = l1;
+                            let len8 = l2;
+                            for i in 0..len8 {
+                                let base = base8
+                                    .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let l3 = *base.add(0).cast::<*mut u8>();
+                                    let l4 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l3, l4, 1);
+                                    let l5 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base7 = l5;
+                                    let len7 = l6;
+                                    _rt::cabi_dealloc(base7, len7 * 1, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base8,
+                                len8 * (4 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                        }
+                        _ => {
+                            let l9 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l10 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l9, l10, 1);
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_resolve_ports_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let base7 = arg2;
+                    let len7 = arg3;
+                    let mut result7 = _rt::Vec::with_capacity(len7);
+                    for i in 0..len7 {
+                        let base = base7
+                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                        let e7 = {
+                            let l1 = *base.add(0).cast::<*mut u8>();
+                            let l2 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len3 = l2;
+                            let bytes3 = _rt::Vec::from_raw_parts(l1.cast(), len3, len3);
+                            let l4 = *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l5 = *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len6 = l5;
+                            ConfigValue {
+                                name: _rt::string_lift(bytes3),
+                                value: _rt::Vec::from_raw_parts(l4.cast(), len6, len6),
+                            }
+                        };
+                        result7.push(e7);
+                    }
+                    _rt::cabi_dealloc(
+                        base7,
+                        len7 * (4 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result8 = T::resolve_ports(_rt::string_lift(bytes0), result7);
+                    let ptr9 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result8 {
+                        Ok(e) => {
+                            *ptr9.add(0).cast::<u8>() = (0i32) as u8;
+                            let InstancePorts {
+                                input_ports: input_ports10,
+                                output_ports: output_ports10,
+                            } = e;
+                            let vec15 = input_ports10;
+                            let len15 = vec15.len();
+                            let layout15 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec15.len() * (7 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let result15 = if layout15.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout15).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout15);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec15.into_iter().enumerate() {
+                                let base = result15
+                                    .add(i * (7 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let PortSpec {
+                                        id: id11,
+                                        name: name11,
+                                        direction: direction11,
+                                        data_type_id: data_type_id11,
+                                    } = e;
+                                    let vec12 = (id11.into_bytes()).into_boxed_slice();
+                                    let ptr12 = vec12.as_ptr().cast::<u8>();
+                                    let len12 = vec12.len();
+                                    ::core::mem::forget(vec12);
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len12;
+                                    *base.add(0).cast::<*mut u8>() = ptr12.cast_mut();
+                                    let vec13 = (name11.into_bytes()).into_boxed_slice();
+                                    let ptr13 = vec13.as_ptr().cast::<u8>();
+                                    let len13 = vec13.len();
+                                    ::core::mem::forget(vec13);
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (direction11.clone() as i32) as u8;
+                                    let vec14 = (data_type_id11.into_bytes())
+                                        .into_boxed_slice();
+                                    let ptr14 = vec14.as_ptr().cast::<u8>();
+                                    let len14 = vec14.len();
+                                    ::core::mem::forget(vec14);
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len14;
+                                    *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr14.cast_mut();
+                                }
+                            }
+                            *ptr9
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len15;
+                            *ptr9
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result15;
+                            let vec20 = output_ports10;
+                            let len20 = vec20.len();
+                            let layout20 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec20.len() * (7 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let result20 = if layout20.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout20).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout20);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec20.into_iter().enumerate() {
+                                let base = result20
+                                    .add(i * (7 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let PortSpec {
+                                        id: id16,
+                                        name: name16,
+                                        direction: direction16,
+                                        data_type_id: data_type_id16,
+                                    } = e;
+                                    let vec17 = (id16.into_bytes()).into_boxed_slice();
+                                    let ptr17 = vec17.as_ptr().cast::<u8>();
+                                    let len17 = vec17.len();
+                                    ::core::mem::forget(vec17);
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len17;
+                                    *base.add(0).cast::<*mut u8>() = ptr17.cast_mut();
+                                    let vec18 = (name16.into_bytes()).into_boxed_slice();
+                                    let ptr18 = vec18.as_ptr().cast::<u8>();
+                                    let len18 = vec18.len();
+                                    ::core::mem::forget(vec18);
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len18;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr18.cast_mut();
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (direction16.clone() as i32) as u8;
+                                    let vec19 = (data_type_id16.into_bytes())
+                                        .into_boxed_slice();
+                                    let ptr19 = vec19.as_ptr().cast::<u8>();
+                                    let len19 = vec19.len();
+                                    ::core::mem::forget(vec19);
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len19;
+                                    *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr19.cast_mut();
+                                }
+                            }
+                            *ptr9
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len20;
+                            *ptr9
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result20;
+                        }
+                        Err(e) => {
+                            *ptr9.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec21 = (e.into_bytes()).into_boxed_slice();
+                            let ptr21 = vec21.as_ptr().cast::<u8>();
+                            let len21 = vec21.len();
+                            ::core::mem::forget(vec21);
+                            *ptr9
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len21;
+                            *ptr9
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr21.cast_mut();
+                        }
+                    };
+                    ptr9
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_resolve_ports<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base9 = l1;
+                            let len9 = l2;
+                            for i in 0..len9 {
+                                let base = base9
+                                    .add(i * (7 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let l3 = *base.add(0).cast::<*mut u8>();
+                                    let l4 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l3, l4, 1);
+                                    let l5 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l5, l6, 1);
+                                    let l7 = *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l8 = *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l7, l8, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base9,
+                                len9 * (7 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let l10 = *arg0
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l11 = *arg0
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base18 = l10;
+                            let len18 = l11;
+                            for i in 0..len18 {
+                                let base = base18
+                                    .add(i * (7 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let l12 = *base.add(0).cast::<*mut u8>();
+                                    let l13 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l12, l13, 1);
+                                    let l14 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l15 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l14, l15, 1);
+                                    let l16 = *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l17 = *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l16, l17, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base18,
+                                len18 * (7 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                        }
+                        _ => {
+                            let l19 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l20 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l19, l20, 1);
+                        }
+                    }
+                }
+                pub trait Guest {
+                    fn list_nodes() -> _rt::Vec<NodeInfo>;
+                    /// Run the node definition `definition-id` with an instance's
+                    /// configuration on serialized inputs keyed by input port id. Returns
+                    /// serialized outputs keyed by output port id.
+                    fn execute(
+                        definition_id: _rt::String,
+                        config: _rt::Vec<ConfigValue>,
+                        inputs: _rt::Vec<PortValue>,
+                    ) -> Result<_rt::Vec<PortValue>, _rt::String>;
+                    /// The ports of an instance of the node definition `definition-id` with
+                    /// the given configuration. Returns why the configuration is invalid.
+                    fn resolve_ports(
+                        definition_id: _rt::String,
+                        config: _rt::Vec<ConfigValue>,
+                    ) -> Result<InstancePorts, _rt::String>;
+                }
+                #[doc(hidden)]
+                macro_rules! __export_cognexus_plugin_nodes_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
                         const _ : () = { #[unsafe (export_name =
                         "cognexus:plugin/nodes#list-nodes")] unsafe extern "C" fn
@@ -432,7 +1821,23 @@ pub mod exports {
                         "cabi_post_cognexus:plugin/nodes#list-nodes")] unsafe extern "C"
                         fn _post_return_list_nodes(arg0 : * mut u8,) { unsafe {
                         $($path_to_types)*:: __post_return_list_nodes::<$ty > (arg0) } }
-                        };
+                        #[unsafe (export_name = "cognexus:plugin/nodes#execute")] unsafe
+                        extern "C" fn export_execute(arg0 : * mut u8, arg1 : usize, arg2
+                        : * mut u8, arg3 : usize, arg4 : * mut u8, arg5 : usize,) -> *
+                        mut u8 { unsafe { $($path_to_types)*:: _export_execute_cabi::<$ty
+                        > (arg0, arg1, arg2, arg3, arg4, arg5) } } #[unsafe (export_name
+                        = "cabi_post_cognexus:plugin/nodes#execute")] unsafe extern "C"
+                        fn _post_return_execute(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_execute::<$ty > (arg0) } }
+                        #[unsafe (export_name = "cognexus:plugin/nodes#resolve-ports")]
+                        unsafe extern "C" fn export_resolve_ports(arg0 : * mut u8, arg1 :
+                        usize, arg2 : * mut u8, arg3 : usize,) -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_resolve_ports_cabi::<$ty > (arg0,
+                        arg1, arg2, arg3) } } #[unsafe (export_name =
+                        "cabi_post_cognexus:plugin/nodes#resolve-ports")] unsafe extern
+                        "C" fn _post_return_resolve_ports(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_resolve_ports::<$ty > (arg0) }
+                        } };
                     };
                 }
                 #[doc(hidden)]
@@ -442,10 +1847,10 @@ pub mod exports {
                 struct _RetArea(
                     [::core::mem::MaybeUninit<
                         u8,
-                    >; 2 * ::core::mem::size_of::<*const u8>()],
+                    >; 5 * ::core::mem::size_of::<*const u8>()],
                 );
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 2
+                    [::core::mem::MaybeUninit::uninit(); 5
                         * ::core::mem::size_of::<*const u8>()],
                 );
             }
@@ -455,13 +1860,112 @@ pub mod exports {
 #[rustfmt::skip]
 mod _rt {
     #![allow(dead_code, clippy::all)]
-    pub use alloc_crate::string::String;
+    pub fn as_i64<T: AsI64>(t: T) -> i64 {
+        t.as_i64()
+    }
+    pub trait AsI64 {
+        fn as_i64(self) -> i64;
+    }
+    impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+        fn as_i64(self) -> i64 {
+            (*self).as_i64()
+        }
+    }
+    impl AsI64 for i64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+    impl AsI64 for u64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
     pub use alloc_crate::vec::Vec;
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    pub use alloc_crate::string::String;
     #[cfg(target_arch = "wasm32")]
     pub fn run_ctors_once() {
         wit_bindgen_rt::run_ctors_once();
     }
     pub use alloc_crate::alloc;
+    pub fn as_f64<T: AsF64>(t: T) -> f64 {
+        t.as_f64()
+    }
+    pub trait AsF64 {
+        fn as_f64(self) -> f64;
+    }
+    impl<'a, T: Copy + AsF64> AsF64 for &'a T {
+        fn as_f64(self) -> f64 {
+            (*self).as_f64()
+        }
+    }
+    impl AsF64 for f64 {
+        #[inline]
+        fn as_f64(self) -> f64 {
+            self as f64
+        }
+    }
     pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
         if size == 0 {
             return;
@@ -469,6 +1973,13 @@ mod _rt {
         let layout = alloc::Layout::from_size_align_unchecked(size, align);
         alloc::dealloc(ptr, layout);
     }
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            String::from_utf8_unchecked(bytes)
+        }
+    }
     extern crate alloc as alloc_crate;
 }
 /// Generates `#[unsafe(no_mangle)]` functions to export the specified type as
@@ -505,15 +2016,34 @@ pub(crate) use __export_nodes_plugin_impl as export;
 #[unsafe(link_section = "component-type:wit-bindgen:0.41.0:cognexus:plugin:nodes-plugin:encoded world")]
 #[doc(hidden)]
 #[allow(clippy::octal_escapes)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 386] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xff\x01\x01A\x02\x01\
-A\x02\x01B\x0a\x01m\x02\x05input\x06output\x04\0\x09direction\x03\0\0\x01r\x04\x02\
-ids\x04names\x09direction\x01\x0cdata-type-ids\x04\0\x09port-spec\x03\0\x02\x01p\
-\x03\x01r\x06\x02ids\x04names\x0bdescriptions\x07versions\x0binput-ports\x04\x0c\
-output-ports\x04\x04\0\x09node-info\x03\0\x05\x01p\x06\x01@\0\0\x07\x04\0\x0alis\
-t-nodes\x01\x08\x04\0\x15cognexus:plugin/nodes\x05\0\x04\0\x1ccognexus:plugin/no\
-des-plugin\x04\0\x0b\x12\x01\0\x0cnodes-plugin\x03\0\0\0G\x09producers\x01\x0cpr\
-ocessed-by\x02\x0dwit-component\x070.227.1\x10wit-bindgen-rust\x060.41.0";
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1226] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xc7\x08\x01A\x02\x01\
+A\x08\x01B\x03\x01@\x01\x0bnanosecondsw\x01\0\x04\0\x05sleep\x01\0\x04\0\x05defe\
+r\x01\0\x03\0\x15cognexus:plugin/clock\x05\0\x01B\x05\x01p}\x01@\x01\x03leny\0\0\
+\x04\0\x0crandom-bytes\x01\x01\x01@\0\0w\x04\0\x0arandom-u64\x01\x02\x03\0\x16co\
+gnexus:plugin/random\x05\x01\x01B\x07\x01m\x05\x05error\x04warn\x04info\x05debug\
+\x05trace\x04\0\x05level\x03\0\0\x01@\x02\x05level\x01\x07messages\x01\0\x04\0\x03\
+log\x01\x02\x01p}\x01@\x04\x05level\x01\x07messages\x07type-ids\x05value\x03\x01\
+\0\x04\0\x09log-value\x01\x04\x03\0\x17cognexus:plugin/logging\x05\x02\x01B%\x01\
+m\x02\x05input\x06output\x04\0\x09direction\x03\0\0\x01r\x04\x02ids\x04names\x09\
+direction\x01\x0cdata-type-ids\x04\0\x09port-spec\x03\0\x02\x01p}\x01q\x02\x05gl\
+yph\x01s\0\x03svg\x01\x04\0\x04\0\x04icon\x03\0\x05\x01p\x04\x01q\x04\x07minimum\
+\x01u\0\x07maximum\x01u\0\x06one-of\x01\x07\0\x0amax-length\x01w\0\x04\0\x14para\
+meter-constraint\x03\0\x08\x01k\x04\x01p\x09\x01r\x06\x04names\x0bdescriptions\x0c\
+data-type-ids\x0ddefault-value\x0a\x08required\x7f\x0bconstraints\x0b\x04\0\x0ep\
+arameter-spec\x03\0\x0c\x01ps\x01k\x06\x01p\x03\x01p\x0d\x01r\x0e\x02ids\x04name\
+s\x0bdescriptions\x08categorys\x04tags\x0e\x04icon\x0f\x07versions\x0binput-port\
+s\x10\x0coutput-ports\x10\x0aparameters\x11\x09branching\x7f\x07merging\x7f\x10c\
+onfigured-ports\x7f\x0ddeterministic\x7f\x04\0\x09node-info\x03\0\x12\x01r\x02\x0b\
+input-ports\x10\x0coutput-ports\x10\x04\0\x0einstance-ports\x03\0\x14\x01r\x02\x07\
+port-ids\x05value\x04\x04\0\x0aport-value\x03\0\x16\x01r\x02\x04names\x05value\x04\
+\x04\0\x0cconfig-value\x03\0\x18\x01p\x13\x01@\0\0\x1a\x04\0\x0alist-nodes\x01\x1b\
+\x01p\x19\x01p\x17\x01j\x01\x1d\x01s\x01@\x03\x0ddefinition-ids\x06config\x1c\x06\
+inputs\x1d\0\x1e\x04\0\x07execute\x01\x1f\x01j\x01\x15\x01s\x01@\x02\x0ddefiniti\
+on-ids\x06config\x1c\0\x20\x04\0\x0dresolve-ports\x01!\x04\0\x15cognexus:plugin/\
+nodes\x05\x03\x04\0\x1ccognexus:plugin/nodes-plugin\x04\0\x0b\x12\x01\0\x0cnodes\
+-plugin\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.22\
+7.1\x10wit-bindgen-rust\x060.41.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
@@ -0,0 +1,154 @@
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, Port, PortBuilder,
+    PortValues,
+};
+use cognexus_types::BooleanType;
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+/// Routes a value to its `true` or `false` output depending on a Boolean
+/// condition; downstream of the other output, the run doesn't go on.
+///
+/// Ports carry one data type, so each value type gets its own Branch
+/// definition, with an id derived from the type's.
+pub struct BranchNode {
+    id: Uuid,
+    value_type_id: Uuid,
+    name: String,
+    description: String,
+}
+
+/// Namespace the ids of Branch definitions are derived in.
+const NAMESPACE: &str = "74683eed-b998-43d0-8132-96c77e1c36cf";
+const CONDITION_PORT_ID: &str = "91e843c9-5a88-4a45-83f4-cd346f01ef29";
+const VALUE_PORT_ID: &str = "09c7fbd4-c54b-4a68-a341-9ec7793291f7";
+const TRUE_PORT_ID: &str = "5da57594-73eb-4123-872d-1b4e2c21defd";
+const FALSE_PORT_ID: &str = "db506f77-be96-49f7-b898-a47e49d1d51a";
+
+impl BranchNode {
+    /// The Branch definition for values of `value_type`.
+    pub fn of(value_type: &dyn DataTypeInfo) -> Self {
+        let namespace = Uuid::parse_str(NAMESPACE).unwrap();
+
+        Self {
+            id: Uuid::new_v5(&namespace, value_type.type_id().as_bytes()),
+            value_type_id: value_type.type_id(),
+            name: format!("Branch ({})", value_type.name()),
+            description: format!(
+                "Routes a {} value to the true or false output",
+                value_type.name()
+            ),
+        }
+    }
+
+    pub fn value_type_id(&self) -> Uuid {
+        self.value_type_id
+    }
+}
+
+impl NodeDefinitionInfo for BranchNode {
+    fn definition_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn category(&self) -> &str {
+        "Flow"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("if"), String::from("condition")]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("split")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let boolean_type = BooleanType;
+        let condition = PortBuilder::default()
+            .with_id(Uuid::parse_str(CONDITION_PORT_ID).unwrap())
+            .with_name("condition")
+            .with_data_type_id(boolean_type.type_id())
+            .build()?;
+        let value = PortBuilder::default()
+            .with_id(Uuid::parse_str(VALUE_PORT_ID).unwrap())
+            .with_name("value")
+            .with_data_type_id(self.value_type_id)
+            .build()?;
+
+        Ok(vec![condition, value])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let when_true = PortBuilder::default()
+            .with_id(Uuid::parse_str(TRUE_PORT_ID).unwrap())
+            .with_name("true")
+            .with_data_type_id(self.value_type_id)
+            .build()?;
+        let when_false = PortBuilder::default()
+            .with_id(Uuid::parse_str(FALSE_PORT_ID).unwrap())
+            .with_name("false")
+            .with_data_type_id(self.value_type_id)
+            .build()?;
+
+        Ok(vec![when_true, when_false])
+    }
+
+    fn is_branching(&self) -> bool {
+        true
+    }
+}
+
+impl NodeDefinition for BranchNode {
+    type Error = NodeError;
+
+    /// Passes the value through unchanged, on the output the condition
+    /// selects only.
+    #[track_caller]
+    fn execute(&self, _config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let condition: bool = inputs
+            .read(&Uuid::parse_str(CONDITION_PORT_ID).unwrap(), &BooleanType)
+            .map_err(|e| NodeError::InvalidInput {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        let value = inputs
+            .get(&Uuid::parse_str(VALUE_PORT_ID).unwrap())
+            .ok_or_else(|| NodeError::InvalidInput {
+                message: String::from("No value provided"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        let port_id = if condition {
+            TRUE_PORT_ID
+        } else {
+            FALSE_PORT_ID
+        };
+
+        let mut outputs = PortValues::new();
+        outputs.insert(Uuid::parse_str(port_id).unwrap(), value.to_vec());
+
+        Ok(outputs)
+    }
+}
@@ -0,0 +1,206 @@
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataType, DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon,
+    ParameterConstraint, ParameterSpec, ParameterSpecBuilder, Port, PortBuilder, PortValues,
+};
+use cognexus_types::{ListType, NumberType};
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+/// Gathers the values on its `item` inputs into one List, in port order.
+///
+/// The number of inputs is variadic: it comes from the instance's `inputs`
+/// parameter, so the node's ports are resolved from its configuration.
+/// Inputs that aren't fed are left out of the List. Like Branch, each value
+/// type gets its own Collect definition, with an id derived from the type's,
+/// whose output is a List of that type.
+pub struct CollectNode {
+    id: Uuid,
+    value_type_id: Uuid,
+    list_type: ListType,
+    name: String,
+    description: String,
+}
+
+/// Namespace the ids of Collect definitions are derived in.
+const NAMESPACE: &str = "734440c6-27c2-4dc8-968c-785e1df2e1ab";
+/// Namespace the ids of item ports are derived in, from their position.
+const ITEM_NAMESPACE: &str = "bee65439-3a41-4b14-b736-dc6804bf82cf";
+const LIST_PORT_ID: &str = "68651072-2185-4915-920f-ef80ce74573f";
+/// Inputs of an instance that doesn't set the parameter.
+const DEFAULT_INPUTS: usize = 2;
+const MAX_INPUTS: usize = 256;
+
+impl CollectNode {
+    /// Name of the parameter holding the number of inputs, a Number.
+    pub const INPUTS_PARAMETER: &str = "inputs";
+
+    /// The Collect definition for values of `value_type`.
+    pub fn of(value_type: &dyn DataTypeInfo) -> Self {
+        let namespace = Uuid::parse_str(NAMESPACE).unwrap();
+
+        Self {
+            id: Uuid::new_v5(&namespace, value_type.type_id().as_bytes()),
+            value_type_id: value_type.type_id(),
+            list_type: ListType::of(value_type),
+            name: format!("Collect ({})", value_type.name()),
+            description: format!("Gathers {} values into a List", value_type.name()),
+        }
+    }
+
+    pub fn value_type_id(&self) -> Uuid {
+        self.value_type_id
+    }
+
+    /// Id of the input port at `index`, counting from zero.
+    pub fn item_port_id(index: usize) -> Uuid {
+        let namespace = Uuid::parse_str(ITEM_NAMESPACE).unwrap();
+        Uuid::new_v5(&namespace, &(index as u64).to_le_bytes())
+    }
+
+    fn inputs(config: &NodeConfig) -> Result<usize, String> {
+        let Some(bytes) = config.get(Self::INPUTS_PARAMETER) else {
+            return Ok(DEFAULT_INPUTS);
+        };
+
+        NumberType
+            .deserialize(bytes)
+            .ok()
+            .and_then(|value| value.downcast::<f64>().ok())
+            .map(|value| *value)
+            .filter(|count| count.fract() == 0.0 && (1.0..=MAX_INPUTS as f64).contains(count))
+            .map(|count| count as usize)
+            .ok_or_else(|| {
+                format!(
+                    "Parameter '{}' must be a whole Number from 1 to {MAX_INPUTS}",
+                    Self::INPUTS_PARAMETER
+                )
+            })
+    }
+
+    fn item_ports(&self, inputs: usize) -> Result<Vec<Port>, ModelError> {
+        (0..inputs)
+            .map(|index| {
+                PortBuilder::default()
+                    .with_id(Self::item_port_id(index))
+                    .with_name(&format!("item {}", index + 1))
+                    .with_data_type_id(self.value_type_id)
+                    .build()
+            })
+            .collect()
+    }
+}
+
+impl NodeDefinitionInfo for CollectNode {
+    fn definition_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn category(&self) -> &str {
+        "Flow"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![
+            String::from("list"),
+            String::from("gather"),
+            String::from("fan-in"),
+        ]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("list")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        let parameter = ParameterSpecBuilder::default()
+            .with_name(Self::INPUTS_PARAMETER)
+            .with_description("Number of inputs to gather")
+            .with_data_type_id(NumberType.type_id())
+            .with_default((DEFAULT_INPUTS as f64).to_le_bytes().to_vec())
+            .with_constraint(ParameterConstraint::Minimum(1.0))
+            .with_constraint(ParameterConstraint::Maximum(MAX_INPUTS as f64))
+            .build()?;
+
+        Ok(vec![parameter])
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        self.item_ports(DEFAULT_INPUTS)
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(LIST_PORT_ID).unwrap())
+            .with_name("list")
+            .with_data_type_id(self.list_type.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    fn has_configured_ports(&self) -> bool {
+        true
+    }
+
+    #[track_caller]
+    fn configured_ports(&self, config: &NodeConfig) -> Result<(Vec<Port>, Vec<Port>), ModelError> {
+        let inputs = Self::inputs(config).map_err(|message| ModelError::ConfigError {
+            message,
+            parameter: String::from(Self::INPUTS_PARAMETER),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        Ok((self.item_ports(inputs)?, self.output_port_specs()?))
+    }
+}
+
+impl NodeDefinition for CollectNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let count = Self::inputs(config).map_err(|message| NodeError::InvalidConfig {
+            message,
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let items: Vec<Vec<u8>> = (0..count)
+            .filter_map(|index| inputs.get(&Self::item_port_id(index)))
+            .map(<[u8]>::to_vec)
+            .collect();
+
+        let mut outputs = PortValues::new();
+        outputs
+            .write(
+                Uuid::parse_str(LIST_PORT_ID).unwrap(),
+                &self.list_type,
+                items,
+            )
+            .map_err(|e| NodeError::SerializationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(outputs)
+    }
+}
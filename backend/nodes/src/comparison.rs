@@ -0,0 +1,164 @@
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, Port, PortBuilder,
+    PortValues,
+};
+use cognexus_types::{BooleanType, NumberType};
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+/// The comparison a [`ComparisonNode`] makes between its two inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOperation {
+    Equals,
+    GreaterThan,
+    LessThan,
+}
+
+/// Compares two Numbers, `a` and `b`, producing a Boolean: `a == b`,
+/// `a > b`, or `a < b`. Each comparison is its own node definition.
+pub struct ComparisonNode {
+    operation: ComparisonOperation,
+}
+
+const EQUALS_ID: &str = "435e18d2-2dc1-4d8c-9428-7c09631a31ce";
+const GREATER_THAN_ID: &str = "320d9993-a0a5-4548-ae2a-158ef11cda17";
+const LESS_THAN_ID: &str = "eece7879-de47-47f3-b9f7-a474df891023";
+const A_PORT_ID: &str = "4cd5f191-5e0e-4902-9eb1-4496ba7508e3";
+const B_PORT_ID: &str = "9ecd6b57-4c37-453a-9275-2e619c7d371e";
+const RESULT_PORT_ID: &str = "6dfa8172-3193-46e9-8d21-442f9b5bd2dd";
+
+impl ComparisonNode {
+    pub fn new(operation: ComparisonOperation) -> Self {
+        Self { operation }
+    }
+
+    pub fn operation(&self) -> ComparisonOperation {
+        self.operation
+    }
+}
+
+impl NodeDefinitionInfo for ComparisonNode {
+    fn definition_id(&self) -> Uuid {
+        let id = match self.operation {
+            ComparisonOperation::Equals => EQUALS_ID,
+            ComparisonOperation::GreaterThan => GREATER_THAN_ID,
+            ComparisonOperation::LessThan => LESS_THAN_ID,
+        };
+
+        Uuid::parse_str(id).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        match self.operation {
+            ComparisonOperation::Equals => "Equals",
+            ComparisonOperation::GreaterThan => "Greater Than",
+            ComparisonOperation::LessThan => "Less Than",
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self.operation {
+            ComparisonOperation::Equals => "True if a equals b",
+            ComparisonOperation::GreaterThan => "True if a is greater than b",
+            ComparisonOperation::LessThan => "True if a is less than b",
+        }
+    }
+
+    fn category(&self) -> &str {
+        "Logic"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        match self.operation {
+            ComparisonOperation::Equals => vec![String::from("compare"), String::from("equal")],
+            ComparisonOperation::GreaterThan => vec![String::from("compare"), String::from("more")],
+            ComparisonOperation::LessThan => vec![String::from("compare"), String::from("fewer")],
+        }
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        let glyph = match self.operation {
+            ComparisonOperation::Equals => "equals",
+            ComparisonOperation::GreaterThan => "greater-than",
+            ComparisonOperation::LessThan => "less-than",
+        };
+
+        Some(NodeIcon::Glyph(String::from(glyph)))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let number_type = NumberType;
+        let a = PortBuilder::default()
+            .with_id(Uuid::parse_str(A_PORT_ID).unwrap())
+            .with_name("a")
+            .with_data_type_id(number_type.type_id())
+            .build()?;
+        let b = PortBuilder::default()
+            .with_id(Uuid::parse_str(B_PORT_ID).unwrap())
+            .with_name("b")
+            .with_data_type_id(number_type.type_id())
+            .build()?;
+
+        Ok(vec![a, b])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let boolean_type = BooleanType;
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(RESULT_PORT_ID).unwrap())
+            .with_name("result")
+            .with_data_type_id(boolean_type.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+}
+
+impl NodeDefinition for ComparisonNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, _config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let read = |port_id: &str| -> Result<f64, NodeError> {
+            inputs
+                .read(&Uuid::parse_str(port_id).unwrap(), &NumberType)
+                .map_err(|e| NodeError::InvalidInput {
+                    message: e.to_string(),
+                    location: ErrorLocation::from(Location::caller()),
+                })
+        };
+        let (a, b) = (read(A_PORT_ID)?, read(B_PORT_ID)?);
+
+        let result = match self.operation {
+            ComparisonOperation::Equals => a == b,
+            ComparisonOperation::GreaterThan => a > b,
+            ComparisonOperation::LessThan => a < b,
+        };
+
+        let mut outputs = PortValues::new();
+        outputs
+            .write(
+                Uuid::parse_str(RESULT_PORT_ID).unwrap(),
+                &BooleanType,
+                result,
+            )
+            .map_err(|e| NodeError::SerializationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(outputs)
+    }
+}
@@ -0,0 +1,125 @@
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, ParameterSpec,
+    ParameterSpecBuilder, Port, PortBuilder, PortValues,
+};
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+/// Emits the value set in its instance's `value` parameter.
+///
+/// Each value type gets its own Constant definition, with an id derived from
+/// the type's, so picking the definition picks the type of the value. The
+/// parameter holds the value in that type's wire format.
+pub struct ConstantNode {
+    id: Uuid,
+    value_type_id: Uuid,
+    name: String,
+    description: String,
+}
+
+/// Namespace the ids of Constant definitions are derived in.
+const NAMESPACE: &str = "28ce27cb-15c3-4cea-a93d-342066a00054";
+const VALUE_PORT_ID: &str = "107420fd-d895-4b91-9b18-bcf31bda92f8";
+
+impl ConstantNode {
+    /// Name of the parameter holding the value to emit.
+    pub const VALUE_PARAMETER: &str = "value";
+
+    /// The Constant definition for values of `value_type`.
+    pub fn of(value_type: &dyn DataTypeInfo) -> Self {
+        let namespace = Uuid::parse_str(NAMESPACE).unwrap();
+
+        Self {
+            id: Uuid::new_v5(&namespace, value_type.type_id().as_bytes()),
+            value_type_id: value_type.type_id(),
+            name: format!("Constant ({})", value_type.name()),
+            description: format!("Emits a configured {} value", value_type.name()),
+        }
+    }
+
+    pub fn value_type_id(&self) -> Uuid {
+        self.value_type_id
+    }
+}
+
+impl NodeDefinitionInfo for ConstantNode {
+    fn definition_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn category(&self) -> &str {
+        "Values"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("literal"), String::from("value")]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("pin")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        let parameter = ParameterSpecBuilder::default()
+            .with_name(Self::VALUE_PARAMETER)
+            .with_description("Value to emit")
+            .with_data_type_id(self.value_type_id)
+            .with_required(true)
+            .build()?;
+
+        Ok(vec![parameter])
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        Ok(vec![])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(VALUE_PORT_ID).unwrap())
+            .with_name("value")
+            .with_data_type_id(self.value_type_id)
+            .build()?;
+
+        Ok(vec![port])
+    }
+}
+
+impl NodeDefinition for ConstantNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, config: &NodeConfig, _inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let value = config
+            .get(Self::VALUE_PARAMETER)
+            .ok_or_else(|| NodeError::InvalidConfig {
+                message: format!("Parameter '{}' is not set", Self::VALUE_PARAMETER),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        let mut outputs = PortValues::new();
+        outputs.insert(Uuid::parse_str(VALUE_PORT_ID).unwrap(), value.to_vec());
+
+        Ok(outputs)
+    }
+}
@@ -0,0 +1,154 @@
+use crate::bindings::cognexus::plugin::clock;
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataType, DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon,
+    ParameterConstraint, ParameterSpec, ParameterSpecBuilder, Port, PortBuilder, PortValues,
+};
+use cognexus_types::{NumberType, SignalType};
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+use std::time::Duration;
+
+/// Passes its signal on after the Number of milliseconds set in its
+/// instance's `duration_ms` parameter.
+///
+/// The wait is deferred to the host clock rather than spent in the call, so
+/// it doesn't hold up other nodes. Cancelling the run ends it early, a wait
+/// longer than the node's timeout fails the node, and replays skip it.
+pub struct DelayNode;
+
+const ID: &str = "cf011c01-fbb1-4317-beb6-c877f92cf9d8";
+const NAME: &str = "Delay";
+const DESCRIPTION: &str = "Passes on a signal after a configured duration";
+const INPUT_PORT_ID: &str = "68b5e11f-c9d4-4a54-bd3b-1e59b0fcaba6";
+const OUTPUT_PORT_ID: &str = "06e95403-f7fa-4365-bee0-2f9daf6d5b46";
+
+impl DelayNode {
+    /// Name of the parameter holding the duration, in milliseconds.
+    pub const DURATION_PARAMETER: &str = "duration_ms";
+
+    #[track_caller]
+    fn duration(config: &NodeConfig) -> Result<Duration, NodeError> {
+        let bytes =
+            config
+                .get(Self::DURATION_PARAMETER)
+                .ok_or_else(|| NodeError::InvalidConfig {
+                    message: format!("Parameter '{}' is not set", Self::DURATION_PARAMETER),
+                    location: ErrorLocation::from(Location::caller()),
+                })?;
+
+        let milliseconds = NumberType
+            .deserialize(bytes)
+            .ok()
+            .and_then(|value| value.downcast::<f64>().ok())
+            .map(|value| *value)
+            .filter(|milliseconds| milliseconds.is_finite() && *milliseconds >= 0.0)
+            .ok_or_else(|| NodeError::InvalidConfig {
+                message: format!(
+                    "Parameter '{}' must be a non-negative Number",
+                    Self::DURATION_PARAMETER
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(Duration::from_secs_f64(milliseconds / 1000.0))
+    }
+}
+
+impl NodeDefinitionInfo for DelayNode {
+    fn definition_id(&self) -> Uuid {
+        Uuid::parse_str(ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> &str {
+        DESCRIPTION
+    }
+
+    fn category(&self) -> &str {
+        "Flow"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![
+            String::from("wait"),
+            String::from("sleep"),
+            String::from("timer"),
+        ]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("clock")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        let parameter = ParameterSpecBuilder::default()
+            .with_name(Self::DURATION_PARAMETER)
+            .with_description("How long to wait, in milliseconds")
+            .with_data_type_id(NumberType.type_id())
+            .with_required(true)
+            .with_constraint(ParameterConstraint::Minimum(0.0))
+            .build()?;
+
+        Ok(vec![parameter])
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(INPUT_PORT_ID).unwrap())
+            .with_name("signal")
+            .with_data_type_id(SignalType.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(OUTPUT_PORT_ID).unwrap())
+            .with_name("signal")
+            .with_data_type_id(SignalType.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    /// Waiting is the point of running it.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+impl NodeDefinition for DelayNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, config: &NodeConfig, _inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let duration = Self::duration(config)?;
+        clock::defer(u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX));
+
+        let mut outputs = PortValues::new();
+        outputs
+            .write(Uuid::parse_str(OUTPUT_PORT_ID).unwrap(), &SignalType, ())
+            .map_err(|e| NodeError::SerializationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(outputs)
+    }
+}
@@ -1,6 +1,9 @@
 use crate::error::NodeError;
 
-use cognexus_model::graph::{DataTypeInfo, NodeDefinition, NodeDefinitionInfo, Port, PortBuilder};
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, Port, PortBuilder,
+    PortValues,
+};
 use cognexus_types::SignalType;
 
 use cognexus_model::error::ModelError;
@@ -27,6 +30,18 @@ impl NodeDefinitionInfo for EndNode {
         DESCRIPTION
     }
 
+    fn category(&self) -> &str {
+        "Flow"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("exit"), String::from("finish")]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("stop")))
+    }
+
     fn model_version(&self) -> Version {
         Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
     }
@@ -51,8 +66,8 @@ impl NodeDefinitionInfo for EndNode {
 impl NodeDefinition for EndNode {
     type Error = NodeError;
 
-    fn execute(&self, _inputs: Vec<u8>) -> Result<Vec<u8>, NodeError> {
+    fn execute(&self, _config: &NodeConfig, _inputs: &PortValues) -> Result<PortValues, NodeError> {
         // End node consumes input and produces no output
-        Ok(vec![])
+        Ok(PortValues::new())
     }
 }
@@ -16,6 +16,12 @@ pub enum NodeError {
         location: ErrorLocation,
     },
 
+    #[error("Invalid configuration: {message} {location}")]
+    InvalidConfig {
+        message: String,
+        location: ErrorLocation,
+    },
+
     #[error("Serialization failed: {message} {location}")]
     SerializationError {
         message: String,
@@ -0,0 +1,606 @@
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, ParameterSpec,
+    ParameterSpecBuilder, Port, PortBuilder, PortValues,
+};
+use cognexus_types::{NumberType, StringType};
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::fmt;
+use std::iter::Peekable;
+use std::panic::Location;
+use std::str::Chars;
+
+/// Evaluates the expression in its instance's `expression` parameter, such
+/// as `a * 2 + b`, over named inputs.
+///
+/// Every variable the expression references becomes an input port of the
+/// instance, so its ports are resolved from its configuration. Variables are
+/// Numbers unless listed in the `string_variables` parameter. The language
+/// has number and `"string"` literals, parentheses, unary `-`, and `+ - * /
+/// %`; `+` with a String operand concatenates, and the other operators only
+/// take Numbers. The result is a String if the expression concatenates and a
+/// Number otherwise.
+pub struct ExpressionNode;
+
+const ID: &str = "4c85cb9a-1fd8-44ca-be3d-a29850e65c02";
+/// Namespace the ids of variable ports are derived in, so a variable keeps
+/// its port, and its edges, while the expression around it changes.
+const VARIABLE_NAMESPACE: &str = "9763c991-1a3c-41c8-a3f1-abef6ffdf89f";
+const RESULT_PORT_ID: &str = "c2be25f6-153d-4bac-aedd-f8dad53a80cd";
+/// How deeply parentheses, negations, and chained operators may nest. The
+/// parser and evaluator recurse once per level, so this keeps a hostile
+/// expression from overflowing the plugin's stack.
+const MAX_DEPTH: usize = 64;
+
+impl ExpressionNode {
+    /// Name of the parameter holding the expression, a String.
+    pub const EXPRESSION_PARAMETER: &str = "expression";
+
+    /// Name of the parameter listing the variables that are Strings,
+    /// separated by commas or whitespace.
+    pub const STRING_VARIABLES_PARAMETER: &str = "string_variables";
+
+    /// Id of the input port for `variable`.
+    pub fn variable_port_id(variable: &str) -> Uuid {
+        let namespace = Uuid::parse_str(VARIABLE_NAMESPACE).unwrap();
+        Uuid::new_v5(&namespace, variable.as_bytes())
+    }
+
+    /// Parse and type-check the configured expression.
+    fn compile(config: &NodeConfig) -> Result<Compiled, String> {
+        let expression = string_parameter(config, Self::EXPRESSION_PARAMETER)?
+            .ok_or_else(|| format!("Parameter '{}' is not set", Self::EXPRESSION_PARAMETER))?;
+        let string_variables =
+            string_parameter(config, Self::STRING_VARIABLES_PARAMETER)?.unwrap_or_default();
+        let string_variables: Vec<&str> = string_variables
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        let expr = Parser::new(&expression).parse()?;
+
+        let mut variables = Vec::new();
+        expr.collect_variables(&mut variables);
+        let variables: Vec<(String, Kind)> = variables
+            .into_iter()
+            .map(|name| {
+                let kind = if string_variables.contains(&name.as_str()) {
+                    Kind::Text
+                } else {
+                    Kind::Number
+                };
+                (name, kind)
+            })
+            .collect();
+
+        let result = expr.kind(&variables)?;
+
+        Ok(Compiled {
+            expr,
+            variables,
+            result,
+        })
+    }
+}
+
+impl NodeDefinitionInfo for ExpressionNode {
+    fn definition_id(&self) -> Uuid {
+        Uuid::parse_str(ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        "Expression"
+    }
+
+    fn description(&self) -> &str {
+        "Evaluates an expression over named Number and String inputs"
+    }
+
+    fn category(&self) -> &str {
+        "Math"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![
+            String::from("formula"),
+            String::from("calculate"),
+            String::from("concatenate"),
+        ]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("function")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        let expression = ParameterSpecBuilder::default()
+            .with_name(Self::EXPRESSION_PARAMETER)
+            .with_description("Expression to evaluate, such as `a * 2 + b`")
+            .with_data_type_id(StringType.type_id())
+            .with_required(true)
+            .build()?;
+        let string_variables = ParameterSpecBuilder::default()
+            .with_name(Self::STRING_VARIABLES_PARAMETER)
+            .with_description("Variables that are Strings, separated by commas")
+            .with_data_type_id(StringType.type_id())
+            .with_required(false)
+            .build()?;
+
+        Ok(vec![expression, string_variables])
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        Ok(vec![])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        Ok(vec![result_port(Kind::Number)?])
+    }
+
+    fn has_configured_ports(&self) -> bool {
+        true
+    }
+
+    #[track_caller]
+    fn configured_ports(&self, config: &NodeConfig) -> Result<(Vec<Port>, Vec<Port>), ModelError> {
+        let compiled = Self::compile(config).map_err(|message| ModelError::ModelError {
+            message,
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let inputs = compiled
+            .variables
+            .iter()
+            .map(|(name, kind)| {
+                PortBuilder::default()
+                    .with_id(Self::variable_port_id(name))
+                    .with_name(name)
+                    .with_data_type_id(kind.type_id())
+                    .build()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok((inputs, vec![result_port(compiled.result)?]))
+    }
+}
+
+impl NodeDefinition for ExpressionNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let compiled = Self::compile(config).map_err(|message| NodeError::InvalidConfig {
+            message,
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let mut values = Vec::with_capacity(compiled.variables.len());
+        for (name, kind) in &compiled.variables {
+            let port_id = Self::variable_port_id(name);
+            let value = match kind {
+                Kind::Number => inputs.read(&port_id, &NumberType).map(Value::Number),
+                Kind::Text => inputs.read(&port_id, &StringType).map(Value::Text),
+            }
+            .map_err(|e| NodeError::InvalidInput {
+                message: format!("Variable '{name}': {e}"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+            values.push((name.as_str(), value));
+        }
+
+        let result =
+            compiled
+                .expr
+                .evaluate(&values)
+                .map_err(|message| NodeError::ExecutionError {
+                    message,
+                    location: ErrorLocation::from(Location::caller()),
+                })?;
+
+        let port_id = Uuid::parse_str(RESULT_PORT_ID).unwrap();
+        let mut outputs = PortValues::new();
+        match result {
+            Value::Number(number) => outputs.write(port_id, &NumberType, number),
+            Value::Text(text) => outputs.write(port_id, &StringType, text),
+        }
+        .map_err(|e| NodeError::SerializationError {
+            message: e.to_string(),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        Ok(outputs)
+    }
+}
+
+fn result_port(kind: Kind) -> Result<Port, ModelError> {
+    PortBuilder::default()
+        .with_id(Uuid::parse_str(RESULT_PORT_ID).unwrap())
+        .with_name("result")
+        .with_data_type_id(kind.type_id())
+        .build()
+}
+
+fn string_parameter(config: &NodeConfig, name: &str) -> Result<Option<String>, String> {
+    config
+        .get(name)
+        .map(|bytes| {
+            String::from_utf8(bytes.to_vec())
+                .map_err(|_| format!("Parameter '{name}' must be a String"))
+        })
+        .transpose()
+}
+
+/// A parsed, type-checked expression and the inputs it reads.
+struct Compiled {
+    expr: Expr,
+    /// Referenced variables in order of first use.
+    variables: Vec<(String, Kind)>,
+    result: Kind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Number,
+    Text,
+}
+
+impl Kind {
+    fn type_id(self) -> Uuid {
+        match self {
+            Kind::Number => NumberType.type_id(),
+            Kind::Text => StringType.type_id(),
+        }
+    }
+}
+
+enum Value {
+    Number(f64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+enum Expr {
+    Number(f64),
+    Text(String),
+    Variable(String),
+    Negate(Box<Expr>),
+    Binary(Operator, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Levels in the tree.
+    fn height(&self) -> usize {
+        match self {
+            Expr::Number(_) | Expr::Text(_) | Expr::Variable(_) => 1,
+            Expr::Negate(operand) => operand.height() + 1,
+            Expr::Binary(_, left, right) => left.height().max(right.height()) + 1,
+        }
+    }
+
+    fn collect_variables(&self, variables: &mut Vec<String>) {
+        match self {
+            Expr::Number(_) | Expr::Text(_) => {}
+            Expr::Variable(name) => {
+                if !variables.contains(name) {
+                    variables.push(name.clone());
+                }
+            }
+            Expr::Negate(operand) => operand.collect_variables(variables),
+            Expr::Binary(_, left, right) => {
+                left.collect_variables(variables);
+                right.collect_variables(variables);
+            }
+        }
+    }
+
+    fn kind(&self, variables: &[(String, Kind)]) -> Result<Kind, String> {
+        match self {
+            Expr::Number(_) => Ok(Kind::Number),
+            Expr::Text(_) => Ok(Kind::Text),
+            Expr::Variable(name) => Ok(variables
+                .iter()
+                .find(|(variable, _)| variable == name)
+                .map_or(Kind::Number, |(_, kind)| *kind)),
+            Expr::Negate(operand) => match operand.kind(variables)? {
+                Kind::Number => Ok(Kind::Number),
+                Kind::Text => Err(String::from("Cannot negate a String")),
+            },
+            Expr::Binary(operator, left, right) => {
+                let (left, right) = (left.kind(variables)?, right.kind(variables)?);
+                match (operator, left, right) {
+                    (_, Kind::Number, Kind::Number) => Ok(Kind::Number),
+                    (Operator::Add, _, _) => Ok(Kind::Text),
+                    (operator, _, _) => Err(format!("Cannot apply {operator:?} to a String")),
+                }
+            }
+        }
+    }
+
+    /// Evaluate a type-checked expression.
+    fn evaluate(&self, variables: &[(&str, Value)]) -> Result<Value, String> {
+        match self {
+            Expr::Number(number) => Ok(Value::Number(*number)),
+            Expr::Text(text) => Ok(Value::Text(text.clone())),
+            Expr::Variable(name) => match variables.iter().find(|(variable, _)| variable == name) {
+                Some((_, Value::Number(number))) => Ok(Value::Number(*number)),
+                Some((_, Value::Text(text))) => Ok(Value::Text(text.clone())),
+                None => Err(format!("Variable '{name}' has no value")),
+            },
+            Expr::Negate(operand) => match operand.evaluate(variables)? {
+                Value::Number(number) => Ok(Value::Number(-number)),
+                Value::Text(_) => Err(String::from("Cannot negate a String")),
+            },
+            Expr::Binary(operator, left, right) => {
+                match (
+                    operator,
+                    left.evaluate(variables)?,
+                    right.evaluate(variables)?,
+                ) {
+                    (Operator::Add, Value::Number(a), Value::Number(b)) => Ok(Value::Number(a + b)),
+                    (Operator::Subtract, Value::Number(a), Value::Number(b)) => {
+                        Ok(Value::Number(a - b))
+                    }
+                    (Operator::Multiply, Value::Number(a), Value::Number(b)) => {
+                        Ok(Value::Number(a * b))
+                    }
+                    (
+                        Operator::Divide | Operator::Remainder,
+                        Value::Number(a),
+                        Value::Number(0.0),
+                    ) => Err(format!("Cannot divide {a} by zero")),
+                    (Operator::Divide, Value::Number(a), Value::Number(b)) => {
+                        Ok(Value::Number(a / b))
+                    }
+                    (Operator::Remainder, Value::Number(a), Value::Number(b)) => {
+                        Ok(Value::Number(a % b))
+                    }
+                    (Operator::Add, a, b) => Ok(Value::Text(format!("{a}{b}"))),
+                    (operator, _, _) => Err(format!("Cannot apply {operator:?} to a String")),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(number) => write!(f, "{number}"),
+            Value::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// Recursive-descent parser:
+///
+/// ```text
+/// sum     = product (("+" | "-") product)*
+/// product = unary (("*" | "/" | "%") unary)*
+/// unary   = "-" unary | primary
+/// primary = number | string | identifier | "(" sum ")"
+/// ```
+///
+/// Expressions nesting deeper than [`MAX_DEPTH`] are rejected.
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.chars().peekable(),
+            depth: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<Expr, String> {
+        let expr = self.sum()?;
+        match self.peek() {
+            None => Ok(expr),
+            Some(c) => Err(format!("Unexpected '{c}' in expression")),
+        }
+    }
+
+    /// The next character that isn't whitespace.
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    /// Recurse one level deeper, into parentheses or a negation, failing
+    /// past [`MAX_DEPTH`].
+    fn nest(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(format!("Expression nests deeper than {MAX_DEPTH} levels"));
+        }
+        Ok(())
+    }
+
+    /// Join two operands, failing if a chain of operators makes the tree
+    /// deeper than [`MAX_DEPTH`].
+    fn binary(operator: Operator, left: Expr, right: Expr) -> Result<Expr, String> {
+        if left.height().max(right.height()) >= MAX_DEPTH {
+            return Err(format!("Expression nests deeper than {MAX_DEPTH} levels"));
+        }
+        Ok(Expr::Binary(operator, Box::new(left), Box::new(right)))
+    }
+
+    fn sum(&mut self) -> Result<Expr, String> {
+        let mut expr = self.product()?;
+        loop {
+            let operator = match self.peek() {
+                Some('+') => Operator::Add,
+                Some('-') => Operator::Subtract,
+                _ => return Ok(expr),
+            };
+            self.chars.next();
+            expr = Self::binary(operator, expr, self.product()?)?;
+        }
+    }
+
+    fn product(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        loop {
+            let operator = match self.peek() {
+                Some('*') => Operator::Multiply,
+                Some('/') => Operator::Divide,
+                Some('%') => Operator::Remainder,
+                _ => return Ok(expr),
+            };
+            self.chars.next();
+            expr = Self::binary(operator, expr, self.unary()?)?;
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some('-') {
+            self.chars.next();
+            self.nest()?;
+            let operand = self.unary()?;
+            self.depth -= 1;
+            return Ok(Expr::Negate(Box::new(operand)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some('(') => {
+                self.chars.next();
+                self.nest()?;
+                let expr = self.sum()?;
+                self.depth -= 1;
+                match self.peek() {
+                    Some(')') => {
+                        self.chars.next();
+                        Ok(expr)
+                    }
+                    _ => Err(String::from("Missing ')' in expression")),
+                }
+            }
+            Some('"') => {
+                self.chars.next();
+                let mut text = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some('"') => return Ok(Expr::Text(text)),
+                        Some('\\') => match self.chars.next() {
+                            Some(c @ ('"' | '\\')) => text.push(c),
+                            Some('n') => text.push('\n'),
+                            Some(c) => return Err(format!("Unknown escape '\\{c}' in string")),
+                            None => return Err(String::from("Unterminated string in expression")),
+                        },
+                        Some(c) => text.push(c),
+                        None => return Err(String::from("Unterminated string in expression")),
+                    }
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let mut literal = String::new();
+                while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                    literal.push(c);
+                }
+                literal
+                    .parse()
+                    .map(Expr::Number)
+                    .map_err(|_| format!("Invalid number '{literal}' in expression"))
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(c) = self.chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                Ok(Expr::Variable(name))
+            }
+            Some(c) => Err(format!("Unexpected '{c}' in expression")),
+            None => Err(String::from("Unexpected end of expression")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(source: &str, variables: &[(&str, Value)]) -> Result<String, String> {
+        Parser::new(source)
+            .parse()?
+            .evaluate(variables)
+            .map(|value| value.to_string())
+    }
+
+    #[test]
+    fn respects_precedence_and_parentheses() {
+        assert_eq!(evaluate("1 + 2 * 3", &[]).unwrap(), "7");
+        assert_eq!(evaluate("(1 + 2) * 3", &[]).unwrap(), "9");
+        assert_eq!(evaluate("10 - 4 - 3", &[]).unwrap(), "3");
+        assert_eq!(evaluate("-2 * -3 % 4", &[]).unwrap(), "2");
+    }
+
+    #[test]
+    fn substitutes_variables_and_concatenates_strings() {
+        let variables = [("x", Value::Number(4.0)), ("name", Value::Text("n".into()))];
+        assert_eq!(evaluate("x * x", &variables).unwrap(), "16");
+        assert_eq!(evaluate("\"a\\\"\" + name + x", &variables).unwrap(), "a\"n4");
+        assert!(evaluate("y", &variables).is_err());
+    }
+
+    #[test]
+    fn rejects_division_by_zero() {
+        assert!(evaluate("1 / 0", &[]).is_err());
+        assert!(evaluate("1 % (2 - 2)", &[]).is_err());
+        assert_eq!(evaluate("0 / 2", &[]).unwrap(), "0");
+    }
+
+    #[test]
+    fn type_checks_strings() {
+        let variables = [
+            (String::from("a"), Kind::Text),
+            (String::from("b"), Kind::Number),
+        ];
+        let kind = |source: &str| Parser::new(source).parse()?.kind(&variables);
+        assert_eq!(kind("a + b").unwrap(), Kind::Text);
+        assert_eq!(kind("b * 2").unwrap(), Kind::Number);
+        assert!(kind("a * 2").is_err());
+        assert!(kind("-a").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for source in ["", "1 +", "(1", "1 2", "\"open", "1..2", "#"] {
+            assert!(evaluate(source, &[]).is_err(), "{source:?} parsed");
+        }
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let within = format!("{}1{}", "(".repeat(MAX_DEPTH - 1), ")".repeat(MAX_DEPTH - 1));
+        assert_eq!(evaluate(&within, &[]).unwrap(), "1");
+
+        let parentheses = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert!(evaluate(&parentheses, &[]).is_err());
+        assert!(evaluate(&format!("{}1", "-".repeat(100_000)), &[]).is_err());
+        assert!(evaluate(&vec!["1"; 100_000].join(" + "), &[]).is_err());
+    }
+}
@@ -0,0 +1,257 @@
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, ParameterSpec,
+    ParameterSpecBuilder, Port, PortBuilder, PortValues,
+};
+use cognexus_types::{BytesType, SignalType, StringType};
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::fs;
+use std::panic::Location;
+
+const PATH_PARAMETER: &str = "path";
+
+/// Reads a file from a directory the host granted this plugin.
+pub struct ReadFileNode;
+
+const READ_ID: &str = "3c81914f-2cae-418e-b4ea-2d6bfcd0b687";
+const READ_PATH_PORT_ID: &str = "7c4390f6-eb29-42df-8155-2ceeadba88af";
+const READ_BYTES_PORT_ID: &str = "7271a9f1-399a-47cd-b3b9-5d2633d9cb59";
+
+impl ReadFileNode {
+    /// Name of the parameter holding the path when the `path` input isn't
+    /// fed.
+    pub const PATH_PARAMETER: &str = PATH_PARAMETER;
+}
+
+/// Writes a file into a directory the host granted this plugin, replacing
+/// it if it exists.
+pub struct WriteFileNode;
+
+const WRITE_ID: &str = "c4ea8a38-96c4-4498-ace6-c4ab06dac0ef";
+const WRITE_PATH_PORT_ID: &str = "572dcac4-f688-449a-95f3-4651ee98ca46";
+const WRITE_BYTES_PORT_ID: &str = "d9515f91-5f88-4a3b-82f1-84a6051d5c0b";
+const WRITE_SIGNAL_PORT_ID: &str = "47d7c7d0-df85-4026-97b6-c4bdd3fd4165";
+
+impl WriteFileNode {
+    /// Name of the parameter holding the path when the `path` input isn't
+    /// fed.
+    pub const PATH_PARAMETER: &str = PATH_PARAMETER;
+}
+
+/// The path from the `path` input if it was fed, or else from the `path`
+/// parameter. Paths are as the plugin sees granted directories, e.g.
+/// `/data/report.json`.
+#[track_caller]
+fn file_path(
+    config: &NodeConfig,
+    inputs: &PortValues,
+    path_port_id: &str,
+) -> Result<String, NodeError> {
+    let bytes = inputs
+        .get(&Uuid::parse_str(path_port_id).unwrap())
+        .or_else(|| config.get(PATH_PARAMETER))
+        .ok_or_else(|| NodeError::InvalidInput {
+            message: format!(
+                "No path: the path input isn't fed and parameter '{PATH_PARAMETER}' is not set"
+            ),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+    String::from_utf8(bytes.to_vec()).map_err(|e| NodeError::InvalidInput {
+        message: format!("Path is not valid UTF-8: {e}"),
+        location: ErrorLocation::from(Location::caller()),
+    })
+}
+
+fn path_port(port_id: &str) -> Result<Port, ModelError> {
+    PortBuilder::default()
+        .with_id(Uuid::parse_str(port_id).unwrap())
+        .with_name("path")
+        .with_data_type_id(StringType.type_id())
+        .build()
+}
+
+fn bytes_port(port_id: &str) -> Result<Port, ModelError> {
+    PortBuilder::default()
+        .with_id(Uuid::parse_str(port_id).unwrap())
+        .with_name("bytes")
+        .with_data_type_id(BytesType.type_id())
+        .build()
+}
+
+impl NodeDefinitionInfo for ReadFileNode {
+    fn definition_id(&self) -> Uuid {
+        Uuid::parse_str(READ_ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        "Read File"
+    }
+
+    fn description(&self) -> &str {
+        "Reads the contents of a file in a granted directory"
+    }
+
+    fn category(&self) -> &str {
+        "IO"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("file"), String::from("load")]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("file-input")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        let parameter = ParameterSpecBuilder::default()
+            .with_name(PATH_PARAMETER)
+            .with_description("Path of the file to read, when the path input isn't fed")
+            .with_data_type_id(StringType.type_id())
+            .with_required(false)
+            .build()?;
+
+        Ok(vec![parameter])
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        Ok(vec![path_port(READ_PATH_PORT_ID)?])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        Ok(vec![bytes_port(READ_BYTES_PORT_ID)?])
+    }
+
+    /// The file can change on disk between runs.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+impl NodeDefinition for ReadFileNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let path = file_path(config, inputs, READ_PATH_PORT_ID)?;
+
+        let bytes = fs::read(&path).map_err(|e| NodeError::ExecutionError {
+            message: format!("Failed to read '{path}': {e}"),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let mut outputs = PortValues::new();
+        outputs.insert(Uuid::parse_str(READ_BYTES_PORT_ID).unwrap(), bytes);
+
+        Ok(outputs)
+    }
+}
+
+impl NodeDefinitionInfo for WriteFileNode {
+    fn definition_id(&self) -> Uuid {
+        Uuid::parse_str(WRITE_ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        "Write File"
+    }
+
+    fn description(&self) -> &str {
+        "Writes bytes to a file in a granted directory"
+    }
+
+    fn category(&self) -> &str {
+        "IO"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("file"), String::from("save")]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("file-output")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        let parameter = ParameterSpecBuilder::default()
+            .with_name(PATH_PARAMETER)
+            .with_description("Path of the file to write, when the path input isn't fed")
+            .with_data_type_id(StringType.type_id())
+            .with_required(false)
+            .build()?;
+
+        Ok(vec![parameter])
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        Ok(vec![
+            path_port(WRITE_PATH_PORT_ID)?,
+            bytes_port(WRITE_BYTES_PORT_ID)?,
+        ])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(WRITE_SIGNAL_PORT_ID).unwrap())
+            .with_name("signal")
+            .with_data_type_id(SignalType.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    /// Writing the file is the point of running it.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+impl NodeDefinition for WriteFileNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let path = file_path(config, inputs, WRITE_PATH_PORT_ID)?;
+        let bytes = inputs
+            .get(&Uuid::parse_str(WRITE_BYTES_PORT_ID).unwrap())
+            .ok_or_else(|| NodeError::InvalidInput {
+                message: String::from("No bytes to write"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        fs::write(&path, bytes).map_err(|e| NodeError::ExecutionError {
+            message: format!("Failed to write '{path}': {e}"),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+        let mut outputs = PortValues::new();
+        outputs
+            .write(
+                Uuid::parse_str(WRITE_SIGNAL_PORT_ID).unwrap(),
+                &SignalType,
+                (),
+            )
+            .map_err(|e| NodeError::SerializationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(outputs)
+    }
+}
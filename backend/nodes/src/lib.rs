@@ -1,8 +1,32 @@
+mod arithmetic;
+mod branch;
+mod collect;
+mod comparison;
+mod constant;
+mod delay;
 mod end;
 pub mod error;
+mod expression;
+mod files;
+mod log;
+mod logic;
+mod merge;
+mod random;
 mod start;
 
+pub use arithmetic::{ArithmeticNode, ArithmeticOperation};
+pub use branch::BranchNode;
+pub use collect::CollectNode;
+pub use comparison::{ComparisonNode, ComparisonOperation};
+pub use constant::ConstantNode;
+pub use delay::DelayNode;
 pub use end::EndNode;
+pub use expression::ExpressionNode;
+pub use files::{ReadFileNode, WriteFileNode};
+pub use log::LogNode;
+pub use logic::{LogicNode, LogicOperation};
+pub use merge::MergeNode;
+pub use random::{GenerateUuidNode, RandomNumberNode};
 pub use start::StartNode;
 
 // -------------------------------------------------------------------------- //
@@ -10,56 +34,191 @@ pub use start::StartNode;
 // Component Model bindings
 mod bindings;
 
-use bindings::exports::cognexus::plugin::nodes::{Direction, Guest, NodeInfo, PortSpec};
-use cognexus_model::graph::NodeDefinitionInfo;
+use bindings::exports::cognexus::plugin::nodes::{
+    ConfigValue, Direction, Guest, Icon, InstancePorts, NodeInfo, ParameterConstraint,
+    ParameterSpec, PortSpec, PortValue,
+};
+use cognexus_model::graph::{
+    self, DataTypeInfo, NodeConfig, NodeDefinition, NodeIcon, Port, PortValues,
+};
+use cognexus_types::{BooleanType, BytesType, JsonType, NumberType, SignalType, StringType};
+use error::NodeError;
+use uuid::Uuid;
 
 struct Component;
 
+/// Every node this plugin provides.
+fn builtin_nodes() -> Vec<Box<dyn NodeDefinition<Error = NodeError>>> {
+    // Branch, Merge, Collect, Constant and Log handle values of any of these
+    let control_flow_types: [&dyn DataTypeInfo; 6] = [
+        &SignalType,
+        &NumberType,
+        &StringType,
+        &BooleanType,
+        &JsonType,
+        &BytesType,
+    ];
+
+    let mut nodes: Vec<Box<dyn NodeDefinition<Error = NodeError>>> = vec![
+        Box::new(StartNode),
+        Box::new(EndNode),
+        Box::new(ArithmeticNode::new(ArithmeticOperation::Add)),
+        Box::new(ArithmeticNode::new(ArithmeticOperation::Subtract)),
+        Box::new(ArithmeticNode::new(ArithmeticOperation::Multiply)),
+        Box::new(ArithmeticNode::new(ArithmeticOperation::Divide)),
+        Box::new(ComparisonNode::new(ComparisonOperation::Equals)),
+        Box::new(ComparisonNode::new(ComparisonOperation::GreaterThan)),
+        Box::new(ComparisonNode::new(ComparisonOperation::LessThan)),
+        Box::new(LogicNode::new(LogicOperation::And)),
+        Box::new(LogicNode::new(LogicOperation::Or)),
+        Box::new(LogicNode::new(LogicOperation::Not)),
+        Box::new(DelayNode),
+        Box::new(ReadFileNode),
+        Box::new(WriteFileNode),
+        Box::new(ExpressionNode),
+        Box::new(RandomNumberNode),
+        Box::new(GenerateUuidNode),
+    ];
+    for value_type in control_flow_types {
+        nodes.push(Box::new(BranchNode::of(value_type)));
+        nodes.push(Box::new(MergeNode::of(value_type)));
+        nodes.push(Box::new(CollectNode::of(value_type)));
+        nodes.push(Box::new(ConstantNode::of(value_type)));
+        nodes.push(Box::new(LogNode::of(value_type)));
+    }
+
+    nodes
+}
+
+fn node_config(config: Vec<ConfigValue>) -> NodeConfig {
+    config
+        .into_iter()
+        .map(|parameter| (parameter.name, parameter.value))
+        .collect()
+}
+
+fn find_node(definition_id: &str) -> Result<Box<dyn NodeDefinition<Error = NodeError>>, String> {
+    builtin_nodes()
+        .into_iter()
+        .find(|node| node.definition_id().to_string() == definition_id)
+        .ok_or_else(|| format!("Unknown node definition: {definition_id}"))
+}
+
+fn port_specs(ports: Vec<Port>, direction: Direction) -> Vec<PortSpec> {
+    ports
+        .iter()
+        .map(|port| PortSpec {
+            id: port.id().to_string(),
+            name: port.name().to_string(),
+            direction,
+            data_type_id: port.data_type_id().to_string(),
+        })
+        .collect()
+}
+
+fn parameter_specs(parameters: Vec<graph::ParameterSpec>) -> Vec<ParameterSpec> {
+    parameters
+        .into_iter()
+        .map(|parameter| ParameterSpec {
+            name: parameter.name().to_string(),
+            description: parameter.description().to_string(),
+            data_type_id: parameter.data_type_id().to_string(),
+            default_value: parameter.default().map(<[u8]>::to_vec),
+            required: parameter.is_required(),
+            constraints: parameter
+                .constraints()
+                .iter()
+                .map(|constraint| match constraint {
+                    graph::ParameterConstraint::Minimum(minimum) => {
+                        ParameterConstraint::Minimum(*minimum)
+                    }
+                    graph::ParameterConstraint::Maximum(maximum) => {
+                        ParameterConstraint::Maximum(*maximum)
+                    }
+                    graph::ParameterConstraint::OneOf(values) => {
+                        ParameterConstraint::OneOf(values.clone())
+                    }
+                    graph::ParameterConstraint::MaxLength(max_length) => {
+                        ParameterConstraint::MaxLength(*max_length as u64)
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
 impl Guest for Component {
     fn list_nodes() -> Vec<NodeInfo> {
-        let start_node = StartNode;
-        let end_node = EndNode;
-
-        vec![
-            // StartNode
-            NodeInfo {
-                id: start_node.definition_id().to_string(),
-                name: start_node.name().to_string(),
-                description: start_node.description().to_string(),
-                version: start_node.model_version().to_string(),
-                input_ports: vec![],
-                output_ports: start_node
-                    .output_port_specs()
-                    .unwrap_or_default()
-                    .iter()
-                    .map(|port| PortSpec {
-                        id: port.id().to_string(),
-                        name: port.name().to_string(),
-                        direction: Direction::Output,
-                        data_type_id: port.data_type_id().to_string(),
-                    })
-                    .collect(),
-            },
-            // EndNode
-            NodeInfo {
-                id: end_node.definition_id().to_string(),
-                name: end_node.name().to_string(),
-                description: end_node.description().to_string(),
-                version: end_node.model_version().to_string(),
-                input_ports: end_node
-                    .input_port_specs()
-                    .unwrap_or_default()
-                    .iter()
-                    .map(|port| PortSpec {
-                        id: port.id().to_string(),
-                        name: port.name().to_string(),
-                        direction: Direction::Input,
-                        data_type_id: port.data_type_id().to_string(),
-                    })
-                    .collect(),
-                output_ports: vec![],
-            },
-        ]
+        builtin_nodes()
+            .iter()
+            .map(|node| NodeInfo {
+                id: node.definition_id().to_string(),
+                name: node.name().to_string(),
+                description: node.description().to_string(),
+                category: node.category().to_string(),
+                tags: node.tags(),
+                icon: node.icon().map(|icon| match icon {
+                    NodeIcon::Glyph(name) => Icon::Glyph(name),
+                    NodeIcon::Svg(svg) => Icon::Svg(svg),
+                }),
+                version: node.model_version().to_string(),
+                input_ports: port_specs(
+                    node.input_port_specs().unwrap_or_default(),
+                    Direction::Input,
+                ),
+                output_ports: port_specs(
+                    node.output_port_specs().unwrap_or_default(),
+                    Direction::Output,
+                ),
+                parameters: parameter_specs(node.parameter_specs().unwrap_or_default()),
+                branching: node.is_branching(),
+                merging: node.is_merging(),
+                configured_ports: node.has_configured_ports(),
+                deterministic: node.is_deterministic(),
+            })
+            .collect()
+    }
+
+    fn execute(
+        definition_id: String,
+        config: Vec<ConfigValue>,
+        inputs: Vec<PortValue>,
+    ) -> Result<Vec<PortValue>, String> {
+        let config = node_config(config);
+
+        let mut values = PortValues::new();
+        for input in inputs {
+            let port_id = Uuid::parse_str(&input.port_id)
+                .map_err(|e| format!("Invalid input port id '{}': {e}", input.port_id))?;
+            values.insert(port_id, input.value);
+        }
+
+        let node = find_node(&definition_id)?;
+
+        let outputs = node.execute(&config, &values).map_err(|e| e.to_string())?;
+
+        Ok(outputs
+            .iter()
+            .map(|(port_id, value)| PortValue {
+                port_id: port_id.to_string(),
+                value: value.to_vec(),
+            })
+            .collect())
+    }
+
+    fn resolve_ports(
+        definition_id: String,
+        config: Vec<ConfigValue>,
+    ) -> Result<InstancePorts, String> {
+        let node = find_node(&definition_id)?;
+        let (inputs, outputs) = node
+            .configured_ports(&node_config(config))
+            .map_err(|e| e.to_string())?;
+
+        Ok(InstancePorts {
+            input_ports: port_specs(inputs, Direction::Input),
+            output_ports: port_specs(outputs, Direction::Output),
+        })
     }
 }
 
@@ -0,0 +1,160 @@
+use crate::bindings::cognexus::plugin::logging::{self, Level};
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, ParameterSpec,
+    ParameterSpecBuilder, Port, PortBuilder, PortValues,
+};
+use cognexus_types::StringType;
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+/// Logs the value passing through it, unchanged, so it can be inspected in
+/// the run's trace.
+///
+/// Like Branch, each value type gets its own Log definition, with an id
+/// derived from the type's. The message logged with the value is the
+/// instance's `message` parameter, a String, or the definition's name.
+pub struct LogNode {
+    id: Uuid,
+    value_type_id: Uuid,
+    name: String,
+    description: String,
+}
+
+/// Namespace the ids of Log definitions are derived in.
+const NAMESPACE: &str = "c9bbc4db-bc0d-4fbf-9eeb-35e3790f5e0c";
+const INPUT_PORT_ID: &str = "68940f6d-75d6-462b-b8ec-7fba3707acd6";
+const OUTPUT_PORT_ID: &str = "93597796-ab77-4fe0-8e7f-5e7a2cf76186";
+
+impl LogNode {
+    /// Name of the parameter holding the message to log with the value.
+    pub const MESSAGE_PARAMETER: &str = "message";
+
+    /// The Log definition for values of `value_type`.
+    pub fn of(value_type: &dyn DataTypeInfo) -> Self {
+        let namespace = Uuid::parse_str(NAMESPACE).unwrap();
+
+        Self {
+            id: Uuid::new_v5(&namespace, value_type.type_id().as_bytes()),
+            value_type_id: value_type.type_id(),
+            name: format!("Log ({})", value_type.name()),
+            description: format!(
+                "Logs a {} value for inspection and passes it on",
+                value_type.name()
+            ),
+        }
+    }
+
+    pub fn value_type_id(&self) -> Uuid {
+        self.value_type_id
+    }
+}
+
+impl NodeDefinitionInfo for LogNode {
+    fn definition_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn category(&self) -> &str {
+        "IO"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("debug"), String::from("print")]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("terminal")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        let parameter = ParameterSpecBuilder::default()
+            .with_name(Self::MESSAGE_PARAMETER)
+            .with_description("Message logged with the value; defaults to the node's name")
+            .with_data_type_id(StringType.type_id())
+            .with_required(false)
+            .build()?;
+
+        Ok(vec![parameter])
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(INPUT_PORT_ID).unwrap())
+            .with_name("value")
+            .with_data_type_id(self.value_type_id)
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(OUTPUT_PORT_ID).unwrap())
+            .with_name("value")
+            .with_data_type_id(self.value_type_id)
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    /// Logging the value is the point of running it.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+impl NodeDefinition for LogNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let value = inputs
+            .get(&Uuid::parse_str(INPUT_PORT_ID).unwrap())
+            .ok_or_else(|| NodeError::InvalidInput {
+                message: String::from("No value to log"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        let message = match config.get(Self::MESSAGE_PARAMETER) {
+            Some(bytes) => {
+                String::from_utf8(bytes.to_vec()).map_err(|_| NodeError::InvalidConfig {
+                    message: format!("Parameter '{}' must be a String", Self::MESSAGE_PARAMETER),
+                    location: ErrorLocation::from(Location::caller()),
+                })?
+            }
+            None => self.name.clone(),
+        };
+
+        logging::log_value(
+            Level::Info,
+            &message,
+            &self.value_type_id.to_string(),
+            value,
+        );
+
+        let mut outputs = PortValues::new();
+        outputs.insert(Uuid::parse_str(OUTPUT_PORT_ID).unwrap(), value.to_vec());
+
+        Ok(outputs)
+    }
+}
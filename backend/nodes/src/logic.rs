@@ -0,0 +1,172 @@
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, Port, PortBuilder,
+    PortValues,
+};
+use cognexus_types::BooleanType;
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+/// The operation a [`LogicNode`] applies to its inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicOperation {
+    And,
+    Or,
+    /// Takes only input `a`.
+    Not,
+}
+
+/// Combines Booleans: `a && b`, `a || b`, or `!a`. Each operation is its own
+/// node definition.
+pub struct LogicNode {
+    operation: LogicOperation,
+}
+
+const AND_ID: &str = "ad5b74dd-3bc2-4373-83d9-8ecc01faf8a7";
+const OR_ID: &str = "0abd1902-834c-45d5-ab36-af7af205dae8";
+const NOT_ID: &str = "cda17ebd-9b8f-46ee-a08e-2e9378735611";
+const A_PORT_ID: &str = "a6fd0410-93dc-4e91-b67c-b88c84105829";
+const B_PORT_ID: &str = "74c1f3ac-b698-4ab9-8680-2846e4edf8f6";
+const RESULT_PORT_ID: &str = "90f5107f-09e6-44c5-ac42-4ba052989cef";
+
+impl LogicNode {
+    pub fn new(operation: LogicOperation) -> Self {
+        Self { operation }
+    }
+
+    pub fn operation(&self) -> LogicOperation {
+        self.operation
+    }
+}
+
+impl NodeDefinitionInfo for LogicNode {
+    fn definition_id(&self) -> Uuid {
+        let id = match self.operation {
+            LogicOperation::And => AND_ID,
+            LogicOperation::Or => OR_ID,
+            LogicOperation::Not => NOT_ID,
+        };
+
+        Uuid::parse_str(id).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        match self.operation {
+            LogicOperation::And => "And",
+            LogicOperation::Or => "Or",
+            LogicOperation::Not => "Not",
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self.operation {
+            LogicOperation::And => "True if both a and b are true",
+            LogicOperation::Or => "True if a or b is true",
+            LogicOperation::Not => "Inverts a",
+        }
+    }
+
+    fn category(&self) -> &str {
+        "Logic"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        match self.operation {
+            LogicOperation::And => vec![String::from("boolean"), String::from("all")],
+            LogicOperation::Or => vec![String::from("boolean"), String::from("any")],
+            LogicOperation::Not => vec![String::from("boolean"), String::from("invert")],
+        }
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        let glyph = match self.operation {
+            LogicOperation::And => "and",
+            LogicOperation::Or => "or",
+            LogicOperation::Not => "not",
+        };
+
+        Some(NodeIcon::Glyph(String::from(glyph)))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let boolean_type = BooleanType;
+        let mut ports = vec![
+            PortBuilder::default()
+                .with_id(Uuid::parse_str(A_PORT_ID).unwrap())
+                .with_name("a")
+                .with_data_type_id(boolean_type.type_id())
+                .build()?,
+        ];
+
+        if self.operation != LogicOperation::Not {
+            ports.push(
+                PortBuilder::default()
+                    .with_id(Uuid::parse_str(B_PORT_ID).unwrap())
+                    .with_name("b")
+                    .with_data_type_id(boolean_type.type_id())
+                    .build()?,
+            );
+        }
+
+        Ok(ports)
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let boolean_type = BooleanType;
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(RESULT_PORT_ID).unwrap())
+            .with_name("result")
+            .with_data_type_id(boolean_type.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+}
+
+impl NodeDefinition for LogicNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, _config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let read = |port_id: &str| -> Result<bool, NodeError> {
+            inputs
+                .read(&Uuid::parse_str(port_id).unwrap(), &BooleanType)
+                .map_err(|e| NodeError::InvalidInput {
+                    message: e.to_string(),
+                    location: ErrorLocation::from(Location::caller()),
+                })
+        };
+        let a = read(A_PORT_ID)?;
+
+        let result = match self.operation {
+            LogicOperation::And => a && read(B_PORT_ID)?,
+            LogicOperation::Or => a || read(B_PORT_ID)?,
+            LogicOperation::Not => !a,
+        };
+
+        let mut outputs = PortValues::new();
+        outputs
+            .write(
+                Uuid::parse_str(RESULT_PORT_ID).unwrap(),
+                &BooleanType,
+                result,
+            )
+            .map_err(|e| NodeError::SerializationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(outputs)
+    }
+}
@@ -0,0 +1,132 @@
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, Port, PortBuilder,
+    PortValues,
+};
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::Uuid;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+/// Re-joins branches: forwards the value of whichever input arrives,
+/// preferring `first` when both do.
+///
+/// Like Branch, each value type gets its own Merge definition, with an id
+/// derived from the type's. Merges can be chained to join more branches.
+pub struct MergeNode {
+    id: Uuid,
+    value_type_id: Uuid,
+    name: String,
+    description: String,
+}
+
+/// Namespace the ids of Merge definitions are derived in.
+const NAMESPACE: &str = "3345696a-122d-46f4-a196-3c5d37036b12";
+const FIRST_PORT_ID: &str = "f2b2dc3d-0ca6-41b7-8d37-208d5b4bbe7c";
+const SECOND_PORT_ID: &str = "9b20f2a3-402c-4d4f-9d43-d7a6f6064b4d";
+const VALUE_PORT_ID: &str = "d066e87e-4d4b-446c-92c0-ca20ff55fe33";
+
+impl MergeNode {
+    /// The Merge definition for values of `value_type`.
+    pub fn of(value_type: &dyn DataTypeInfo) -> Self {
+        let namespace = Uuid::parse_str(NAMESPACE).unwrap();
+
+        Self {
+            id: Uuid::new_v5(&namespace, value_type.type_id().as_bytes()),
+            value_type_id: value_type.type_id(),
+            name: format!("Merge ({})", value_type.name()),
+            description: format!(
+                "Forwards the {} value of whichever branch ran",
+                value_type.name()
+            ),
+        }
+    }
+
+    pub fn value_type_id(&self) -> Uuid {
+        self.value_type_id
+    }
+}
+
+impl NodeDefinitionInfo for MergeNode {
+    fn definition_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn category(&self) -> &str {
+        "Flow"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("join")]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("merge")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let first = PortBuilder::default()
+            .with_id(Uuid::parse_str(FIRST_PORT_ID).unwrap())
+            .with_name("first")
+            .with_data_type_id(self.value_type_id)
+            .build()?;
+        let second = PortBuilder::default()
+            .with_id(Uuid::parse_str(SECOND_PORT_ID).unwrap())
+            .with_name("second")
+            .with_data_type_id(self.value_type_id)
+            .build()?;
+
+        Ok(vec![first, second])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(VALUE_PORT_ID).unwrap())
+            .with_name("value")
+            .with_data_type_id(self.value_type_id)
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    fn is_merging(&self) -> bool {
+        true
+    }
+}
+
+impl NodeDefinition for MergeNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, _config: &NodeConfig, inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let value = inputs
+            .get(&Uuid::parse_str(FIRST_PORT_ID).unwrap())
+            .or_else(|| inputs.get(&Uuid::parse_str(SECOND_PORT_ID).unwrap()))
+            .ok_or_else(|| NodeError::InvalidInput {
+                message: String::from("Neither input has a value"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        let mut outputs = PortValues::new();
+        outputs.insert(Uuid::parse_str(VALUE_PORT_ID).unwrap(), value.to_vec());
+
+        Ok(outputs)
+    }
+}
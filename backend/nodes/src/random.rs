@@ -0,0 +1,280 @@
+use crate::bindings::cognexus::plugin::random;
+use crate::error::NodeError;
+
+use cognexus_model::graph::{
+    DataType, DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon,
+    ParameterSpec, ParameterSpecBuilder, Port, PortBuilder, PortValues,
+};
+use cognexus_types::{NumberType, SignalType, StringType};
+
+use cognexus_model::error::ModelError;
+use semver::Version;
+use uuid::{Builder, Uuid};
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+/// Emits a random Number from the range set by its instance's `min` and
+/// `max` parameters, each time its signal arrives.
+///
+/// The randomness comes from the host, so replaying a recorded run draws
+/// the same Numbers.
+pub struct RandomNumberNode;
+
+const RANDOM_NUMBER_ID: &str = "d9840b68-36ee-4c6b-99e3-478119d3b269";
+const RANDOM_NUMBER_NAME: &str = "Random Number";
+const RANDOM_NUMBER_DESCRIPTION: &str = "Emits a random Number from a configured range";
+const RANDOM_NUMBER_INPUT_PORT_ID: &str = "dcfd2098-bbdf-424f-b242-6c2f994c492c";
+const RANDOM_NUMBER_OUTPUT_PORT_ID: &str = "31c7aeb5-a546-4948-949e-da0d9a09f042";
+/// Range of an instance that doesn't set the parameters.
+const DEFAULT_MIN: f64 = 0.0;
+const DEFAULT_MAX: f64 = 1.0;
+
+impl RandomNumberNode {
+    /// Name of the parameter holding the smallest Number emitted.
+    pub const MIN_PARAMETER: &str = "min";
+    /// Name of the parameter holding the bound the emitted Numbers stay
+    /// below.
+    pub const MAX_PARAMETER: &str = "max";
+
+    #[track_caller]
+    fn bound(config: &NodeConfig, parameter: &str, default: f64) -> Result<f64, NodeError> {
+        let Some(bytes) = config.get(parameter) else {
+            return Ok(default);
+        };
+
+        NumberType
+            .deserialize(bytes)
+            .ok()
+            .and_then(|value| value.downcast::<f64>().ok())
+            .map(|value| *value)
+            .filter(|bound| bound.is_finite())
+            .ok_or_else(|| NodeError::InvalidConfig {
+                message: format!("Parameter '{parameter}' must be a finite Number"),
+                location: ErrorLocation::from(Location::caller()),
+            })
+    }
+
+    /// The configured range, from `min` up to but not including `max`.
+    #[track_caller]
+    fn range(config: &NodeConfig) -> Result<(f64, f64), NodeError> {
+        let min = Self::bound(config, Self::MIN_PARAMETER, DEFAULT_MIN)?;
+        let max = Self::bound(config, Self::MAX_PARAMETER, DEFAULT_MAX)?;
+
+        if min > max {
+            return Err(NodeError::InvalidConfig {
+                message: format!(
+                    "Parameter '{}' ({min}) is greater than '{}' ({max})",
+                    Self::MIN_PARAMETER,
+                    Self::MAX_PARAMETER
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        Ok((min, max))
+    }
+}
+
+impl NodeDefinitionInfo for RandomNumberNode {
+    fn definition_id(&self) -> Uuid {
+        Uuid::parse_str(RANDOM_NUMBER_ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        RANDOM_NUMBER_NAME
+    }
+
+    fn description(&self) -> &str {
+        RANDOM_NUMBER_DESCRIPTION
+    }
+
+    fn category(&self) -> &str {
+        "Math"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![
+            String::from("random"),
+            String::from("rng"),
+            String::from("dice"),
+        ]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("dice")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        let min = ParameterSpecBuilder::default()
+            .with_name(Self::MIN_PARAMETER)
+            .with_description("Smallest Number to emit")
+            .with_data_type_id(NumberType.type_id())
+            .with_default(DEFAULT_MIN.to_le_bytes().to_vec())
+            .build()?;
+
+        let max = ParameterSpecBuilder::default()
+            .with_name(Self::MAX_PARAMETER)
+            .with_description("Bound the emitted Numbers stay below")
+            .with_data_type_id(NumberType.type_id())
+            .with_default(DEFAULT_MAX.to_le_bytes().to_vec())
+            .build()?;
+
+        Ok(vec![min, max])
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(RANDOM_NUMBER_INPUT_PORT_ID).unwrap())
+            .with_name("signal")
+            .with_data_type_id(SignalType.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(RANDOM_NUMBER_OUTPUT_PORT_ID).unwrap())
+            .with_name("number")
+            .with_data_type_id(NumberType.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    /// Every execution draws a new number.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+impl NodeDefinition for RandomNumberNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, config: &NodeConfig, _inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let (min, max) = Self::range(config)?;
+
+        // The top 53 bits make an evenly spread f64 in [0, 1)
+        let unit = (random::random_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        let number = (min + unit * (max - min)).min(max);
+
+        let mut outputs = PortValues::new();
+        outputs
+            .write(
+                Uuid::parse_str(RANDOM_NUMBER_OUTPUT_PORT_ID).unwrap(),
+                &NumberType,
+                number,
+            )
+            .map_err(|e| NodeError::SerializationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(outputs)
+    }
+}
+
+/// Emits a new random (version 4) UUID as a String each time its signal
+/// arrives.
+///
+/// Like Random Number, it draws from the host's randomness, so replays
+/// generate the same UUIDs.
+pub struct GenerateUuidNode;
+
+const GENERATE_UUID_ID: &str = "0e84dacb-d468-48a2-a87b-f95a68238314";
+const GENERATE_UUID_NAME: &str = "Generate UUID";
+const GENERATE_UUID_DESCRIPTION: &str = "Emits a new random UUID";
+const GENERATE_UUID_INPUT_PORT_ID: &str = "92ad700e-3eae-4212-a776-9985c769bb55";
+const GENERATE_UUID_OUTPUT_PORT_ID: &str = "9942b263-8c5b-488f-bb08-efaf62a3fd22";
+
+impl NodeDefinitionInfo for GenerateUuidNode {
+    fn definition_id(&self) -> Uuid {
+        Uuid::parse_str(GENERATE_UUID_ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        GENERATE_UUID_NAME
+    }
+
+    fn description(&self) -> &str {
+        GENERATE_UUID_DESCRIPTION
+    }
+
+    fn category(&self) -> &str {
+        "Values"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![
+            String::from("uuid"),
+            String::from("guid"),
+            String::from("identifier"),
+            String::from("random"),
+        ]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("fingerprint")))
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(GENERATE_UUID_INPUT_PORT_ID).unwrap())
+            .with_name("signal")
+            .with_data_type_id(SignalType.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        let port = PortBuilder::default()
+            .with_id(Uuid::parse_str(GENERATE_UUID_OUTPUT_PORT_ID).unwrap())
+            .with_name("uuid")
+            .with_data_type_id(StringType.type_id())
+            .build()?;
+
+        Ok(vec![port])
+    }
+
+    /// Every execution draws a new UUID.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+impl NodeDefinition for GenerateUuidNode {
+    type Error = NodeError;
+
+    #[track_caller]
+    fn execute(&self, _config: &NodeConfig, _inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&random::random_bytes(16));
+        let uuid = Builder::from_random_bytes(bytes).into_uuid();
+
+        let mut outputs = PortValues::new();
+        outputs
+            .write(
+                Uuid::parse_str(GENERATE_UUID_OUTPUT_PORT_ID).unwrap(),
+                &StringType,
+                uuid.to_string(),
+            )
+            .map_err(|e| NodeError::SerializationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(outputs)
+    }
+}
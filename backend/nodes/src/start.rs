@@ -1,12 +1,19 @@
 use crate::error::NodeError;
 
-use cognexus_model::graph::{DataTypeInfo, NodeDefinition, NodeDefinitionInfo, Port, PortBuilder};
+use cognexus_model::graph::{
+    DataTypeInfo, NodeConfig, NodeDefinition, NodeDefinitionInfo, NodeIcon, Port, PortBuilder,
+    PortValues,
+};
 use cognexus_types::SignalType;
 
 use cognexus_model::error::ModelError;
 use semver::Version;
 use uuid::Uuid;
 
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
 pub struct StartNode;
 
 const ID: &str = "40ebe0be-d2db-4eed-80f3-91267352ee42";
@@ -27,6 +34,18 @@ impl NodeDefinitionInfo for StartNode {
         DESCRIPTION
     }
 
+    fn category(&self) -> &str {
+        "Flow"
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("entry"), String::from("begin")]
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        Some(NodeIcon::Glyph(String::from("play")))
+    }
+
     fn model_version(&self) -> Version {
         Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
     }
@@ -51,7 +70,16 @@ impl NodeDefinitionInfo for StartNode {
 impl NodeDefinition for StartNode {
     type Error = NodeError;
 
-    fn execute(&self, _inputs: Vec<u8>) -> Result<Vec<u8>, NodeError> {
-        Ok(vec![])
+    #[track_caller]
+    fn execute(&self, _config: &NodeConfig, _inputs: &PortValues) -> Result<PortValues, NodeError> {
+        let mut outputs = PortValues::new();
+        outputs
+            .write(Uuid::parse_str(OUTPUT_PORT_ID).unwrap(), &SignalType, ())
+            .map_err(|e| NodeError::SerializationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(outputs)
     }
 }
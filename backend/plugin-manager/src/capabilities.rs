@@ -0,0 +1,50 @@
+//! Host resources a plugin has been granted access to.
+
+use crate::error::PluginManagerError;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+use std::path::PathBuf;
+
+use wasmtime_wasi::{DirPerms, FilePerms, WasiCtxBuilder};
+
+/// A host directory a plugin may access. Plugins see nothing of the host's
+/// filesystem beyond the directories they are granted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryGrant {
+    pub host_path: PathBuf,
+    /// Path the plugin opens the directory at, e.g. `/data`.
+    pub guest_path: String,
+    /// Let the plugin create, change, and delete files in the directory.
+    /// Otherwise it may only read them.
+    pub writable: bool,
+}
+
+/// Preopen `directories` in a WASI context being built.
+#[track_caller]
+pub(crate) fn preopen(
+    builder: &mut WasiCtxBuilder,
+    directories: &[DirectoryGrant],
+) -> Result<(), PluginManagerError> {
+    for grant in directories {
+        let (dir_perms, file_perms) = if grant.writable {
+            (DirPerms::all(), FilePerms::all())
+        } else {
+            (DirPerms::READ, FilePerms::READ)
+        };
+
+        builder
+            .preopened_dir(&grant.host_path, &grant.guest_path, dir_perms, file_perms)
+            .map_err(|e| PluginManagerError::IoError {
+                message: format!(
+                    "Failed to open granted directory {}: {e}",
+                    grant.host_path.display()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+                source: Some(e.into()),
+            })?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! The host side of the clock interface node plugins import.
+
+use crate::State;
+use crate::loader::nodes_world::cognexus::plugin::clock;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use wasmtime::Trap;
+
+/// Wakes the plugin calls on a loader's engine that are waiting in the host
+/// when one of them is interrupted, so it can stop waiting.
+#[derive(Default)]
+pub(crate) struct Wakeups {
+    lock: Mutex<()>,
+    woken: Condvar,
+}
+
+impl Wakeups {
+    pub(crate) fn wake_all(&self) {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        self.woken.notify_all();
+    }
+}
+
+/// What waits in the current call are cut short by: the interrupt flag of
+/// the run it belongs to and its time limit, and how long the call has asked
+/// for its outputs to be held back. Replayed calls don't wait at all, since
+/// their clock readings are played back anyway.
+#[derive(Default)]
+pub(crate) struct HostClock {
+    wakeups: Arc<Wakeups>,
+    interrupted: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+    timed_out: Arc<AtomicBool>,
+    deferred: Option<Duration>,
+    skip_waits: bool,
+}
+
+impl HostClock {
+    pub(crate) fn new(skip_waits: bool) -> Self {
+        Self {
+            skip_waits,
+            ..Self::default()
+        }
+    }
+
+    /// Bound waits by a new call, which is stopped once `interrupted` is
+    /// set. `timed_out` is set if a wait runs into `deadline`.
+    pub(crate) fn start_call(
+        &mut self,
+        wakeups: Arc<Wakeups>,
+        interrupted: Arc<AtomicBool>,
+        deadline: Option<Instant>,
+        timed_out: Arc<AtomicBool>,
+    ) {
+        self.wakeups = wakeups;
+        self.interrupted = interrupted;
+        self.deadline = deadline;
+        self.timed_out = timed_out;
+        self.deferred = None;
+    }
+
+    /// How long the call asked for its outputs to be held back after it
+    /// returns, if it did.
+    pub(crate) fn take_deferred(&mut self) -> Option<Duration> {
+        self.deferred.take()
+    }
+
+    /// True once the current call's run has been interrupted.
+    pub(crate) fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::SeqCst)
+    }
+
+    /// Block for `duration`, trapping early if the call is interrupted or
+    /// reaches its deadline first.
+    fn sleep(&self, duration: Duration) -> wasmtime::Result<()> {
+        if self.skip_waits {
+            return Ok(());
+        }
+
+        let until = Instant::now() + duration;
+        let mut guard = self
+            .wakeups
+            .lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        loop {
+            if self.is_interrupted() {
+                return Err(Trap::Interrupt.into());
+            }
+
+            let now = Instant::now();
+            if self.deadline.is_some_and(|deadline| now >= deadline) {
+                self.timed_out.store(true, Ordering::SeqCst);
+                return Err(Trap::Interrupt.into());
+            }
+            if now >= until {
+                return Ok(());
+            }
+
+            let wake_at = self.deadline.map_or(until, |deadline| deadline.min(until));
+            guard = self
+                .wakeups
+                .woken
+                .wait_timeout(guard, wake_at - now)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+}
+
+impl clock::Host for State {
+    fn sleep(&mut self, nanoseconds: u64) -> wasmtime::Result<()> {
+        self.clock.sleep(Duration::from_nanos(nanoseconds))
+    }
+
+    fn defer(&mut self, nanoseconds: u64) -> wasmtime::Result<()> {
+        if !self.clock.skip_waits {
+            let deferred = self.clock.deferred.unwrap_or_default();
+            self.clock.deferred = Some(deferred.saturating_add(Duration::from_nanos(nanoseconds)));
+        }
+        Ok(())
+    }
+}
@@ -29,6 +29,42 @@ pub enum PluginManagerError {
         source: wasmtime::Error,
     },
 
+    #[error("Timeout error: {message} {location}")]
+    TimeoutError {
+        message: String,
+        location: ErrorLocation,
+        #[source]
+        source: wasmtime::Error,
+    },
+
+    #[error("Fuel exhausted: {message} {location}")]
+    FuelExhaustedError {
+        message: String,
+        location: ErrorLocation,
+        #[source]
+        source: wasmtime::Error,
+    },
+
+    #[error("Memory limit error: {message} {location}")]
+    MemoryLimitError {
+        message: String,
+        location: ErrorLocation,
+        #[source]
+        source: wasmtime::Error,
+    },
+
+    #[error("Invalid value: {message} {location}")]
+    InvalidValueError {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("Package error: {message} {location}")]
+    PackageError {
+        message: String,
+        location: ErrorLocation,
+    },
+
     #[error("Lock error: {message}")]
     LockError {
         message: String,
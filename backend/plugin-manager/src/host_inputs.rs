@@ -0,0 +1,275 @@
+//! Recording and replaying what a plugin call reads from the host, so the
+//! call can be reproduced exactly.
+
+use crate::capabilities::{DirectoryGrant, preopen};
+use crate::error::PluginManagerError;
+use crate::random::HostRandom;
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use wasmtime_wasi::{HostMonotonicClock, HostWallClock, RngCore, WasiCtx, WasiCtxBuilder};
+
+/// Everything a plugin call read from the host's clocks and random sources,
+/// in the order it read them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostInputs {
+    /// Readings of `wasi:clocks/wall-clock`, as time since the Unix epoch.
+    pub wall_clock: Vec<Duration>,
+    /// Readings of `wasi:clocks/monotonic-clock`, in nanoseconds.
+    pub monotonic_clock: Vec<u64>,
+    /// Bytes handed out by `wasi:random/random`.
+    pub random_bytes: Vec<u8>,
+    /// Bytes handed out by `wasi:random/insecure`.
+    pub insecure_random_bytes: Vec<u8>,
+    /// The value of `wasi:random/insecure-seed`.
+    pub insecure_random_seed: u128,
+    /// The seed of `cognexus:plugin/random`. Recordings made before the
+    /// interface existed have none, and replay with zero.
+    #[serde(default)]
+    pub random_seed: u64,
+}
+
+/// Where a plugin call's clock readings and randomness come from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HostMode {
+    /// Read the host's real clocks and random sources.
+    #[default]
+    Live,
+    /// Read the real ones, and return what was read with the call's usage.
+    Record,
+    /// Play back earlier readings. A call that reads more than was recorded
+    /// has diverged from the recording; it sees the last clock readings
+    /// again and zeroed random bytes.
+    Replay(HostInputs),
+}
+
+/// Host inputs being recorded during a call.
+#[derive(Clone, Default)]
+pub(crate) struct HostLog(Arc<Mutex<HostInputs>>);
+
+impl HostLog {
+    fn lock(&self) -> MutexGuard<'_, HostInputs> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn take(&self) -> HostInputs {
+        std::mem::take(&mut *self.lock())
+    }
+}
+
+/// Build the WASI context for a call in `mode`, with `directories`
+/// preopened, the source of its host randomness, and the log it records
+/// into when recording.
+#[track_caller]
+pub(crate) fn host_ctx(
+    mode: HostMode,
+    directories: &[DirectoryGrant],
+) -> Result<(WasiCtx, HostRandom, Option<HostLog>), PluginManagerError> {
+    let mut builder = WasiCtxBuilder::new();
+    preopen(&mut builder, directories)?;
+
+    let ctx = match mode {
+        HostMode::Live => (builder.build(), HostRandom::new(rand_u64()), None),
+        HostMode::Record => {
+            let log = HostLog::default();
+            let seed = u128::from(rand_u64()) << 64 | u128::from(rand_u64());
+            let random_seed = rand_u64();
+            log.lock().insecure_random_seed = seed;
+            log.lock().random_seed = random_seed;
+
+            builder
+                .wall_clock(RecordingWallClock {
+                    inner: wasmtime_wasi::clocks::wall_clock(),
+                    log: log.clone(),
+                })
+                .monotonic_clock(RecordingMonotonicClock {
+                    inner: wasmtime_wasi::clocks::monotonic_clock(),
+                    log: log.clone(),
+                })
+                .secure_random(RecordingRng {
+                    inner: wasmtime_wasi::thread_rng(),
+                    log: log.clone(),
+                    insecure: false,
+                })
+                .insecure_random(RecordingRng {
+                    inner: wasmtime_wasi::thread_rng(),
+                    log: log.clone(),
+                    insecure: true,
+                })
+                .insecure_random_seed(seed);
+
+            (builder.build(), HostRandom::new(random_seed), Some(log))
+        }
+        HostMode::Replay(inputs) => {
+            builder
+                .wall_clock(ReplayClock::new(inputs.wall_clock))
+                .monotonic_clock(ReplayClock::new(inputs.monotonic_clock))
+                .secure_random(ReplayRng::new(inputs.random_bytes))
+                .insecure_random(ReplayRng::new(inputs.insecure_random_bytes))
+                .insecure_random_seed(inputs.insecure_random_seed);
+
+            (builder.build(), HostRandom::new(inputs.random_seed), None)
+        }
+    };
+
+    Ok(ctx)
+}
+
+fn rand_u64() -> u64 {
+    wasmtime_wasi::thread_rng().next_u64()
+}
+
+struct RecordingWallClock {
+    inner: Box<dyn HostWallClock + Send>,
+    log: HostLog,
+}
+
+impl HostWallClock for RecordingWallClock {
+    fn resolution(&self) -> Duration {
+        self.inner.resolution()
+    }
+
+    fn now(&self) -> Duration {
+        let now = self.inner.now();
+        self.log.lock().wall_clock.push(now);
+        now
+    }
+}
+
+struct RecordingMonotonicClock {
+    inner: Box<dyn HostMonotonicClock + Send>,
+    log: HostLog,
+}
+
+impl HostMonotonicClock for RecordingMonotonicClock {
+    fn resolution(&self) -> u64 {
+        self.inner.resolution()
+    }
+
+    fn now(&self) -> u64 {
+        let now = self.inner.now();
+        self.log.lock().monotonic_clock.push(now);
+        now
+    }
+}
+
+struct RecordingRng {
+    inner: Box<dyn RngCore + Send>,
+    log: HostLog,
+    insecure: bool,
+}
+
+impl RngCore for RecordingRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest);
+
+        let mut log = self.log.lock();
+        if self.insecure {
+            log.insecure_random_bytes.extend_from_slice(dest);
+        } else {
+            log.random_bytes.extend_from_slice(dest);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), cap_rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Plays back recorded clock readings, repeating the last one once they run
+/// out.
+struct ReplayClock<T> {
+    readings: Mutex<(VecDeque<T>, T)>,
+}
+
+impl<T: Copy + Default> ReplayClock<T> {
+    fn new(readings: Vec<T>) -> Self {
+        Self {
+            readings: Mutex::new((readings.into(), T::default())),
+        }
+    }
+
+    fn next(&self) -> T {
+        let mut readings = self.readings.lock().unwrap_or_else(PoisonError::into_inner);
+        let (remaining, last) = &mut *readings;
+
+        if let Some(reading) = remaining.pop_front() {
+            *last = reading;
+        }
+        *last
+    }
+}
+
+impl HostWallClock for ReplayClock<Duration> {
+    fn resolution(&self) -> Duration {
+        Duration::from_nanos(1)
+    }
+
+    fn now(&self) -> Duration {
+        self.next()
+    }
+}
+
+impl HostMonotonicClock for ReplayClock<u64> {
+    fn resolution(&self) -> u64 {
+        1
+    }
+
+    fn now(&self) -> u64 {
+        self.next()
+    }
+}
+
+/// Plays back recorded random bytes, then zeros once they run out.
+struct ReplayRng {
+    bytes: VecDeque<u8>,
+}
+
+impl ReplayRng {
+    fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes: bytes.into(),
+        }
+    }
+}
+
+impl RngCore for ReplayRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest {
+            *byte = self.bytes.pop_front().unwrap_or(0);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), cap_rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+//! What a component imports and exports, described in WIT syntax.
+
+use serde::Serialize;
+
+use wasmtime::Engine;
+use wasmtime::component::Component;
+use wasmtime::component::types::{ComponentFunc, ComponentItem, ResourceType, Type};
+
+/// Everything a component imports and exports, in the order it lists them.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ComponentInterfaces {
+    pub imports: Vec<ComponentInterface>,
+    pub exports: Vec<ComponentInterface>,
+}
+
+/// One import or export, most often an interface such as
+/// `cognexus:plugin/nodes`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentInterface {
+    pub name: String,
+    /// What sort of item it is: "instance" for an interface, otherwise
+    /// "func", "type", "resource", "component", "module" or "core func".
+    pub kind: &'static str,
+    /// Types it defines, such as `port-spec = record { id: string }`.
+    pub types: Vec<String>,
+    /// Functions it has, such as `list-nodes: func() -> list<node-info>`.
+    pub functions: Vec<String>,
+}
+
+pub(crate) fn describe_component(engine: &Engine, component: &Component) -> ComponentInterfaces {
+    let component_type = component.component_type();
+
+    ComponentInterfaces {
+        imports: component_type
+            .imports(engine)
+            .map(|(name, item)| describe_item(engine, name, item))
+            .collect(),
+        exports: component_type
+            .exports(engine)
+            .map(|(name, item)| describe_item(engine, name, item))
+            .collect(),
+    }
+}
+
+fn describe_item(engine: &Engine, name: &str, item: ComponentItem) -> ComponentInterface {
+    let mut interface = ComponentInterface {
+        name: String::from(name),
+        kind: "instance",
+        types: Vec::new(),
+        functions: Vec::new(),
+    };
+
+    match item {
+        ComponentItem::ComponentInstance(instance) => {
+            let mut names = Names::default();
+            let mut functions = Vec::new();
+            for (name, item) in instance.exports(engine) {
+                match item {
+                    ComponentItem::Type(ty) => names.types.push((String::from(name), ty)),
+                    ComponentItem::Resource(resource) => {
+                        names.resources.push((String::from(name), resource))
+                    }
+                    ComponentItem::ComponentFunc(func) => functions.push((name, func)),
+                    _ => {}
+                }
+            }
+
+            interface.types = names
+                .types
+                .iter()
+                .map(|(name, ty)| format!("{name} = {}", names.define(ty)))
+                .chain(
+                    names
+                        .resources
+                        .iter()
+                        .map(|(name, _)| format!("{name} = resource")),
+                )
+                .collect();
+            interface.functions = functions
+                .into_iter()
+                .map(|(name, func)| format!("{name}: {}", names.func(&func)))
+                .collect();
+        }
+        ComponentItem::ComponentFunc(func) => {
+            interface.kind = "func";
+            interface.functions = vec![format!("{name}: {}", Names::default().func(&func))];
+        }
+        ComponentItem::Type(ty) => {
+            interface.kind = "type";
+            interface.types = vec![format!("{name} = {}", Names::default().define(&ty))];
+        }
+        ComponentItem::Resource(_) => interface.kind = "resource",
+        ComponentItem::Component(_) => interface.kind = "component",
+        ComponentItem::Module(_) => interface.kind = "module",
+        ComponentItem::CoreFunc(_) => interface.kind = "core func",
+    }
+
+    interface
+}
+
+/// The types an interface names, so they can be written by name where
+/// other types and functions use them.
+#[derive(Default)]
+struct Names {
+    types: Vec<(String, Type)>,
+    resources: Vec<(String, ResourceType)>,
+}
+
+impl Names {
+    /// `ty` by its name, or written out if it has none.
+    fn name(&self, ty: &Type) -> String {
+        match self.types.iter().find(|(_, named)| named == ty) {
+            Some((name, _)) => name.clone(),
+            None => self.define(ty),
+        }
+    }
+
+    fn resource(&self, resource: &ResourceType) -> String {
+        self.resources
+            .iter()
+            .find(|(_, named)| named == resource)
+            .map(|(name, _)| name.clone())
+            .unwrap_or_else(|| String::from("resource"))
+    }
+
+    /// `ty` written out, with the types inside it by name.
+    fn define(&self, ty: &Type) -> String {
+        let join = |names: Vec<String>| names.join(", ");
+
+        match ty {
+            Type::Bool => String::from("bool"),
+            Type::S8 => String::from("s8"),
+            Type::U8 => String::from("u8"),
+            Type::S16 => String::from("s16"),
+            Type::U16 => String::from("u16"),
+            Type::S32 => String::from("s32"),
+            Type::U32 => String::from("u32"),
+            Type::S64 => String::from("s64"),
+            Type::U64 => String::from("u64"),
+            Type::Float32 => String::from("f32"),
+            Type::Float64 => String::from("f64"),
+            Type::Char => String::from("char"),
+            Type::String => String::from("string"),
+            Type::ErrorContext => String::from("error-context"),
+            Type::List(list) => format!("list<{}>", self.name(&list.ty())),
+            Type::Option(option) => format!("option<{}>", self.name(&option.ty())),
+            Type::Tuple(tuple) => format!(
+                "tuple<{}>",
+                join(tuple.types().map(|ty| self.name(&ty)).collect())
+            ),
+            Type::Record(record) => format!(
+                "record {{ {} }}",
+                join(
+                    record
+                        .fields()
+                        .map(|field| format!("{}: {}", field.name, self.name(&field.ty)))
+                        .collect()
+                )
+            ),
+            Type::Variant(variant) => format!(
+                "variant {{ {} }}",
+                join(
+                    variant
+                        .cases()
+                        .map(|case| match &case.ty {
+                            Some(ty) => format!("{}({})", case.name, self.name(ty)),
+                            None => String::from(case.name),
+                        })
+                        .collect()
+                )
+            ),
+            Type::Enum(enumeration) => format!(
+                "enum {{ {} }}",
+                join(enumeration.names().map(String::from).collect())
+            ),
+            Type::Flags(flags) => {
+                format!(
+                    "flags {{ {} }}",
+                    join(flags.names().map(String::from).collect())
+                )
+            }
+            Type::Result(result) => match (result.ok(), result.err()) {
+                (None, None) => String::from("result"),
+                (Some(ok), None) => format!("result<{}>", self.name(&ok)),
+                (None, Some(err)) => format!("result<_, {}>", self.name(&err)),
+                (Some(ok), Some(err)) => {
+                    format!("result<{}, {}>", self.name(&ok), self.name(&err))
+                }
+            },
+            Type::Own(resource) => self.resource(resource),
+            Type::Borrow(resource) => format!("borrow<{}>", self.resource(resource)),
+            Type::Future(future) => match future.ty() {
+                Some(ty) => format!("future<{}>", self.name(&ty)),
+                None => String::from("future"),
+            },
+            Type::Stream(stream) => match stream.ty() {
+                Some(ty) => format!("stream<{}>", self.name(&ty)),
+                None => String::from("stream"),
+            },
+        }
+    }
+
+    fn func(&self, func: &ComponentFunc) -> String {
+        let params: Vec<String> = func
+            .params()
+            .map(|(name, ty)| format!("{name}: {}", self.name(&ty)))
+            .collect();
+        let results: Vec<String> = func.results().map(|ty| self.name(&ty)).collect();
+        let keyword = if func.async_() { "async func" } else { "func" };
+
+        match results.as_slice() {
+            [] => format!("{keyword}({})", params.join(", ")),
+            [result] => format!("{keyword}({}) -> {result}", params.join(", ")),
+            _ => format!(
+                "{keyword}({}) -> ({})",
+                params.join(", "),
+                results.join(", ")
+            ),
+        }
+    }
+}
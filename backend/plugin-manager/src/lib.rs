@@ -1,26 +1,55 @@
 //! Plugin manager for discovering and loading WASM component plugins.
 
+mod capabilities;
+mod clock;
 mod error;
+mod host_inputs;
+mod introspection;
+mod limits;
 mod loader;
+mod logging;
+mod package;
+mod plugin_node_definition;
+mod random;
 mod registry;
 mod scanner;
 mod state;
 mod translator;
 
+pub use capabilities::DirectoryGrant;
 pub use error::PluginManagerError;
-pub use loader::Loader;
+pub use host_inputs::{HostInputs, HostMode};
+pub use introspection::{ComponentInterface, ComponentInterfaces};
+pub use limits::{CallLimits, CallUsage};
+pub use loader::{InterruptHandle, Loader, NodeInstance, component_kinds};
+pub use logging::{LoggedValue, PluginLog};
+pub use package::{PACKAGE_EXTENSION, PluginManifest, PluginPackage, parse_manifest};
+pub use plugin_node_definition::PluginNodeDefinition;
 pub use registry::Registry;
 pub use scanner::scan_directory;
 pub use state::State;
 
-use crate::translator::{wit_node_to_proto, wit_type_to_proto};
+use crate::loader::nodes_world::exports::cognexus::plugin::nodes::PortValue as WitPortValue;
+use crate::scanner::WASM_EXTENSION;
+use crate::translator::{
+    node_config_to_wit, port_values_to_wit, wit_node_to_proto, wit_ports_to_model,
+    wit_to_port_values, wit_type_to_proto,
+};
+
+use cognexus_model::graph::{NodeConfig, Port, PortValues};
 
 use common::error::error_location::ErrorLocation;
 
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs;
 use std::panic::Location;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 use log::{debug, info};
+use uuid::Uuid;
+use wasmtime::component::Component;
 
 pub const TYPES_KIND: &str = "types";
 pub const NODES_KIND: &str = "nodes";
@@ -29,6 +58,17 @@ pub const NODES_KIND: &str = "nodes";
 pub struct PluginManager {
     builtin_path: PathBuf,
     loader: Loader,
+    /// Component each discovered node definition came from, keyed by definition id.
+    node_components: HashMap<String, Component>,
+    /// Name of the plugin each discovered node definition came from, keyed
+    /// by definition id.
+    node_plugins: HashMap<String, String>,
+    /// Directories each plugin may access, keyed by plugin name.
+    directory_grants: HashMap<String, Vec<DirectoryGrant>>,
+    /// Component each discovered data type came from, keyed by type id.
+    type_components: HashMap<String, Component>,
+    /// Long-lived instances of stateful nodes, keyed by instance id.
+    shared_instances: Mutex<HashMap<Uuid, Arc<Mutex<NodeInstance>>>>,
 }
 
 impl PluginManager {
@@ -39,6 +79,11 @@ impl PluginManager {
         Ok(Self {
             builtin_path,
             loader,
+            node_components: HashMap::new(),
+            node_plugins: HashMap::new(),
+            directory_grants: HashMap::new(),
+            type_components: HashMap::new(),
+            shared_instances: Mutex::new(HashMap::new()),
         })
     }
 
@@ -46,6 +91,7 @@ impl PluginManager {
     ///
     /// This scans for .wasm files, loads each component, determines its type
     /// by introspecting exports, and calls the appropriate discovery function.
+    /// A plugin is named after its file, without the extension.
     pub fn discover_plugins(&mut self, registry: &Registry) -> Result<(), PluginManagerError> {
         // Scan for .wasm files
         let component_paths = scan_directory(&self.builtin_path)?;
@@ -61,6 +107,10 @@ impl PluginManager {
 
             // Load the component
             let component = self.loader.load_component(&path)?;
+            let plugin_name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
 
             // Determine component type by introspecting its exports
             let kind = self.loader.determine_component_kind(&component)?;
@@ -71,7 +121,9 @@ impl PluginManager {
                     info!("Discovered {} type(s)", types.len());
                     for type_info in types {
                         debug!("  Type: {} ({})", type_info.name, type_info.id);
-                        let type_def = wit_type_to_proto(type_info);
+                        self.type_components
+                            .insert(type_info.id.clone(), component.clone());
+                        let type_def = wit_type_to_proto(type_info)?;
                         registry.register_type(type_def)?;
                     }
                 }
@@ -80,6 +132,10 @@ impl PluginManager {
                     info!("Discovered {} node(s)", nodes.len());
                     for node_info in nodes {
                         debug!("  Node: {} ({})", node_info.name, node_info.id);
+                        self.node_components
+                            .insert(node_info.id.clone(), component.clone());
+                        self.node_plugins
+                            .insert(node_info.id.clone(), plugin_name.clone());
                         let node_def = wit_node_to_proto(node_info);
                         registry.register_node(node_def)?;
                     }
@@ -97,4 +153,473 @@ impl PluginManager {
 
         Ok(())
     }
+
+    /// Install the plugin in the `.cxp` package at `path` into the plugin
+    /// directory as `<name>.wasm`, replacing an installed plugin of that
+    /// name, then reload the plugins into `registry`. Returns the package's
+    /// manifest.
+    ///
+    /// The component is only written once it matches its checksum and is a
+    /// types or nodes plugin. A signature stays in the package as the
+    /// publisher made it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PackageError` if the package can't be read or fails its
+    /// checksum, another error if the component isn't a plugin, and an
+    /// `IoError` if it can't be written.
+    #[track_caller]
+    pub fn install_package(
+        &mut self,
+        path: &Path,
+        registry: &Registry,
+    ) -> Result<PluginManifest, PluginManagerError> {
+        let bytes = fs::read(path).map_err(|e| PluginManagerError::IoError {
+            message: format!("Failed to read package {}: {e}", path.display()),
+            location: ErrorLocation::from(Location::caller()),
+            source: Some(Box::new(e)),
+        })?;
+        let package = PluginPackage::from_bytes(&bytes)?;
+
+        let component = self.loader.compile_component(&package.wasm)?;
+        self.loader.determine_component_kind(&component)?;
+
+        // Written aside and renamed into place, so discovery never sees a
+        // partly written component
+        let installed = self
+            .builtin_path
+            .join(&package.manifest.name)
+            .with_extension(WASM_EXTENSION);
+        let partial = installed.with_extension("part");
+        fs::create_dir_all(&self.builtin_path)
+            .and_then(|_| fs::write(&partial, &package.wasm))
+            .and_then(|_| fs::rename(&partial, &installed))
+            .map_err(|e| PluginManagerError::IoError {
+                message: format!(
+                    "Failed to install plugin '{}' to {}: {e}",
+                    package.manifest.name,
+                    installed.display()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+                source: Some(Box::new(e)),
+            })?;
+
+        info!(
+            "Installed plugin '{}' {} to {}",
+            package.manifest.name,
+            package.manifest.version,
+            installed.display()
+        );
+
+        self.discover_plugins(registry)?;
+        Ok(package.manifest)
+    }
+
+    /// True if a discovered plugin provides the node definition.
+    pub fn provides_node(&self, definition_id: &str) -> bool {
+        self.node_components.contains_key(definition_id)
+    }
+
+    /// Name of the plugin that provides the node definition.
+    pub fn node_plugin(&self, definition_id: &str) -> Option<&str> {
+        self.node_plugins.get(definition_id).map(String::as_str)
+    }
+
+    /// Let the plugin named `plugin` access a host directory when its nodes
+    /// execute, in addition to any directories it was granted before.
+    ///
+    /// Grants apply to executions started afterwards. Shared instances keep
+    /// the directories they were created with until they are released.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the host path isn't a directory.
+    #[track_caller]
+    pub fn grant_directory(
+        &mut self,
+        plugin: &str,
+        grant: DirectoryGrant,
+    ) -> Result<(), PluginManagerError> {
+        if !grant.host_path.is_dir() {
+            return Err(PluginManagerError::IoError {
+                message: format!(
+                    "Cannot grant plugin '{plugin}' access to {}: not a directory",
+                    grant.host_path.display()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            });
+        }
+
+        info!(
+            "Granted plugin '{plugin}' {} access to {} as {}",
+            if grant.writable {
+                "read-write"
+            } else {
+                "read-only"
+            },
+            grant.host_path.display(),
+            grant.guest_path
+        );
+        self.directory_grants
+            .entry(String::from(plugin))
+            .or_default()
+            .push(grant);
+
+        Ok(())
+    }
+
+    /// Directories the plugin named `plugin` may access.
+    pub fn directory_grants(&self, plugin: &str) -> &[DirectoryGrant] {
+        self.directory_grants
+            .get(plugin)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// True if a discovered plugin provides the data type.
+    pub fn provides_type(&self, type_id: &str) -> bool {
+        self.type_components.contains_key(type_id)
+    }
+
+    /// Check that `value` is a well-formed value of a data type, through the
+    /// plugin that provides it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidValueError` if the plugin rejects the value, and
+    /// another error if no plugin provides the type or the plugin traps.
+    #[track_caller]
+    pub fn validate_value(&self, type_id: &str, value: &[u8]) -> Result<(), PluginManagerError> {
+        let component =
+            self.type_components
+                .get(type_id)
+                .ok_or_else(|| PluginManagerError::PluginError {
+                    message: format!("No plugin provides data type {type_id}"),
+                    location: ErrorLocation::from(Location::caller()),
+                    source: None,
+                })?;
+
+        self.loader
+            .validate_value(component, type_id, value)?
+            .map_err(|message| PluginManagerError::InvalidValueError {
+                message: format!("Not a valid value of type {type_id}: {message}"),
+                location: ErrorLocation::from(Location::caller()),
+            })
+    }
+
+    /// The (input ports, output ports) of an instance of a node definition
+    /// with `config`, as resolved by the plugin that provides it. Set them on
+    /// the node with `Graph::set_node_ports` for definitions whose ports
+    /// depend on their configuration; for any other definition they are the
+    /// definition's own.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no plugin provides the definition, the plugin
+    /// traps, or it rejects the configuration.
+    #[track_caller]
+    pub fn resolve_ports(
+        &self,
+        definition_id: &str,
+        config: &NodeConfig,
+    ) -> Result<(Vec<Port>, Vec<Port>), PluginManagerError> {
+        let component = self.node_component(definition_id)?;
+
+        let ports = self
+            .loader
+            .resolve_ports(component, definition_id, &node_config_to_wit(config))?
+            .map_err(|message| PluginManagerError::PluginError {
+                message: format!(
+                    "Node definition {definition_id} rejected its configuration: {message}"
+                ),
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            })?;
+
+        Ok((
+            wit_ports_to_model(ports.input_ports)?,
+            wit_ports_to_model(ports.output_ports)?,
+        ))
+    }
+
+    /// Execute a node definition through the plugin that provides it, with
+    /// the configuration of the instance being executed.
+    ///
+    /// The plugin runs in a fresh instance that is discarded afterwards,
+    /// reads its clocks and randomness as `host` says, and sees only the
+    /// directories it has been granted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no plugin provides the definition, the plugin
+    /// traps (including when interrupted), it exceeds one of `limits`, or
+    /// its execute export reports a failure.
+    #[track_caller]
+    pub fn execute_node(
+        &self,
+        definition_id: &str,
+        config: &NodeConfig,
+        inputs: &PortValues,
+        limits: &CallLimits,
+        host: HostMode,
+    ) -> Result<(PortValues, CallUsage), PluginManagerError> {
+        let component = self.node_component(definition_id)?;
+
+        let (outputs, usage) = self.loader.execute_node(
+            component,
+            definition_id,
+            &node_config_to_wit(config),
+            &port_values_to_wit(inputs),
+            limits,
+            host,
+            self.node_directories(definition_id),
+        )?;
+
+        Ok((node_outputs(definition_id, outputs)?, usage))
+    }
+
+    /// Execute a node definition in the shared instance `instance_id`,
+    /// creating it on first use.
+    ///
+    /// Calls with the same instance id run one at a time in the same
+    /// instance, so the plugin's state carries over between them. Shared
+    /// instances always run live: their host inputs can't be recorded or
+    /// replayed, as their state already depends on earlier calls. An
+    /// instance that traps (including on a timeout or exhausted limit) is
+    /// discarded, and the next call starts a new one.
+    ///
+    /// # Errors
+    ///
+    /// As for [`PluginManager::execute_node`].
+    #[track_caller]
+    pub fn execute_node_shared(
+        &self,
+        instance_id: Uuid,
+        definition_id: &str,
+        config: &NodeConfig,
+        inputs: &PortValues,
+        limits: &CallLimits,
+    ) -> Result<(PortValues, CallUsage), PluginManagerError> {
+        let component = self.node_component(definition_id)?;
+
+        let instance = match self.lock_shared_instances().entry(instance_id) {
+            Entry::Occupied(entry) => Arc::clone(entry.get()),
+            Entry::Vacant(entry) => {
+                let instance = self
+                    .loader
+                    .new_node_instance(self.node_directories(definition_id))?;
+                Arc::clone(entry.insert(Arc::new(Mutex::new(instance))))
+            }
+        };
+        let mut instance = instance.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let outcome = self.loader.execute_node_in(
+            &mut instance,
+            component,
+            definition_id,
+            &node_config_to_wit(config),
+            &port_values_to_wit(inputs),
+            limits,
+        );
+
+        let (outputs, usage) = outcome.inspect_err(|_| {
+            self.release_shared_instance(instance_id);
+        })?;
+
+        Ok((node_outputs(definition_id, outputs)?, usage))
+    }
+
+    /// Drop the shared instance `instance_id`, if there is one. The next
+    /// call using it starts with fresh state.
+    pub fn release_shared_instance(&self, instance_id: Uuid) {
+        self.lock_shared_instances().remove(&instance_id);
+    }
+
+    #[track_caller]
+    fn node_component(&self, definition_id: &str) -> Result<&Component, PluginManagerError> {
+        self.node_components
+            .get(definition_id)
+            .ok_or_else(|| PluginManagerError::PluginError {
+                message: format!("No plugin provides node definition {definition_id}"),
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            })
+    }
+
+    fn node_directories(&self, definition_id: &str) -> &[DirectoryGrant] {
+        self.node_plugin(definition_id)
+            .map(|plugin| self.directory_grants(plugin))
+            .unwrap_or_default()
+    }
+
+    fn lock_shared_instances(&self) -> MutexGuard<'_, HashMap<Uuid, Arc<Mutex<NodeInstance>>>> {
+        self.shared_instances
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// A new handle for interrupting the node executions given it in their
+    /// [`CallLimits`], leaving other executions running.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.loader.interrupt_handle()
+    }
+}
+
+/// Convert a plugin's execute result into port values.
+#[track_caller]
+fn node_outputs(
+    definition_id: &str,
+    outputs: Result<Vec<WitPortValue>, String>,
+) -> Result<PortValues, PluginManagerError> {
+    let outputs = outputs.map_err(|message| PluginManagerError::PluginError {
+        message: format!("Node definition {definition_id} failed: {message}"),
+        location: ErrorLocation::from(Location::caller()),
+        source: None,
+    })?;
+
+    wit_to_port_values(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    const TYPE_ID: &str = "a5b8f1de-0c4e-4f57-9d3a-6e2b7c1d9f40";
+
+    /// A types plugin whose `list-types` returns one type, `Example`, and
+    /// whose `validate` accepts every value.
+    const TYPES_PLUGIN: &str = r#"
+        (component
+            (core module $m
+                (memory (export "memory") 1)
+                (global $next (mut i32) (i32.const 1024))
+                (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                    (local $at i32)
+                    (local.set $at (global.get $next))
+                    (global.set $next (i32.add (global.get $next) (local.get 3)))
+                    (local.get $at))
+                (func (export "list-types") (result i32) (i32.const 0))
+                (func (export "validate") (param i32 i32 i32 i32) (result i32) (i32.const 512))
+                (data (i32.const 0) "\10\00\00\00\01\00\00\00")
+                (data (i32.const 16)
+                    "\40\00\00\00\24\00\00\00" "\64\00\00\00\07\00\00\00"
+                    "\00\00\00\00\00\00\00\00" "\6b\00\00\00\05\00\00\00"
+                    "\00\00\00\00\00\00\00\00")
+                (data (i32.const 64) "a5b8f1de-0c4e-4f57-9d3a-6e2b7c1d9f40" "Example" "1.0.0")
+            )
+            (core instance $i (instantiate $m))
+            (type $field-info (record (field "name" string) (field "type-id" string)))
+            (type $type-info (record
+                (field "id" string)
+                (field "name" string)
+                (field "description" string)
+                (field "version" string)
+                (field "fields" (list $field-info))))
+            (func $list-types (result (list $type-info))
+                (canon lift (core func $i "list-types") (memory $i "memory")
+                    (realloc (func $i "realloc"))))
+            (func $validate
+                (param "type-id" string) (param "value" (list u8))
+                (result (result (error string)))
+                (canon lift (core func $i "validate") (memory $i "memory")
+                    (realloc (func $i "realloc"))))
+            (instance $types
+                (export "field-info" (type $field-info))
+                (export "type-info" (type $type-info))
+                (export "list-types" (func $list-types))
+                (export "validate" (func $validate)))
+            (export "cognexus:plugin/types" (instance $types))
+        )
+    "#;
+
+    const MANIFEST: &str = "name = \"example\"\nversion = \"1.0.0\"\n";
+
+    /// A fresh, empty plugin directory and a `.cxp` file next to it, both
+    /// removed when dropped.
+    struct Scratch {
+        root: PathBuf,
+    }
+
+    impl Scratch {
+        fn new() -> Self {
+            let root = env::temp_dir().join(format!("cognexus-install-{}", Uuid::new_v4()));
+            fs::create_dir_all(&root).unwrap();
+            Self { root }
+        }
+
+        fn plugins(&self) -> PathBuf {
+            self.root.join("plugins")
+        }
+
+        /// Write `package` as `inspect package` does.
+        fn write_package(&self, package: &PluginPackage) -> PathBuf {
+            let path = self.root.join("example").with_extension(PACKAGE_EXTENSION);
+            fs::write(&path, package.to_bytes().unwrap()).unwrap();
+            path
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    fn package() -> PluginPackage {
+        let wasm = wat::parse_str(TYPES_PLUGIN).unwrap();
+        PluginPackage::new(String::from(MANIFEST), wasm, None).unwrap()
+    }
+
+    #[test]
+    fn installs_a_package_and_reloads() {
+        let scratch = Scratch::new();
+        let package = package();
+        let path = scratch.write_package(&package);
+        let registry = Registry::default();
+        let mut manager = PluginManager::new(scratch.plugins()).unwrap();
+
+        let manifest = manager.install_package(&path, &registry).unwrap();
+
+        assert_eq!(manifest, package.manifest);
+        let installed = fs::read(scratch.plugins().join("example.wasm")).unwrap();
+        assert_eq!(installed, package.wasm);
+        assert!(manager.provides_type(TYPE_ID));
+        assert_eq!(registry.get_type(TYPE_ID).unwrap().unwrap().name, "Example");
+    }
+
+    #[test]
+    fn refuses_a_component_that_fails_its_checksum() {
+        let scratch = Scratch::new();
+        let mut package = package();
+        package.checksum = "0".repeat(64);
+        let path = scratch.write_package(&package);
+        let mut manager = PluginManager::new(scratch.plugins()).unwrap();
+
+        let installed = manager.install_package(&path, &Registry::default());
+
+        assert!(matches!(
+            installed,
+            Err(PluginManagerError::PackageError { .. })
+        ));
+        assert!(!scratch.plugins().exists());
+    }
+
+    #[test]
+    fn refuses_a_component_that_is_not_a_plugin() {
+        let scratch = Scratch::new();
+        let wasm = wat::parse_str("(component)").unwrap();
+        let package = PluginPackage::new(String::from(MANIFEST), wasm, None).unwrap();
+        let path = scratch.write_package(&package);
+        let mut manager = PluginManager::new(scratch.plugins()).unwrap();
+
+        assert!(
+            manager
+                .install_package(&path, &Registry::default())
+                .is_err()
+        );
+        assert!(!scratch.plugins().exists());
+    }
 }
@@ -0,0 +1,95 @@
+//! Resource limits for a single plugin call, and what the call consumed.
+
+use crate::host_inputs::HostInputs;
+use crate::loader::InterruptHandle;
+use crate::logging::PluginLog;
+
+use std::time::Duration;
+
+use wasmtime::ResourceLimiter;
+
+/// Limits applied to one plugin call. Unset limits don't apply.
+#[derive(Debug, Clone, Default)]
+pub struct CallLimits {
+    /// Trap the call once it has run this long.
+    pub timeout: Option<Duration>,
+    /// Trap the call once it has consumed this much fuel.
+    pub fuel: Option<u64>,
+    /// Refuse to grow the instance's linear memory beyond this many bytes.
+    pub memory_bytes: Option<usize>,
+    /// Trap the call once this handle is interrupted.
+    pub interrupt: Option<InterruptHandle>,
+}
+
+/// Resources one successful plugin call consumed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallUsage {
+    pub fuel_consumed: u64,
+    /// Largest total linear memory the instance held at once.
+    pub peak_memory_bytes: usize,
+    /// What the call read from the host, when it was made in
+    /// [`HostMode::Record`](crate::HostMode::Record).
+    pub host_inputs: Option<HostInputs>,
+    /// What the plugin logged, in order.
+    pub logs: Vec<PluginLog>,
+    /// How long the plugin asked for the call's outputs to be held back
+    /// after it returned, through the host clock's `defer`.
+    pub deferred: Option<Duration>,
+}
+
+/// Enforces a call's memory limit and records how much memory it used.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryLimiter {
+    limit: Option<usize>,
+    memory_bytes: usize,
+    peak_memory_bytes: usize,
+    exceeded: bool,
+}
+
+impl MemoryLimiter {
+    /// Apply a new limit for the next call, keeping track of the memory the
+    /// instance already holds.
+    pub(crate) fn reset(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+        self.peak_memory_bytes = self.memory_bytes;
+        self.exceeded = false;
+    }
+
+    pub(crate) fn peak_memory_bytes(&self) -> usize {
+        self.peak_memory_bytes
+    }
+
+    /// True if the instance tried to grow past the limit.
+    pub(crate) fn exceeded(&self) -> bool {
+        self.exceeded
+    }
+}
+
+impl ResourceLimiter for MemoryLimiter {
+    fn memory_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        // A component can have several memories; the limit covers them all
+        let total = self.memory_bytes.saturating_sub(current) + desired;
+        if self.limit.is_some_and(|limit| total > limit) {
+            self.exceeded = true;
+            return Ok(false);
+        }
+
+        self.memory_bytes = total;
+        self.peak_memory_bytes = self.peak_memory_bytes.max(total);
+        Ok(true)
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        _desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        Ok(true)
+    }
+}
@@ -1,16 +1,27 @@
 //! Loader for WASM components using wasmtime.
 
 use crate::State;
+use crate::capabilities::DirectoryGrant;
+use crate::clock::{HostClock, Wakeups};
 use crate::error::PluginManagerError;
+use crate::host_inputs::{HostLog, HostMode, host_ctx};
+use crate::introspection::{ComponentInterfaces, describe_component};
+use crate::limits::{CallLimits, CallUsage};
 use crate::{NODES_KIND, TYPES_KIND};
 
 use common::error::error_location::ErrorLocation;
 
+use std::fmt;
 use std::panic::Location;
 use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{RecvTimeoutError, channel};
+use std::thread;
+use std::time::Instant;
 
-use wasmtime::component::{Component, Linker};
-use wasmtime::{Config, Engine, Store};
+use wasmtime::component::{Component, HasSelf, Linker};
+use wasmtime::{Config, Engine, Store, Trap, UpdateDeadline};
 use wasmtime_wasi::p2;
 
 // WIT interface identifiers
@@ -29,24 +40,37 @@ pub mod nodes_world {
     wasmtime::component::bindgen!({
         path: "../../wit",
         world: "nodes-plugin",
+        imports: { default: trappable },
     });
 }
 
 /// Loads and interrogates WASM components.
 pub struct Loader {
     engine: Engine,
+    /// Wakes the calls waiting on the host clock when one of them is
+    /// interrupted.
+    wakeups: Arc<Wakeups>,
 }
 
 impl Loader {
     /// Create a new plugin loader with a configured wasmtime engine.
+    ///
+    /// Epoch interruption is enabled so running plugin code can be stopped
+    /// through an [`InterruptHandle`] or when a call runs out of time, and
+    /// fuel is metered so calls can be given a fuel limit.
     #[track_caller]
     pub fn new() -> Result<Self, PluginManagerError> {
         let mut config = Config::default();
         config.wasm_component_model(true);
+        config.epoch_interruption(true);
+        config.consume_fuel(true);
 
         let engine = Engine::new(&config)?;
 
-        Ok(Self { engine })
+        Ok(Self {
+            engine,
+            wakeups: Arc::new(Wakeups::default()),
+        })
     }
 
     /// Load a component from a file path.
@@ -55,21 +79,185 @@ impl Loader {
         Component::from_file(&self.engine, path).map_err(PluginManagerError::from_wasmtime)
     }
 
-    /// Generic discovery helper that sets up WASI, instantiates a plugin, and calls a discovery function.
-    fn discover<T, F>(&self, call_fn: F) -> Result<T, PluginManagerError>
+    /// Compile a component from the bytes of a `.wasm` file.
+    #[track_caller]
+    pub fn compile_component(&self, wasm: &[u8]) -> Result<Component, PluginManagerError> {
+        Component::from_binary(&self.engine, wasm).map_err(PluginManagerError::from_wasmtime)
+    }
+
+    /// A new handle for interrupting the plugin calls made with it, through
+    /// [`CallLimits::interrupt`], on this loader's engine.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle {
+            engine: self.engine.clone(),
+            wakeups: Arc::clone(&self.wakeups),
+            interrupted: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Generic helper that sets up WASI, instantiates a plugin in a fresh store, and calls into it.
+    ///
+    /// The store, and with it the plugin's memory and WASI state, is dropped
+    /// when the call returns. `host` decides where the plugin's clock
+    /// readings and randomness come from, and the plugin can only reach the
+    /// host filesystem through `directories`.
+    #[track_caller]
+    fn call_plugin<T, F>(
+        &self,
+        limits: &CallLimits,
+        host: HostMode,
+        directories: &[DirectoryGrant],
+        call_fn: F,
+    ) -> Result<(T, CallUsage), PluginManagerError>
     where
         F: FnOnce(&mut Store<State>, &Linker<State>) -> Result<T, wasmtime::Error>,
     {
-        // Create linker with WASI support
+        let linker = self.linker()?;
+        let (mut store, log) = self.new_store(host, directories)?;
+
+        let (value, mut usage) =
+            self.call_in_store(&mut store, limits, |store| call_fn(store, &linker))?;
+        usage.host_inputs = log.map(|log| log.take());
+
+        Ok((value, usage))
+    }
+
+    /// Create a linker with WASI and the host interfaces plugins import.
+    #[track_caller]
+    fn linker(&self) -> Result<Linker<State>, PluginManagerError> {
         let mut linker = Linker::<State>::new(&self.engine);
         p2::add_to_linker_sync(&mut linker)?;
+        nodes_world::NodesPlugin::add_to_linker::<State, HasSelf<State>>(&mut linker, |state| {
+            state
+        })?;
+        Ok(linker)
+    }
+
+    /// Create a store with WASI state holding only `directories`: no other
+    /// preopened directories, environment, or arguments. Returns the log
+    /// host inputs are recorded into when `host` is [`HostMode::Record`].
+    #[track_caller]
+    fn new_store(
+        &self,
+        host: HostMode,
+        directories: &[DirectoryGrant],
+    ) -> Result<(Store<State>, Option<HostLog>), PluginManagerError> {
+        let clock = HostClock::new(matches!(host, HostMode::Replay(_)));
+        let (ctx, random, log) = host_ctx(host, directories)?;
+        let mut store = Store::new(&self.engine, State::new(ctx, clock, random));
+        store.limiter(|state| &mut state.limiter);
+        Ok((store, log))
+    }
+
+    /// Call into a plugin held to `limits`.
+    ///
+    /// With a timeout, a watchdog advances the epoch once it expires, and
+    /// the call traps with a `TimeoutError`, as it does when the timeout
+    /// passes while the plugin waits on the host clock. Running out of fuel or memory
+    /// fails it with a `FuelExhaustedError` or `MemoryLimitError`.
+    #[track_caller]
+    fn call_in_store<T, F>(
+        &self,
+        store: &mut Store<State>,
+        limits: &CallLimits,
+        call_fn: F,
+    ) -> Result<(T, CallUsage), PluginManagerError>
+    where
+        F: FnOnce(&mut Store<State>) -> Result<T, wasmtime::Error>,
+    {
+        store.data_mut().limiter.reset(limits.memory_bytes);
+        store.data_mut().logs.clear();
+
+        let fuel = limits.fuel.unwrap_or(u64::MAX);
+        store.set_fuel(fuel)?;
+
+        let timeout = limits.timeout;
 
-        // Create store with state
-        let state = State::default();
-        let mut store = Store::new(&self.engine, state);
+        let interrupted = limits
+            .interrupt
+            .as_ref()
+            .map_or_else(Arc::default, |handle| Arc::clone(&handle.interrupted));
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let callback_timed_out = Arc::clone(&timed_out);
+
+        // Waits in the host end on the same interrupt flag and time limit
+        store.data_mut().clock.start_call(
+            Arc::clone(&self.wakeups),
+            interrupted,
+            deadline,
+            Arc::clone(&timed_out),
+        );
+
+        // Every epoch tick checks in with the callback, which traps only if
+        // this call's run was interrupted or its own time limit passed
+        store.set_epoch_deadline(1);
+        store.epoch_deadline_callback(move |ctx| {
+            if ctx.data().clock.is_interrupted() {
+                return Ok(UpdateDeadline::Interrupt);
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                callback_timed_out.store(true, Ordering::SeqCst);
+                return Ok(UpdateDeadline::Interrupt);
+            }
+            Ok(UpdateDeadline::Continue(1))
+        });
+
+        // Dropping `finished` when the call returns stops the watchdog early
+        let (finished, watch) = channel::<()>();
+        if let Some(timeout) = timeout {
+            let engine = self.engine.clone();
+            thread::spawn(move || {
+                if let Err(RecvTimeoutError::Timeout) = watch.recv_timeout(timeout) {
+                    engine.increment_epoch();
+                }
+            });
+        }
 
         // Call the provided discovery function with store and linker
-        call_fn(&mut store, &linker).map_err(PluginManagerError::from_wasmtime)
+        let result = call_fn(store);
+        drop(finished);
+
+        match result {
+            Ok(value) => {
+                let usage = CallUsage {
+                    fuel_consumed: fuel - store.get_fuel()?,
+                    peak_memory_bytes: store.data().limiter.peak_memory_bytes(),
+                    host_inputs: None,
+                    logs: std::mem::take(&mut store.data_mut().logs),
+                    deferred: store.data_mut().clock.take_deferred(),
+                };
+                Ok((value, usage))
+            }
+            Err(error) if timed_out.load(Ordering::SeqCst) => {
+                Err(PluginManagerError::TimeoutError {
+                    message: format!(
+                        "Plugin call did not finish within {:?}",
+                        timeout.unwrap_or_default()
+                    ),
+                    location: ErrorLocation::from(Location::caller()),
+                    source: error,
+                })
+            }
+            Err(error) if error.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) => {
+                Err(PluginManagerError::FuelExhaustedError {
+                    message: format!("Plugin call used up its {fuel} unit(s) of fuel"),
+                    location: ErrorLocation::from(Location::caller()),
+                    source: error,
+                })
+            }
+            Err(error) if store.data().limiter.exceeded() => {
+                Err(PluginManagerError::MemoryLimitError {
+                    message: format!(
+                        "Plugin call needed more than {} byte(s) of memory",
+                        limits.memory_bytes.unwrap_or_default()
+                    ),
+                    location: ErrorLocation::from(Location::caller()),
+                    source: error,
+                })
+            }
+            Err(error) => Err(PluginManagerError::from_wasmtime(error)),
+        }
     }
 
     /// Discover data types from a types-plugin component.
@@ -79,10 +267,39 @@ impl Loader {
         component: &Component,
     ) -> Result<Vec<types_world::exports::cognexus::plugin::types::TypeInfo>, PluginManagerError>
     {
-        self.discover(|store, linker| {
-            let plugin = types_world::TypesPlugin::instantiate(&mut *store, component, linker)?;
-            plugin.cognexus_plugin_types().call_list_types(&mut *store)
-        })
+        self.call_plugin(
+            &CallLimits::default(),
+            HostMode::Live,
+            &[],
+            |store, linker| {
+                let plugin = types_world::TypesPlugin::instantiate(&mut *store, component, linker)?;
+                plugin.cognexus_plugin_types().call_list_types(&mut *store)
+            },
+        )
+        .map(|(types, _)| types)
+    }
+
+    /// Check a serialized value against a type exported by a types-plugin
+    /// component. The inner error is why the plugin rejected the value.
+    #[track_caller]
+    pub fn validate_value(
+        &self,
+        component: &Component,
+        type_id: &str,
+        value: &[u8],
+    ) -> Result<Result<(), String>, PluginManagerError> {
+        self.call_plugin(
+            &CallLimits::default(),
+            HostMode::Live,
+            &[],
+            |store, linker| {
+                let plugin = types_world::TypesPlugin::instantiate(&mut *store, component, linker)?;
+                plugin
+                    .cognexus_plugin_types()
+                    .call_validate(&mut *store, type_id, value)
+            },
+        )
+        .map(|(outcome, _)| outcome)
     }
 
     /// Discover nodes from a nodes-plugin component.
@@ -92,9 +309,135 @@ impl Loader {
         component: &Component,
     ) -> Result<Vec<nodes_world::exports::cognexus::plugin::nodes::NodeInfo>, PluginManagerError>
     {
-        self.discover(|store, linker| {
+        self.call_plugin(
+            &CallLimits::default(),
+            HostMode::Live,
+            &[],
+            |store, linker| {
+                let plugin = nodes_world::NodesPlugin::instantiate(&mut *store, component, linker)?;
+                plugin.cognexus_plugin_nodes().call_list_nodes(&mut *store)
+            },
+        )
+        .map(|(nodes, _)| nodes)
+    }
+
+    /// Resolve the ports of an instance of a node definition exported by a
+    /// nodes-plugin component. The inner error is the plugin's reason for
+    /// rejecting the configuration.
+    #[track_caller]
+    pub fn resolve_ports(
+        &self,
+        component: &Component,
+        definition_id: &str,
+        config: &[nodes_world::exports::cognexus::plugin::nodes::ConfigValue],
+    ) -> Result<
+        Result<nodes_world::exports::cognexus::plugin::nodes::InstancePorts, String>,
+        PluginManagerError,
+    > {
+        self.call_plugin(
+            &CallLimits::default(),
+            HostMode::Live,
+            &[],
+            |store, linker| {
+                let plugin = nodes_world::NodesPlugin::instantiate(&mut *store, component, linker)?;
+                plugin.cognexus_plugin_nodes().call_resolve_ports(
+                    &mut *store,
+                    definition_id,
+                    config,
+                )
+            },
+        )
+        .map(|(ports, _)| ports)
+    }
+
+    /// Execute one node definition exported by a nodes-plugin component.
+    ///
+    /// Every call gets its own instance and store, so plugin state never
+    /// leaks between executions and a trap can't affect any other call. The
+    /// outer error covers instantiation failures and traps; the inner one is
+    /// the error message the plugin itself returned. The call is held to
+    /// `limits`, and what it consumed is returned with it, including its
+    /// host inputs when `host` records them. The plugin can reach the host
+    /// filesystem only through `directories`.
+    #[track_caller]
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_node(
+        &self,
+        component: &Component,
+        definition_id: &str,
+        config: &[nodes_world::exports::cognexus::plugin::nodes::ConfigValue],
+        inputs: &[nodes_world::exports::cognexus::plugin::nodes::PortValue],
+        limits: &CallLimits,
+        host: HostMode,
+        directories: &[DirectoryGrant],
+    ) -> Result<
+        (
+            Result<Vec<nodes_world::exports::cognexus::plugin::nodes::PortValue>, String>,
+            CallUsage,
+        ),
+        PluginManagerError,
+    > {
+        self.call_plugin(limits, host, directories, |store, linker| {
             let plugin = nodes_world::NodesPlugin::instantiate(&mut *store, component, linker)?;
-            plugin.cognexus_plugin_nodes().call_list_nodes(&mut *store)
+            plugin
+                .cognexus_plugin_nodes()
+                .call_execute(&mut *store, definition_id, config, inputs)
+        })
+    }
+
+    /// Create an empty instance slot for [`Loader::execute_node_in`], whose
+    /// instance can reach the host filesystem only through `directories`.
+    #[track_caller]
+    pub fn new_node_instance(
+        &self,
+        directories: &[DirectoryGrant],
+    ) -> Result<NodeInstance, PluginManagerError> {
+        Ok(NodeInstance {
+            store: self.new_store(HostMode::Live, directories)?.0,
+            plugin: None,
+        })
+    }
+
+    /// Execute one node definition in a long-lived `instance`, for nodes
+    /// that keep state between executions.
+    ///
+    /// The component is instantiated on the first call and reused after
+    /// that, so its memory persists across calls and counts against each
+    /// call's memory limit. The instance always reads the host's real clocks
+    /// and randomness. Once a call traps, the instance shouldn't be used
+    /// again. Errors are as for [`Loader::execute_node`].
+    #[track_caller]
+    pub fn execute_node_in(
+        &self,
+        instance: &mut NodeInstance,
+        component: &Component,
+        definition_id: &str,
+        config: &[nodes_world::exports::cognexus::plugin::nodes::ConfigValue],
+        inputs: &[nodes_world::exports::cognexus::plugin::nodes::PortValue],
+        limits: &CallLimits,
+    ) -> Result<
+        (
+            Result<Vec<nodes_world::exports::cognexus::plugin::nodes::PortValue>, String>,
+            CallUsage,
+        ),
+        PluginManagerError,
+    > {
+        let linker = self.linker()?;
+        let NodeInstance { store, plugin } = instance;
+
+        self.call_in_store(store, limits, |store| {
+            let plugin = match plugin {
+                Some(plugin) => plugin,
+                None => plugin.insert(nodes_world::NodesPlugin::instantiate(
+                    &mut *store,
+                    component,
+                    &linker,
+                )?),
+            };
+
+            plugin
+                .cognexus_plugin_nodes()
+                .call_execute(&mut *store, definition_id, config, inputs)
         })
     }
 
@@ -102,20 +445,14 @@ impl Loader {
     ///
     /// Returns "types" if it exports the cognexus:plugin/types interface,
     /// "nodes" if it exports cognexus:plugin/nodes, or an error if neither.
+    /// A component exporting both is taken as the first it lists.
     #[track_caller]
     pub fn determine_component_kind(
         &self,
         component: &Component,
     ) -> Result<&'static str, PluginManagerError> {
-        let component_type = component.component_type();
-
-        for (name, _item) in component_type.exports(&self.engine) {
-            if name == TYPES_INTERFACE {
-                return Ok(TYPES_KIND);
-            }
-            if name == NODES_INTERFACE {
-                return Ok(NODES_KIND);
-            }
+        if let Some(kind) = component_kinds(&self.engine, component).first() {
+            return Ok(kind);
         }
 
         Err(PluginManagerError::PluginError {
@@ -126,4 +463,181 @@ impl Loader {
             source: None,
         })
     }
+
+    /// Every interface and function a component imports and exports, to
+    /// see why it isn't the kind of plugin it was meant to be.
+    pub fn component_interfaces(&self, component: &Component) -> ComponentInterfaces {
+        describe_component(&self.engine, component)
+    }
+}
+
+/// Every kind of plugin a component is, by the interfaces it exports, in the
+/// order it lists them: "types", "nodes", both, or neither.
+pub fn component_kinds(engine: &Engine, component: &Component) -> Vec<&'static str> {
+    component
+        .component_type()
+        .exports(engine)
+        .filter_map(|(name, _item)| match name {
+            TYPES_INTERFACE => Some(TYPES_KIND),
+            NODES_INTERFACE => Some(NODES_KIND),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A nodes-plugin instance that outlives a single call, with its own store.
+pub struct NodeInstance {
+    store: Store<State>,
+    plugin: Option<nodes_world::NodesPlugin>,
+}
+
+/// Stops the plugin calls made with it, e.g. the calls of one run.
+///
+/// Interrupting traps the calls given this handle in their
+/// [`CallLimits`] that are in progress, including calls waiting on the
+/// host clock, and any made with it afterwards once they check in. Calls
+/// made without it, on the same engine or not, run on. Clones share the
+/// handle; it is cheap to clone and safe to use from any thread, e.g. from
+/// a cancellation callback.
+#[derive(Clone)]
+pub struct InterruptHandle {
+    engine: Engine,
+    wakeups: Arc<Wakeups>,
+    interrupted: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    pub fn interrupt(&self) {
+        self.interrupted.store(true, Ordering::SeqCst);
+        self.wakeups.wake_all();
+        // Running plugin code only checks its flag on an epoch tick
+        self.engine.increment_epoch();
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::SeqCst)
+    }
+}
+
+impl fmt::Debug for InterruptHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InterruptHandle")
+            .field("interrupted", &self.is_interrupted())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    /// Exports `spin`, which loops until it is stopped, `nap`, which waits
+    /// 300ms on the host clock, and `hold`, which defers its outputs by
+    /// 300ms twice.
+    const SLEEPER: &str = r#"
+        (component
+            (import "cognexus:plugin/clock" (instance $clock
+                (export "sleep" (func (param "nanoseconds" u64)))
+                (export "defer" (func (param "nanoseconds" u64)))
+            ))
+            (core func $sleep (canon lower (func $clock "sleep")))
+            (core func $defer (canon lower (func $clock "defer")))
+            (core module $m
+                (import "clock" "sleep" (func $sleep (param i64)))
+                (import "clock" "defer" (func $defer (param i64)))
+                (func (export "spin") (loop $forever (br $forever)))
+                (func (export "nap") (call $sleep (i64.const 300000000)))
+                (func (export "hold")
+                    (call $defer (i64.const 300000000))
+                    (call $defer (i64.const 300000000)))
+            )
+            (core instance $i (instantiate $m
+                (with "clock" (instance
+                    (export "sleep" (func $sleep))
+                    (export "defer" (func $defer))
+                ))
+            ))
+            (func (export "spin") (canon lift (core func $i "spin")))
+            (func (export "nap") (canon lift (core func $i "nap")))
+            (func (export "hold") (canon lift (core func $i "hold")))
+        )
+    "#;
+
+    fn call(
+        loader: &Loader,
+        component: &Component,
+        export: &str,
+        interrupt: InterruptHandle,
+    ) -> Result<((), CallUsage), PluginManagerError> {
+        let limits = CallLimits {
+            interrupt: Some(interrupt),
+            ..CallLimits::default()
+        };
+
+        loader.call_plugin(&limits, HostMode::Live, &[], |store, linker| {
+            let instance = linker.instantiate(&mut *store, component)?;
+            let export = instance.get_typed_func::<(), ()>(&mut *store, export)?;
+            export.call(&mut *store, ())?;
+            export.post_return(store)
+        })
+    }
+
+    #[test]
+    fn interrupting_one_run_leaves_others_running() {
+        let loader = Loader::new().unwrap();
+        let component = Component::new(&loader.engine, SLEEPER).unwrap();
+        let (cancelled, running) = (loader.interrupt_handle(), loader.interrupt_handle());
+
+        let (spun, napped) = thread::scope(|scope| {
+            let spin = scope.spawn(|| call(&loader, &component, "spin", cancelled.clone()));
+            let nap = scope.spawn(|| {
+                let started = Instant::now();
+                call(&loader, &component, "nap", running.clone()).map(|_| started.elapsed())
+            });
+
+            thread::sleep(Duration::from_millis(50));
+            cancelled.interrupt();
+            (spin.join().unwrap(), nap.join().unwrap())
+        });
+
+        assert!(spun.is_err());
+        assert!(napped.unwrap() >= Duration::from_millis(300));
+        assert!(!running.is_interrupted());
+    }
+
+    #[test]
+    fn interrupting_wakes_a_call_waiting_on_the_host_clock() {
+        let loader = Loader::new().unwrap();
+        let component = Component::new(&loader.engine, SLEEPER).unwrap();
+        let interrupt = loader.interrupt_handle();
+
+        let napped = thread::scope(|scope| {
+            let nap = scope.spawn(|| {
+                let started = Instant::now();
+                let outcome = call(&loader, &component, "nap", interrupt.clone());
+                (outcome, started.elapsed())
+            });
+
+            thread::sleep(Duration::from_millis(20));
+            interrupt.interrupt();
+            nap.join().unwrap()
+        });
+
+        assert!(napped.0.is_err());
+        assert!(napped.1 < Duration::from_millis(300));
+    }
+
+    #[test]
+    fn deferring_returns_at_once_and_reports_the_wait() {
+        let loader = Loader::new().unwrap();
+        let component = Component::new(&loader.engine, SLEEPER).unwrap();
+
+        let started = Instant::now();
+        let (_, usage) = call(&loader, &component, "hold", loader.interrupt_handle()).unwrap();
+
+        assert!(started.elapsed() < Duration::from_millis(300));
+        assert_eq!(usage.deferred, Some(Duration::from_millis(600)));
+    }
 }
@@ -0,0 +1,66 @@
+//! The host side of the logging interface node plugins import.
+
+use crate::State;
+use crate::loader::nodes_world::cognexus::plugin::logging::{self, Level};
+
+use log::log;
+
+/// A message a plugin logged during a call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginLog {
+    pub level: log::Level,
+    pub message: String,
+    /// The value logged with the message, if any.
+    pub value: Option<LoggedValue>,
+}
+
+/// A serialized value a plugin logged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedValue {
+    pub type_id: String,
+    pub bytes: Vec<u8>,
+}
+
+impl State {
+    fn push_log(&mut self, level: Level, message: String, value: Option<LoggedValue>) {
+        let level = match level {
+            Level::Error => log::Level::Error,
+            Level::Warn => log::Level::Warn,
+            Level::Info => log::Level::Info,
+            Level::Debug => log::Level::Debug,
+            Level::Trace => log::Level::Trace,
+        };
+        log!(target: "plugin", level, "{message}");
+
+        self.logs.push(PluginLog {
+            level,
+            message,
+            value,
+        });
+    }
+}
+
+impl logging::Host for State {
+    fn log(&mut self, level: Level, message: String) -> wasmtime::Result<()> {
+        self.push_log(level, message, None);
+        Ok(())
+    }
+
+    fn log_value(
+        &mut self,
+        level: Level,
+        message: String,
+        type_id: String,
+        value: Vec<u8>,
+    ) -> wasmtime::Result<()> {
+        self.push_log(
+            level,
+            message,
+            Some(LoggedValue {
+                type_id,
+                bytes: value,
+            }),
+        );
+        Ok(())
+    }
+}
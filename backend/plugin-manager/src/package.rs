@@ -0,0 +1,328 @@
+//! Plugins bundled into one `.cxp` file, ready to install.
+//!
+//! A package is the magic bytes `CXPK`, a little-endian `u16` format
+//! version, then entries back to back: each a little-endian `u16` length of
+//! its name, the UTF-8 name, a little-endian `u32` length of its data and
+//! the data. Every package has a `manifest.toml`, the `plugin.wasm`
+//! component and `checksum.sha256`, the component's SHA-256 in hex, and may
+//! have a `signature`: a detached signature of the component, kept as the
+//! publisher made it. Entries a reader doesn't know are skipped.
+
+use crate::error::PluginManagerError;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Extension of package files.
+pub const PACKAGE_EXTENSION: &str = "cxp";
+
+const MAGIC: &[u8; 4] = b"CXPK";
+const FORMAT_VERSION: u16 = 1;
+
+const MANIFEST_ENTRY: &str = "manifest.toml";
+const WASM_ENTRY: &str = "plugin.wasm";
+const CHECKSUM_ENTRY: &str = "checksum.sha256";
+const SIGNATURE_ENTRY: &str = "signature";
+
+/// What a package says about the plugin in it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// The plugin's name, which it's installed under as `<name>.wasm`.
+    pub name: String,
+    /// Version of the package, as a semantic version.
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub authors: Vec<String>,
+}
+
+/// A plugin component with its manifest, checksum and signature.
+#[derive(Debug, Clone)]
+pub struct PluginPackage {
+    pub manifest: PluginManifest,
+    /// The manifest as written, comments and all.
+    pub manifest_source: String,
+    pub wasm: Vec<u8>,
+    /// SHA-256 of the component, in hex.
+    pub checksum: String,
+    pub signature: Option<Vec<u8>>,
+}
+
+impl PluginPackage {
+    /// Bundle a component with its manifest, checking that the manifest
+    /// names the plugin and versions it.
+    #[track_caller]
+    pub fn new(
+        manifest_source: String,
+        wasm: Vec<u8>,
+        signature: Option<Vec<u8>>,
+    ) -> Result<Self, PluginManagerError> {
+        let manifest = parse_manifest(&manifest_source)?;
+        let checksum = checksum(&wasm);
+
+        Ok(Self {
+            manifest,
+            manifest_source,
+            wasm,
+            checksum,
+            signature,
+        })
+    }
+
+    /// The package as the bytes of a `.cxp` file.
+    #[track_caller]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PluginManagerError> {
+        let mut bytes = Vec::with_capacity(self.wasm.len() + self.manifest_source.len() + 256);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+
+        write_entry(&mut bytes, MANIFEST_ENTRY, self.manifest_source.as_bytes())?;
+        write_entry(&mut bytes, WASM_ENTRY, &self.wasm)?;
+        write_entry(&mut bytes, CHECKSUM_ENTRY, self.checksum.as_bytes())?;
+        if let Some(signature) = &self.signature {
+            write_entry(&mut bytes, SIGNATURE_ENTRY, signature)?;
+        }
+
+        Ok(bytes)
+    }
+
+    /// Read a package from the bytes of a `.cxp` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PackageError` if the bytes aren't a package this version
+    /// can read, an entry is missing, the manifest is invalid, or the
+    /// component doesn't match its checksum.
+    #[track_caller]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PluginManagerError> {
+        if bytes.len() < MAGIC.len() + 2 || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(package_error("Not a plugin package"));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != FORMAT_VERSION {
+            return Err(package_error(format!(
+                "Package format version {version} is not supported"
+            )));
+        }
+
+        let mut manifest_source = None;
+        let mut wasm = None;
+        let mut checksum_entry = None;
+        let mut signature = None;
+
+        let mut rest = &bytes[MAGIC.len() + 2..];
+        while !rest.is_empty() {
+            let (name, data, remaining) = read_entry(rest)?;
+            match name {
+                MANIFEST_ENTRY => {
+                    manifest_source = Some(String::from_utf8(data.to_vec()).map_err(|_| {
+                        package_error(format!("{MANIFEST_ENTRY} is not valid UTF-8"))
+                    })?)
+                }
+                WASM_ENTRY => wasm = Some(data.to_vec()),
+                CHECKSUM_ENTRY => checksum_entry = Some(String::from_utf8_lossy(data).into_owned()),
+                SIGNATURE_ENTRY => signature = Some(data.to_vec()),
+                _ => {}
+            }
+            rest = remaining;
+        }
+
+        let missing = |entry: &str| package_error(format!("Package has no {entry}"));
+        let manifest_source = manifest_source.ok_or_else(|| missing(MANIFEST_ENTRY))?;
+        let wasm = wasm.ok_or_else(|| missing(WASM_ENTRY))?;
+        let checksum_entry = checksum_entry.ok_or_else(|| missing(CHECKSUM_ENTRY))?;
+
+        let package = Self::new(manifest_source, wasm, signature)?;
+        if !package.checksum.eq_ignore_ascii_case(checksum_entry.trim()) {
+            return Err(package_error(format!(
+                "Component doesn't match its checksum: expected {}, got {}",
+                checksum_entry.trim(),
+                package.checksum
+            )));
+        }
+
+        Ok(package)
+    }
+}
+
+/// Parse and check a package manifest.
+#[track_caller]
+pub fn parse_manifest(source: &str) -> Result<PluginManifest, PluginManagerError> {
+    let manifest: PluginManifest = toml::from_str(source)
+        .map_err(|e| package_error(format!("Invalid {MANIFEST_ENTRY}: {e}")))?;
+
+    // The name becomes a file name when the plugin is installed
+    let valid_name = !manifest.name.is_empty()
+        && manifest
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid_name {
+        return Err(package_error(format!(
+            "Plugin name '{}' must be non-empty and only letters, digits, '_' and '-'",
+            manifest.name
+        )));
+    }
+    if Version::parse(&manifest.version).is_err() {
+        return Err(package_error(format!(
+            "Plugin version '{}' is not a semantic version",
+            manifest.version
+        )));
+    }
+
+    Ok(manifest)
+}
+
+fn checksum(wasm: &[u8]) -> String {
+    hex::encode(Sha256::digest(wasm))
+}
+
+#[track_caller]
+fn write_entry(bytes: &mut Vec<u8>, name: &str, data: &[u8]) -> Result<(), PluginManagerError> {
+    let data_len = u32::try_from(data.len())
+        .map_err(|_| package_error(format!("{name} is too large to package")))?;
+
+    bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(name.as_bytes());
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    bytes.extend_from_slice(data);
+
+    Ok(())
+}
+
+/// The name and data of the entry `bytes` starts with, and the bytes after
+/// it.
+#[track_caller]
+fn read_entry(bytes: &[u8]) -> Result<(&str, &[u8], &[u8]), PluginManagerError> {
+    let truncated = || package_error("Package is truncated");
+
+    let (name_len, rest) = bytes.split_at_checked(2).ok_or_else(truncated)?;
+    let name_len = u16::from_le_bytes([name_len[0], name_len[1]]) as usize;
+    let (name, rest) = rest.split_at_checked(name_len).ok_or_else(truncated)?;
+    let name =
+        std::str::from_utf8(name).map_err(|_| package_error("Package entry name is not UTF-8"))?;
+
+    let (data_len, rest) = rest.split_at_checked(4).ok_or_else(truncated)?;
+    let data_len =
+        u32::from_le_bytes([data_len[0], data_len[1], data_len[2], data_len[3]]) as usize;
+    let (data, rest) = rest.split_at_checked(data_len).ok_or_else(truncated)?;
+
+    Ok((name, data, rest))
+}
+
+#[track_caller]
+fn package_error(message: impl Into<String>) -> PluginManagerError {
+    PluginManagerError::PackageError {
+        message: message.into(),
+        location: ErrorLocation::from(Location::caller()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "name = \"example\"\nversion = \"1.2.0\"\n";
+
+    fn package() -> PluginPackage {
+        PluginPackage::new(String::from(MANIFEST), b"\0asm".to_vec(), Some(vec![7; 4])).unwrap()
+    }
+
+    fn error_message(result: Result<PluginPackage, PluginManagerError>) -> String {
+        match result {
+            Err(PluginManagerError::PackageError { message, .. }) => message,
+            other => panic!("expected a package error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let package = package();
+        let read = PluginPackage::from_bytes(&package.to_bytes().unwrap()).unwrap();
+
+        assert_eq!(read.manifest, package.manifest);
+        assert_eq!(read.manifest_source, MANIFEST);
+        assert_eq!(read.wasm, package.wasm);
+        assert_eq!(read.checksum, package.checksum);
+        assert_eq!(read.signature, package.signature);
+    }
+
+    #[test]
+    fn skips_unknown_entries() {
+        let mut bytes = package().to_bytes().unwrap();
+        write_entry(&mut bytes, "future.entry", b"data").unwrap();
+
+        assert!(PluginPackage::from_bytes(&bytes).is_ok());
+    }
+
+    #[test]
+    fn rejects_other_files_and_versions() {
+        assert_eq!(
+            error_message(PluginPackage::from_bytes(b"")),
+            "Not a plugin package"
+        );
+        assert_eq!(
+            error_message(PluginPackage::from_bytes(b"CXPK\x01")),
+            "Not a plugin package"
+        );
+        assert_eq!(
+            error_message(PluginPackage::from_bytes(b"\0asm\x01\0")),
+            "Not a plugin package"
+        );
+        assert_eq!(
+            error_message(PluginPackage::from_bytes(b"CXPK\x02\0")),
+            "Package format version 2 is not supported"
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_packages() {
+        // Without the optional signature, no cut leaves a complete package
+        let package = PluginPackage::new(String::from(MANIFEST), b"\0asm".to_vec(), None).unwrap();
+        let bytes = package.to_bytes().unwrap();
+
+        for len in MAGIC.len() + 2..bytes.len() {
+            let result = PluginPackage::from_bytes(&bytes[..len]);
+            assert!(result.is_err(), "accepted a package cut at {len} bytes");
+        }
+        assert!(read_entry(&[5]).is_err());
+        assert!(read_entry(&[5, 0, b'a']).is_err());
+        assert!(read_entry(b"\x01\0a\xff\xff\xff\xff").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        assert_eq!(
+            error_message(PluginPackage::from_bytes(b"CXPK\x01\0\x01\0\xff\0\0\0\0")),
+            "Package entry name is not UTF-8"
+        );
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        write_entry(&mut bytes, MANIFEST_ENTRY, MANIFEST.as_bytes()).unwrap();
+        write_entry(&mut bytes, WASM_ENTRY, b"\0asm").unwrap();
+        assert_eq!(
+            error_message(PluginPackage::from_bytes(&bytes)),
+            "Package has no checksum.sha256"
+        );
+
+        write_entry(&mut bytes, CHECKSUM_ENTRY, &[b'0'; 64]).unwrap();
+        assert!(
+            error_message(PluginPackage::from_bytes(&bytes)).starts_with("Component doesn't match")
+        );
+    }
+
+    #[test]
+    fn checks_manifests() {
+        assert!(parse_manifest(MANIFEST).is_ok());
+        assert!(parse_manifest("name = \"../escape\"\nversion = \"1.0.0\"").is_err());
+        assert!(parse_manifest("name = \"example\"\nversion = \"one\"").is_err());
+        assert!(parse_manifest("version = \"1.0.0\"").is_err());
+    }
+}
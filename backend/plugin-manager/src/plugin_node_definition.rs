@@ -0,0 +1,192 @@
+//! Model-side view of a node definition discovered in a plugin.
+
+use crate::error::PluginManagerError;
+
+use cognexus_model::error::ModelError;
+use cognexus_model::graph::{
+    NodeDefinitionInfo, NodeIcon, ParameterConstraint, ParameterSpec, ParameterSpecBuilder, Port,
+    PortBuilder,
+};
+
+use common::error::error_location::ErrorLocation;
+
+use proto::{NodeDefinition, PortSpec, node_icon, parameter_constraint};
+
+use std::panic::Location;
+
+use semver::Version;
+use uuid::Uuid;
+
+/// A plugin node definition usable in a model `NodeDefinitionRegistry`.
+///
+/// The node logic stays inside the plugin component; this only carries the
+/// metadata graphs need to place and wire the node. Run it through
+/// `PluginManager::execute_node`.
+pub struct PluginNodeDefinition {
+    id: Uuid,
+    version: Version,
+    definition: NodeDefinition,
+}
+
+impl PluginNodeDefinition {
+    /// Wrap a discovered definition, validating its id and version.
+    #[track_caller]
+    pub fn new(definition: NodeDefinition) -> Result<Self, PluginManagerError> {
+        let id = Uuid::parse_str(&definition.id).map_err(|e| PluginManagerError::PluginError {
+            message: format!("Node definition has invalid id '{}'", definition.id),
+            location: ErrorLocation::from(Location::caller()),
+            source: Some(Box::new(e)),
+        })?;
+
+        let version =
+            Version::parse(&definition.version).map_err(|e| PluginManagerError::PluginError {
+                message: format!(
+                    "Node definition {} has invalid version '{}'",
+                    definition.id, definition.version
+                ),
+                location: ErrorLocation::from(Location::caller()),
+                source: Some(Box::new(e)),
+            })?;
+
+        Ok(Self {
+            id,
+            version,
+            definition,
+        })
+    }
+}
+
+impl NodeDefinitionInfo for PluginNodeDefinition {
+    fn definition_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    fn description(&self) -> &str {
+        &self.definition.description
+    }
+
+    fn category(&self) -> &str {
+        &self.definition.category
+    }
+
+    fn tags(&self) -> Vec<String> {
+        self.definition.tags.clone()
+    }
+
+    fn icon(&self) -> Option<NodeIcon> {
+        match self.definition.icon.as_ref()?.kind.as_ref()? {
+            node_icon::Kind::Glyph(name) => Some(NodeIcon::Glyph(name.clone())),
+            node_icon::Kind::Svg(svg) => Some(NodeIcon::Svg(svg.clone())),
+        }
+    }
+
+    fn model_version(&self) -> Version {
+        self.version.clone()
+    }
+
+    fn input_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        self.definition
+            .input_ports
+            .iter()
+            .map(spec_to_port)
+            .collect()
+    }
+
+    fn output_port_specs(&self) -> Result<Vec<Port>, ModelError> {
+        self.definition
+            .output_ports
+            .iter()
+            .map(spec_to_port)
+            .collect()
+    }
+
+    fn parameter_specs(&self) -> Result<Vec<ParameterSpec>, ModelError> {
+        self.definition
+            .parameters
+            .iter()
+            .map(proto_to_parameter)
+            .collect()
+    }
+
+    fn is_branching(&self) -> bool {
+        self.definition.branching
+    }
+
+    fn is_merging(&self) -> bool {
+        self.definition.merging
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.definition.deterministic
+    }
+
+    /// Resolving the ports of a configured instance needs the plugin, so
+    /// use `PluginManager::resolve_ports` for definitions that have them;
+    /// this only gives the definition's own ports.
+    fn has_configured_ports(&self) -> bool {
+        self.definition.configured_ports
+    }
+}
+
+#[track_caller]
+pub(crate) fn spec_to_port(spec: &PortSpec) -> Result<Port, ModelError> {
+    let parse = |value: &str, what: &str| {
+        Uuid::parse_str(value).map_err(|e| ModelError::PortError {
+            message: format!("Invalid {what} '{value}': {e}"),
+            port_name: spec.name.clone(),
+            data_type_id: Uuid::nil(),
+            location: ErrorLocation::from(Location::caller()),
+        })
+    };
+
+    PortBuilder::default()
+        .with_id(parse(&spec.id, "port id")?)
+        .with_name(&spec.name)
+        .with_data_type_id(parse(&spec.data_type_id, "data type id")?)
+        .build()
+}
+
+#[track_caller]
+fn proto_to_parameter(parameter: &proto::ParameterSpec) -> Result<ParameterSpec, ModelError> {
+    let data_type_id =
+        Uuid::parse_str(&parameter.data_type_id).map_err(|e| ModelError::ConfigError {
+            message: format!("Invalid data type id '{}': {e}", parameter.data_type_id),
+            parameter: parameter.name.clone(),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+    let mut builder = ParameterSpecBuilder::default()
+        .with_name(&parameter.name)
+        .with_description(&parameter.description)
+        .with_data_type_id(data_type_id)
+        .with_required(parameter.required);
+
+    if let Some(default) = &parameter.default_value {
+        builder = builder.with_default(default.clone());
+    }
+
+    for constraint in &parameter.constraints {
+        let constraint = match &constraint.kind {
+            Some(parameter_constraint::Kind::Minimum(minimum)) => {
+                ParameterConstraint::Minimum(*minimum)
+            }
+            Some(parameter_constraint::Kind::Maximum(maximum)) => {
+                ParameterConstraint::Maximum(*maximum)
+            }
+            Some(parameter_constraint::Kind::OneOf(allowed)) => {
+                ParameterConstraint::OneOf(allowed.values.clone())
+            }
+            Some(parameter_constraint::Kind::MaxLength(max_length)) => {
+                ParameterConstraint::MaxLength(*max_length as usize)
+            }
+            None => continue,
+        };
+        builder = builder.with_constraint(constraint);
+    }
+
+    builder.build()
+}
@@ -0,0 +1,48 @@
+//! The host side of the random interface node plugins import.
+
+use crate::State;
+use crate::loader::nodes_world::cognexus::plugin::random;
+
+use cap_rand::rngs::StdRng;
+use cap_rand::{RngCore, SeedableRng};
+
+/// Largest number of bytes one `random-bytes` call may ask for.
+const MAX_RANDOM_BYTES: u32 = 1024 * 1024;
+
+/// The source a store's host randomness is drawn from. The same seed draws
+/// the same values, which is how a replayed call reproduces a recorded one.
+pub(crate) struct HostRandom {
+    rng: StdRng,
+}
+
+impl HostRandom {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for HostRandom {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl random::Host for State {
+    fn random_bytes(&mut self, len: u32) -> wasmtime::Result<Vec<u8>> {
+        if len > MAX_RANDOM_BYTES {
+            return Err(wasmtime::Error::msg(format!(
+                "Plugin asked for {len} random bytes, more than the limit of {MAX_RANDOM_BYTES}"
+            )));
+        }
+
+        let mut bytes = vec![0; len as usize];
+        self.random.rng.fill_bytes(&mut bytes);
+        Ok(bytes)
+    }
+
+    fn random_u64(&mut self) -> wasmtime::Result<u64> {
+        Ok(self.random.rng.next_u64())
+    }
+}
@@ -8,7 +8,7 @@ use std::fs;
 use std::panic::Location;
 use std::path::{Path, PathBuf};
 
-const WASM_EXTENSION: &str = "wasm";
+pub(crate) const WASM_EXTENSION: &str = "wasm";
 
 /// Scan a directory for .wasm component files.
 ///
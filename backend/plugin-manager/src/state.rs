@@ -1,5 +1,10 @@
 //! WASI state for plugin execution.
 
+use crate::clock::HostClock;
+use crate::limits::MemoryLimiter;
+use crate::logging::PluginLog;
+use crate::random::HostRandom;
+
 use wasmtime::component::ResourceTable;
 use wasmtime_wasi::{WasiCtx, WasiCtxView, WasiView};
 
@@ -7,6 +12,22 @@ use wasmtime_wasi::{WasiCtx, WasiCtxView, WasiView};
 pub struct State {
     ctx: WasiCtx,
     table: ResourceTable,
+    pub(crate) limiter: MemoryLimiter,
+    pub(crate) clock: HostClock,
+    pub(crate) random: HostRandom,
+    /// What the plugin logged during the current call.
+    pub(crate) logs: Vec<PluginLog>,
+}
+
+impl State {
+    pub(crate) fn new(ctx: WasiCtx, clock: HostClock, random: HostRandom) -> Self {
+        Self {
+            ctx,
+            clock,
+            random,
+            ..Self::default()
+        }
+    }
 }
 
 impl WasiView for State {
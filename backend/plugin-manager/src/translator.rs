@@ -1,20 +1,68 @@
 //! Translation layer between WIT plugin interfaces and Protobuf messages.
 
+use crate::error::PluginManagerError;
+use crate::loader::nodes_world::exports::cognexus::plugin::nodes::ParameterConstraint as WitParameterConstraint;
 use crate::loader::{nodes_world, types_world};
+use crate::plugin_node_definition::spec_to_port;
+
+use cognexus_model::graph::{NodeConfig, Port, PortValues};
+
+use common::error::error_location::ErrorLocation;
 
 // Import generated protobuf types
-use proto::{Direction, NodeDefinition, PortSpec, TypeDefinition};
+use proto::{
+    AllowedValues, Direction, NodeDefinition, NodeIcon, ParameterConstraint, ParameterSpec,
+    PortSpec, TypeDefinition, TypeField, node_icon, parameter_constraint,
+};
+
+use std::collections::HashSet;
+use std::panic::Location;
 
-/// Convert WIT TypeInfo to Protobuf TypeDefinition
+use uuid::Uuid;
+
+/// Convert WIT TypeInfo to Protobuf TypeDefinition, rejecting structured
+/// types whose fields can't be validated against
+#[track_caller]
 pub fn wit_type_to_proto(
     wit: types_world::exports::cognexus::plugin::types::TypeInfo,
-) -> TypeDefinition {
-    TypeDefinition {
+) -> Result<TypeDefinition, PluginManagerError> {
+    let mut names = HashSet::new();
+    let mut fields = Vec::with_capacity(wit.fields.len());
+
+    for field in wit.fields {
+        if !names.insert(field.name.clone()) {
+            return Err(PluginManagerError::PluginError {
+                message: format!(
+                    "Type '{}' declares field '{}' more than once",
+                    wit.name, field.name
+                ),
+                location: ErrorLocation::from(Location::caller()),
+                source: None,
+            });
+        }
+
+        Uuid::parse_str(&field.type_id).map_err(|e| PluginManagerError::PluginError {
+            message: format!(
+                "Field '{}' of type '{}' has invalid type id '{}'",
+                field.name, wit.name, field.type_id
+            ),
+            location: ErrorLocation::from(Location::caller()),
+            source: Some(Box::new(e)),
+        })?;
+
+        fields.push(TypeField {
+            name: field.name,
+            type_id: field.type_id,
+        });
+    }
+
+    Ok(TypeDefinition {
         id: wit.id,
         name: wit.name,
         description: wit.description,
         version: wit.version,
-    }
+        fields,
+    })
 }
 
 /// Convert WIT NodeInfo to Protobuf NodeDefinition
@@ -32,9 +80,69 @@ pub fn wit_node_to_proto(
             .into_iter()
             .map(wit_port_to_proto)
             .collect(),
+        parameters: wit
+            .parameters
+            .into_iter()
+            .map(wit_parameter_to_proto)
+            .collect(),
+        branching: wit.branching,
+        merging: wit.merging,
+        configured_ports: wit.configured_ports,
+        deterministic: wit.deterministic,
+        category: wit.category,
+        tags: wit.tags,
+        icon: wit.icon.map(wit_icon_to_proto),
     }
 }
 
+/// Convert WIT ParameterSpec to Protobuf ParameterSpec
+fn wit_parameter_to_proto(
+    wit: nodes_world::exports::cognexus::plugin::nodes::ParameterSpec,
+) -> ParameterSpec {
+    let constraints = wit
+        .constraints
+        .into_iter()
+        .map(|constraint| {
+            let kind = match constraint {
+                WitParameterConstraint::Minimum(minimum) => {
+                    parameter_constraint::Kind::Minimum(minimum)
+                }
+                WitParameterConstraint::Maximum(maximum) => {
+                    parameter_constraint::Kind::Maximum(maximum)
+                }
+                WitParameterConstraint::OneOf(values) => {
+                    parameter_constraint::Kind::OneOf(AllowedValues { values })
+                }
+                WitParameterConstraint::MaxLength(max_length) => {
+                    parameter_constraint::Kind::MaxLength(max_length)
+                }
+            };
+            ParameterConstraint { kind: Some(kind) }
+        })
+        .collect();
+
+    ParameterSpec {
+        name: wit.name,
+        description: wit.description,
+        data_type_id: wit.data_type_id,
+        default_value: wit.default_value,
+        required: wit.required,
+        constraints,
+    }
+}
+
+/// Convert WIT Icon to Protobuf NodeIcon
+fn wit_icon_to_proto(wit: nodes_world::exports::cognexus::plugin::nodes::Icon) -> NodeIcon {
+    let kind = match wit {
+        nodes_world::exports::cognexus::plugin::nodes::Icon::Glyph(name) => {
+            node_icon::Kind::Glyph(name)
+        }
+        nodes_world::exports::cognexus::plugin::nodes::Icon::Svg(svg) => node_icon::Kind::Svg(svg),
+    };
+
+    NodeIcon { kind: Some(kind) }
+}
+
 /// Convert WIT PortSpec to Protobuf PortSpec
 fn wit_port_to_proto(wit: nodes_world::exports::cognexus::plugin::nodes::PortSpec) -> PortSpec {
     PortSpec {
@@ -45,6 +153,23 @@ fn wit_port_to_proto(wit: nodes_world::exports::cognexus::plugin::nodes::PortSpe
     }
 }
 
+/// Convert the WIT port specs a plugin resolved for a node instance to
+/// model ports
+#[track_caller]
+pub fn wit_ports_to_model(
+    wit: Vec<nodes_world::exports::cognexus::plugin::nodes::PortSpec>,
+) -> Result<Vec<Port>, PluginManagerError> {
+    wit.into_iter()
+        .map(|port| {
+            spec_to_port(&wit_port_to_proto(port)).map_err(|e| PluginManagerError::PluginError {
+                message: String::from("Plugin resolved an invalid port"),
+                location: ErrorLocation::from(Location::caller()),
+                source: Some(Box::new(e)),
+            })
+        })
+        .collect()
+}
+
 /// Convert WIT Direction to Protobuf Direction
 fn wit_direction_to_proto(
     wit: nodes_world::exports::cognexus::plugin::nodes::Direction,
@@ -54,3 +179,53 @@ fn wit_direction_to_proto(
         nodes_world::exports::cognexus::plugin::nodes::Direction::Output => Direction::Output,
     }
 }
+
+/// Convert engine port values to the WIT list passed to a plugin's execute
+pub fn port_values_to_wit(
+    values: &PortValues,
+) -> Vec<nodes_world::exports::cognexus::plugin::nodes::PortValue> {
+    values
+        .iter()
+        .map(
+            |(port_id, value)| nodes_world::exports::cognexus::plugin::nodes::PortValue {
+                port_id: port_id.to_string(),
+                value: value.to_vec(),
+            },
+        )
+        .collect()
+}
+
+/// Convert a node instance's configuration to WIT config values
+pub fn node_config_to_wit(
+    config: &NodeConfig,
+) -> Vec<nodes_world::exports::cognexus::plugin::nodes::ConfigValue> {
+    config
+        .iter()
+        .map(
+            |(name, value)| nodes_world::exports::cognexus::plugin::nodes::ConfigValue {
+                name: String::from(name),
+                value: value.to_vec(),
+            },
+        )
+        .collect()
+}
+
+/// Convert the WIT list returned by a plugin's execute to engine port values
+#[track_caller]
+pub fn wit_to_port_values(
+    wit: Vec<nodes_world::exports::cognexus::plugin::nodes::PortValue>,
+) -> Result<PortValues, PluginManagerError> {
+    let mut values = PortValues::new();
+
+    for port_value in wit {
+        let port_id =
+            Uuid::parse_str(&port_value.port_id).map_err(|e| PluginManagerError::PluginError {
+                message: format!("Plugin returned invalid port id '{}'", port_value.port_id),
+                location: ErrorLocation::from(Location::caller()),
+                source: Some(Box::new(e)),
+            })?;
+        values.insert(port_id, port_value.value);
+    }
+
+    Ok(values)
+}
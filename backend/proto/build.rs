@@ -1,8 +1,11 @@
+use std::env;
 use std::error::Error;
 use std::fs;
+use std::path::PathBuf;
 
 const PROTO_DIR: &str = "../../proto/";
 const PROTO_FILE_EXT: &str = "proto";
+const DESCRIPTOR_SET_FILE: &str = "file_descriptor_set.bin";
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Collect all .proto files from the directory
@@ -19,9 +22,14 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let proto_files_refs: Vec<&str> = proto_files_str.iter().map(|s| s.as_str()).collect();
 
-    // Configure prost to derive serde traits
+    let descriptor_set_path = PathBuf::from(env::var("OUT_DIR")?).join(DESCRIPTOR_SET_FILE);
+
+    // Configure prost to derive serde traits, and keep the descriptors of
+    // every message for reflection at runtime
     prost_build::Config::new()
         .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
+        .file_descriptor_set_path(descriptor_set_path)
+        .enable_type_names()
         .compile_protos(&proto_files_refs, &[PROTO_DIR])?;
 
     Ok(())
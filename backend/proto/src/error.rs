@@ -0,0 +1,11 @@
+use common::error::error_location::ErrorLocation;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProtoError {
+    #[error("Framing Error: {message} {location}")]
+    FramingError {
+        message: String,
+        location: ErrorLocation,
+    },
+}
@@ -0,0 +1,255 @@
+//! Commands packed back to back into one byte stream.
+//!
+//! Every frame is a little-endian `u32` length of the message, a
+//! little-endian `u16` tag, the field number of the command in the
+//! `Command` oneof, and the encoded message itself. Frames may be split
+//! across the chunks the stream arrives in.
+
+use crate::error::ProtoError;
+use crate::{Command, command};
+use common::error::error_location::ErrorLocation;
+use prost::Message;
+use prost::encoding::{WireType, encode_key, encode_varint};
+use std::panic::Location as PanicLocation;
+
+/// Bytes ahead of every message: its length, then its tag.
+pub const FRAME_HEADER_LEN: usize = 6;
+/// Longest message a frame may hold, so a corrupt length can't make the
+/// decoder wait on gigabytes that never come.
+pub const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// A command read out of a frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub tag: u16,
+    /// The command, or nothing if the tag is one of a newer schema.
+    pub command: Option<command::Command>,
+}
+
+/// Append `message` to `out` as a frame tagged `tag`.
+#[track_caller]
+pub fn encode_frame(tag: u16, message: &impl Message, out: &mut Vec<u8>) -> Result<(), ProtoError> {
+    let length = message.encoded_len();
+    if length > MAX_FRAME_LEN {
+        return Err(ProtoError::FramingError {
+            message: format!("Message of {length} bytes is over the {MAX_FRAME_LEN} byte limit"),
+            location: ErrorLocation::from(PanicLocation::caller()),
+        });
+    }
+
+    out.reserve(FRAME_HEADER_LEN + length);
+    out.extend_from_slice(&(length as u32).to_le_bytes());
+    out.extend_from_slice(&tag.to_le_bytes());
+    message.encode_raw(out);
+
+    Ok(())
+}
+
+/// Reads frames out of a stream fed to it in chunks of any size, holding
+/// on to the end of one that's incomplete until the rest of it arrives.
+#[derive(Debug, Default)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+    /// Where the next frame starts in the buffer.
+    offset: usize,
+}
+
+impl FrameDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the next chunk of the stream.
+    pub fn push(&mut self, bytes: &[u8]) {
+        // Drop the frames already read before growing the buffer
+        if self.offset > 0 {
+            self.buffer.drain(..self.offset);
+            self.offset = 0;
+        }
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Bytes pushed but not read as frames yet.
+    pub fn pending(&self) -> usize {
+        self.buffer.len() - self.offset
+    }
+
+    /// Forget everything pushed, as after an error, since the stream can't
+    /// be picked up again mid-frame.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.offset = 0;
+    }
+
+    /// The next complete frame, if one has arrived.
+    #[track_caller]
+    pub fn next_frame(&mut self) -> Result<Option<Frame>, ProtoError> {
+        let rest = &self.buffer[self.offset..];
+        if rest.len() < FRAME_HEADER_LEN {
+            return Ok(None);
+        }
+
+        let length = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let tag = u16::from_le_bytes([rest[4], rest[5]]);
+        if length > MAX_FRAME_LEN {
+            return Err(ProtoError::FramingError {
+                message: format!(
+                    "Frame of {length} bytes is over the {MAX_FRAME_LEN} byte limit (tag {tag})"
+                ),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            });
+        }
+        if tag == 0 {
+            return Err(ProtoError::FramingError {
+                message: String::from("Frame has no tag"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            });
+        }
+        if rest.len() < FRAME_HEADER_LEN + length {
+            return Ok(None);
+        }
+
+        let body = &rest[FRAME_HEADER_LEN..FRAME_HEADER_LEN + length];
+        self.offset += FRAME_HEADER_LEN + length;
+
+        // Decode it as the field of a `Command` it would be in a batch, so
+        // tags newer than this schema are skipped like unknown fields
+        let mut field = Vec::with_capacity(length + 8);
+        encode_key(tag as u32, WireType::LengthDelimited, &mut field);
+        encode_varint(length as u64, &mut field);
+        field.extend_from_slice(body);
+
+        let command = Command::decode(field.as_slice()).map_err(|e| ProtoError::FramingError {
+            message: format!("Failed to decode frame with tag {tag}: {e}"),
+            location: ErrorLocation::from(PanicLocation::caller()),
+        })?;
+
+        Ok(Some(Frame {
+            tag,
+            command: command.command,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PanCameraCommand, ZoomCameraCommand};
+
+    const PAN_CAMERA_TAG: u16 = 2;
+    const ZOOM_CAMERA_TAG: u16 = 3;
+
+    fn pan(delta_x: f32) -> PanCameraCommand {
+        PanCameraCommand {
+            delta_x,
+            delta_y: -1.0,
+        }
+    }
+
+    fn stream() -> Vec<u8> {
+        let zoom = ZoomCameraCommand {
+            delta: 2.0,
+            pivot_x: 3.0,
+            pivot_y: 4.0,
+        };
+        let mut bytes = Vec::new();
+        encode_frame(PAN_CAMERA_TAG, &pan(1.0), &mut bytes).unwrap();
+        encode_frame(ZOOM_CAMERA_TAG, &zoom, &mut bytes).unwrap();
+        encode_frame(PAN_CAMERA_TAG, &pan(5.0), &mut bytes).unwrap();
+        bytes
+    }
+
+    fn expected() -> Vec<Option<command::Command>> {
+        vec![
+            Some(command::Command::PanCamera(pan(1.0))),
+            Some(command::Command::ZoomCamera(ZoomCameraCommand {
+                delta: 2.0,
+                pivot_x: 3.0,
+                pivot_y: 4.0,
+            })),
+            Some(command::Command::PanCamera(pan(5.0))),
+        ]
+    }
+
+    fn drain(decoder: &mut FrameDecoder) -> Vec<Option<command::Command>> {
+        let mut commands = Vec::new();
+        while let Some(frame) = decoder.next_frame().unwrap() {
+            commands.push(frame.command);
+        }
+        commands
+    }
+
+    #[test]
+    fn decodes_frames_in_one_chunk() {
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&stream());
+
+        assert_eq!(drain(&mut decoder), expected());
+        assert_eq!(decoder.pending(), 0);
+    }
+
+    #[test]
+    fn decodes_frames_split_across_chunks() {
+        let bytes = stream();
+
+        for chunk_len in 1..bytes.len() {
+            let mut decoder = FrameDecoder::new();
+            let mut commands = Vec::new();
+            for chunk in bytes.chunks(chunk_len) {
+                decoder.push(chunk);
+                commands.extend(drain(&mut decoder));
+            }
+
+            assert_eq!(commands, expected(), "chunks of {chunk_len} bytes");
+            assert_eq!(decoder.pending(), 0);
+        }
+    }
+
+    #[test]
+    fn waits_for_the_rest_of_a_frame() {
+        let bytes = stream();
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&bytes[..FRAME_HEADER_LEN - 1]);
+        assert_eq!(decoder.next_frame().unwrap(), None);
+
+        decoder.push(&bytes[FRAME_HEADER_LEN - 1..FRAME_HEADER_LEN + 1]);
+        assert_eq!(decoder.next_frame().unwrap(), None);
+        assert_eq!(decoder.pending(), FRAME_HEADER_LEN + 1);
+    }
+
+    #[test]
+    fn skips_unknown_tags() {
+        let mut bytes = Vec::new();
+        encode_frame(999, &pan(1.0), &mut bytes).unwrap();
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&bytes);
+
+        let frame = decoder.next_frame().unwrap().unwrap();
+        assert_eq!(frame.tag, 999);
+        assert_eq!(frame.command, None);
+    }
+
+    #[test]
+    fn rejects_bad_headers() {
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&(MAX_FRAME_LEN as u32 + 1).to_le_bytes());
+        decoder.push(&PAN_CAMERA_TAG.to_le_bytes());
+        assert!(decoder.next_frame().is_err());
+
+        decoder.reset();
+        assert_eq!(decoder.pending(), 0);
+        decoder.push(&[0, 0, 0, 0, 0, 0]);
+        assert!(decoder.next_frame().is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_messages() {
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&3u32.to_le_bytes());
+        decoder.push(&PAN_CAMERA_TAG.to_le_bytes());
+        // A fixed32 field cut short
+        decoder.push(&[0x0d, 0, 0]);
+
+        assert!(decoder.next_frame().is_err());
+    }
+}
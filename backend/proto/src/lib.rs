@@ -1,4 +1,22 @@
+pub mod error;
+pub mod framing;
+
+/// Version of the command and event protocol, bumped when a change breaks
+/// hosts or renderers built against the one before.
+pub const PROTOCOL_VERSION: u32 = 1;
+/// Version of the messages within the protocol, bumped when messages or
+/// fields are added. Either side can ignore what the other's newer schema
+/// adds.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Encoded `google.protobuf.FileDescriptorSet` describing every message and
+/// enum of the protocol, for tooling to inspect at runtime.
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/file_descriptor_set.bin"));
+
 // Include the generated protobuf code
 include!(concat!(env!("OUT_DIR"), "/cognexus.commands.rs"));
 include!(concat!(env!("OUT_DIR"), "/cognexus.events.rs"));
+include!(concat!(env!("OUT_DIR"), "/cognexus.execution.rs"));
+include!(concat!(env!("OUT_DIR"), "/cognexus.graph.rs"));
 include!(concat!(env!("OUT_DIR"), "/cognexus.registry.rs"));
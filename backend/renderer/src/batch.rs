@@ -0,0 +1,125 @@
+use crate::error::RendererError;
+use crate::shaders::edge::InstanceRaw as EdgeInstanceRaw;
+use crate::shaders::node::InstanceRaw as NodeInstanceRaw;
+use crate::shaders::port::InstanceRaw as PortInstanceRaw;
+use crate::shaders::quad::InstanceRaw as QuadInstanceRaw;
+use crate::shaders::sprite::InstanceRaw as SpriteInstanceRaw;
+use crate::shaders::text::InstanceRaw as GlyphInstanceRaw;
+use cognexus_model::drawable::{DrawOrder, Layer};
+use std::ops::Range;
+
+/// The instances of each layer: those of `Layer::ALL[i]` are at index `i`.
+pub type LayerRanges = [Range<u32>; Layer::COUNT];
+
+/// Instances placed at a world z, which the depth test stacks them by.
+pub trait Stacked {
+    fn set_z(&mut self, z: f32);
+}
+
+/// The world z of every draw order in the scene, higher for drawables
+/// stacked higher, so the depth test keeps what's stacked on top in front
+/// whatever kind of drawable it is and whenever it's drawn.
+///
+/// Orders are ranked rather than scaled, so any z-indices fit the camera's
+/// depth range, -1 to 1, exclusive at both ends.
+pub struct StackingDepths {
+    orders: Vec<DrawOrder>,
+}
+
+impl StackingDepths {
+    pub fn new(orders: impl IntoIterator<Item = DrawOrder>) -> Self {
+        let mut orders: Vec<DrawOrder> = orders.into_iter().collect();
+        orders.sort_unstable();
+        orders.dedup();
+
+        Self { orders }
+    }
+
+    /// The z of drawables in `order`. Orders that weren't ranked go just
+    /// below the next higher one.
+    pub fn z(&self, order: DrawOrder) -> f32 {
+        let rank = self.orders.binary_search(&order).unwrap_or_else(|i| i);
+
+        -1.0 + 2.0 * (rank + 1) as f32 / (self.orders.len() + 1) as f32
+    }
+}
+
+/// Instances of one kind of drawable, sorted into draw order, so each layer's
+/// are contiguous and can be drawn with one instanced call.
+pub struct LayeredBatch<R> {
+    pub instances: Vec<R>,
+    pub ranges: LayerRanges,
+}
+
+impl<R: Stacked> LayeredBatch<R> {
+    /// Sort `items` by their draw order, keeping the order they were added
+    /// in for ties, and `emit` the instances of each, placed at the depth of
+    /// its order.
+    pub fn build<T>(
+        items: &[T],
+        order: impl Fn(&T) -> DrawOrder,
+        depths: &StackingDepths,
+        mut emit: impl FnMut(&T, &mut Vec<R>) -> Result<(), RendererError>,
+    ) -> Result<Self, RendererError> {
+        let mut sorted: Vec<&T> = items.iter().collect();
+        // Stable, so ties keep their insertion order
+        sorted.sort_by_key(|item| order(item));
+
+        let mut instances = Vec::new();
+        let mut ranges = LayerRanges::default();
+        let mut sorted = sorted.into_iter().peekable();
+
+        for layer in Layer::ALL {
+            let start = instances.len() as u32;
+            while let Some(item) = sorted.next_if(|item| order(item).layer == layer) {
+                let first = instances.len();
+                emit(item, &mut instances)?;
+
+                let z = depths.z(order(item));
+                for instance in &mut instances[first..] {
+                    instance.set_z(z);
+                }
+            }
+            ranges[layer as usize] = start..instances.len() as u32;
+        }
+
+        Ok(Self { instances, ranges })
+    }
+}
+
+impl Stacked for QuadInstanceRaw {
+    fn set_z(&mut self, z: f32) {
+        // The translation's z, which the unit quad's vertices sit at
+        self.model[3][2] = z;
+    }
+}
+
+impl Stacked for EdgeInstanceRaw {
+    fn set_z(&mut self, z: f32) {
+        self.start[2] = z;
+    }
+}
+
+impl Stacked for NodeInstanceRaw {
+    fn set_z(&mut self, z: f32) {
+        self.position[2] = z;
+    }
+}
+
+impl Stacked for PortInstanceRaw {
+    fn set_z(&mut self, z: f32) {
+        self.position[2] = z;
+    }
+}
+
+impl Stacked for SpriteInstanceRaw {
+    fn set_z(&mut self, z: f32) {
+        self.position[2] = z;
+    }
+}
+
+impl Stacked for GlyphInstanceRaw {
+    fn set_z(&mut self, z: f32) {
+        self.position[2] = z;
+    }
+}
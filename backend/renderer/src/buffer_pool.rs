@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wgpu::{Buffer, BufferDescriptor, BufferUsages, Device};
+
+/// Smallest buffer the pool hands out, in bytes.
+const MIN_BUFFER_SIZE: u64 = 256;
+/// Released buffers kept of each usage and size; more are destroyed.
+const MAX_POOLED_PER_CLASS: usize = 4;
+
+/// Buffers no longer in use, kept to be handed out again instead of
+/// allocating new ones, across frames and across the kinds of drawable they
+/// held.
+///
+/// Buffers come in power-of-two sizes, so one released by a shrinking batch
+/// fits a growing one. The pool is a shared handle: clones refer to the same
+/// buffers, so readbacks finishing after the frame can still return theirs.
+#[derive(Clone, Default)]
+pub struct BufferPool {
+    inner: Rc<RefCell<PoolState>>,
+}
+
+#[derive(Default)]
+struct PoolState {
+    free: HashMap<(BufferUsages, u64), Vec<Buffer>>,
+    /// Buffers created since the count was last taken.
+    allocations: u32,
+}
+
+impl BufferPool {
+    /// A buffer of at least `min_size` bytes for `usage`, reused from the
+    /// pool when one of its size is free.
+    pub fn acquire(
+        &self,
+        device: &Device,
+        label: &str,
+        usage: BufferUsages,
+        min_size: u64,
+    ) -> Buffer {
+        let size = size_class(min_size);
+        let mut state = self.inner.borrow_mut();
+
+        if let Some(buffer) = state
+            .free
+            .get_mut(&(usage, size))
+            .and_then(|buffers| buffers.pop())
+        {
+            return buffer;
+        }
+
+        state.allocations += 1;
+        device.create_buffer(&BufferDescriptor {
+            label: Some(label),
+            size,
+            usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Hand `buffer` back to be reused, or destroy it when enough of its
+    /// size are already free. Buffers that didn't come from the pool are
+    /// destroyed.
+    pub fn release(&self, buffer: Buffer) {
+        let size = buffer.size();
+        if size != size_class(size) {
+            buffer.destroy();
+            return;
+        }
+
+        let mut state = self.inner.borrow_mut();
+        let free = state.free.entry((buffer.usage(), size)).or_default();
+        if free.len() < MAX_POOLED_PER_CLASS {
+            free.push(buffer);
+        } else {
+            buffer.destroy();
+        }
+    }
+
+    /// How many buffers were created since the last call.
+    pub fn take_allocations(&self) -> u32 {
+        std::mem::take(&mut self.inner.borrow_mut().allocations)
+    }
+
+    /// Bytes held in free buffers.
+    pub fn pooled_bytes(&self) -> u64 {
+        self.inner
+            .borrow()
+            .free
+            .iter()
+            .map(|((_, size), buffers)| size * buffers.len() as u64)
+            .sum()
+    }
+}
+
+/// The size of the buffers handed out for `min_size` bytes.
+fn size_class(min_size: u64) -> u64 {
+    min_size.max(MIN_BUFFER_SIZE).next_power_of_two()
+}
@@ -0,0 +1,147 @@
+use crate::buffer_pool::BufferPool;
+use crate::error::RendererError;
+use common::error::error_location::ErrorLocation;
+use std::panic::Location as PanicLocation;
+use wgpu::{
+    Buffer, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT, CommandEncoder, Device, Extent3d, MapMode,
+    Origin3d, TexelCopyBufferInfo, TexelCopyBufferLayout, TexelCopyTextureInfo, Texture,
+    TextureAspect, TextureFormat,
+};
+
+/// A frame copied out of a texture into a buffer the CPU can read, waiting
+/// to be encoded as a PNG.
+pub struct FrameReadback {
+    /// Where the buffer goes back to once the frame is read.
+    pool: BufferPool,
+    buffer: Buffer,
+    width: u32,
+    height: u32,
+    /// Bytes per row in the buffer: the row's pixels, padded to the copy
+    /// alignment.
+    padded_bytes_per_row: u32,
+    /// Whether the texture stores blue first, so the channels need swapping
+    /// into RGBA.
+    bgra: bool,
+}
+
+impl FrameReadback {
+    /// Encode a copy of all of `texture` into a readback buffer from the
+    /// pool. Only 8-bit RGBA and BGRA textures can be read back.
+    #[track_caller]
+    pub fn new(
+        device: &Device,
+        pool: &BufferPool,
+        encoder: &mut CommandEncoder,
+        texture: &Texture,
+    ) -> Result<Self, RendererError> {
+        let bgra = match texture.format() {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => false,
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => true,
+            format => {
+                return Err(RendererError::CaptureError {
+                    message: format!("Can't read back a {format:?} texture"),
+                    location: ErrorLocation::from(PanicLocation::caller()),
+                });
+            }
+        };
+
+        let width = texture.width();
+        let height = texture.height();
+        let padded_bytes_per_row =
+            (width * 4).div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT) * COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = pool.acquire(
+            device,
+            "Frame Readback Buffer",
+            BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            padded_bytes_per_row as u64 * height as u64,
+        );
+
+        encoder.copy_texture_to_buffer(
+            TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Ok(Self {
+            pool: pool.clone(),
+            buffer,
+            width,
+            height,
+            padded_bytes_per_row,
+            bgra,
+        })
+    }
+
+    /// Wait for the copy to finish, once its commands are submitted, and
+    /// encode the frame as an 8-bit RGBA PNG.
+    pub async fn into_png(self) -> Result<Vec<u8>, RendererError> {
+        // Pooled buffers can be bigger than the frame
+        let slice = self
+            .buffer
+            .slice(..self.padded_bytes_per_row as u64 * self.height as u64);
+        let (sender, receiver) = futures_channel::oneshot::channel();
+        slice.map_async(MapMode::Read, move |result| {
+            // Nobody is waiting any more if the future was dropped
+            let _ = sender.send(result);
+        });
+
+        receiver
+            .await
+            .map_err(|_| RendererError::CaptureError {
+                message: String::from("The device was lost before the frame was read back"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            })?
+            .map_err(|e| RendererError::CaptureError {
+                message: format!("Failed to map the readback buffer: {e}"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            })?;
+
+        let row_bytes = (self.width * 4) as usize;
+        let mut pixels = Vec::with_capacity(row_bytes * self.height as usize);
+        {
+            let mapped = slice.get_mapped_range();
+            for row in mapped.chunks_exact(self.padded_bytes_per_row as usize) {
+                pixels.extend_from_slice(&row[..row_bytes]);
+            }
+        }
+        self.buffer.unmap();
+        self.pool.release(self.buffer);
+
+        if self.bgra {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(|e| RendererError::CaptureError {
+                message: format!("Failed to encode the frame as PNG: {e}"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            })?;
+
+        Ok(png)
+    }
+}
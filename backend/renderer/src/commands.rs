@@ -1,61 +1,1492 @@
 use crate::error::RendererError;
-use crate::renderer::Renderer;
+use crate::events::RendererEvent;
+use crate::renderer::{DEFAULT_ANIMATION_DURATION, Renderer};
+use crate::stats::FrameStats;
+use crate::theme::Theme;
+use cognexus_model::camera::camera_animation::Easing;
+use cognexus_model::drawable::{DrawOrder, DrawableId, Layer};
+use cognexus_model::geometry::edge::{Edge, EdgeEnd};
+use cognexus_model::geometry::node_widget::NodeWidget;
+use cognexus_model::geometry::port_anchor::{PortAnchor, PortDirection};
 use cognexus_model::geometry::quad::Quad;
+use cognexus_model::geometry::text_label::{TextAlign, TextLabel};
 use common::error::error_location::ErrorLocation;
-use prost::Message;
-use proto::{DrawQuadCommand, PanCameraCommand, ResizeViewportCommand, ZoomCameraCommand};
+use glam::Vec2;
+use prost::{Message, Name};
+use proto::{
+    AnimateCameraCommand, AnimateDrawableCommand, AnimationEasing, BeginMarqueeCommand,
+    CameraState, CaptureFrameCommand, ChangeStackingCommand, ClearSceneCommand, CommandBatch,
+    CommandResult, CommandStatus, Compression, DescribeProtocolCommand, DrawEdgeCommand,
+    DrawNodeCommand, DrawQuadCommand, DrawableClickedEvent, DropPendingEdgeCommand, EdgeEndpoint,
+    EndMarqueeCommand, ErrorEvent, FILE_DESCRIPTOR_SET, FitViewCommand, FrameCapturedEvent,
+    GetCameraStateCommand, GetRenderStatsCommand, HelloAckEvent, HelloCommand, HoverChangedEvent,
+    MarqueeSelectionEvent, NodePort, PROTOCOL_VERSION, PanCameraCommand, PendingEdgeDroppedEvent,
+    PickCommand, PickResultEvent, PointerClickCommand, PointerMoveCommand, PollEventsCommand,
+    PortSide, ProtocolDescriptionEvent, RemoveDrawableCommand, RenderStatsEvent,
+    RendererEventBatch, ResizeViewportCommand, Rgba, SCHEMA_VERSION, SceneSnapshot,
+    SelectionChangedEvent, SetCameraCommand, SetSelectionCommand, SetThemeCommand,
+    SetZIndexCommand, StackingChange, UpdateDrawableColorCommand, UpdateDrawableStateCommand,
+    UpdateDrawableTransformCommand, UpdateMarqueeCommand, UpdatePendingEdgeCommand,
+    UploadIconCommand, ZoomCameraCommand, command, edge_endpoint, renderer_event,
+};
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::io::{self, Read};
+use uuid::Uuid;
+
+/// How nodes from `DrawNodeCommand` are drawn, in world units: the width of
+/// their border, the radius of their corners, the height of their header,
+/// and the diameter of their ports.
+const NODE_BORDER_WIDTH: f32 = 1.5;
+const NODE_CORNER_RADIUS: f32 = 8.0;
+const NODE_HEADER_HEIGHT: f32 = 24.0;
+const NODE_PORT_SIZE: f32 = 10.0;
+/// Width of edges from `DrawEdgeCommand` that don't set one, in world units.
+const DEFAULT_EDGE_WIDTH: f32 = 2.0;
+/// How comments in a `SceneSnapshot` that don't set their own look.
+const DEFAULT_COMMENT_FONT_SIZE: f32 = 16.0;
+const DEFAULT_COMMENT_COLOR: [f32; 4] = [0.75, 0.75, 0.7, 1.0];
+/// Most a compressed command may inflate to, so a small payload can't
+/// exhaust memory.
+const MAX_INFLATED_COMMAND_LEN: usize = 64 * 1024 * 1024;
+
+/// What a handler replies to the host with when its command succeeds.
+pub trait Reply {
+    fn into_payload(self) -> Vec<u8>;
+}
+
+impl Reply for () {
+    fn into_payload(self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+/// An encoded event.
+impl Reply for Vec<u8> {
+    fn into_payload(self) -> Vec<u8> {
+        self
+    }
+}
+
+/// Encode what a handler returned for the command the host sent as
+/// `request_id` as a `CommandResult`: its reply if it succeeded, otherwise
+/// the error it failed with.
+pub fn command_result<T: Reply>(request_id: u32, result: Result<T, RendererError>) -> Vec<u8> {
+    let result = match result {
+        Ok(reply) => CommandResult {
+            request_id,
+            status: CommandStatus::Ok as i32,
+            error: None,
+            payload: reply.into_payload(),
+        },
+        Err(e) => CommandResult {
+            request_id,
+            status: CommandStatus::Failed as i32,
+            error: Some(ErrorEvent {
+                message: e.to_string(),
+                error_code: String::from(e.code()),
+            }),
+            payload: Vec::new(),
+        },
+    };
+
+    result.encode_to_vec()
+}
+
+/// Returns an encoded `ProtocolDescriptionEvent` listing the commands this
+/// build handles, with the descriptors of the whole protocol. Like the
+/// hello, it's answered whatever protocol the host speaks.
+pub fn handle_describe_protocol(
+    renderer: &Renderer,
+    bytes: &[u8],
+) -> Result<Vec<u8>, RendererError> {
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    decode_command::<DescribeProtocolCommand>(bytes)?;
+
+    let event = ProtocolDescriptionEvent {
+        protocol_version: PROTOCOL_VERSION,
+        schema_version: SCHEMA_VERSION,
+        commands: handled_commands(),
+        file_descriptor_set: FILE_DESCRIPTOR_SET.to_vec(),
+    };
+
+    Ok(event.encode_to_vec())
+}
+
+/// Note the versions the host speaks and return an encoded `HelloAckEvent`
+/// with the renderer's. Hosts of another protocol version are turned away:
+/// every command after is refused. Schema versions may differ, since each
+/// side ignores what it doesn't know of the other's.
+pub fn handle_hello(renderer: &mut Renderer, bytes: &[u8]) -> Result<Vec<u8>, RendererError> {
+    let command = decode_command::<HelloCommand>(bytes)?;
+
+    renderer.set_host_protocol(command.protocol_version, command.schema_version);
+
+    let accepted = command.protocol_version == PROTOCOL_VERSION;
+    // The first the host prefers that the renderer can undo
+    let compression = command
+        .compressions()
+        .find(|compression| *compression == Compression::Zstd)
+        .filter(|_| accepted)
+        .unwrap_or(Compression::None);
+    renderer.set_command_compression(compression);
+
+    let event = HelloAckEvent {
+        protocol_version: PROTOCOL_VERSION,
+        schema_version: SCHEMA_VERSION,
+        accepted,
+        message: if accepted {
+            String::new()
+        } else {
+            protocol_mismatch(command.protocol_version)
+        },
+        compression: compression as u32,
+    };
+
+    Ok(event.encode_to_vec())
+}
 
 pub fn handle_draw_quad(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
-    let command = DrawQuadCommand::decode(bytes).map_err(|e| RendererError::CommandError {
-        message: format!("Failed to decode DrawQuadCommand: {e}"),
-        location: ErrorLocation::from(std::panic::Location::caller()),
-    })?;
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<DrawQuadCommand>(bytes)?;
+
+    apply_draw_quad(renderer, command)?;
+    renderer.render()?;
 
+    Ok(())
+}
+
+fn apply_draw_quad(renderer: &mut Renderer, command: DrawQuadCommand) -> Result<(), RendererError> {
     let quad = Quad {
         position: [command.x, command.y, command.z],
         size: [command.width, command.height],
         color: [command.r, command.g, command.b, command.a],
+        order: DrawOrder::default(),
     };
 
     renderer.add_quad(quad);
+
+    Ok(())
+}
+
+pub fn handle_draw_node(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<DrawNodeCommand>(bytes)?;
+
+    apply_draw_node(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_draw_node(renderer: &mut Renderer, command: DrawNodeCommand) -> Result<(), RendererError> {
+    let (widget, ports) = node_drawables(renderer.theme(), &command)?;
+    renderer.draw_node(widget, &command.title, &ports);
+
+    Ok(())
+}
+
+/// The node widget and port anchors `command` describes, with the colors it
+/// leaves unset taken from `theme`.
+fn node_drawables(
+    theme: &Theme,
+    command: &DrawNodeCommand,
+) -> Result<(NodeWidget, Vec<PortAnchor>), RendererError> {
+    let id = parse_drawable_id(&command.id)?;
+    let color = |rgba: Option<Rgba>, default: [f32; 4]| {
+        rgba.map_or(default, |rgba| [rgba.r, rgba.g, rgba.b, rgba.a])
+    };
+
+    let widget = NodeWidget {
+        id,
+        position: [command.x, command.y, 0.0],
+        size: [command.width, command.height],
+        fill_color: color(command.fill_color, theme.node_fill),
+        border_color: color(command.border_color, theme.node_border),
+        header_color: color(command.header_color, theme.node_header),
+        border_width: NODE_BORDER_WIDTH,
+        corner_radius: NODE_CORNER_RADIUS,
+        header_height: NODE_HEADER_HEIGHT,
+        selected: command.selected,
+        hovered: false,
+        order: DrawOrder::new(Layer::Nodes, command.z_index),
+    };
+
+    let sides = command
+        .ports
+        .iter()
+        .map(port_direction)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let count = |direction: PortDirection| sides.iter().filter(|&&d| d == direction).count();
+    let (inputs, outputs) = (count(PortDirection::Input), count(PortDirection::Output));
+    let mut indices = (0, 0);
+
+    let mut ports = Vec::with_capacity(command.ports.len());
+    for (port, &direction) in command.ports.iter().zip(&sides) {
+        let (index, count) = match direction {
+            PortDirection::Input => (&mut indices.0, inputs),
+            PortDirection::Output => (&mut indices.1, outputs),
+        };
+        let position = widget.port_anchor_position(direction, *index, count);
+        *index += 1;
+
+        // Ports of no known type are drawn like edges
+        let color =
+            Uuid::parse_str(&port.data_type_id).map_or(theme.edge, PortAnchor::data_type_color);
+
+        ports.push(PortAnchor {
+            id: parse_drawable_id(&port.id)?,
+            node: Some(id),
+            position,
+            size: [NODE_PORT_SIZE, NODE_PORT_SIZE],
+            color,
+            direction,
+            connected: port.connected,
+            hovered: false,
+            order: widget.order,
+        });
+    }
+
+    Ok((widget, ports))
+}
+
+pub fn handle_draw_edge(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<DrawEdgeCommand>(bytes)?;
+
+    apply_draw_edge(renderer, command)?;
     renderer.render()?;
 
     Ok(())
 }
 
+fn apply_draw_edge(renderer: &mut Renderer, command: DrawEdgeCommand) -> Result<(), RendererError> {
+    let edge = edge_drawable(renderer.theme(), &command)?;
+    renderer.draw_edge(edge);
+
+    Ok(())
+}
+
+/// The edge `command` describes, in the theme's color unless it sets one.
+fn edge_drawable(theme: &Theme, command: &DrawEdgeCommand) -> Result<Edge, RendererError> {
+    Ok(Edge {
+        id: parse_drawable_id(&command.id)?,
+        source: edge_end(command.source.as_ref(), "source")?,
+        target: edge_end(command.target.as_ref(), "target")?,
+        width: if command.width > 0.0 {
+            command.width
+        } else {
+            DEFAULT_EDGE_WIDTH
+        },
+        color: command
+            .color
+            .as_ref()
+            .map_or(theme.edge, |rgba| [rgba.r, rgba.g, rgba.b, rgba.a]),
+        dashed: command.dashed,
+        order: DrawOrder::new(Layer::Edges, command.z_index),
+    })
+}
+
+/// Replace the whole scene with the snapshot's, and move the camera to its
+/// camera state. The snapshot is checked in full first, so the scene is
+/// left as it was if any of it is invalid.
+pub fn handle_scene_snapshot(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let snapshot = decode_command::<SceneSnapshot>(bytes)?;
+
+    let theme = renderer.theme();
+    let nodes = snapshot
+        .nodes
+        .iter()
+        .map(|node| node_drawables(theme, node))
+        .collect::<Result<Vec<_>, _>>()?;
+    let edges = snapshot
+        .edges
+        .iter()
+        .map(|edge| edge_drawable(theme, edge))
+        .collect::<Result<Vec<_>, _>>()?;
+    let comments: Vec<_> = snapshot
+        .comments
+        .into_iter()
+        .map(|comment| TextLabel {
+            text: comment.text,
+            position: [comment.x, comment.y, 0.0],
+            size: if comment.font_size > 0.0 {
+                comment.font_size
+            } else {
+                DEFAULT_COMMENT_FONT_SIZE
+            },
+            color: comment.color.map_or(DEFAULT_COMMENT_COLOR, |rgba| {
+                [rgba.r, rgba.g, rgba.b, rgba.a]
+            }),
+            align: TextAlign::Left,
+            order: DrawOrder::new(Layer::Background, 0),
+        })
+        .collect();
+    let camera = snapshot
+        .camera
+        .map(|camera| check_camera_view(camera.x, camera.y, camera.zoom).map(|()| camera))
+        .transpose()?;
+
+    renderer.clear_scene();
+    for ((widget, ports), node) in nodes.into_iter().zip(&snapshot.nodes) {
+        renderer.draw_node(widget, &node.title, &ports);
+    }
+    for edge in edges {
+        renderer.draw_edge(edge);
+    }
+    for comment in comments {
+        renderer.add_text_label(comment);
+    }
+    if let Some(camera) = camera {
+        renderer.set_camera(camera.x, camera.y, camera.zoom);
+    }
+    renderer.render()?;
+
+    Ok(())
+}
+
+pub fn handle_update_drawable_transform(
+    renderer: &mut Renderer,
+    bytes: &[u8],
+) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<UpdateDrawableTransformCommand>(bytes)?;
+
+    apply_update_drawable_transform(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_update_drawable_transform(
+    renderer: &mut Renderer,
+    command: UpdateDrawableTransformCommand,
+) -> Result<(), RendererError> {
+    let id = parse_drawable_id(&command.drawable_id)?;
+    let size = (command.width > 0.0 && command.height > 0.0)
+        .then(|| Vec2::new(command.width, command.height));
+
+    if !renderer.set_drawable_transform(id, Vec2::new(command.x, command.y), size) {
+        return Err(no_drawable(id));
+    }
+
+    Ok(())
+}
+
+pub fn handle_update_drawable_color(
+    renderer: &mut Renderer,
+    bytes: &[u8],
+) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<UpdateDrawableColorCommand>(bytes)?;
+
+    apply_update_drawable_color(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_update_drawable_color(
+    renderer: &mut Renderer,
+    command: UpdateDrawableColorCommand,
+) -> Result<(), RendererError> {
+    let id = parse_drawable_id(&command.drawable_id)?;
+    let color = |rgba: Option<Rgba>| rgba.map(|rgba| [rgba.r, rgba.g, rgba.b, rgba.a]);
+
+    if !renderer.set_drawable_color(
+        id,
+        color(command.color),
+        color(command.border_color),
+        color(command.header_color),
+    ) {
+        return Err(no_drawable(id));
+    }
+
+    Ok(())
+}
+
+pub fn handle_update_drawable_state(
+    renderer: &mut Renderer,
+    bytes: &[u8],
+) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<UpdateDrawableStateCommand>(bytes)?;
+
+    apply_update_drawable_state(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_update_drawable_state(
+    renderer: &mut Renderer,
+    command: UpdateDrawableStateCommand,
+) -> Result<(), RendererError> {
+    let id = parse_drawable_id(&command.drawable_id)?;
+    if !renderer.set_drawable_state(id, command.selected, command.connected) {
+        return Err(no_drawable(id));
+    }
+
+    Ok(())
+}
+
+pub fn handle_set_selection(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<SetSelectionCommand>(bytes)?;
+
+    apply_set_selection(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_set_selection(
+    renderer: &mut Renderer,
+    command: SetSelectionCommand,
+) -> Result<(), RendererError> {
+    let ids = command
+        .drawable_ids
+        .iter()
+        .map(|id| parse_drawable_id(id))
+        .collect::<Result<Vec<_>, _>>()?;
+    renderer.set_selection(&ids).map_err(no_node_widget)
+}
+
+pub fn handle_remove_drawable(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<RemoveDrawableCommand>(bytes)?;
+
+    apply_remove_drawable(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_remove_drawable(
+    renderer: &mut Renderer,
+    command: RemoveDrawableCommand,
+) -> Result<(), RendererError> {
+    let id = parse_drawable_id(&command.drawable_id)?;
+    if !renderer.remove_drawable(id) {
+        return Err(no_drawable(id));
+    }
+
+    Ok(())
+}
+
+pub fn handle_clear_scene(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<ClearSceneCommand>(bytes)?;
+
+    apply_clear_scene(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_clear_scene(
+    renderer: &mut Renderer,
+    _command: ClearSceneCommand,
+) -> Result<(), RendererError> {
+    renderer.clear_scene();
+
+    Ok(())
+}
+
 pub fn handle_pan_camera(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
-    let command = PanCameraCommand::decode(bytes).map_err(|e| RendererError::CommandError {
-        message: format!("Failed to decode PanCameraCommand: {}", e),
-        location: ErrorLocation::from(std::panic::Location::caller()),
-    })?;
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
 
-    renderer.pan_camera(command.delta_x, command.delta_y);
+    let command = decode_command::<PanCameraCommand>(bytes)?;
+
+    apply_pan_camera(renderer, command)?;
     renderer.render()?;
 
     Ok(())
 }
 
+fn apply_pan_camera(
+    renderer: &mut Renderer,
+    command: PanCameraCommand,
+) -> Result<(), RendererError> {
+    renderer.pan_camera(command.delta_x, command.delta_y);
+
+    Ok(())
+}
+
 pub fn handle_zoom_camera(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
-    let command = ZoomCameraCommand::decode(bytes).map_err(|e| RendererError::CommandError {
-        message: format!("Failed to decode ZoomCameraCommand: {}", e),
-        location: ErrorLocation::from(std::panic::Location::caller()),
-    })?;
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<ZoomCameraCommand>(bytes)?;
+
+    apply_zoom_camera(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
 
+fn apply_zoom_camera(
+    renderer: &mut Renderer,
+    command: ZoomCameraCommand,
+) -> Result<(), RendererError> {
     renderer.zoom_camera(command.delta, command.pivot_x, command.pivot_y);
+
+    Ok(())
+}
+
+/// Returns an encoded `CameraState` with where the camera is now.
+pub fn handle_get_camera_state(
+    renderer: &Renderer,
+    bytes: &[u8],
+) -> Result<Vec<u8>, RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    decode_command::<GetCameraStateCommand>(bytes)?;
+
+    let (position, zoom) = renderer.camera_view();
+    let state = CameraState {
+        x: position.x,
+        y: position.y,
+        zoom,
+    };
+
+    Ok(state.encode_to_vec())
+}
+
+pub fn handle_set_camera(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<SetCameraCommand>(bytes)?;
+
+    apply_set_camera(renderer, command)?;
     renderer.render()?;
 
     Ok(())
 }
 
+fn apply_set_camera(
+    renderer: &mut Renderer,
+    command: SetCameraCommand,
+) -> Result<(), RendererError> {
+    check_camera_view(command.x, command.y, command.zoom)?;
+    renderer.set_camera(command.x, command.y, command.zoom);
+
+    Ok(())
+}
+
+pub fn handle_animate_camera(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<AnimateCameraCommand>(bytes)?;
+
+    apply_animate_camera(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_animate_camera(
+    renderer: &mut Renderer,
+    command: AnimateCameraCommand,
+) -> Result<(), RendererError> {
+    check_camera_view(command.x, command.y, command.zoom)?;
+    let duration = animation_duration(command.duration)?;
+    renderer.animate_camera(
+        command.x,
+        command.y,
+        command.zoom,
+        duration,
+        easing(command.easing()),
+    );
+
+    Ok(())
+}
+
+pub fn handle_animate_drawable(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<AnimateDrawableCommand>(bytes)?;
+
+    apply_animate_drawable(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_animate_drawable(
+    renderer: &mut Renderer,
+    command: AnimateDrawableCommand,
+) -> Result<(), RendererError> {
+    let id = parse_drawable_id(&command.drawable_id)?;
+    let duration = animation_duration(command.duration)?;
+    let size = (command.width > 0.0 && command.height > 0.0)
+        .then(|| Vec2::new(command.width, command.height));
+
+    let easing = easing(command.easing());
+    if !renderer.animate_drawable(id, Vec2::new(command.x, command.y), size, duration, easing) {
+        return Err(no_drawable(id));
+    }
+
+    Ok(())
+}
+
 pub fn handle_resize_viewport(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
-    let command =
-        ResizeViewportCommand::decode(bytes).map_err(|e| RendererError::CommandError {
-            message: format!("Failed to decode ResizeViewportCommand: {}", e),
-            location: ErrorLocation::from(std::panic::Location::caller()),
-        })?;
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<ResizeViewportCommand>(bytes)?;
 
-    renderer.resize_viewport(command.width, command.height);
+    apply_resize_viewport(renderer, command)?;
     renderer.render()?;
 
     Ok(())
 }
+
+fn apply_resize_viewport(
+    renderer: &mut Renderer,
+    command: ResizeViewportCommand,
+) -> Result<(), RendererError> {
+    // Hosts that don't send a pixel ratio keep the one the canvas has
+    if command.dpr > 0.0 {
+        renderer.resize_viewport(command.width, command.height, command.dpr)?;
+    } else {
+        renderer.resize(command.width, command.height);
+    }
+
+    Ok(())
+}
+
+pub fn handle_fit_view(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<FitViewCommand>(bytes)?;
+
+    apply_fit_view(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_fit_view(renderer: &mut Renderer, command: FitViewCommand) -> Result<(), RendererError> {
+    renderer.fit_view(command.padding);
+
+    Ok(())
+}
+
+pub fn handle_upload_icon(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<UploadIconCommand>(bytes)?;
+
+    apply_upload_icon(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_upload_icon(
+    renderer: &mut Renderer,
+    command: UploadIconCommand,
+) -> Result<(), RendererError> {
+    renderer.upload_icon(&command.name, command.width, command.height, &command.rgba)?;
+
+    Ok(())
+}
+
+/// Replace the colors the command sets in the current theme.
+pub fn handle_set_theme(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<SetThemeCommand>(bytes)?;
+
+    apply_set_theme(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_set_theme(renderer: &mut Renderer, command: SetThemeCommand) -> Result<(), RendererError> {
+    let current = renderer.theme();
+    let color = |rgba: Option<Rgba>, current: [f32; 4]| {
+        rgba.map_or(current, |rgba| [rgba.r, rgba.g, rgba.b, rgba.a])
+    };
+
+    let theme = Theme {
+        background: color(command.background, current.background),
+        grid_minor: color(command.grid_minor, current.grid_minor),
+        grid_major: color(command.grid_major, current.grid_major),
+        node_fill: color(command.node_fill, current.node_fill),
+        node_border: color(command.node_border, current.node_border),
+        node_header: color(command.node_header, current.node_header),
+        edge: color(command.edge, current.edge),
+    };
+
+    renderer.set_theme(theme);
+
+    Ok(())
+}
+
+pub fn handle_get_render_stats(
+    renderer: &Renderer,
+    bytes: &[u8],
+) -> Result<Vec<u8>, RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    decode_command::<GetRenderStatsCommand>(bytes)?;
+
+    Ok(render_stats_event(&renderer.stats()).encode_to_vec())
+}
+
+pub fn handle_change_stacking(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<ChangeStackingCommand>(bytes)?;
+
+    apply_change_stacking(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_change_stacking(
+    renderer: &mut Renderer,
+    command: ChangeStackingCommand,
+) -> Result<(), RendererError> {
+    let id = parse_drawable_id(&command.drawable_id)?;
+    let restacked = match StackingChange::try_from(command.change) {
+        Ok(StackingChange::BringToFront) => renderer.bring_to_front(id),
+        Ok(StackingChange::SendToBack) => renderer.send_to_back(id),
+        _ => {
+            return Err(RendererError::CommandError {
+                message: format!("Unknown stacking change: {}", command.change),
+                location: ErrorLocation::from(std::panic::Location::caller()),
+            });
+        }
+    };
+
+    if !restacked {
+        return Err(no_node_widget(id));
+    }
+
+    Ok(())
+}
+
+pub fn handle_set_z_index(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<SetZIndexCommand>(bytes)?;
+
+    apply_set_z_index(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_set_z_index(
+    renderer: &mut Renderer,
+    command: SetZIndexCommand,
+) -> Result<(), RendererError> {
+    let id = parse_drawable_id(&command.drawable_id)?;
+    if !renderer.set_z_index(id, command.z_index) {
+        return Err(no_node_widget(id));
+    }
+
+    Ok(())
+}
+
+pub fn handle_begin_marquee(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<BeginMarqueeCommand>(bytes)?;
+
+    apply_begin_marquee(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_begin_marquee(
+    renderer: &mut Renderer,
+    command: BeginMarqueeCommand,
+) -> Result<(), RendererError> {
+    renderer.begin_marquee(command.x, command.y);
+
+    Ok(())
+}
+
+pub fn handle_update_marquee(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<UpdateMarqueeCommand>(bytes)?;
+
+    apply_update_marquee(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_update_marquee(
+    renderer: &mut Renderer,
+    command: UpdateMarqueeCommand,
+) -> Result<(), RendererError> {
+    renderer.update_marquee(command.x, command.y);
+
+    Ok(())
+}
+
+/// Remove the selection rectangle and return an encoded
+/// `MarqueeSelectionEvent` listing the drawables inside it.
+pub fn handle_end_marquee(renderer: &mut Renderer, bytes: &[u8]) -> Result<Vec<u8>, RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    decode_command::<EndMarqueeCommand>(bytes)?;
+
+    let selected = renderer.end_marquee();
+    renderer.render()?;
+
+    let event = MarqueeSelectionEvent {
+        drawable_ids: selected.iter().map(|id| id.to_string()).collect(),
+    };
+
+    Ok(event.encode_to_vec())
+}
+
+pub fn handle_update_pending_edge(
+    renderer: &mut Renderer,
+    bytes: &[u8],
+) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<UpdatePendingEdgeCommand>(bytes)?;
+
+    apply_update_pending_edge(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_update_pending_edge(
+    renderer: &mut Renderer,
+    command: UpdatePendingEdgeCommand,
+) -> Result<(), RendererError> {
+    let source = parse_drawable_id(&command.source_port_id)?;
+    let compatible = command
+        .compatible_port_ids
+        .iter()
+        .map(|id| parse_drawable_id(id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    renderer.update_pending_edge(source, command.pointer_x, command.pointer_y, &compatible);
+
+    Ok(())
+}
+
+/// Remove the connection being dragged and return an encoded
+/// `PendingEdgeDroppedEvent` naming the port it was dropped on.
+pub fn handle_drop_pending_edge(
+    renderer: &mut Renderer,
+    bytes: &[u8],
+) -> Result<Vec<u8>, RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    decode_command::<DropPendingEdgeCommand>(bytes)?;
+
+    let target = renderer.drop_pending_edge();
+    renderer.render()?;
+
+    let event = PendingEdgeDroppedEvent {
+        target_port_id: target.map(|id| id.to_string()).unwrap_or_default(),
+    };
+
+    Ok(event.encode_to_vec())
+}
+
+pub fn handle_pick(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<PickCommand>(bytes)?;
+
+    apply_pick(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_pick(renderer: &mut Renderer, command: PickCommand) -> Result<(), RendererError> {
+    renderer.request_pick(command.x, command.y);
+
+    Ok(())
+}
+
+pub fn handle_pointer_move(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<PointerMoveCommand>(bytes)?;
+
+    apply_pointer_move(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_pointer_move(
+    renderer: &mut Renderer,
+    command: PointerMoveCommand,
+) -> Result<(), RendererError> {
+    renderer.pointer_moved(command.x, command.y);
+
+    Ok(())
+}
+
+pub fn handle_pointer_click(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let command = decode_command::<PointerClickCommand>(bytes)?;
+
+    apply_pointer_click(renderer, command)?;
+    renderer.render()?;
+
+    Ok(())
+}
+
+fn apply_pointer_click(
+    renderer: &mut Renderer,
+    command: PointerClickCommand,
+) -> Result<(), RendererError> {
+    renderer.pointer_clicked(command.x, command.y);
+
+    Ok(())
+}
+
+/// Return an encoded `RendererEventBatch` of every event queued since the
+/// last poll, oldest first.
+pub fn handle_poll_events(renderer: &mut Renderer, bytes: &[u8]) -> Result<Vec<u8>, RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    decode_command::<PollEventsCommand>(bytes)?;
+
+    let id = |drawable: Option<DrawableId>| drawable.map(|id| id.to_string()).unwrap_or_default();
+    let events = renderer
+        .take_events()
+        .into_iter()
+        .map(|event| {
+            let event = match event {
+                RendererEvent::Picked { drawable, world } => {
+                    renderer_event::Event::PickResult(PickResultEvent {
+                        drawable_id: id(drawable),
+                        world_x: world.x,
+                        world_y: world.y,
+                    })
+                }
+                RendererEvent::Clicked { drawable, world } => {
+                    renderer_event::Event::DrawableClicked(DrawableClickedEvent {
+                        drawable_id: id(drawable),
+                        world_x: world.x,
+                        world_y: world.y,
+                    })
+                }
+                RendererEvent::HoverChanged { previous, current } => {
+                    renderer_event::Event::HoverChanged(HoverChangedEvent {
+                        previous_drawable_id: id(previous),
+                        drawable_id: id(current),
+                    })
+                }
+                RendererEvent::FrameStats(stats) => {
+                    renderer_event::Event::FrameStats(render_stats_event(&stats))
+                }
+                RendererEvent::SelectionChanged(selection) => {
+                    renderer_event::Event::SelectionChanged(SelectionChangedEvent {
+                        drawable_ids: selection.iter().map(|id| id.to_string()).collect(),
+                    })
+                }
+            };
+
+            proto::RendererEvent { event: Some(event) }
+        })
+        .collect();
+
+    Ok(RendererEventBatch { events }.encode_to_vec())
+}
+
+/// Apply every command in the batch, in order, and render once, after the
+/// last. Stops at the first command that fails, leaving the ones before it
+/// applied.
+pub fn handle_batch(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    let batch = decode_command::<CommandBatch>(bytes)?;
+
+    for (index, command) in batch.commands.into_iter().enumerate() {
+        let Some(command) = command.command else {
+            return Err(RendererError::CommandError {
+                message: format!("Command {index} of the batch is empty"),
+                location: ErrorLocation::from(std::panic::Location::caller()),
+            });
+        };
+
+        apply_command(renderer, command)?;
+    }
+    renderer.render()?;
+
+    Ok(())
+}
+
+/// Apply every command framed in `bytes`, in order, and render once, after
+/// the last. A frame cut short at the end waits for the rest of it in the
+/// next call. Stops at the first command that fails, leaving the ones before
+/// it applied and dropping what's left of the stream. Commands with tags
+/// newer than this schema are skipped.
+pub fn handle_frames(renderer: &mut Renderer, bytes: &[u8]) -> Result<(), RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    renderer.frame_decoder_mut().push(bytes);
+
+    let mut applied = 0;
+    loop {
+        let frame = match renderer.frame_decoder_mut().next_frame() {
+            Ok(Some(frame)) => frame,
+            Ok(None) => break,
+            Err(e) => {
+                renderer.frame_decoder_mut().reset();
+                return Err(RendererError::CommandError {
+                    message: format!("Failed to read command frames: {e}"),
+                    location: ErrorLocation::from(std::panic::Location::caller()),
+                });
+            }
+        };
+
+        if let Some(command) = frame.command
+            && let Err(e) = apply_command(renderer, command)
+        {
+            renderer.frame_decoder_mut().reset();
+            return Err(e);
+        }
+        applied += 1;
+    }
+
+    if applied > 0 {
+        renderer.render()?;
+    }
+
+    Ok(())
+}
+
+fn apply_command(renderer: &mut Renderer, command: command::Command) -> Result<(), RendererError> {
+    match command {
+        command::Command::DrawQuad(command) => apply_draw_quad(renderer, command),
+        command::Command::PanCamera(command) => apply_pan_camera(renderer, command),
+        command::Command::ZoomCamera(command) => apply_zoom_camera(renderer, command),
+        command::Command::ResizeViewport(command) => apply_resize_viewport(renderer, command),
+        command::Command::SetTheme(command) => apply_set_theme(renderer, command),
+        command::Command::FitView(command) => apply_fit_view(renderer, command),
+        command::Command::UploadIcon(command) => apply_upload_icon(renderer, command),
+        command::Command::BeginMarquee(command) => apply_begin_marquee(renderer, command),
+        command::Command::UpdateMarquee(command) => apply_update_marquee(renderer, command),
+        command::Command::UpdatePendingEdge(command) => {
+            apply_update_pending_edge(renderer, command)
+        }
+        command::Command::ChangeStacking(command) => apply_change_stacking(renderer, command),
+        command::Command::SetZIndex(command) => apply_set_z_index(renderer, command),
+        command::Command::Pick(command) => apply_pick(renderer, command),
+        command::Command::PointerMove(command) => apply_pointer_move(renderer, command),
+        command::Command::PointerClick(command) => apply_pointer_click(renderer, command),
+        command::Command::DrawNode(command) => apply_draw_node(renderer, command),
+        command::Command::DrawEdge(command) => apply_draw_edge(renderer, command),
+        command::Command::RemoveDrawable(command) => apply_remove_drawable(renderer, command),
+        command::Command::ClearScene(command) => apply_clear_scene(renderer, command),
+        command::Command::UpdateDrawableTransform(command) => {
+            apply_update_drawable_transform(renderer, command)
+        }
+        command::Command::UpdateDrawableColor(command) => {
+            apply_update_drawable_color(renderer, command)
+        }
+        command::Command::SetCamera(command) => apply_set_camera(renderer, command),
+        command::Command::SetSelection(command) => apply_set_selection(renderer, command),
+        command::Command::AnimateCamera(command) => apply_animate_camera(renderer, command),
+        command::Command::AnimateDrawable(command) => apply_animate_drawable(renderer, command),
+        command::Command::UpdateDrawableState(command) => {
+            apply_update_drawable_state(renderer, command)
+        }
+    }
+}
+
+/// Render a frame offscreen and resolve to an encoded `FrameCapturedEvent`
+/// holding it as a PNG.
+pub fn handle_capture_frame(
+    renderer: &mut Renderer,
+    bytes: &[u8],
+) -> Result<impl Future<Output = Result<Vec<u8>, RendererError>> + 'static, RendererError> {
+    check_protocol(renderer)?;
+    let bytes = &*command_bytes(renderer, bytes)?;
+
+    decode_command::<CaptureFrameCommand>(bytes)?;
+
+    let (width, height) = renderer.size();
+    let png = renderer.capture_frame()?;
+
+    Ok(async move {
+        let event = FrameCapturedEvent {
+            png: png.await?,
+            width,
+            height,
+        };
+
+        Ok(event.encode_to_vec())
+    })
+}
+
+fn render_stats_event(stats: &FrameStats) -> RenderStatsEvent {
+    RenderStatsEvent {
+        frame_time_ms: stats.frame_time_ms,
+        frame_interval_ms: stats.frame_interval_ms,
+        draw_calls: stats.draw_calls,
+        quad_instances: stats.quad_instances,
+        node_instances: stats.node_instances,
+        glyph_instances: stats.glyph_instances,
+        instance_buffer_bytes: stats.instance_buffer_bytes,
+        sprite_instances: stats.sprite_instances,
+        port_instances: stats.port_instances,
+        edge_instances: stats.edge_instances,
+        buffer_allocations: stats.buffer_allocations,
+        pooled_buffer_bytes: stats.pooled_buffer_bytes,
+    }
+}
+
+/// Refuse commands from a host that said hello with another protocol
+/// version. Hosts that never did are trusted to speak this one.
+#[track_caller]
+fn check_protocol(renderer: &Renderer) -> Result<(), RendererError> {
+    match renderer.host_protocol() {
+        Some((version, _)) if version != PROTOCOL_VERSION => Err(RendererError::CommandError {
+            message: protocol_mismatch(version),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn protocol_mismatch(host_version: u32) -> String {
+    format!("The host speaks protocol version {host_version}, the renderer {PROTOCOL_VERSION}")
+}
+
+#[track_caller]
+fn port_direction(port: &NodePort) -> Result<PortDirection, RendererError> {
+    match PortSide::try_from(port.side) {
+        Ok(PortSide::Input) => Ok(PortDirection::Input),
+        Ok(PortSide::Output) => Ok(PortDirection::Output),
+        _ => Err(RendererError::CommandError {
+            message: format!("Port {} has no side: {}", port.id, port.side),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        }),
+    }
+}
+
+/// The `which` end of an edge, "source" or "target".
+#[track_caller]
+fn edge_end(endpoint: Option<&EdgeEndpoint>, which: &str) -> Result<EdgeEnd, RendererError> {
+    match endpoint.and_then(|endpoint| endpoint.endpoint.as_ref()) {
+        Some(edge_endpoint::Endpoint::PortId(id)) => Ok(EdgeEnd::Port(parse_drawable_id(id)?)),
+        Some(edge_endpoint::Endpoint::Point(point)) => {
+            Ok(EdgeEnd::Point(Vec2::new(point.x, point.y)))
+        }
+        None => Err(RendererError::CommandError {
+            message: format!("Edge has no {which}"),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        }),
+    }
+}
+
+/// Full names of every message the renderer has a handler for, in the order
+/// of the handlers.
+fn handled_commands() -> Vec<String> {
+    vec![
+        HelloCommand::full_name(),
+        DescribeProtocolCommand::full_name(),
+        DrawQuadCommand::full_name(),
+        DrawNodeCommand::full_name(),
+        DrawEdgeCommand::full_name(),
+        UpdateDrawableTransformCommand::full_name(),
+        UpdateDrawableColorCommand::full_name(),
+        UpdateDrawableStateCommand::full_name(),
+        SceneSnapshot::full_name(),
+        SetSelectionCommand::full_name(),
+        RemoveDrawableCommand::full_name(),
+        ClearSceneCommand::full_name(),
+        PanCameraCommand::full_name(),
+        ZoomCameraCommand::full_name(),
+        GetCameraStateCommand::full_name(),
+        SetCameraCommand::full_name(),
+        AnimateCameraCommand::full_name(),
+        AnimateDrawableCommand::full_name(),
+        ResizeViewportCommand::full_name(),
+        FitViewCommand::full_name(),
+        UploadIconCommand::full_name(),
+        SetThemeCommand::full_name(),
+        GetRenderStatsCommand::full_name(),
+        UpdatePendingEdgeCommand::full_name(),
+        DropPendingEdgeCommand::full_name(),
+        BeginMarqueeCommand::full_name(),
+        UpdateMarqueeCommand::full_name(),
+        EndMarqueeCommand::full_name(),
+        ChangeStackingCommand::full_name(),
+        SetZIndexCommand::full_name(),
+        PickCommand::full_name(),
+        PointerMoveCommand::full_name(),
+        PointerClickCommand::full_name(),
+        PollEventsCommand::full_name(),
+        CommandBatch::full_name(),
+        CaptureFrameCommand::full_name(),
+    ]
+}
+
+/// A `CommandResult` as JSON, to read replies in the browser's console. The
+/// payload stays encoded, as an array of bytes.
+#[cfg(feature = "debug-json")]
+pub fn command_result_json(bytes: &[u8]) -> Result<String, RendererError> {
+    let result = CommandResult::decode(bytes).map_err(|e| RendererError::CommandError {
+        message: format!("Failed to decode CommandResult: {e}"),
+        location: ErrorLocation::from(std::panic::Location::caller()),
+    })?;
+
+    serde_json::to_string_pretty(&result).map_err(|e| RendererError::CommandError {
+        message: format!("Failed to encode CommandResult as JSON: {e}"),
+        location: ErrorLocation::from(std::panic::Location::caller()),
+    })
+}
+
+/// Decode a command from its protobuf encoding or, in builds with the
+/// `debug-json` feature, from JSON, for messages written by hand in the
+/// browser's console. JSON is told apart by its opening brace, which never
+/// starts an encoded message.
+#[track_caller]
+fn decode_command<T: Message + Default + Name + DeserializeOwned>(
+    bytes: &[u8],
+) -> Result<T, RendererError> {
+    #[cfg(feature = "debug-json")]
+    if bytes.first() == Some(&b'{') {
+        return serde_json::from_slice(bytes).map_err(|e| RendererError::CommandError {
+            message: format!("Failed to decode {} from JSON: {e}", T::NAME),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        });
+    }
+
+    T::decode(bytes).map_err(|e| RendererError::CommandError {
+        message: format!("Failed to decode {}: {e}", T::NAME),
+        location: ErrorLocation::from(std::panic::Location::caller()),
+    })
+}
+
+/// The bytes of a command, inflated if the host compressed them. Once the
+/// hello agrees on a compression, every command starts with a byte saying
+/// whether the rest is compressed with it: the `Compression` it used, or
+/// none for small commands not worth it.
+#[track_caller]
+fn command_bytes<'a>(renderer: &Renderer, bytes: &'a [u8]) -> Result<Cow<'a, [u8]>, RendererError> {
+    decompress_command(renderer.command_compression(), bytes)
+}
+
+/// The bytes of a command sent after the hello agreed on `agreed`.
+#[track_caller]
+fn decompress_command(agreed: Compression, bytes: &[u8]) -> Result<Cow<'_, [u8]>, RendererError> {
+    if agreed == Compression::None {
+        return Ok(Cow::Borrowed(bytes));
+    }
+
+    let Some((&flag, rest)) = bytes.split_first() else {
+        return Err(RendererError::CommandError {
+            message: String::from("Command is missing its compression byte"),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        });
+    };
+
+    if flag == Compression::None as u8 {
+        Ok(Cow::Borrowed(rest))
+    } else if flag == agreed as u8 {
+        inflate(rest).map(Cow::Owned)
+    } else {
+        Err(RendererError::CommandError {
+            message: format!("Command is compressed with {flag}, not the agreed {agreed:?}"),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        })
+    }
+}
+
+/// Decompress a zstd frame, refusing one that would inflate past
+/// `MAX_INFLATED_COMMAND_LEN`.
+#[track_caller]
+fn inflate(compressed: &[u8]) -> Result<Vec<u8>, RendererError> {
+    let failed = |e: io::Error| RendererError::CommandError {
+        message: format!("Failed to inflate command: {e}"),
+        location: ErrorLocation::from(std::panic::Location::caller()),
+    };
+
+    let mut inflated = Vec::new();
+    zstd::stream::read::Decoder::with_buffer(compressed)
+        .map_err(failed)?
+        .take(MAX_INFLATED_COMMAND_LEN as u64 + 1)
+        .read_to_end(&mut inflated)
+        .map_err(failed)?;
+
+    if inflated.len() > MAX_INFLATED_COMMAND_LEN {
+        return Err(RendererError::CommandError {
+            message: format!("Command inflates past {MAX_INFLATED_COMMAND_LEN} bytes"),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        });
+    }
+
+    Ok(inflated)
+}
+
+/// An animation's duration in seconds, the default for 0.
+#[track_caller]
+fn animation_duration(duration: f32) -> Result<f32, RendererError> {
+    if !duration.is_finite() || duration < 0.0 {
+        return Err(RendererError::CommandError {
+            message: format!("Invalid animation duration: {duration}"),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        });
+    }
+
+    Ok(if duration > 0.0 {
+        duration
+    } else {
+        DEFAULT_ANIMATION_DURATION
+    })
+}
+
+fn easing(easing: AnimationEasing) -> Easing {
+    match easing {
+        AnimationEasing::Linear => Easing::Linear,
+        AnimationEasing::EaseOut => Easing::EaseOut,
+        AnimationEasing::Unspecified | AnimationEasing::EaseInOut => Easing::EaseInOut,
+    }
+}
+
+/// Check a camera position and zoom are numbers the camera can take.
+#[track_caller]
+fn check_camera_view(x: f32, y: f32, zoom: f32) -> Result<(), RendererError> {
+    if !x.is_finite() || !y.is_finite() || !zoom.is_finite() || zoom <= 0.0 {
+        return Err(RendererError::CommandError {
+            message: format!("Invalid camera view: ({x}, {y}) at zoom {zoom}"),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        });
+    }
+
+    Ok(())
+}
+
+#[track_caller]
+fn parse_drawable_id(id: &str) -> Result<DrawableId, RendererError> {
+    Uuid::parse_str(id)
+        .map(DrawableId)
+        .map_err(|e| RendererError::CommandError {
+            message: format!("Invalid drawable id '{id}': {e}"),
+            location: ErrorLocation::from(std::panic::Location::caller()),
+        })
+}
+
+#[track_caller]
+fn no_node_widget(id: DrawableId) -> RendererError {
+    RendererError::CommandError {
+        message: format!("No node widget with id {id}"),
+        location: ErrorLocation::from(std::panic::Location::caller()),
+    }
+}
+
+#[track_caller]
+fn no_drawable(id: DrawableId) -> RendererError {
+    RendererError::CommandError {
+        message: format!("No drawable with id {id}"),
+        location: ErrorLocation::from(std::panic::Location::caller()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compressed(bytes: &[u8]) -> Vec<u8> {
+        let mut command = vec![Compression::Zstd as u8];
+        command.extend(zstd::bulk::compress(bytes, 1).unwrap());
+        command
+    }
+
+    #[test]
+    fn passes_commands_through_without_compression() {
+        let bytes = decompress_command(Compression::None, &[1, 2, 3]).unwrap();
+        assert!(matches!(bytes, Cow::Borrowed(&[1, 2, 3])));
+    }
+
+    #[test]
+    fn reads_the_compression_byte() {
+        let bytes =
+            decompress_command(Compression::Zstd, &[Compression::None as u8, 4, 5]).unwrap();
+        assert!(matches!(bytes, Cow::Borrowed(&[4, 5])));
+
+        let command = compressed(b"draw a node");
+        let bytes = decompress_command(Compression::Zstd, &command).unwrap();
+        assert_eq!(&*bytes, b"draw a node");
+    }
+
+    #[test]
+    fn rejects_bad_compression_bytes() {
+        assert!(decompress_command(Compression::Zstd, &[]).is_err());
+        assert!(decompress_command(Compression::Zstd, &[7, 1, 2]).is_err());
+        assert!(decompress_command(Compression::Zstd, &[Compression::Zstd as u8, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn limits_how_far_commands_inflate() {
+        let at_limit = compressed(&vec![0; MAX_INFLATED_COMMAND_LEN]);
+        let bytes = decompress_command(Compression::Zstd, &at_limit).unwrap();
+        assert_eq!(bytes.len(), MAX_INFLATED_COMMAND_LEN);
+
+        let over_limit = compressed(&vec![0; MAX_INFLATED_COMMAND_LEN + 1]);
+        assert!(decompress_command(Compression::Zstd, &over_limit).is_err());
+    }
+}
@@ -15,6 +15,44 @@ pub enum RendererError {
         message: String,
         location: ErrorLocation,
     },
+
+    #[error("Configuration Error: {message} {location}")]
+    ConfigurationError {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("Text Error: {message} {location}")]
+    TextError {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("Atlas Error: {message} {location}")]
+    AtlasError {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("Capture Error: {message} {location}")]
+    CaptureError {
+        message: String,
+        location: ErrorLocation,
+    },
+}
+
+impl RendererError {
+    /// A short name for the kind of error, for hosts to match on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RendererError::WgpuError { .. } => "WGPU_ERROR",
+            RendererError::CommandError { .. } => "COMMAND_ERROR",
+            RendererError::ConfigurationError { .. } => "CONFIGURATION_ERROR",
+            RendererError::TextError { .. } => "TEXT_ERROR",
+            RendererError::AtlasError { .. } => "ATLAS_ERROR",
+            RendererError::CaptureError { .. } => "CAPTURE_ERROR",
+        }
+    }
 }
 
 impl From<RendererError> for JsValue {
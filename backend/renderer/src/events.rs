@@ -0,0 +1,62 @@
+use crate::stats::FrameStats;
+use cognexus_model::drawable::DrawableId;
+use glam::Vec2;
+use std::collections::VecDeque;
+
+/// Most events kept for the host between polls. Past it the oldest are
+/// dropped, so a host that never polls doesn't grow the queue forever.
+const MAX_QUEUED_EVENTS: usize = 256;
+
+/// Something the renderer tells the host, like what the pointer clicked.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RendererEvent {
+    /// The answer to a pick request: the topmost drawable at the point, if
+    /// any, and where the point is in world units.
+    Picked {
+        drawable: Option<DrawableId>,
+        world: Vec2,
+    },
+    /// The pointer clicked the drawable, or empty canvas when there's none.
+    Clicked {
+        drawable: Option<DrawableId>,
+        world: Vec2,
+    },
+    /// The pointer moved onto a different drawable, or off of one.
+    HoverChanged {
+        previous: Option<DrawableId>,
+        current: Option<DrawableId>,
+    },
+    /// A frame was drawn.
+    FrameStats(FrameStats),
+    /// The node widgets selected, in the order they were added, now that
+    /// the selection changed.
+    SelectionChanged(Vec<DrawableId>),
+}
+
+/// Events waiting for the host to poll for them, oldest first.
+///
+/// Only the latest frame's stats are kept: each frame replaces the stats of
+/// the one before if they weren't polled yet.
+#[derive(Debug, Default)]
+pub struct EventQueue {
+    events: VecDeque<RendererEvent>,
+}
+
+impl EventQueue {
+    pub fn push(&mut self, event: RendererEvent) {
+        if matches!(event, RendererEvent::FrameStats(_)) {
+            self.events
+                .retain(|queued| !matches!(queued, RendererEvent::FrameStats(_)));
+        }
+
+        if self.events.len() == MAX_QUEUED_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    /// Every queued event, oldest first, leaving the queue empty.
+    pub fn drain(&mut self) -> Vec<RendererEvent> {
+        self.events.drain(..).collect()
+    }
+}
@@ -0,0 +1,355 @@
+use crate::error::RendererError;
+use crate::shaders::text::InstanceRaw;
+use cognexus_model::geometry::text_label::{TextAlign, TextLabel};
+use common::error::error_location::ErrorLocation;
+use std::collections::HashMap;
+use std::panic::Location as PanicLocation;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use wgpu::wgt::TextureViewDescriptor;
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, Device,
+    Extent3d, FilterMode, Origin3d, Queue, SamplerBindingType, SamplerDescriptor, ShaderStages,
+    TexelCopyBufferLayout, TexelCopyTextureInfo, Texture, TextureAspect, TextureDescriptor,
+    TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureViewDimension,
+};
+
+/// Width and height of the atlas texture, in pixels.
+const ATLAS_SIZE: u32 = 1024;
+/// Font size glyphs are rasterized at, in pixels. Labels scale the glyphs to
+/// their own size.
+const FONT_SIZE: f32 = 48.0;
+const FONT_FAMILY: &str = "sans-serif";
+/// Empty pixels around every glyph, so neighbours don't bleed into each other
+/// when sampled.
+const GLYPH_PADDING: u32 = 2;
+/// Height of a glyph's cell, and the distance between lines, relative to the
+/// font size.
+const LINE_HEIGHT: f32 = 1.25;
+
+/// Where a rasterized glyph sits in the atlas, and how far it moves the pen.
+#[derive(Debug, Clone, Copy)]
+struct Glyph {
+    /// Top-left and bottom-right of the glyph's cell, in texture coordinates.
+    /// `None` for glyphs with nothing to draw, like spaces.
+    uv: Option<([f32; 2], [f32; 2])>,
+    /// Cell size in atlas pixels, padding included.
+    cell: [f32; 2],
+    /// Horizontal advance in atlas pixels.
+    advance: f32,
+}
+
+/// A texture of glyphs, rasterized on demand by the browser's 2D canvas, so
+/// text is drawn with the platform fonts without shipping any.
+///
+/// Glyphs are packed in rows, left to right, and stay in the atlas once
+/// rasterized.
+pub struct GlyphAtlas {
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+    texture: Texture,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+    glyphs: HashMap<char, Glyph>,
+    /// Where the next glyph goes, and the tallest glyph in its row.
+    cursor: (u32, u32),
+    row_height: u32,
+}
+
+impl GlyphAtlas {
+    #[track_caller]
+    pub fn new(device: &Device) -> Result<Self, RendererError> {
+        let canvas = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.create_element("canvas").ok())
+            .and_then(|element| element.dyn_into::<HtmlCanvasElement>().ok())
+            .ok_or_else(|| RendererError::TextError {
+                message: String::from("Failed to create the glyph canvas"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            })?;
+
+        let cell_height = Self::cell_height();
+        canvas.set_width(cell_height * 4);
+        canvas.set_height(cell_height);
+
+        let context = canvas
+            .get_context("2d")
+            .ok()
+            .flatten()
+            .and_then(|context| context.dyn_into::<CanvasRenderingContext2d>().ok())
+            .ok_or_else(|| RendererError::TextError {
+                message: String::from("Failed to get a 2D context for the glyph canvas"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            })?;
+
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("Glyph Atlas"),
+            size: Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::R8Unorm,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&TextureViewDescriptor::default());
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Glyph Atlas Sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Glyph Atlas Bind Group Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Glyph Atlas Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        let atlas = Self {
+            canvas,
+            context,
+            texture,
+            bind_group_layout,
+            bind_group,
+            glyphs: HashMap::new(),
+            cursor: (0, 0),
+            row_height: 0,
+        };
+        atlas.reset_context();
+
+        Ok(atlas)
+    }
+
+    pub fn bind_group_layout(&self) -> &BindGroupLayout {
+        &self.bind_group_layout
+    }
+
+    pub fn bind_group(&self) -> &BindGroup {
+        &self.bind_group
+    }
+
+    /// Lay out `label` as one glyph instance per visible character,
+    /// rasterizing any glyphs the atlas doesn't hold yet.
+    #[track_caller]
+    pub fn layout(
+        &mut self,
+        queue: &Queue,
+        label: &TextLabel,
+        instances: &mut Vec<InstanceRaw>,
+    ) -> Result<(), RendererError> {
+        // World units per atlas pixel
+        let scale = label.size / FONT_SIZE;
+        let [x, y, z] = label.position;
+
+        for (index, line) in label.text.lines().enumerate() {
+            let glyphs = line
+                .chars()
+                .map(|ch| self.glyph(queue, ch))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let width: f32 = glyphs.iter().map(|glyph| glyph.advance).sum::<f32>() * scale;
+            let mut pen = match label.align {
+                TextAlign::Left => x,
+                TextAlign::Center => x - width / 2.0,
+                TextAlign::Right => x - width,
+            };
+            let line_y = y - index as f32 * FONT_SIZE * LINE_HEIGHT * scale;
+
+            for glyph in glyphs {
+                if let Some((uv_min, uv_max)) = glyph.uv {
+                    let size = [glyph.cell[0] * scale, glyph.cell[1] * scale];
+                    let left = pen - GLYPH_PADDING as f32 * scale;
+
+                    instances.push(InstanceRaw {
+                        position: [left + size[0] / 2.0, line_y, z],
+                        size,
+                        uv_min,
+                        uv_max,
+                        color: label.color,
+                    });
+                }
+
+                pen += glyph.advance * scale;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cell_height() -> u32 {
+        (FONT_SIZE * LINE_HEIGHT).ceil() as u32 + GLYPH_PADDING * 2
+    }
+
+    /// Resizing the canvas resets its drawing state, so this runs whenever it
+    /// grows.
+    fn reset_context(&self) {
+        self.context
+            .set_font(&format!("{FONT_SIZE}px {FONT_FAMILY}"));
+        self.context.set_text_baseline("middle");
+        self.context.set_fill_style_str("white");
+    }
+
+    #[track_caller]
+    fn glyph(&mut self, queue: &Queue, ch: char) -> Result<Glyph, RendererError> {
+        if let Some(glyph) = self.glyphs.get(&ch) {
+            return Ok(*glyph);
+        }
+
+        let text = ch.to_string();
+        let advance = self
+            .context
+            .measure_text(&text)
+            .map_err(|e| RendererError::TextError {
+                message: format!("Failed to measure glyph '{ch}': {e:?}"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            })?
+            .width() as f32;
+
+        let cell_width = advance.ceil() as u32 + GLYPH_PADDING * 2;
+        let cell_height = Self::cell_height();
+        let cell = [cell_width as f32, cell_height as f32];
+
+        if ch.is_whitespace() {
+            let glyph = Glyph {
+                uv: None,
+                cell,
+                advance,
+            };
+            self.glyphs.insert(ch, glyph);
+            return Ok(glyph);
+        }
+
+        let (x, y) = self.allocate(cell_width, cell_height)?;
+
+        if cell_width > self.canvas.width() {
+            self.canvas.set_width(cell_width);
+            self.reset_context();
+        }
+
+        self.context.clear_rect(
+            0.0,
+            0.0,
+            self.canvas.width() as f64,
+            self.canvas.height() as f64,
+        );
+        self.context
+            .fill_text(&text, GLYPH_PADDING as f64, cell_height as f64 / 2.0)
+            .map_err(|e| RendererError::TextError {
+                message: format!("Failed to rasterize glyph '{ch}': {e:?}"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            })?;
+
+        let pixels = self
+            .context
+            .get_image_data(0.0, 0.0, cell_width as f64, cell_height as f64)
+            .map_err(|e| RendererError::TextError {
+                message: format!("Failed to read glyph '{ch}': {e:?}"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            })?
+            .data();
+
+        // The canvas draws white, so the alpha channel is the coverage
+        let coverage: Vec<u8> = pixels.chunks_exact(4).map(|pixel| pixel[3]).collect();
+
+        queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: Origin3d { x, y, z: 0 },
+                aspect: TextureAspect::All,
+            },
+            &coverage,
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(cell_width),
+                rows_per_image: Some(cell_height),
+            },
+            Extent3d {
+                width: cell_width,
+                height: cell_height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let size = ATLAS_SIZE as f32;
+        let glyph = Glyph {
+            uv: Some((
+                [x as f32 / size, y as f32 / size],
+                [
+                    (x + cell_width) as f32 / size,
+                    (y + cell_height) as f32 / size,
+                ],
+            )),
+            cell,
+            advance,
+        };
+        self.glyphs.insert(ch, glyph);
+
+        Ok(glyph)
+    }
+
+    /// Reserve a `width` by `height` cell, starting a new row when the
+    /// current one is full.
+    #[track_caller]
+    fn allocate(&mut self, width: u32, height: u32) -> Result<(u32, u32), RendererError> {
+        if self.cursor.0 + width > ATLAS_SIZE {
+            self.cursor = (0, self.cursor.1 + self.row_height);
+            self.row_height = 0;
+        }
+
+        if width > ATLAS_SIZE || self.cursor.1 + height > ATLAS_SIZE {
+            return Err(RendererError::TextError {
+                message: format!("Glyph atlas is full ({} glyphs)", self.glyphs.len()),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            });
+        }
+
+        let origin = self.cursor;
+        self.cursor.0 += width;
+        self.row_height = self.row_height.max(height);
+
+        Ok(origin)
+    }
+}
@@ -0,0 +1,235 @@
+use crate::error::RendererError;
+use common::error::error_location::ErrorLocation;
+use std::collections::HashMap;
+use std::panic::Location as PanicLocation;
+use wgpu::wgt::TextureViewDescriptor;
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, Device,
+    Extent3d, FilterMode, Origin3d, Queue, SamplerBindingType, SamplerDescriptor, ShaderStages,
+    TexelCopyBufferLayout, TexelCopyTextureInfo, Texture, TextureAspect, TextureDescriptor,
+    TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureViewDimension,
+};
+
+/// Width and height of the atlas texture, in pixels.
+const ATLAS_SIZE: u32 = 1024;
+/// Empty pixels around every icon, so neighbours don't bleed into each other
+/// when sampled.
+const ICON_PADDING: u32 = 1;
+
+/// Where an icon sits in the atlas.
+#[derive(Debug, Clone, Copy)]
+pub struct AtlasRegion {
+    /// Top-left and bottom-right of the icon, in texture coordinates.
+    pub uv_min: [f32; 2],
+    pub uv_max: [f32; 2],
+    /// Top-left corner and size, in atlas pixels.
+    origin: (u32, u32),
+    size: (u32, u32),
+}
+
+/// A texture of color images uploaded by name, like node icons and status
+/// badges, for sprites to draw regions of.
+///
+/// Icons are packed in rows, left to right. Uploading an icon again under
+/// the same name replaces it, in place when its size is unchanged.
+pub struct IconAtlas {
+    texture: Texture,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+    regions: HashMap<String, AtlasRegion>,
+    /// Where the next icon goes, and the tallest icon in its row.
+    cursor: (u32, u32),
+    row_height: u32,
+}
+
+impl IconAtlas {
+    pub fn new(device: &Device) -> Self {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("Icon Atlas"),
+            size: Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8UnormSrgb,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&TextureViewDescriptor::default());
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Icon Atlas Sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Icon Atlas Bind Group Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Icon Atlas Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        Self {
+            texture,
+            bind_group_layout,
+            bind_group,
+            regions: HashMap::new(),
+            cursor: (0, 0),
+            row_height: 0,
+        }
+    }
+
+    pub fn bind_group_layout(&self) -> &BindGroupLayout {
+        &self.bind_group_layout
+    }
+
+    pub fn bind_group(&self) -> &BindGroup {
+        &self.bind_group
+    }
+
+    /// Where the icon uploaded as `name` is, if there is one.
+    pub fn region(&self, name: &str) -> Option<AtlasRegion> {
+        self.regions.get(name).copied()
+    }
+
+    /// Upload a `width` by `height` image of RGBA pixels, row by row, under
+    /// `name`.
+    #[track_caller]
+    pub fn upload(
+        &mut self,
+        queue: &Queue,
+        name: &str,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+    ) -> Result<AtlasRegion, RendererError> {
+        let expected = width as usize * height as usize * 4;
+        if width == 0 || height == 0 || rgba.len() != expected {
+            return Err(RendererError::AtlasError {
+                message: format!(
+                    "Icon '{name}' is {} bytes, expected {expected} for {width}x{height} RGBA",
+                    rgba.len()
+                ),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            });
+        }
+
+        let origin = match self.regions.get(name) {
+            Some(region) if region.size == (width, height) => region.origin,
+            _ => self.allocate(name, width, height)?,
+        };
+
+        queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: origin.0,
+                    y: origin.1,
+                    z: 0,
+                },
+                aspect: TextureAspect::All,
+            },
+            rgba,
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(height),
+            },
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let size = ATLAS_SIZE as f32;
+        let region = AtlasRegion {
+            uv_min: [origin.0 as f32 / size, origin.1 as f32 / size],
+            uv_max: [
+                (origin.0 + width) as f32 / size,
+                (origin.1 + height) as f32 / size,
+            ],
+            origin,
+            size: (width, height),
+        };
+        self.regions.insert(String::from(name), region);
+
+        Ok(region)
+    }
+
+    /// Reserve space for a `width` by `height` icon, padding included,
+    /// starting a new row when the current one is full. Returns where the
+    /// icon itself goes.
+    #[track_caller]
+    fn allocate(
+        &mut self,
+        name: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(u32, u32), RendererError> {
+        let cell_width = width + ICON_PADDING * 2;
+        let cell_height = height + ICON_PADDING * 2;
+
+        if self.cursor.0 + cell_width > ATLAS_SIZE {
+            self.cursor = (0, self.cursor.1 + self.row_height);
+            self.row_height = 0;
+        }
+
+        if cell_width > ATLAS_SIZE || self.cursor.1 + cell_height > ATLAS_SIZE {
+            return Err(RendererError::AtlasError {
+                message: format!(
+                    "Icon atlas has no room for '{name}' ({width}x{height}, {} icons)",
+                    self.regions.len()
+                ),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            });
+        }
+
+        let origin = (self.cursor.0 + ICON_PADDING, self.cursor.1 + ICON_PADDING);
+        self.cursor.0 += cell_width;
+        self.row_height = self.row_height.max(cell_height);
+
+        Ok(origin)
+    }
+}
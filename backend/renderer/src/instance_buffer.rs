@@ -0,0 +1,87 @@
+use crate::buffer_pool::BufferPool;
+use bytemuck::Pod;
+use std::marker::PhantomData;
+use wgpu::{Buffer, BufferSlice, BufferUsages, Device, Queue};
+
+/// Instances the buffer holds before it first has to grow.
+const INITIAL_CAPACITY: usize = 64;
+/// How much emptier than its capacity the buffer has to be before it's
+/// swapped for a smaller one.
+const SHRINK_FACTOR: usize = 4;
+
+/// A vertex buffer of per-instance data of type `T`, reused every frame and
+/// only swapped when the instances outgrow it, or shrink far below it.
+/// Swapped buffers come from and go back to the pool.
+pub struct InstanceBuffer<T> {
+    label: &'static str,
+    pool: BufferPool,
+    buffer: Buffer,
+    capacity: usize,
+    _instance: PhantomData<T>,
+}
+
+impl<T: Pod> InstanceBuffer<T> {
+    pub fn new(device: &Device, pool: &BufferPool, label: &'static str) -> Self {
+        let buffer = acquire::<T>(device, pool, label, INITIAL_CAPACITY);
+
+        Self {
+            label,
+            pool: pool.clone(),
+            capacity: capacity::<T>(&buffer),
+            buffer,
+            _instance: PhantomData,
+        }
+    }
+
+    /// Write `instances` into the buffer, trading it for a big enough one
+    /// first if it's too small, or for a smaller one if it's mostly unused.
+    /// Returns the instance count.
+    pub fn upload(&mut self, device: &Device, queue: &Queue, instances: &[T]) -> u32 {
+        if instances.is_empty() {
+            return 0;
+        }
+
+        let too_small = instances.len() > self.capacity;
+        let too_big = self.capacity > INITIAL_CAPACITY
+            && instances.len().saturating_mul(SHRINK_FACTOR) < self.capacity;
+
+        if too_small || too_big {
+            let buffer = acquire::<T>(
+                device,
+                &self.pool,
+                self.label,
+                instances.len().max(INITIAL_CAPACITY),
+            );
+            self.capacity = capacity::<T>(&buffer);
+            self.pool
+                .release(std::mem::replace(&mut self.buffer, buffer));
+        }
+
+        queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(instances));
+
+        instances.len() as u32
+    }
+
+    /// Bytes allocated for the buffer.
+    pub fn size(&self) -> u64 {
+        self.buffer.size()
+    }
+
+    pub fn slice(&self) -> BufferSlice<'_> {
+        self.buffer.slice(..)
+    }
+}
+
+fn acquire<T>(device: &Device, pool: &BufferPool, label: &str, instances: usize) -> Buffer {
+    pool.acquire(
+        device,
+        label,
+        BufferUsages::VERTEX | BufferUsages::COPY_DST,
+        (instances * size_of::<T>()) as wgpu::BufferAddress,
+    )
+}
+
+/// Instances of `T` that fit in `buffer`.
+fn capacity<T>(buffer: &Buffer) -> usize {
+    buffer.size() as usize / size_of::<T>()
+}
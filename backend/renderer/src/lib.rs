@@ -1,8 +1,20 @@
 #![cfg(target_arch = "wasm32")]
+mod batch;
+mod buffer_pool;
+mod capture;
 mod commands;
-mod error;
+pub mod error;
+pub mod events;
+mod glyph_atlas;
+mod icon_atlas;
+mod instance_buffer;
+mod minimap;
+pub mod options;
+mod pipelines;
 mod renderer;
 pub mod shaders;
+pub mod stats;
+pub mod theme;
 
 use crate::error::RendererError;
 use cognexus_model::geometry::quad::Quad;
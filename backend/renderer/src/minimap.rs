@@ -0,0 +1,216 @@
+use crate::buffer_pool::BufferPool;
+use crate::instance_buffer::InstanceBuffer;
+use crate::renderer::CameraUniform;
+use crate::shaders::node::InstanceRaw as NodeInstanceRaw;
+use glam::{Mat4, Vec2};
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, Buffer, BufferSlice,
+    BufferUsages, Device, Queue,
+};
+
+/// Size of the minimap, in logical pixels.
+const MINIMAP_WIDTH: u32 = 200;
+const MINIMAP_HEIGHT: u32 = 150;
+/// Gap between the minimap and the bottom-right corner of the surface.
+const MINIMAP_MARGIN: u32 = 16;
+/// Empty space around the scene, relative to its size.
+const SCENE_PADDING: f32 = 0.1;
+
+const PANEL_COLOR: [f32; 4] = [0.05, 0.05, 0.05, 0.85];
+const PANEL_BORDER_COLOR: [f32; 4] = [0.3, 0.3, 0.3, 1.0];
+const VIEW_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.08];
+const VIEW_BORDER_COLOR: [f32; 4] = [0.9, 0.9, 0.9, 1.0];
+/// Depths of the panel, behind the whole scene, and of the visible region's
+/// rectangle, in front of it.
+const PANEL_BACK_Z: f32 = -1.0;
+const VIEW_FRONT_Z: f32 = 1.0;
+/// Border widths, in minimap pixels.
+const PANEL_BORDER_WIDTH: f32 = 1.0;
+const VIEW_BORDER_WIDTH: f32 = 1.5;
+
+/// Where the minimap goes on the surface, in physical pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct MinimapViewport {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A small overview of the whole scene in the corner of the surface, with a
+/// rectangle marking the part the main camera shows.
+///
+/// It has a camera of its own, fitted to the scene every frame, and is drawn
+/// in a separate pass over the main one, at low detail: without the grid or
+/// text.
+pub struct Minimap {
+    visible: bool,
+    camera_buffer: Buffer,
+    camera_bind_group: BindGroup,
+    /// The panel behind the scene and the visible region's rectangle.
+    overlay_buffer: InstanceBuffer<NodeInstanceRaw>,
+}
+
+impl Minimap {
+    pub fn new(device: &Device, pool: &BufferPool, camera_layout: &BindGroupLayout) -> Self {
+        let camera_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Minimap Camera Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[CameraUniform::from_view_proj(
+                Mat4::IDENTITY,
+                1.0,
+                1.0,
+                [MINIMAP_WIDTH as f32, MINIMAP_HEIGHT as f32],
+            )]),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Minimap Camera Bind Group"),
+            layout: camera_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        Self {
+            visible: false,
+            camera_buffer,
+            camera_bind_group,
+            overlay_buffer: InstanceBuffer::new(device, pool, "Minimap Overlay Buffer"),
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn camera_bind_group(&self) -> &BindGroup {
+        &self.camera_bind_group
+    }
+
+    pub fn overlay_slice(&self) -> BufferSlice<'_> {
+        self.overlay_buffer.slice()
+    }
+
+    /// Fit the minimap's camera around the scene's bounds and the main
+    /// camera's `visible` region, both in world units, and upload its
+    /// uniform and overlay. Returns where to draw it on a surface of
+    /// `surface_size` physical pixels, at `pixel_ratio` physical pixels per
+    /// logical one, or nothing when it's hidden or the surface is too small
+    /// to fit it.
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_size: (u32, u32),
+        pixel_ratio: f32,
+        scene: Option<(Vec2, Vec2)>,
+        visible: (Vec2, Vec2),
+    ) -> Option<MinimapViewport> {
+        let (surface_width, surface_height) = surface_size;
+        let physical = |logical: u32| (logical as f32 * pixel_ratio).round() as u32;
+        let (width, height, margin) = (
+            physical(MINIMAP_WIDTH),
+            physical(MINIMAP_HEIGHT),
+            physical(MINIMAP_MARGIN),
+        );
+
+        if !self.visible
+            || width == 0
+            || height == 0
+            || surface_width < width + margin * 2
+            || surface_height < height + margin * 2
+        {
+            return None;
+        }
+
+        let (mut min, mut max) = visible;
+        if let Some((scene_min, scene_max)) = scene {
+            min = min.min(scene_min);
+            max = max.max(scene_max);
+        }
+
+        // Pad the bounds, then widen them to the minimap's aspect ratio so
+        // nothing is stretched
+        let center = (min + max) / 2.0;
+        let mut size = (max - min) * (1.0 + SCENE_PADDING * 2.0);
+        let aspect = MINIMAP_WIDTH as f32 / MINIMAP_HEIGHT as f32;
+        if size.x / size.y > aspect {
+            size.y = size.x / aspect;
+        } else {
+            size.x = size.y * aspect;
+        }
+        let half = size / 2.0;
+
+        let view_proj = Mat4::orthographic_rh(
+            center.x - half.x,
+            center.x + half.x,
+            center.y - half.y,
+            center.y + half.y,
+            -1.0,
+            1.0,
+        );
+        // Same convention as the main camera: 2 / zoom world units tall
+        let uniform = CameraUniform::from_view_proj(
+            view_proj,
+            2.0 / size.y,
+            pixel_ratio,
+            [MINIMAP_WIDTH as f32, MINIMAP_HEIGHT as f32],
+        );
+        queue.write_buffer(&self.camera_buffer, 0, bytemuck::cast_slice(&[uniform]));
+
+        let world_per_pixel = size.y / MINIMAP_HEIGHT as f32;
+        let overlay = [
+            overlay_rect(
+                center,
+                size,
+                PANEL_BACK_Z,
+                PANEL_COLOR,
+                PANEL_BORDER_COLOR,
+                PANEL_BORDER_WIDTH * world_per_pixel,
+            ),
+            overlay_rect(
+                (visible.0 + visible.1) / 2.0,
+                visible.1 - visible.0,
+                VIEW_FRONT_Z,
+                VIEW_COLOR,
+                VIEW_BORDER_COLOR,
+                VIEW_BORDER_WIDTH * world_per_pixel,
+            ),
+        ];
+        self.overlay_buffer.upload(device, queue, &overlay);
+
+        Some(MinimapViewport {
+            x: surface_width - width - margin,
+            y: surface_height - height - margin,
+            width,
+            height,
+        })
+    }
+}
+
+/// A plain bordered rectangle at `z`, drawn with the node pipeline.
+fn overlay_rect(
+    center: Vec2,
+    size: Vec2,
+    z: f32,
+    fill_color: [f32; 4],
+    border_color: [f32; 4],
+    border_width: f32,
+) -> NodeInstanceRaw {
+    NodeInstanceRaw {
+        position: [center.x, center.y, z],
+        size: size.to_array(),
+        fill_color,
+        border_color,
+        header_color: fill_color,
+        shape: [border_width, 0.0, 0.0, 0.0],
+        state: 0,
+    }
+}
@@ -0,0 +1,88 @@
+use crate::error::RendererError;
+use common::error::error_location::ErrorLocation;
+use std::panic::Location as PanicLocation;
+use wasm_bindgen::prelude::*;
+use wgpu::PresentMode;
+
+/// How frames are handed to the display.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PresentModePreference {
+    /// Wait for vertical blank, never tearing. Supported everywhere.
+    #[default]
+    Fifo,
+    /// Replace the queued frame with the newest one, without tearing.
+    Mailbox,
+    /// Present right away, tearing if need be, as fast as frames come.
+    Immediate,
+}
+
+impl PresentModePreference {
+    fn present_mode(self) -> PresentMode {
+        match self {
+            PresentModePreference::Fifo => PresentMode::Fifo,
+            PresentModePreference::Mailbox => PresentMode::Mailbox,
+            PresentModePreference::Immediate => PresentMode::Immediate,
+        }
+    }
+}
+
+/// How a renderer presents and paces its frames, chosen when it's created.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RendererOptions {
+    present_mode: PresentModePreference,
+    target_frame_rate: Option<f32>,
+}
+
+#[wasm_bindgen]
+impl RendererOptions {
+    /// Vsynced presentation, with as many frames as the host asks for.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_present_mode(mut self, present_mode: PresentModePreference) -> Self {
+        self.present_mode = present_mode;
+        self
+    }
+
+    /// Draw at most `frames_per_second` frames a second, however often
+    /// `render` is called. Frames skipped for pacing are drawn by a later
+    /// call.
+    pub fn with_target_frame_rate(mut self, frames_per_second: f32) -> Self {
+        self.target_frame_rate = Some(frames_per_second);
+        self
+    }
+}
+
+impl RendererOptions {
+    /// The preferred present mode if the surface supports it, otherwise
+    /// Fifo, which every surface does.
+    pub fn present_mode(&self, supported: &[PresentMode]) -> PresentMode {
+        let preferred = self.present_mode.present_mode();
+
+        if supported.contains(&preferred) {
+            preferred
+        } else {
+            PresentMode::Fifo
+        }
+    }
+
+    /// Least time between frames, in milliseconds, if the frame rate is
+    /// capped.
+    #[track_caller]
+    pub fn min_frame_interval(&self) -> Result<Option<f64>, RendererError> {
+        match self.target_frame_rate {
+            Some(rate) if !rate.is_finite() || rate <= 0.0 => {
+                Err(RendererError::ConfigurationError {
+                    message: format!("Target frame rate must be a positive number: {rate}"),
+                    location: ErrorLocation::from(PanicLocation::caller()),
+                })
+            }
+            Some(rate) => Ok(Some(1000.0 / rate as f64)),
+            None => Ok(None),
+        }
+    }
+}
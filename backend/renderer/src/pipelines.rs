@@ -0,0 +1,232 @@
+use crate::shaders::edge::InstanceRaw as EdgeInstanceRaw;
+use crate::shaders::node::InstanceRaw as NodeInstanceRaw;
+use crate::shaders::overlay::InstanceRaw as OverlayInstanceRaw;
+use crate::shaders::port::InstanceRaw as PortInstanceRaw;
+use crate::shaders::quad::{InstanceRaw, Vertex};
+use crate::shaders::sprite::InstanceRaw as SpriteInstanceRaw;
+use crate::shaders::text::InstanceRaw as GlyphInstanceRaw;
+use crate::shaders::{edge, grid, node, overlay, port, quad, sprite, text};
+use wgpu::{
+    BindGroupLayout, BlendState, ColorTargetState, ColorWrites, CompareFunction, DepthStencilState,
+    Device, FragmentState, FrontFace, MultisampleState, PipelineLayout, PipelineLayoutDescriptor,
+    PolygonMode, PrimitiveState, PrimitiveTopology, RenderPipeline, RenderPipelineDescriptor,
+    ShaderModuleDescriptor, ShaderSource, TextureFormat, VertexBufferLayout, VertexState,
+};
+
+/// Format of the depth target drawables are stacked with.
+pub const DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+
+/// Every render pipeline the renderer draws with, all targeting the same
+/// color format and sample count, so they're rebuilt together when either
+/// changes.
+pub struct Pipelines {
+    /// Draws the background grid across the whole viewport.
+    pub grid: RenderPipeline,
+    pub quad: RenderPipeline,
+    /// Draws segments of edge curves on the quad vertices.
+    pub edge: RenderPipeline,
+    /// Draws node widgets on the quad vertices.
+    pub node: RenderPipeline,
+    /// Draws port anchors on the quad vertices.
+    pub port: RenderPipeline,
+    /// Draws icons from the icon atlas on the quad vertices.
+    pub sprite: RenderPipeline,
+    /// Draws glyphs from the atlas on the quad vertices.
+    pub text: RenderPipeline,
+    /// Draws screen-space rectangles over the scene.
+    pub overlay: RenderPipeline,
+}
+
+impl Pipelines {
+    pub fn new(
+        device: &Device,
+        camera_layout: &BindGroupLayout,
+        grid_layout: &BindGroupLayout,
+        icon_layout: &BindGroupLayout,
+        atlas_layout: &BindGroupLayout,
+        format: TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let render_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Render Pipeline Layout"),
+            bind_group_layouts: &[camera_layout],
+            push_constant_ranges: &[],
+        });
+
+        let grid_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Grid Pipeline Layout"),
+            bind_group_layouts: &[camera_layout, grid_layout],
+            push_constant_ranges: &[],
+        });
+
+        let sprite_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Sprite Pipeline Layout"),
+            bind_group_layouts: &[camera_layout, icon_layout],
+            push_constant_ranges: &[],
+        });
+
+        let text_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Text Pipeline Layout"),
+            bind_group_layouts: &[camera_layout, atlas_layout],
+            push_constant_ranges: &[],
+        });
+
+        let target = Target {
+            format,
+            sample_count,
+        };
+
+        Self {
+            grid: target.pipeline(
+                device,
+                "Grid Pipeline",
+                &grid_pipeline_layout,
+                (grid::LABEL, grid::SHADER_SOURCE),
+                &[Vertex::desc()],
+                Depth::Ignored,
+            ),
+            quad: target.pipeline(
+                device,
+                "Render Pipeline",
+                &render_pipeline_layout,
+                (quad::LABEL, quad::SHADER_SOURCE),
+                &[Vertex::desc(), InstanceRaw::desc()],
+                Depth::Stacked,
+            ),
+            edge: target.pipeline(
+                device,
+                "Edge Pipeline",
+                &render_pipeline_layout,
+                (edge::LABEL, edge::SHADER_SOURCE),
+                &[Vertex::desc(), EdgeInstanceRaw::desc()],
+                Depth::Stacked,
+            ),
+            node: target.pipeline(
+                device,
+                "Node Pipeline",
+                &render_pipeline_layout,
+                (node::LABEL, node::SHADER_SOURCE),
+                &[Vertex::desc(), NodeInstanceRaw::desc()],
+                Depth::Stacked,
+            ),
+            port: target.pipeline(
+                device,
+                "Port Pipeline",
+                &render_pipeline_layout,
+                (port::LABEL, port::SHADER_SOURCE),
+                &[Vertex::desc(), PortInstanceRaw::desc()],
+                Depth::Stacked,
+            ),
+            sprite: target.pipeline(
+                device,
+                "Sprite Pipeline",
+                &sprite_pipeline_layout,
+                (sprite::LABEL, sprite::SHADER_SOURCE),
+                &[Vertex::desc(), SpriteInstanceRaw::desc()],
+                Depth::Stacked,
+            ),
+            text: target.pipeline(
+                device,
+                "Text Pipeline",
+                &text_pipeline_layout,
+                (text::LABEL, text::SHADER_SOURCE),
+                &[Vertex::desc(), GlyphInstanceRaw::desc()],
+                Depth::Stacked,
+            ),
+            overlay: target.pipeline(
+                device,
+                "Overlay Pipeline",
+                &render_pipeline_layout,
+                (overlay::LABEL, overlay::SHADER_SOURCE),
+                &[Vertex::desc(), OverlayInstanceRaw::desc()],
+                Depth::Ignored,
+            ),
+        }
+    }
+}
+
+/// How a pipeline uses the depth target.
+enum Depth {
+    /// Drawn where nothing stacked higher has been, and hiding what's
+    /// stacked lower. Equal depths pass, so ties are drawn in order.
+    Stacked,
+    /// Drawn regardless of depth, without writing it, like the grid behind
+    /// everything and gizmos over everything.
+    Ignored,
+}
+
+/// What the pipelines render into.
+struct Target {
+    format: TextureFormat,
+    sample_count: u32,
+}
+
+impl Target {
+    /// A pipeline drawing vertices laid out as `buffers` with the shader's
+    /// `vs_main` and `fs_main`, alpha-blended onto the target and using its
+    /// depth as `depth` says.
+    fn pipeline(
+        &self,
+        device: &Device,
+        label: &str,
+        layout: &PipelineLayout,
+        (shader_label, shader_source): (&str, &str),
+        buffers: &[VertexBufferLayout<'static>],
+        depth: Depth,
+    ) -> RenderPipeline {
+        let (depth_write_enabled, depth_compare) = match depth {
+            Depth::Stacked => (true, CompareFunction::LessEqual),
+            Depth::Ignored => (false, CompareFunction::Always),
+        };
+
+        let shader = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some(shader_label),
+            source: ShaderSource::Wgsl(shader_source.into()),
+        });
+
+        device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some(label),
+            layout: Some(layout),
+            vertex: VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"), // Match fn in .wgsl
+                compilation_options: Default::default(),
+                buffers,
+            },
+            fragment: Some(FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(ColorTargetState {
+                    format: self.format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: FrontFace::Ccw,
+                // cull_mode: Some(Face::Back),
+                cull_mode: None,
+                polygon_mode: PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled,
+                depth_compare,
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: MultisampleState {
+                count: self.sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        })
+    }
+}
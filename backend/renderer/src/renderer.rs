@@ -1,9 +1,42 @@
+use crate::batch::{LayerRanges, LayeredBatch, StackingDepths};
+use crate::buffer_pool::BufferPool;
+use crate::capture::FrameReadback;
 use crate::error::RendererError;
-use crate::shaders::quad::{INDICES, InstanceRaw, LABEL, SHADER_SOURCE, VERTICES, Vertex};
-use cognexus_model::camera::camera_2d::{Camera2D, Camera2DBuilder};
-use cognexus_model::drawable::Drawable;
+use crate::events::{EventQueue, RendererEvent};
+use crate::glyph_atlas::GlyphAtlas;
+use crate::icon_atlas::IconAtlas;
+use crate::instance_buffer::InstanceBuffer;
+use crate::minimap::Minimap;
+use crate::options::RendererOptions;
+use crate::pipelines::{DEPTH_FORMAT, Pipelines};
+use crate::shaders::edge::InstanceRaw as EdgeInstanceRaw;
+use crate::shaders::grid::GridUniform;
+use crate::shaders::node::InstanceRaw as NodeInstanceRaw;
+use crate::shaders::overlay::InstanceRaw as OverlayInstanceRaw;
+use crate::shaders::port::InstanceRaw as PortInstanceRaw;
+use crate::shaders::quad::{INDICES, InstanceRaw, VERTICES};
+use crate::shaders::sprite::InstanceRaw as SpriteInstanceRaw;
+use crate::shaders::text::InstanceRaw as GlyphInstanceRaw;
+use crate::stats::FrameStats;
+use crate::theme::Theme;
+use cognexus_model::camera::camera_2d::{Camera2D, Camera2DBuilder, DEFAULT_PIXEL_RATIO};
+use cognexus_model::camera::camera_animation::{CameraAnimation, Easing};
+use cognexus_model::drawable::{DrawOrder, DrawableId, Layer, Pickable};
+use cognexus_model::geometry::edge::{Edge, EdgeEnd};
+use cognexus_model::geometry::edge_curve::EdgeCurve;
+use cognexus_model::geometry::node_widget::NodeWidget;
+use cognexus_model::geometry::port_anchor::{PortAnchor, PortDirection};
 use cognexus_model::geometry::quad::Quad;
+use cognexus_model::geometry::sprite::Sprite;
+use cognexus_model::geometry::text_label::{TextAlign, TextLabel};
+use cognexus_model::geometry::transform_animation::TransformAnimation;
 use common::error::error_location::ErrorLocation;
+use glam::{Mat4, Vec2};
+use js_sys::{Promise, Uint8Array};
+use proto::Compression;
+use proto::framing::FrameDecoder;
+use std::collections::HashMap;
+use std::ops::Range;
 use std::panic::Location as PanicLocation;
 use wasm_bindgen::prelude::*;
 use web_sys::HtmlCanvasElement;
@@ -11,41 +44,227 @@ use wgpu::PowerPreference::HighPerformance;
 use wgpu::util::{BufferInitDescriptor, DeviceExt};
 use wgpu::wgt::TextureViewDescriptor;
 use wgpu::{
-    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor,
-    BindGroupLayoutEntry, BindingType, BlendState, Buffer, BufferBindingType, BufferUsages, Color,
-    ColorTargetState, ColorWrites, CommandEncoderDescriptor, CompositeAlphaMode, Device,
-    DeviceDescriptor, Features, FragmentState, FrontFace, IndexFormat, Instance, Limits, LoadOp,
-    MemoryHints, MultisampleState, Operations, PipelineLayoutDescriptor, PolygonMode,
-    PrimitiveState, PrimitiveTopology, Queue, RenderPassColorAttachment, RenderPassDescriptor,
-    RenderPipeline, RenderPipelineDescriptor, RequestAdapterOptions, ShaderModuleDescriptor,
-    ShaderSource, ShaderStages, StoreOp, Surface, SurfaceConfiguration, TextureUsages, VertexState,
+    Adapter, Backend, Backends, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, Buffer, BufferBindingType,
+    BufferUsages, Color, CommandEncoder, CommandEncoderDescriptor, CompositeAlphaMode, Device,
+    DeviceDescriptor, Extent3d, Features, IndexFormat, Instance, InstanceDescriptor, Limits,
+    LoadOp, MemoryHints, Operations, PresentMode, Queue, RenderPassColorAttachment,
+    RenderPassDepthStencilAttachment, RenderPassDescriptor, RequestAdapterOptions, ShaderStages,
+    StoreOp, Surface, SurfaceConfiguration, SurfaceError, Texture, TextureDescriptor,
+    TextureDimension, TextureFormat, TextureUsages, TextureView,
+};
+
+/// Samples per pixel unless changed with `set_sample_count`, smoothing the
+/// edges of quads and widgets.
+const DEFAULT_SAMPLE_COUNT: u32 = 4;
+/// Color format of offscreen renderers, one every device can render to and
+/// read back.
+const OFFSCREEN_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+/// Sample counts every WebGPU device supports for color targets.
+const SUPPORTED_SAMPLE_COUNTS: [u32; 2] = [1, 4];
+/// How much earlier than the target frame rate allows a frame may start, in
+/// milliseconds, so jitter in when `render` is called doesn't skip frames.
+const FRAME_PACING_SLACK_MS: f64 = 1.0;
+/// How long animated moves, like fitting the view, take unless told
+/// otherwise, in seconds.
+pub const DEFAULT_ANIMATION_DURATION: f32 = 0.3;
+/// Font size of the stats overlay, and its gap from the top-left corner, in
+/// logical pixels.
+const STATS_OVERLAY_FONT_SIZE: f32 = 12.0;
+const STATS_OVERLAY_MARGIN: f32 = 8.0;
+const STATS_OVERLAY_COLOR: [f32; 4] = [0.9, 0.9, 0.9, 1.0];
+/// Font size of node titles relative to the header they're drawn in, and
+/// their gap from its left end relative to the font size.
+const NODE_TITLE_SCALE: f32 = 0.55;
+const NODE_TITLE_INDENT: f32 = 0.75;
+const NODE_TITLE_COLOR: [f32; 4] = [0.95, 0.95, 0.95, 1.0];
+/// Width, dash, and gap of the connection being dragged, and how close to a
+/// compatible port the pointer snaps to it, in logical pixels.
+const PENDING_EDGE_WIDTH: f32 = 2.0;
+const PENDING_EDGE_DASH: f32 = 8.0;
+const PENDING_EDGE_GAP: f32 = 6.0;
+const PORT_SNAP_DISTANCE: f32 = 24.0;
+/// Straight segments edge curves are drawn with.
+const EDGE_CURVE_SEGMENTS: usize = 32;
+/// Dash and gap lengths of dashed edges, relative to their width.
+const EDGE_DASH: f32 = 4.0;
+const EDGE_GAP: f32 = 3.0;
+/// How far from an edge, in logical pixels beyond its width, the pointer
+/// still picks it, so thin edges are easy to hit at any zoom.
+const EDGE_PICK_TOLERANCE: f32 = 4.0;
+/// Colors of the selection marquee, and its border width in logical pixels.
+const MARQUEE_FILL_COLOR: [f32; 4] = [0.3, 0.55, 0.95, 0.15];
+const MARQUEE_BORDER_COLOR: [f32; 4] = [0.3, 0.55, 0.95, 0.9];
+const MARQUEE_BORDER_WIDTH: f32 = 1.0;
+/// The connection being dragged goes over everything else.
+const PENDING_EDGE_ORDER: DrawOrder = DrawOrder {
+    layer: Layer::Overlay,
+    z_index: 0,
 };
 
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
-struct CameraUniform {
+pub(crate) struct CameraUniform {
     view_proj: [[f32; 4]; 4],
+    view_proj_inv: [[f32; 4]; 4],
+    zoom: f32,
+    pixel_ratio: f32,
+    viewport_size: [f32; 2],
+}
+
+impl CameraUniform {
+    fn from_camera(camera: &Camera2D) -> Self {
+        let (width, height) = camera.viewport_size();
+
+        Self::from_view_proj(
+            camera.view_projection_matrix(),
+            camera.zoom(),
+            camera.pixel_ratio(),
+            [width as f32, height as f32],
+        )
+    }
+
+    pub(crate) fn from_view_proj(
+        view_proj: Mat4,
+        zoom: f32,
+        pixel_ratio: f32,
+        viewport_size: [f32; 2],
+    ) -> Self {
+        Self {
+            view_proj: view_proj.to_cols_array_2d(),
+            view_proj_inv: view_proj.inverse().to_cols_array_2d(),
+            zoom,
+            pixel_ratio,
+            viewport_size,
+        }
+    }
+}
+
+/// Where each layer's instances are in each instance buffer.
+struct SceneRanges {
+    quads: LayerRanges,
+    edges: LayerRanges,
+    widgets: LayerRanges,
+    ports: LayerRanges,
+    sprites: LayerRanges,
+    glyphs: LayerRanges,
+    /// Screen-space overlays, drawn over every layer.
+    gizmos: Range<u32>,
+}
+
+/// A connection being dragged out of a port, drawn until it's dropped.
+struct PendingEdge {
+    source: DrawableId,
+    /// Where the pointer is, in world units.
+    pointer: Vec2,
+    /// The compatible port it's snapped to, if any.
+    target: Option<DrawableId>,
+}
+
+/// A rectangle being dragged out to select what's inside it, between the
+/// screen positions the drag started at and is at now.
+struct Marquee {
+    start: Vec2,
+    end: Vec2,
+}
+
+/// A curve drawn with the edge pipeline.
+enum EdgeDrawing<'a> {
+    Connected(&'a Edge),
+    Pending(&'a PendingEdge),
+}
+
+/// What frames are drawn into.
+enum RenderTarget {
+    /// A canvas, presented every frame.
+    Surface(Surface<'static>),
+    /// A texture frames are left in, when rendering without a canvas.
+    Texture(Texture),
 }
 
 #[wasm_bindgen]
 pub struct Renderer {
-    surface: Surface<'static>,
+    target: RenderTarget,
     device: Device,
     queue: Queue,
+    /// WebGPU, or WebGL2 where the webview hasn't got it.
+    backend: Backend,
     #[allow(dead_code)]
     config: SurfaceConfiguration,
-    #[allow(dead_code)]
     size: (u32, u32),
-    render_pipeline: RenderPipeline,
+    pipelines: Pipelines,
+    sample_count: u32,
+    /// Resolved to the surface texture every frame when multisampling.
+    msaa_target: Option<TextureView>,
+    /// Where drawables are stacked, with as many samples as the color.
+    depth_target: TextureView,
     vertex_buffer: Buffer,
     index_buffer: Buffer,
     num_indices: u32,
+    /// Recycles the instance and readback buffers.
+    buffer_pool: BufferPool,
+    instance_buffer: InstanceBuffer<InstanceRaw>,
+    edge_instance_buffer: InstanceBuffer<EdgeInstanceRaw>,
+    node_instance_buffer: InstanceBuffer<NodeInstanceRaw>,
+    port_instance_buffer: InstanceBuffer<PortInstanceRaw>,
+    sprite_instance_buffer: InstanceBuffer<SpriteInstanceRaw>,
+    glyph_instance_buffer: InstanceBuffer<GlyphInstanceRaw>,
+    gizmo_instance_buffer: InstanceBuffer<OverlayInstanceRaw>,
+    icon_atlas: IconAtlas,
+    glyph_atlas: GlyphAtlas,
     #[allow(dead_code)]
     camera: Camera2D,
     #[allow(dead_code)]
     camera_buffer: Buffer,
+    camera_bind_group_layout: BindGroupLayout,
     camera_bind_group: BindGroup,
-    drawables: Vec<Box<dyn Drawable>>,
+    theme: Theme,
+    /// The theme's grid colors.
+    grid_buffer: Buffer,
+    grid_bind_group_layout: BindGroupLayout,
+    grid_bind_group: BindGroup,
+    minimap: Minimap,
+    /// The scene, drawn each frame by layer, then within a layer quads,
+    /// edges, node widgets, port anchors, sprites, and text, each sorted by
+    /// z-index, and stacked by their order with the depth target.
+    quads: Vec<Quad>,
+    edges: Vec<Edge>,
+    node_widgets: Vec<NodeWidget>,
+    port_anchors: Vec<PortAnchor>,
+    sprites: Vec<Sprite>,
+    /// Laid out into glyphs each frame.
+    text_labels: Vec<TextLabel>,
+    /// Titles drawn in the headers of node widgets, following them.
+    node_titles: HashMap<DrawableId, String>,
+    pending_edge: Option<PendingEdge>,
+    marquee: Option<Marquee>,
+    /// The protocol and schema versions the host said it speaks, if it did.
+    host_protocol: Option<(u32, u32)>,
+    /// What the host agreed to compress commands with in its hello.
+    command_compression: Compression,
+    /// Holds on to a command frame cut short until the rest of it arrives.
+    frame_decoder: FrameDecoder,
+    /// The drawable under the pointer, as of its last move.
+    hovered: Option<DrawableId>,
+    /// Waiting for the host to poll for them.
+    events: EventQueue,
+    /// Whether node widgets were selected or deselected since the host
+    /// last polled for events.
+    selection_changed: bool,
+    /// Whether the scene or camera changed since the last present, so
+    /// rendering again would draw something different.
+    dirty: bool,
+    /// The camera move in progress, and when it started, in milliseconds.
+    camera_animation: Option<(CameraAnimation, f64)>,
+    /// Node widgets and port anchors moving, with when they started to.
+    drawable_animations: HashMap<DrawableId, (TransformAnimation, f64)>,
+    stats: FrameStats,
+    /// When the last frame started, in milliseconds.
+    last_frame_start: Option<f64>,
+    /// Least time between frames when the frame rate is capped.
+    min_frame_interval: Option<f64>,
+    /// Whether the stats are drawn over the top-left corner of the canvas.
+    stats_overlay_visible: bool,
 }
 
 #[wasm_bindgen]
@@ -55,39 +274,22 @@ impl Renderer {
         width: u32,
         height: u32,
     ) -> Result<Self, RendererError> {
-        let instance = Instance::default();
-        let surface = instance
-            .create_surface(wgpu::SurfaceTarget::Canvas(canvas))
-            .map_err(|e| RendererError::WgpuError {
-                message: format!("Failed to create surface from canvas: {}", e),
-                location: ErrorLocation::from(PanicLocation::caller()),
-            })?;
-
-        let adapter = instance
-            .request_adapter(&RequestAdapterOptions {
-                power_preference: HighPerformance,
-                compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
-            })
-            .await
-            .map_err(|e| RendererError::WgpuError {
-                message: format!("No suitable GPU adapter found: {e}"),
-                location: ErrorLocation::from(PanicLocation::caller()),
-            })?;
+        Self::new_with_options(canvas, width, height, RendererOptions::default()).await
+    }
 
-        let (device, queue) = adapter
-            .request_device(&DeviceDescriptor {
-                label: Some("Cognexus Device"),
-                required_features: Features::empty(),
-                required_limits: Limits::default(),
-                memory_hints: MemoryHints::default(),
-                ..Default::default()
-            })
-            .await
-            .map_err(|e| RendererError::WgpuError {
-                message: format!("Failed to create device: {}", e),
-                location: ErrorLocation::from(PanicLocation::caller()),
-            })?;
+    /// A renderer drawing into `canvas` that presents and paces its frames
+    /// as `options` say.
+    ///
+    /// It draws with WebGPU where the webview has it, and otherwise falls
+    /// back to WebGL2, within the limits older GPUs have.
+    pub async fn new_with_options(
+        canvas: HtmlCanvasElement,
+        width: u32,
+        height: u32,
+        options: RendererOptions,
+    ) -> Result<Self, RendererError> {
+        let (surface, adapter) = request_canvas_adapter(canvas).await?;
+        let gpu = request_device(&adapter).await?;
 
         let surface_caps = surface.get_capabilities(&adapter);
         let surface_format = surface_caps
@@ -96,278 +298,2322 @@ impl Renderer {
             .copied()
             .find(|f| f.is_srgb())
             .unwrap_or(surface_caps.formats[0]);
+        let present_mode = options.present_mode(&surface_caps.present_modes);
 
-        let config = SurfaceConfiguration {
-            usage: TextureUsages::RENDER_ATTACHMENT,
-            format: surface_format,
-            width,
-            height,
-            present_mode: wgpu::PresentMode::AutoNoVsync,
-            alpha_mode: CompositeAlphaMode::Auto,
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
-        };
+        // The canvas is sized in logical pixels, but drawn at the display's
+        // resolution so it stays sharp on high-density screens
+        let pixel_ratio = web_sys::window()
+            .map(|window| window.device_pixel_ratio() as f32)
+            .filter(|ratio| ratio.is_finite() && *ratio > 0.0)
+            .unwrap_or(DEFAULT_PIXEL_RATIO);
+
+        Self::with_target(
+            &adapter,
+            gpu,
+            Some((surface, present_mode)),
+            surface_format,
+            (width, height),
+            pixel_ratio,
+            options,
+        )
+    }
 
-        let shader = device.create_shader_module(ShaderModuleDescriptor {
-            label: Some(LABEL),
-            source: ShaderSource::Wgsl(SHADER_SOURCE.into()),
+    /// A renderer drawing into a texture of `width` by `height` pixels
+    /// instead of a canvas, e.g. to export images or thumbnails without
+    /// showing them. Frames stay in the texture; read them back with
+    /// `capture_frame`.
+    ///
+    /// Needs WebGPU: WebGL2 can only draw into a canvas.
+    pub async fn new_offscreen(width: u32, height: u32) -> Result<Self, RendererError> {
+        let instance = Instance::new(&InstanceDescriptor {
+            backends: Backends::BROWSER_WEBGPU,
+            ..Default::default()
         });
 
-        let camera = Camera2DBuilder::default()
-            .with_viewport(width, height)
-            .build()
+        let adapter = instance
+            .request_adapter(&RequestAdapterOptions {
+                power_preference: HighPerformance,
+                compatible_surface: None,
+                force_fallback_adapter: false,
+            })
+            .await
             .map_err(|e| RendererError::WgpuError {
-                message: format!("Failed to create Camera: {e}"),
+                message: format!("No suitable GPU adapter found: {e}"),
                 location: ErrorLocation::from(PanicLocation::caller()),
             })?;
 
-        let camera_uniform = CameraUniform {
-            view_proj: camera.view_projection_matrix().to_cols_array_2d(),
+        let gpu = request_device(&adapter).await?;
+
+        Self::with_target(
+            &adapter,
+            gpu,
+            None,
+            OFFSCREEN_FORMAT,
+            (width, height),
+            DEFAULT_PIXEL_RATIO,
+            RendererOptions::default(),
+        )
+    }
+
+    pub fn pan_camera(&mut self, delta_x: f32, delta_y: f32) {
+        // Input takes over from any camera move in progress
+        self.camera_animation = None;
+        self.camera.pan_by_screen_delta(delta_x, delta_y);
+        self.update_camera_uniform();
+    }
+
+    pub fn zoom_camera(&mut self, scroll_delta: f32, screen_x: f32, screen_y: f32) {
+        self.camera_animation = None;
+        self.camera
+            .zoom_toward_point(scroll_delta, screen_x, screen_y);
+        self.update_camera_uniform();
+    }
+
+    /// Move the camera to center on everything in the scene and zoom to fit
+    /// it, with `padding` logical pixels to spare around it. An empty scene
+    /// leaves the camera where it is.
+    pub fn fit_view(&mut self, padding: f32) {
+        let Some((min, max)) = self.scene_bounds() else {
+            return;
         };
 
-        let camera_buffer = device.create_buffer_init(&BufferInitDescriptor {
-            label: Some("Camera Uniform Buffer"),
-            contents: bytemuck::cast_slice(&[camera_uniform]),
-            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
-        });
+        let mut target = self.camera.clone();
+        target.fit_to_bounds(min, max, padding);
+        self.animate_camera_to(target.position().x, target.position().y, target.zoom());
+    }
 
-        let camera_bind_group_layout =
-            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
-                label: Some("Camera Bind Group Layout"),
-                entries: &[BindGroupLayoutEntry {
-                    binding: 0, // @binding(0) in shader
-                    visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
-                    ty: BindingType::Buffer {
-                        ty: BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                }],
-            });
+    /// Move the camera to the world position `x`, `y` at `zoom` over a
+    /// moment, instead of all at once. Each `render` moves it along until it
+    /// gets there, or pan or zoom input interrupts it.
+    pub fn animate_camera_to(&mut self, x: f32, y: f32, zoom: f32) {
+        self.animate_camera(x, y, zoom, DEFAULT_ANIMATION_DURATION, Easing::EaseInOut);
+    }
 
-        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
-            label: Some("Camera Bind Group"),
-            layout: &camera_bind_group_layout,
-            entries: &[BindGroupEntry {
-                binding: 0,
-                resource: camera_buffer.as_entire_binding(),
-            }],
-        });
+    /// Move the camera to the world position `x`, `y` at `zoom` all at once,
+    /// ending any camera move in progress. The zoom is clamped to its limits.
+    pub fn set_camera(&mut self, x: f32, y: f32, zoom: f32) {
+        self.camera_animation = None;
+        self.camera.set_view(Vec2::new(x, y), zoom);
+        self.update_camera_uniform();
+    }
 
-        let render_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
-            label: Some("Render Pipeline Layout"),
-            bind_group_layouts: &[&camera_bind_group_layout], // No uniforms/textures yet
-            push_constant_ranges: &[],
-        });
+    /// Resize the canvas to `width` by `height` logical pixels and widen or
+    /// narrow the camera's view to match, keeping its position and zoom.
+    ///
+    /// A zero-sized canvas (e.g. a hidden or collapsed one) can't back a
+    /// surface, so the last size is kept until it's visible again, and sizes
+    /// past the device's texture limit are clamped to it.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 || (width, height) == self.camera.viewport_size() {
+            return;
+        }
 
-        let render_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(&render_pipeline_layout),
-            vertex: VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"), // Match fn in .wgsl
-                compilation_options: Default::default(),
-                buffers: &[Vertex::desc(), InstanceRaw::desc()],
-            },
-            fragment: Some(FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                compilation_options: Default::default(),
-                targets: &[Some(ColorTargetState {
-                    format: config.format,
-                    blend: Some(BlendState::ALPHA_BLENDING),
-                    write_mask: ColorWrites::ALL,
-                })],
-            }),
-            primitive: PrimitiveState {
-                topology: PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: FrontFace::Ccw,
-                // cull_mode: Some(Face::Back),
-                cull_mode: None,
-                polygon_mode: PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
-            cache: None,
-        });
+        self.camera.set_viewport_size(width, height);
+        self.configure_surface();
+        self.update_camera_uniform();
+    }
 
-        let vertex_buffer = device.create_buffer_init(&BufferInitDescriptor {
-            label: Some("Quad vertex Buffer"),
-            contents: bytemuck::cast_slice(VERTICES),
-            usage: BufferUsages::VERTEX,
-        });
+    /// Resize the canvas to `width` by `height` logical pixels drawn at
+    /// `pixel_ratio` physical pixels each, reconfiguring the surface once for
+    /// both, as when the window is resized or moves to another display.
+    ///
+    /// A zero size keeps the last one, like `resize`.
+    pub fn resize_viewport(
+        &mut self,
+        width: u32,
+        height: u32,
+        pixel_ratio: f32,
+    ) -> Result<(), RendererError> {
+        self.camera.set_pixel_ratio(pixel_ratio).map_err(|e| {
+            RendererError::ConfigurationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            }
+        })?;
 
-        let index_buffer = device.create_buffer_init(&BufferInitDescriptor {
-            label: Some("Quad Index Buffer"),
-            contents: bytemuck::cast_slice(INDICES),
-            usage: BufferUsages::INDEX,
-        });
+        if width != 0 && height != 0 {
+            self.camera.set_viewport_size(width, height);
+        }
 
-        let num_indices = INDICES.len() as u32;
+        self.configure_surface();
+        self.update_camera_uniform();
 
-        surface.configure(&device, &config);
+        Ok(())
+    }
 
-        Ok(Self {
-            surface,
-            device,
-            queue,
-            config,
-            size: (width, height),
-            render_pipeline,
-            vertex_buffer,
-            index_buffer,
-            num_indices,
-            camera,
-            camera_buffer,
-            camera_bind_group,
-            drawables: Vec::new(),
+    /// Draw at `pixel_ratio` physical pixels per logical pixel, e.g. when the
+    /// window moves to a display of another density. Input stays in logical
+    /// pixels.
+    pub fn set_pixel_ratio(&mut self, pixel_ratio: f32) -> Result<(), RendererError> {
+        self.camera.set_pixel_ratio(pixel_ratio).map_err(|e| {
+            RendererError::ConfigurationError {
+                message: e.to_string(),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            }
+        })?;
+
+        self.configure_surface();
+        self.update_camera_uniform();
+
+        Ok(())
+    }
+
+    pub fn pixel_ratio(&self) -> f32 {
+        self.camera.pixel_ratio()
+    }
+
+    /// "webgpu", or "webgl2" when drawing through the fallback.
+    pub fn backend(&self) -> String {
+        String::from(match self.backend {
+            Backend::Gl => "webgl2",
+            _ => "webgpu",
         })
     }
 
-    pub fn pan_camera(&mut self, delta_x: f32, delta_y: f32) {
-        self.camera.pan_by_screen_delta(delta_x, delta_y);
-        self.update_camera_uniform();
+    /// Draw with `count` samples per pixel (4 by default), or 1 to turn
+    /// multisampling off. WebGPU only guarantees 1 and 4.
+    pub fn set_sample_count(&mut self, count: u32) -> Result<(), RendererError> {
+        if !SUPPORTED_SAMPLE_COUNTS.contains(&count) {
+            return Err(RendererError::ConfigurationError {
+                message: format!(
+                    "Unsupported sample count {count}, expected one of {SUPPORTED_SAMPLE_COUNTS:?}"
+                ),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            });
+        }
+
+        if count == self.sample_count {
+            return Ok(());
+        }
+
+        self.pipelines = Pipelines::new(
+            &self.device,
+            &self.camera_bind_group_layout,
+            &self.grid_bind_group_layout,
+            self.icon_atlas.bind_group_layout(),
+            self.glyph_atlas.bind_group_layout(),
+            self.config.format,
+            count,
+        );
+        self.sample_count = count;
+        self.msaa_target = create_msaa_target(&self.device, &self.config, count);
+        self.depth_target = create_depth_target(&self.device, &self.config, count);
+        self.dirty = true;
+
+        Ok(())
     }
 
-    pub fn zoom_camera(&mut self, scroll_delta: f32, screen_x: f32, screen_y: f32) {
-        self.camera
-            .zoom_toward_point(scroll_delta, screen_x, screen_y);
-        self.update_camera_uniform();
+    pub fn is_minimap_visible(&self) -> bool {
+        self.minimap.is_visible()
     }
 
-    pub fn update_camera_uniform(&self) {
-        let camera_uniform = CameraUniform {
-            view_proj: self.camera.view_projection_matrix().to_cols_array_2d(),
-        };
+    /// Show or hide the minimap in the bottom-right corner.
+    pub fn set_minimap_visible(&mut self, visible: bool) {
+        self.minimap.set_visible(visible);
+        self.dirty = true;
+    }
+
+    pub fn is_stats_overlay_visible(&self) -> bool {
+        self.stats_overlay_visible
+    }
+
+    /// Show or hide the last frame's stats over the top-left corner.
+    pub fn set_stats_overlay_visible(&mut self, visible: bool) {
+        self.stats_overlay_visible = visible;
+        self.dirty = true;
+    }
+
+    /// Render on the next call to `render`, even if nothing changed.
+    pub fn request_frame(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Whether the next call to `render` will draw a frame.
+    pub fn needs_frame(&self) -> bool {
+        self.dirty || self.camera_animation.is_some() || !self.drawable_animations.is_empty()
+    }
+
+    pub fn update_camera_uniform(&mut self) {
+        let camera_uniform = CameraUniform::from_camera(&self.camera);
 
         self.queue.write_buffer(
             &self.camera_buffer,
             0,
             bytemuck::cast_slice(&[camera_uniform]),
         );
+        self.dirty = true;
     }
 
+    /// Draw and present a frame, unless nothing changed since the last one.
+    ///
+    /// Hosts can call this every animation frame: a static canvas costs
+    /// nothing until the scene or camera changes or `request_frame` is
+    /// called.
     pub fn render(&mut self) -> Result<(), RendererError> {
-        let output = self
-            .surface
-            .get_current_texture()
-            .map_err(|e| RendererError::WgpuError {
-                message: format!("Failed to get texture: {e}"),
-                location: ErrorLocation::from(PanicLocation::caller()),
-            })?;
+        self.advance_camera_animation();
+        self.advance_drawable_animations();
 
-        let view = output
-            .texture
-            .create_view(&TextureViewDescriptor::default());
+        if !self.dirty {
+            return Ok(());
+        }
 
-        let mut encoder = self
-            .device
-            .create_command_encoder(&CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
-            });
+        let start = now();
 
-        {
-            let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
-                label: Some("Render pass"),
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: Operations {
-                        load: LoadOp::Clear(Color {
-                            r: 0.1,
-                            g: 0.1,
-                            b: 0.1,
-                            a: 1.0,
-                        }),
-                        store: StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                occlusion_query_set: None,
-                timestamp_writes: None,
-            });
+        // Too soon for the target frame rate: stay dirty so a later call
+        // draws the frame
+        let too_soon = matches!(
+            (self.min_frame_interval, self.last_frame_start),
+            (Some(interval), Some(last)) if start - last < interval - FRAME_PACING_SLACK_MS
+        );
+        if too_soon {
+            return Ok(());
+        }
 
-            render_pass.set_pipeline(&self.render_pipeline);
-            render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.set_index_buffer(self.index_buffer.slice(..), IndexFormat::Uint16);
+        let (output, view) = match &self.target {
+            RenderTarget::Surface(surface) => {
+                let output = match surface.get_current_texture() {
+                    // The surface went stale, e.g. the canvas was resized
+                    // behind our back, so configure it again and retry once
+                    Err(SurfaceError::Lost | SurfaceError::Outdated) => {
+                        surface.configure(&self.device, &self.config);
+                        surface.get_current_texture()
+                    }
+                    result => result,
+                }
+                .map_err(|e| RendererError::WgpuError {
+                    message: format!("Failed to get texture: {e}"),
+                    location: ErrorLocation::from(PanicLocation::caller()),
+                })?;
 
-            // Draw all drawables
-            for drawable in &self.drawables {
-                let instance_data = InstanceRaw {
-                    model: drawable.model_matrix().to_cols_array_2d(),
-                    color: drawable.color(),
-                };
-
-                let instance_buffer = self.device.create_buffer_init(&BufferInitDescriptor {
-                    label: Some("Instance Buffer"),
-                    contents: bytemuck::cast_slice(&[instance_data]),
-                    usage: BufferUsages::VERTEX,
-                });
-
-                render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
-                render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
+                let view = output
+                    .texture
+                    .create_view(&TextureViewDescriptor::default());
+                (Some(output), view)
             }
-        }
+            RenderTarget::Texture(texture) => {
+                (None, texture.create_view(&TextureViewDescriptor::default()))
+            }
+        };
+
+        let encoder = self.encode_frame(&view)?;
 
         self.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
+        if let Some(output) = output {
+            output.present();
+        }
+        self.dirty = false;
+
+        self.stats.frame_time_ms = (now() - start) as f32;
+        self.stats.frame_interval_ms = self
+            .last_frame_start
+            .map_or(0.0, |last| (start - last) as f32);
+        self.last_frame_start = Some(start);
+        self.events.push(RendererEvent::FrameStats(self.stats));
 
         Ok(())
     }
 
-    pub fn handle_draw_quad_command(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
-        crate::commands::handle_draw_quad(self, bytes)?;
+    /// Id of the topmost pickable drawable under the screen position, as
+    /// text, or nothing when the position is over empty canvas.
+    pub fn pick_drawable(&self, screen_x: f32, screen_y: f32) -> Option<String> {
+        self.pick(screen_x, screen_y).map(|id| id.to_string())
+    }
 
-        Ok(())
+    // Command handlers take the id the host sent the command as and answer
+    // with an encoded `CommandResult`, failures included, rather than
+    // throwing.
+
+    /// Show an encoded `CommandResult` as JSON, for debugging.
+    #[cfg(feature = "debug-json")]
+    pub fn command_result_json(bytes: &[u8]) -> Result<String, JsValue> {
+        Ok(crate::commands::command_result_json(bytes)?)
     }
 
-    pub fn handle_pan_camera_command(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
-        crate::commands::handle_pan_camera(self, bytes)?;
-        Ok(())
+    /// Replies with an encoded `ProtocolDescriptionEvent` of the commands
+    /// this build handles and the descriptors of the protocol.
+    pub fn handle_describe_protocol_command(&self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_describe_protocol(self, bytes),
+        )
     }
 
-    pub fn handle_zoom_camera_command(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
-        crate::commands::handle_zoom_camera(self, bytes)?;
-        Ok(())
+    /// Replies with an encoded `HelloAckEvent` saying whether the renderer
+    /// speaks the host's protocol.
+    pub fn handle_hello_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_hello(self, bytes))
     }
 
-    pub fn handle_resize_viewport_command(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
-        crate::commands::handle_resize_viewport(self, bytes)?;
-        Ok(())
+    pub fn handle_draw_quad_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_draw_quad(self, bytes))
     }
-}
 
-impl Renderer {
-    pub fn add_quad(&mut self, quad: Quad) {
-        self.drawables.push(Box::new(quad));
+    pub fn handle_draw_node_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_draw_node(self, bytes))
     }
 
-    pub fn resize_viewport(&mut self, width: u32, height: u32) {
-        // Update stored size
-        self.size = (width, height);
+    pub fn handle_draw_edge_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_draw_edge(self, bytes))
+    }
 
-        // Reconfigure surface
-        self.config.width = width;
-        self.config.height = height;
-        self.surface.configure(&self.device, &self.config);
+    pub fn handle_update_drawable_transform_command(
+        &mut self,
+        request_id: u32,
+        bytes: &[u8],
+    ) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_update_drawable_transform(self, bytes),
+        )
+    }
 
-        // Update camera viewport
-        self.camera = Camera2DBuilder::default()
-            .with_viewport(width, height)
-            .build()
-            .expect("Failed to rebuild camera with new viewport");
+    pub fn handle_update_drawable_color_command(
+        &mut self,
+        request_id: u32,
+        bytes: &[u8],
+    ) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_update_drawable_color(self, bytes),
+        )
+    }
 
-        self.update_camera_uniform();
+    pub fn handle_update_drawable_state_command(
+        &mut self,
+        request_id: u32,
+        bytes: &[u8],
+    ) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_update_drawable_state(self, bytes),
+        )
+    }
+
+    pub fn handle_scene_snapshot_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_scene_snapshot(self, bytes),
+        )
+    }
+
+    pub fn handle_set_selection_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_set_selection(self, bytes),
+        )
+    }
+
+    pub fn handle_remove_drawable_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_remove_drawable(self, bytes),
+        )
+    }
+
+    pub fn handle_clear_scene_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_clear_scene(self, bytes),
+        )
+    }
+
+    pub fn handle_pan_camera_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_pan_camera(self, bytes))
+    }
+
+    pub fn handle_zoom_camera_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_zoom_camera(self, bytes),
+        )
+    }
+
+    /// Replies with an encoded `CameraState` with where the camera is now.
+    pub fn handle_get_camera_state_command(&self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_get_camera_state(self, bytes),
+        )
+    }
+
+    pub fn handle_set_camera_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_set_camera(self, bytes))
+    }
+
+    pub fn handle_animate_camera_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_animate_camera(self, bytes),
+        )
+    }
+
+    pub fn handle_animate_drawable_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_animate_drawable(self, bytes),
+        )
+    }
+
+    pub fn handle_resize_viewport_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_resize_viewport(self, bytes),
+        )
     }
+
+    pub fn handle_fit_view_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_fit_view(self, bytes))
+    }
+
+    pub fn handle_upload_icon_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_upload_icon(self, bytes),
+        )
+    }
+
+    pub fn handle_set_theme_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_set_theme(self, bytes))
+    }
+
+    /// Replies with an encoded `RenderStatsEvent` describing the last frame.
+    pub fn handle_get_render_stats_command(&self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_get_render_stats(self, bytes),
+        )
+    }
+
+    pub fn handle_update_pending_edge_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_update_pending_edge(self, bytes),
+        )
+    }
+
+    /// Replies with an encoded `PendingEdgeDroppedEvent` naming the port the
+    /// connection was dropped on.
+    pub fn handle_drop_pending_edge_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_drop_pending_edge(self, bytes),
+        )
+    }
+
+    pub fn handle_begin_marquee_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_begin_marquee(self, bytes),
+        )
+    }
+
+    pub fn handle_update_marquee_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_update_marquee(self, bytes),
+        )
+    }
+
+    /// Replies with an encoded `MarqueeSelectionEvent` listing what the rectangle
+    /// selected.
+    pub fn handle_end_marquee_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_end_marquee(self, bytes),
+        )
+    }
+
+    pub fn handle_change_stacking_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_change_stacking(self, bytes),
+        )
+    }
+
+    pub fn handle_set_z_index_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_set_z_index(self, bytes),
+        )
+    }
+
+    pub fn handle_pick_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_pick(self, bytes))
+    }
+
+    pub fn handle_pointer_move_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_pointer_move(self, bytes),
+        )
+    }
+
+    pub fn handle_pointer_click_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_pointer_click(self, bytes),
+        )
+    }
+
+    /// Replies with an encoded `RendererEventBatch` of the events queued since
+    /// the last poll.
+    pub fn handle_poll_events_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(
+            request_id,
+            crate::commands::handle_poll_events(self, bytes),
+        )
+    }
+
+    /// Apply a `CommandBatch` and render once, however many commands it
+    /// holds.
+    pub fn handle_batch_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_batch(self, bytes))
+    }
+
+    /// Apply the commands framed in a stream chunk and render once. A
+    /// frame cut short waits for the rest of it in the next chunk.
+    pub fn handle_frames_command(&mut self, request_id: u32, bytes: &[u8]) -> Vec<u8> {
+        crate::commands::command_result(request_id, crate::commands::handle_frames(self, bytes))
+    }
+
+    /// Resolves to an encoded `CommandResult` replying with a
+    /// `FrameCapturedEvent` holding the canvas as a PNG.
+    pub fn handle_capture_frame_command(&mut self, request_id: u32, bytes: &[u8]) -> Promise {
+        let capture = crate::commands::handle_capture_frame(self, bytes);
+
+        wasm_bindgen_futures::future_to_promise(async move {
+            let event = match capture {
+                Ok(capture) => capture.await,
+                Err(e) => Err(e),
+            };
+            let result = crate::commands::command_result(request_id, event);
+            Ok(Uint8Array::from(result.as_slice()).into())
+        })
+    }
+}
+
+impl Renderer {
+    /// Everything but the device and what's drawn into is the same for
+    /// every renderer. Without a surface, and the mode to present it with,
+    /// frames are drawn into a texture.
+    fn with_target(
+        adapter: &Adapter,
+        (device, queue): (Device, Queue),
+        surface: Option<(Surface<'static>, PresentMode)>,
+        format: TextureFormat,
+        (width, height): (u32, u32),
+        pixel_ratio: f32,
+        options: RendererOptions,
+    ) -> Result<Self, RendererError> {
+        let min_frame_interval = options.min_frame_interval()?;
+
+        let camera = Camera2DBuilder::default()
+            .with_viewport(width, height)
+            .with_pixel_ratio(pixel_ratio)
+            .build()
+            .map_err(|e| RendererError::WgpuError {
+                message: format!("Failed to create Camera: {e}"),
+                location: ErrorLocation::from(PanicLocation::caller()),
+            })?;
+
+        let size = surface_size(&camera, &device);
+
+        let config = SurfaceConfiguration {
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface
+                .as_ref()
+                .map_or(PresentMode::Fifo, |(_, present_mode)| *present_mode),
+            alpha_mode: CompositeAlphaMode::Auto,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        let camera_uniform = CameraUniform::from_camera(&camera);
+
+        let camera_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Camera Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[camera_uniform]),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[BindGroupLayoutEntry {
+                    binding: 0, // @binding(0) in shader
+                    visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+
+        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        let theme = Theme::default();
+
+        let grid_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Grid Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[GridUniform::from_theme(&theme)]),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let grid_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Grid Bind Group Layout"),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let grid_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Grid Bind Group"),
+            layout: &grid_bind_group_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: grid_buffer.as_entire_binding(),
+            }],
+        });
+
+        let icon_atlas = IconAtlas::new(&device);
+        let glyph_atlas = GlyphAtlas::new(&device)?;
+
+        // WebGL2 devices may not multisample every format
+        let multisampled = |format: TextureFormat| {
+            adapter
+                .get_texture_format_features(format)
+                .flags
+                .sample_count_supported(DEFAULT_SAMPLE_COUNT)
+        };
+        let sample_count = if multisampled(config.format) && multisampled(DEPTH_FORMAT) {
+            DEFAULT_SAMPLE_COUNT
+        } else {
+            1
+        };
+
+        let pipelines = Pipelines::new(
+            &device,
+            &camera_bind_group_layout,
+            &grid_bind_group_layout,
+            icon_atlas.bind_group_layout(),
+            glyph_atlas.bind_group_layout(),
+            config.format,
+            sample_count,
+        );
+
+        let vertex_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Quad vertex Buffer"),
+            contents: bytemuck::cast_slice(VERTICES),
+            usage: BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Quad Index Buffer"),
+            contents: bytemuck::cast_slice(INDICES),
+            usage: BufferUsages::INDEX,
+        });
+
+        let num_indices = INDICES.len() as u32;
+
+        let buffer_pool = BufferPool::default();
+        let instance_buffer = InstanceBuffer::new(&device, &buffer_pool, "Instance Buffer");
+        let edge_instance_buffer =
+            InstanceBuffer::new(&device, &buffer_pool, "Edge Instance Buffer");
+        let node_instance_buffer =
+            InstanceBuffer::new(&device, &buffer_pool, "Node Instance Buffer");
+        let port_instance_buffer =
+            InstanceBuffer::new(&device, &buffer_pool, "Port Instance Buffer");
+        let sprite_instance_buffer =
+            InstanceBuffer::new(&device, &buffer_pool, "Sprite Instance Buffer");
+        let glyph_instance_buffer =
+            InstanceBuffer::new(&device, &buffer_pool, "Glyph Instance Buffer");
+        let gizmo_instance_buffer =
+            InstanceBuffer::new(&device, &buffer_pool, "Gizmo Instance Buffer");
+
+        let target = match surface {
+            Some((surface, _)) => {
+                surface.configure(&device, &config);
+                RenderTarget::Surface(surface)
+            }
+            None => {
+                RenderTarget::Texture(create_color_texture(&device, &config, "Offscreen Target"))
+            }
+        };
+
+        let msaa_target = create_msaa_target(&device, &config, sample_count);
+        let depth_target = create_depth_target(&device, &config, sample_count);
+
+        let minimap = Minimap::new(&device, &buffer_pool, &camera_bind_group_layout);
+
+        Ok(Self {
+            target,
+            device,
+            queue,
+            backend: adapter.get_info().backend,
+            config,
+            size,
+            pipelines,
+            sample_count,
+            msaa_target,
+            depth_target,
+            vertex_buffer,
+            index_buffer,
+            num_indices,
+            buffer_pool,
+            instance_buffer,
+            edge_instance_buffer,
+            node_instance_buffer,
+            port_instance_buffer,
+            sprite_instance_buffer,
+            glyph_instance_buffer,
+            gizmo_instance_buffer,
+            icon_atlas,
+            glyph_atlas,
+            camera,
+            camera_buffer,
+            camera_bind_group_layout,
+            camera_bind_group,
+            theme,
+            grid_buffer,
+            grid_bind_group_layout,
+            grid_bind_group,
+            minimap,
+            quads: Vec::new(),
+            node_widgets: Vec::new(),
+            edges: Vec::new(),
+            port_anchors: Vec::new(),
+            sprites: Vec::new(),
+            text_labels: Vec::new(),
+            node_titles: HashMap::new(),
+            pending_edge: None,
+            marquee: None,
+            host_protocol: None,
+            command_compression: Compression::None,
+            frame_decoder: FrameDecoder::new(),
+            hovered: None,
+            events: EventQueue::default(),
+            selection_changed: false,
+            dirty: true,
+            camera_animation: None,
+            drawable_animations: HashMap::new(),
+            stats: FrameStats::default(),
+            last_frame_start: None,
+            min_frame_interval,
+            stats_overlay_visible: false,
+        })
+    }
+
+    /// Width and height of the drawing surface, in physical pixels.
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// The protocol and schema versions the host said it speaks in its
+    /// hello, if it sent one.
+    pub fn host_protocol(&self) -> Option<(u32, u32)> {
+        self.host_protocol
+    }
+
+    pub fn set_host_protocol(&mut self, protocol_version: u32, schema_version: u32) {
+        self.host_protocol = Some((protocol_version, schema_version));
+    }
+
+    /// Move the camera to the world position `x`, `y` at `zoom` over
+    /// `duration` seconds, eased by `easing`. Each `render` moves it along
+    /// until it gets there, or pan or zoom input interrupts it.
+    pub fn animate_camera(&mut self, x: f32, y: f32, zoom: f32, duration: f32, easing: Easing) {
+        let animation = CameraAnimation::new(
+            (self.camera.position(), self.camera.zoom()),
+            (
+                Vec2::new(x, y),
+                zoom.clamp(self.camera.zoom_min(), self.camera.zoom_max()),
+            ),
+            duration,
+            easing,
+        );
+
+        self.camera_animation = Some((animation, now()));
+        self.dirty = true;
+    }
+
+    /// Move the node widget, with its ports, or port anchor `id` to
+    /// `position` over `duration` seconds, eased by `easing`, resizing it to
+    /// `size` along the way if given. Replaces any animation of it in
+    /// progress. Returns whether there was one.
+    pub fn animate_drawable(
+        &mut self,
+        id: DrawableId,
+        position: Vec2,
+        size: Option<Vec2>,
+        duration: f32,
+        easing: Easing,
+    ) -> bool {
+        let Some(from) = self.drawable_transform(id) else {
+            return false;
+        };
+
+        let animation =
+            TransformAnimation::new(from, (position, size.unwrap_or(from.1)), duration, easing);
+        self.drawable_animations.insert(id, (animation, now()));
+        self.dirty = true;
+        true
+    }
+
+    /// The center and size of the node widget or port anchor `id`, in world
+    /// units.
+    pub fn drawable_transform(&self, id: DrawableId) -> Option<(Vec2, Vec2)> {
+        let (position, size) =
+            if let Some(widget) = self.node_widgets.iter().find(|widget| widget.id == id) {
+                (widget.position, widget.size)
+            } else {
+                let anchor = self.port_anchors.iter().find(|anchor| anchor.id == id)?;
+                (anchor.position, anchor.size)
+            };
+
+        Some((Vec2::new(position[0], position[1]), Vec2::from(size)))
+    }
+
+    /// Where the camera is centered, in world units, and its zoom.
+    pub fn camera_view(&self) -> (Vec2, f32) {
+        (self.camera.position(), self.camera.zoom())
+    }
+
+    pub fn command_compression(&self) -> Compression {
+        self.command_compression
+    }
+
+    pub fn set_command_compression(&mut self, compression: Compression) {
+        self.command_compression = compression;
+    }
+
+    pub fn frame_decoder_mut(&mut self) -> &mut FrameDecoder {
+        &mut self.frame_decoder
+    }
+
+    /// Draw with `theme`'s background and grid colors from the next frame
+    /// on.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.queue.write_buffer(
+            &self.grid_buffer,
+            0,
+            bytemuck::cast_slice(&[GridUniform::from_theme(&theme)]),
+        );
+        self.theme = theme;
+        self.dirty = true;
+    }
+
+    /// What the last frame drew and what it cost.
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+
+    pub fn add_quad(&mut self, quad: Quad) {
+        self.quads.push(quad);
+        self.dirty = true;
+    }
+
+    /// Replace the quads in the scene with `quads` and render them in one
+    /// pass.
+    pub fn draw_quads(&mut self, quads: &[Quad]) -> Result<(), RendererError> {
+        self.quads.clear();
+        self.quads.extend_from_slice(quads);
+        self.dirty = true;
+        self.render()
+    }
+
+    pub fn add_node_widget(&mut self, widget: NodeWidget) {
+        self.node_widgets.push(widget);
+        self.dirty = true;
+    }
+
+    /// Replace the node widgets in the scene with `widgets` and render them
+    /// in one pass.
+    pub fn draw_node_widgets(&mut self, widgets: &[NodeWidget]) -> Result<(), RendererError> {
+        self.node_widgets.clear();
+        self.node_widgets.extend_from_slice(widgets);
+        self.dirty = true;
+        self.render()
+    }
+
+    /// Add `widget` to the scene, or replace the node widget with its id,
+    /// with `title` in its header and `ports` in place of the ones on it
+    /// before. Whatever the pointer is over stays highlighted.
+    pub fn draw_node(&mut self, mut widget: NodeWidget, title: &str, ports: &[PortAnchor]) {
+        let id = widget.id;
+        widget.hovered = self.hovered == Some(id);
+        match self
+            .node_widgets
+            .iter_mut()
+            .find(|existing| existing.id == id)
+        {
+            Some(existing) => {
+                self.selection_changed |= existing.selected != widget.selected;
+                *existing = widget;
+            }
+            None => {
+                self.selection_changed |= widget.selected;
+                self.node_widgets.push(widget);
+            }
+        }
+
+        self.port_anchors.retain(|anchor| anchor.node != Some(id));
+        self.port_anchors
+            .extend(ports.iter().map(|anchor| PortAnchor {
+                hovered: self.hovered == Some(anchor.id),
+                ..*anchor
+            }));
+
+        if title.is_empty() {
+            self.node_titles.remove(&id);
+        } else {
+            self.node_titles.insert(id, String::from(title));
+        }
+        self.dirty = true;
+    }
+
+    pub fn add_port_anchor(&mut self, anchor: PortAnchor) {
+        self.port_anchors.push(anchor);
+        self.dirty = true;
+    }
+
+    /// Replace the port anchors in the scene with `anchors` and render them
+    /// in one pass.
+    pub fn draw_port_anchors(&mut self, anchors: &[PortAnchor]) -> Result<(), RendererError> {
+        self.port_anchors.clear();
+        self.port_anchors.extend_from_slice(anchors);
+        self.dirty = true;
+        self.render()
+    }
+
+    pub fn add_edge(&mut self, edge: Edge) {
+        self.edges.push(edge);
+        self.dirty = true;
+    }
+
+    /// Add `edge` to the scene, or replace the edge with its id.
+    pub fn draw_edge(&mut self, edge: Edge) {
+        match self
+            .edges
+            .iter_mut()
+            .find(|existing| existing.id == edge.id)
+        {
+            Some(existing) => *existing = edge,
+            None => self.edges.push(edge),
+        }
+        self.dirty = true;
+    }
+
+    /// Replace the edges in the scene with `edges` and render them in one
+    /// pass.
+    pub fn draw_edges(&mut self, edges: &[Edge]) -> Result<(), RendererError> {
+        self.edges.clear();
+        self.edges.extend_from_slice(edges);
+        self.dirty = true;
+        self.render()
+    }
+
+    /// Move the node widget or port anchor `id` to `position`, in world
+    /// units, and resize it to `size` if given. A node's ports move with it,
+    /// down its sides, and an animation of it in progress ends. Returns
+    /// whether there was one.
+    pub fn set_drawable_transform(
+        &mut self,
+        id: DrawableId,
+        position: Vec2,
+        size: Option<Vec2>,
+    ) -> bool {
+        self.drawable_animations.remove(&id);
+        self.place_drawable(id, position, size)
+    }
+
+    fn place_drawable(&mut self, id: DrawableId, position: Vec2, size: Option<Vec2>) -> bool {
+        if let Some(widget) = self.node_widgets.iter_mut().find(|widget| widget.id == id) {
+            widget.position = [position.x, position.y, widget.position[2]];
+            if let Some(size) = size {
+                widget.size = size.to_array();
+            }
+            let widget = *widget;
+
+            // Spread down each side in the order the ports were drawn in
+            for direction in [PortDirection::Input, PortDirection::Output] {
+                let on_side =
+                    |anchor: &PortAnchor| anchor.node == Some(id) && anchor.direction == direction;
+                let count = self.port_anchors.iter().filter(|a| on_side(a)).count();
+                for (index, anchor) in self
+                    .port_anchors
+                    .iter_mut()
+                    .filter(|a| on_side(a))
+                    .enumerate()
+                {
+                    anchor.position = widget.port_anchor_position(direction, index, count);
+                }
+            }
+        } else if let Some(anchor) = self.port_anchors.iter_mut().find(|anchor| anchor.id == id) {
+            anchor.position = [position.x, position.y, anchor.position[2]];
+            if let Some(size) = size {
+                anchor.size = size.to_array();
+            }
+        } else {
+            return false;
+        }
+
+        self.dirty = true;
+        true
+    }
+
+    /// Recolor the node widget, port anchor, or edge `id`: `color` is a
+    /// node's body, or the whole of a port or edge, and a node's `border`
+    /// and `header` are its own. Colors not given stay as they are. Returns
+    /// whether there was one.
+    pub fn set_drawable_color(
+        &mut self,
+        id: DrawableId,
+        color: Option<[f32; 4]>,
+        border: Option<[f32; 4]>,
+        header: Option<[f32; 4]>,
+    ) -> bool {
+        if let Some(widget) = self.node_widgets.iter_mut().find(|widget| widget.id == id) {
+            widget.fill_color = color.unwrap_or(widget.fill_color);
+            widget.border_color = border.unwrap_or(widget.border_color);
+            widget.header_color = header.unwrap_or(widget.header_color);
+        } else if let Some(anchor) = self.port_anchors.iter_mut().find(|anchor| anchor.id == id) {
+            anchor.color = color.unwrap_or(anchor.color);
+        } else if let Some(edge) = self.edges.iter_mut().find(|edge| edge.id == id) {
+            edge.color = color.unwrap_or(edge.color);
+        } else {
+            return false;
+        }
+
+        self.dirty = true;
+        true
+    }
+
+    /// Mark the node widget `id` selected or not, or the port anchor `id`
+    /// connected or not. States not given stay as they are. Returns whether
+    /// there was one.
+    pub fn set_drawable_state(
+        &mut self,
+        id: DrawableId,
+        selected: Option<bool>,
+        connected: Option<bool>,
+    ) -> bool {
+        if let Some(widget) = self.node_widgets.iter_mut().find(|widget| widget.id == id) {
+            let selected = selected.unwrap_or(widget.selected);
+            self.selection_changed |= widget.selected != selected;
+            widget.selected = selected;
+        } else if let Some(anchor) = self.port_anchors.iter_mut().find(|anchor| anchor.id == id) {
+            anchor.connected = connected.unwrap_or(anchor.connected);
+        } else {
+            return false;
+        }
+
+        self.dirty = true;
+        true
+    }
+
+    /// Remove the node widget, with its title and ports, port anchor, or
+    /// edge `id` from the scene. Returns whether there was one.
+    ///
+    /// Edges on ports that are removed stay in the scene, undrawn, until
+    /// they're removed too or the ports come back.
+    pub fn remove_drawable(&mut self, id: DrawableId) -> bool {
+        let count = self.node_widgets.len() + self.port_anchors.len() + self.edges.len();
+
+        self.selection_changed |= self
+            .node_widgets
+            .iter()
+            .any(|widget| widget.id == id && widget.selected);
+        self.node_widgets.retain(|widget| widget.id != id);
+        self.port_anchors
+            .retain(|anchor| anchor.id != id && anchor.node != Some(id));
+        self.edges.retain(|edge| edge.id != id);
+        self.node_titles.remove(&id);
+
+        let removed = count != self.node_widgets.len() + self.port_anchors.len() + self.edges.len();
+        if removed {
+            // Ports go with their node, which may have been hovered
+            if self
+                .hovered
+                .is_some_and(|hovered| !self.has_pickable(hovered))
+            {
+                self.hovered = None;
+            }
+            self.dirty = true;
+        }
+
+        removed
+    }
+
+    /// Remove everything from the scene, along with the connection being
+    /// dragged and the selection marquee, e.g. to switch to another graph.
+    /// The camera, theme, and uploaded icons stay as they are.
+    pub fn clear_scene(&mut self) {
+        self.selection_changed |= self.node_widgets.iter().any(|widget| widget.selected);
+        self.drawable_animations.clear();
+        self.quads.clear();
+        self.edges.clear();
+        self.node_widgets.clear();
+        self.port_anchors.clear();
+        self.sprites.clear();
+        self.text_labels.clear();
+        self.node_titles.clear();
+        self.pending_edge = None;
+        self.marquee = None;
+        self.hovered = None;
+        self.dirty = true;
+    }
+
+    /// The topmost pickable drawable under the screen position.
+    ///
+    /// Picking is a query over the retained scene on the CPU, in draw order
+    /// reversed, so whatever is drawn on top wins.
+    pub fn pick(&self, screen_x: f32, screen_y: f32) -> Option<DrawableId> {
+        let point = self.camera.screen_to_world(screen_x, screen_y);
+        let (_, height) = self.camera.viewport_size();
+        // World units per logical pixel
+        let pixel = 2.0 / self.camera.zoom() / height as f32;
+
+        // Of equal orders, ports are drawn over widgets, widgets over edges,
+        // and the one added last is drawn last
+        let edges = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, edge)| {
+                self.edge_curve(edge).is_some_and(|curve| {
+                    curve.distance_to(point, EDGE_CURVE_SEGMENTS)
+                        <= edge.width / 2.0 + EDGE_PICK_TOLERANCE * pixel
+                })
+            })
+            .map(|(index, edge)| ((edge.order, 0, index), edge.id));
+        let widgets = self
+            .node_widgets
+            .iter()
+            .enumerate()
+            .filter(|(_, widget)| widget.contains(point))
+            .map(|(index, widget)| ((widget.order, 1, index), widget.id()));
+        let ports = self
+            .port_anchors
+            .iter()
+            .enumerate()
+            .filter(|(_, anchor)| anchor.contains(point))
+            .map(|(index, anchor)| ((anchor.order, 2, index), anchor.id()));
+
+        edges
+            .chain(widgets)
+            .chain(ports)
+            .max_by_key(|(key, _)| *key)
+            .map(|(_, id)| id)
+    }
+
+    /// Queue what's under the screen position, for the host to poll.
+    pub fn request_pick(&mut self, screen_x: f32, screen_y: f32) {
+        let drawable = self.pick(screen_x, screen_y);
+        let world = self.camera.screen_to_world(screen_x, screen_y);
+
+        self.events.push(RendererEvent::Picked { drawable, world });
+    }
+
+    /// Queue a click at the screen position on whatever is there.
+    pub fn pointer_clicked(&mut self, screen_x: f32, screen_y: f32) {
+        let drawable = self.pick(screen_x, screen_y);
+        let world = self.camera.screen_to_world(screen_x, screen_y);
+
+        self.events.push(RendererEvent::Clicked { drawable, world });
+    }
+
+    /// Highlight the node widget or port anchor under the pointer at the
+    /// screen position instead of the one it was over before, queuing the
+    /// change when it's over a different drawable.
+    pub fn pointer_moved(&mut self, screen_x: f32, screen_y: f32) {
+        let current = self.pick(screen_x, screen_y);
+        if current == self.hovered {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.hovered, current);
+
+        for widget in &mut self.node_widgets {
+            if Some(widget.id) == previous || Some(widget.id) == current {
+                widget.hovered = Some(widget.id) == current;
+            }
+        }
+        for anchor in &mut self.port_anchors {
+            if Some(anchor.id) == previous || Some(anchor.id) == current {
+                anchor.hovered = Some(anchor.id) == current;
+            }
+        }
+
+        self.events
+            .push(RendererEvent::HoverChanged { previous, current });
+        self.dirty = true;
+    }
+
+    /// Every event queued since the last call, oldest first, then the
+    /// selection if it changed since.
+    pub fn take_events(&mut self) -> Vec<RendererEvent> {
+        if std::mem::take(&mut self.selection_changed) {
+            self.events
+                .push(RendererEvent::SelectionChanged(self.selection()));
+        }
+
+        self.events.drain()
+    }
+
+    /// The node widgets selected, in the order they were added.
+    pub fn selection(&self) -> Vec<DrawableId> {
+        self.node_widgets
+            .iter()
+            .filter(|widget| widget.selected)
+            .map(|widget| widget.id)
+            .collect()
+    }
+
+    /// Select exactly the node widgets in `ids`, deselecting every other.
+    /// Returns the first id that isn't a node widget's, if any, leaving the
+    /// selection as it was.
+    pub fn set_selection(&mut self, ids: &[DrawableId]) -> Result<(), DrawableId> {
+        if let Some(&missing) = ids
+            .iter()
+            .find(|id| !self.node_widgets.iter().any(|widget| widget.id == **id))
+        {
+            return Err(missing);
+        }
+
+        for widget in &mut self.node_widgets {
+            let selected = ids.contains(&widget.id);
+            if widget.selected != selected {
+                widget.selected = selected;
+                self.selection_changed = true;
+                self.dirty = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start or move the connection being dragged out of the port `source`
+    /// to the pointer at the screen position. It snaps to the nearest of the
+    /// `compatible` ports close enough to the pointer.
+    pub fn update_pending_edge(
+        &mut self,
+        source: DrawableId,
+        screen_x: f32,
+        screen_y: f32,
+        compatible: &[DrawableId],
+    ) {
+        let pointer = self.camera.screen_to_world(screen_x, screen_y);
+        let (_, height) = self.camera.viewport_size();
+        // World units per logical pixel
+        let pixel = 2.0 / self.camera.zoom() / height as f32;
+        let reach = PORT_SNAP_DISTANCE * pixel;
+
+        let target = self
+            .port_anchors
+            .iter()
+            .filter(|anchor| compatible.contains(&anchor.id))
+            .map(|anchor| {
+                let center = Vec2::new(anchor.position[0], anchor.position[1]);
+                (center.distance(pointer), anchor.id)
+            })
+            .filter(|(distance, _)| *distance <= reach)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, id)| id);
+
+        self.pending_edge = Some(PendingEdge {
+            source,
+            pointer,
+            target,
+        });
+        self.dirty = true;
+    }
+
+    /// The port the connection being dragged is snapped to, if any.
+    pub fn pending_edge_target(&self) -> Option<DrawableId> {
+        self.pending_edge.as_ref().and_then(|edge| edge.target)
+    }
+
+    /// Stack the node widget `id` above every other one in its layer, with
+    /// its ports. Returns whether there is such a widget.
+    pub fn bring_to_front(&mut self, id: DrawableId) -> bool {
+        let top = self.sibling_z_indices(id).max();
+        self.restack(id, |z_index| {
+            top.map_or(z_index, |top| top.saturating_add(1))
+        })
+    }
+
+    /// Stack the node widget `id` below every other one in its layer, with
+    /// its ports. Returns whether there is such a widget.
+    pub fn send_to_back(&mut self, id: DrawableId) -> bool {
+        let bottom = self.sibling_z_indices(id).min();
+        self.restack(id, |z_index| {
+            bottom.map_or(z_index, |bottom| bottom.saturating_sub(1))
+        })
+    }
+
+    /// Give the node widget `id`, and its ports, the z-index `z_index`.
+    /// Returns whether there is such a widget.
+    pub fn set_z_index(&mut self, id: DrawableId, z_index: i32) -> bool {
+        self.restack(id, |_| z_index)
+    }
+
+    /// Start dragging out a selection rectangle from the screen position.
+    pub fn begin_marquee(&mut self, screen_x: f32, screen_y: f32) {
+        let start = Vec2::new(screen_x, screen_y);
+        self.marquee = Some(Marquee { start, end: start });
+        self.dirty = true;
+    }
+
+    /// Stretch the selection rectangle to the screen position. Does nothing
+    /// unless one was begun.
+    pub fn update_marquee(&mut self, screen_x: f32, screen_y: f32) {
+        if let Some(marquee) = &mut self.marquee {
+            marquee.end = Vec2::new(screen_x, screen_y);
+            self.dirty = true;
+        }
+    }
+
+    /// Remove the selection rectangle. Returns the node widgets wholly
+    /// inside it, in the order they were added.
+    pub fn end_marquee(&mut self) -> Vec<DrawableId> {
+        let Some(marquee) = self.marquee.take() else {
+            return Vec::new();
+        };
+        self.dirty = true;
+
+        let start = self
+            .camera
+            .screen_to_world(marquee.start.x, marquee.start.y);
+        let end = self.camera.screen_to_world(marquee.end.x, marquee.end.y);
+        let (min, max) = (start.min(end), start.max(end));
+
+        self.node_widgets
+            .iter()
+            .filter(|widget| {
+                let center = Vec2::new(widget.position[0], widget.position[1]);
+                let half = Vec2::from(widget.size).abs() / 2.0;
+                (center - half).cmpge(min).all() && (center + half).cmple(max).all()
+            })
+            .map(|widget| widget.id())
+            .collect()
+    }
+
+    /// Remove the connection being dragged. Returns the port it was snapped
+    /// to, which it was dropped on, if any.
+    pub fn drop_pending_edge(&mut self) -> Option<DrawableId> {
+        let edge = self.pending_edge.take()?;
+        self.dirty = true;
+
+        edge.target
+    }
+
+    /// Upload a `width` by `height` image of RGBA pixels to the icon atlas
+    /// as `name`, for sprites to draw. Uploading a name again replaces its
+    /// image.
+    pub fn upload_icon(
+        &mut self,
+        name: &str,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+    ) -> Result<(), RendererError> {
+        self.icon_atlas
+            .upload(&self.queue, name, width, height, rgba)?;
+        self.dirty = true;
+
+        Ok(())
+    }
+
+    pub fn add_sprite(&mut self, sprite: Sprite) {
+        self.sprites.push(sprite);
+        self.dirty = true;
+    }
+
+    /// Replace the sprites in the scene with `sprites` and render them in
+    /// one pass.
+    pub fn draw_sprites(&mut self, sprites: &[Sprite]) -> Result<(), RendererError> {
+        self.sprites.clear();
+        self.sprites.extend_from_slice(sprites);
+        self.dirty = true;
+        self.render()
+    }
+
+    pub fn add_text_label(&mut self, label: TextLabel) {
+        self.text_labels.push(label);
+        self.dirty = true;
+    }
+
+    /// Replace the text in the scene with `labels` and render it in one pass.
+    pub fn draw_text(&mut self, labels: &[TextLabel]) -> Result<(), RendererError> {
+        self.text_labels.clear();
+        self.text_labels.extend_from_slice(labels);
+        self.dirty = true;
+        self.render()
+    }
+
+    /// Encode the passes drawing the scene, grid and minimap included, into
+    /// `view`, which must match the surface's size and format.
+    fn encode_frame(&mut self, view: &TextureView) -> Result<CommandEncoder, RendererError> {
+        let background = self.theme.background;
+        let mut encoder = self
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("Render Encoder"),
+            });
+
+        let ranges = self.upload_instances()?;
+        let mut draw_calls = 0;
+
+        let scene_bounds = self.scene_bounds();
+        let visible = self.visible_region();
+        let minimap_viewport = self.minimap.prepare(
+            &self.device,
+            &self.queue,
+            self.size,
+            self.camera.pixel_ratio(),
+            scene_bounds,
+            visible,
+        );
+
+        // When multisampling, draw into the multisampled target and resolve
+        // it to the surface; its samples aren't needed after that, unless the
+        // minimap pass draws over them
+        let (color_view, resolve_target, store) = match &self.msaa_target {
+            Some(msaa_view) if minimap_viewport.is_none() => {
+                (msaa_view, Some(view), StoreOp::Discard)
+            }
+            Some(msaa_view) => (msaa_view, Some(view), StoreOp::Store),
+            None => (view, None, StoreOp::Store),
+        };
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("Render pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: color_view,
+                    resolve_target,
+                    ops: Operations {
+                        load: LoadOp::Clear(Color {
+                            r: background[0] as f64,
+                            g: background[1] as f64,
+                            b: background[2] as f64,
+                            a: background[3] as f64,
+                        }),
+                        store,
+                    },
+                    depth_slice: None,
+                })],
+                // Only needed while drawing; the minimap pass clears it again
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                    view: &self.depth_target,
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Clear(1.0),
+                        store: StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), IndexFormat::Uint16);
+
+            // The grid goes first, behind everything else
+            render_pass.set_pipeline(&self.pipelines.grid);
+            render_pass.set_bind_group(1, &self.grid_bind_group, &[]);
+            render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
+            draw_calls += 1;
+
+            // Painter's order: layer by layer, each layer's quads, then its
+            // edges, node widgets, port anchors, sprites, and text, one
+            // instanced call each
+            for layer in Layer::ALL {
+                let index = layer as usize;
+
+                if !ranges.quads[index].is_empty() {
+                    render_pass.set_pipeline(&self.pipelines.quad);
+                    render_pass.set_vertex_buffer(1, self.instance_buffer.slice());
+                    render_pass.draw_indexed(0..self.num_indices, 0, ranges.quads[index].clone());
+                    draw_calls += 1;
+                }
+
+                if !ranges.edges[index].is_empty() {
+                    render_pass.set_pipeline(&self.pipelines.edge);
+                    render_pass.set_vertex_buffer(1, self.edge_instance_buffer.slice());
+                    render_pass.draw_indexed(0..self.num_indices, 0, ranges.edges[index].clone());
+                    draw_calls += 1;
+                }
+
+                if !ranges.widgets[index].is_empty() {
+                    render_pass.set_pipeline(&self.pipelines.node);
+                    render_pass.set_vertex_buffer(1, self.node_instance_buffer.slice());
+                    render_pass.draw_indexed(0..self.num_indices, 0, ranges.widgets[index].clone());
+                    draw_calls += 1;
+                }
+
+                if !ranges.ports[index].is_empty() {
+                    render_pass.set_pipeline(&self.pipelines.port);
+                    render_pass.set_vertex_buffer(1, self.port_instance_buffer.slice());
+                    render_pass.draw_indexed(0..self.num_indices, 0, ranges.ports[index].clone());
+                    draw_calls += 1;
+                }
+
+                if !ranges.sprites[index].is_empty() {
+                    render_pass.set_pipeline(&self.pipelines.sprite);
+                    render_pass.set_bind_group(1, self.icon_atlas.bind_group(), &[]);
+                    render_pass.set_vertex_buffer(1, self.sprite_instance_buffer.slice());
+                    render_pass.draw_indexed(0..self.num_indices, 0, ranges.sprites[index].clone());
+                    draw_calls += 1;
+                }
+
+                if !ranges.glyphs[index].is_empty() {
+                    render_pass.set_pipeline(&self.pipelines.text);
+                    render_pass.set_bind_group(1, self.glyph_atlas.bind_group(), &[]);
+                    render_pass.set_vertex_buffer(1, self.glyph_instance_buffer.slice());
+                    render_pass.draw_indexed(0..self.num_indices, 0, ranges.glyphs[index].clone());
+                    draw_calls += 1;
+                }
+            }
+
+            // Screen-space gizmos, like the selection marquee, over the scene
+            if !ranges.gizmos.is_empty() {
+                render_pass.set_pipeline(&self.pipelines.overlay);
+                render_pass.set_vertex_buffer(1, self.gizmo_instance_buffer.slice());
+                render_pass.draw_indexed(0..self.num_indices, 0, ranges.gizmos.clone());
+                draw_calls += 1;
+            }
+        }
+
+        if let Some(viewport) = minimap_viewport {
+            let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("Minimap pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: color_view,
+                    resolve_target,
+                    ops: Operations {
+                        load: LoadOp::Load,
+                        store: match resolve_target {
+                            Some(_) => StoreOp::Discard,
+                            None => StoreOp::Store,
+                        },
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                    view: &self.depth_target,
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Clear(1.0),
+                        store: StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_viewport(
+                viewport.x as f32,
+                viewport.y as f32,
+                viewport.width as f32,
+                viewport.height as f32,
+                0.0,
+                1.0,
+            );
+            render_pass.set_scissor_rect(viewport.x, viewport.y, viewport.width, viewport.height);
+            render_pass.set_bind_group(0, self.minimap.camera_bind_group(), &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), IndexFormat::Uint16);
+
+            // The panel behind the scene
+            render_pass.set_pipeline(&self.pipelines.node);
+            render_pass.set_vertex_buffer(1, self.minimap.overlay_slice());
+            render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
+            draw_calls += 1;
+
+            // The scene at low detail: its shapes in painter's order, no text
+            for layer in Layer::ALL {
+                let index = layer as usize;
+
+                if !ranges.quads[index].is_empty() {
+                    render_pass.set_pipeline(&self.pipelines.quad);
+                    render_pass.set_vertex_buffer(1, self.instance_buffer.slice());
+                    render_pass.draw_indexed(0..self.num_indices, 0, ranges.quads[index].clone());
+                    draw_calls += 1;
+                }
+
+                if !ranges.widgets[index].is_empty() {
+                    render_pass.set_pipeline(&self.pipelines.node);
+                    render_pass.set_vertex_buffer(1, self.node_instance_buffer.slice());
+                    render_pass.draw_indexed(0..self.num_indices, 0, ranges.widgets[index].clone());
+                    draw_calls += 1;
+                }
+            }
+
+            // And the main camera's visible region over it
+            render_pass.set_pipeline(&self.pipelines.node);
+            render_pass.set_vertex_buffer(1, self.minimap.overlay_slice());
+            render_pass.draw_indexed(0..self.num_indices, 0, 1..2);
+            draw_calls += 1;
+        }
+
+        let instances = |ranges: &LayerRanges| ranges.iter().map(|range| range.len() as u32).sum();
+        self.stats.draw_calls = draw_calls;
+        self.stats.quad_instances = instances(&ranges.quads);
+        self.stats.edge_instances = instances(&ranges.edges);
+        self.stats.node_instances = instances(&ranges.widgets);
+        self.stats.port_instances = instances(&ranges.ports);
+        self.stats.sprite_instances = instances(&ranges.sprites);
+        self.stats.glyph_instances = instances(&ranges.glyphs);
+        self.stats.instance_buffer_bytes = self.instance_buffer.size()
+            + self.edge_instance_buffer.size()
+            + self.node_instance_buffer.size()
+            + self.port_instance_buffer.size()
+            + self.sprite_instance_buffer.size()
+            + self.glyph_instance_buffer.size()
+            + self.gizmo_instance_buffer.size();
+        self.stats.buffer_allocations = self.buffer_pool.take_allocations();
+        self.stats.pooled_buffer_bytes = self.buffer_pool.pooled_bytes();
+
+        Ok(encoder)
+    }
+
+    /// Render the scene into an offscreen texture the size of the surface and
+    /// read it back as a PNG, e.g. to export the canvas as an image.
+    ///
+    /// The frame is drawn right away, whether or not anything changed, but
+    /// reading it back waits on the GPU, so the PNG comes from the returned
+    /// future. The surface is left as it was.
+    #[track_caller]
+    pub fn capture_frame(
+        &mut self,
+    ) -> Result<impl Future<Output = Result<Vec<u8>, RendererError>> + 'static, RendererError> {
+        let texture = create_color_texture(&self.device, &self.config, "Capture Target");
+        let view = texture.create_view(&TextureViewDescriptor::default());
+
+        let mut encoder = self.encode_frame(&view)?;
+        let readback = FrameReadback::new(&self.device, &self.buffer_pool, &mut encoder, &texture)?;
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        Ok(readback.into_png())
+    }
+
+    /// The smallest and largest corners of the box around every quad and
+    /// node widget, in world units, or nothing for an empty scene.
+    fn scene_bounds(&self) -> Option<(Vec2, Vec2)> {
+        let quads = self.quads.iter().map(|quad| (quad.position, quad.size));
+        let widgets = self
+            .node_widgets
+            .iter()
+            .map(|widget| (widget.position, widget.size));
+
+        quads
+            .chain(widgets)
+            .map(|(position, size)| {
+                let center = Vec2::new(position[0], position[1]);
+                let half = Vec2::from(size).abs() / 2.0;
+                (center - half, center + half)
+            })
+            .reduce(|(min, max), (item_min, item_max)| (min.min(item_min), max.max(item_max)))
+    }
+
+    /// The smallest and largest corners of the part of the world the camera
+    /// shows.
+    fn visible_region(&self) -> (Vec2, Vec2) {
+        let (width, height) = self.camera.viewport_size();
+        let bottom_left = self.camera.screen_to_world(0.0, height as f32);
+        let top_right = self.camera.screen_to_world(width as f32, 0.0);
+
+        (bottom_left, top_right)
+    }
+
+    /// Move the drawables being animated to where their animations are by
+    /// now, ending each once it's there or its drawable is gone.
+    fn advance_drawable_animations(&mut self) {
+        if self.drawable_animations.is_empty() {
+            return;
+        }
+
+        let now = now();
+        let animations: Vec<_> = self
+            .drawable_animations
+            .iter()
+            .map(|(id, (animation, start))| (*id, *animation, *start))
+            .collect();
+        for (id, animation, start) in animations {
+            let elapsed = ((now - start) / 1000.0) as f32;
+            let (position, size) = animation.sample(elapsed);
+            if !self.place_drawable(id, position, Some(size)) || animation.is_finished(elapsed) {
+                self.drawable_animations.remove(&id);
+            }
+        }
+    }
+
+    /// Move the camera to where its animation is by now, ending the
+    /// animation once it's there.
+    fn advance_camera_animation(&mut self) {
+        let Some((animation, start)) = self.camera_animation else {
+            return;
+        };
+
+        let elapsed = ((now() - start) / 1000.0) as f32;
+        let (position, zoom) = animation.sample(elapsed);
+        self.camera.set_view(position, zoom);
+        self.update_camera_uniform();
+
+        if animation.is_finished(elapsed) {
+            self.camera_animation = None;
+        }
+    }
+
+    /// Match the surface or texture to the camera's physical viewport size, if it
+    /// changed.
+    fn configure_surface(&mut self) {
+        let size = surface_size(&self.camera, &self.device);
+        if size == self.size {
+            return;
+        }
+
+        self.size = size;
+        self.config.width = size.0;
+        self.config.height = size.1;
+        match &mut self.target {
+            RenderTarget::Surface(surface) => surface.configure(&self.device, &self.config),
+            RenderTarget::Texture(texture) => {
+                *texture = create_color_texture(&self.device, &self.config, "Offscreen Target");
+            }
+        }
+        self.msaa_target = create_msaa_target(&self.device, &self.config, self.sample_count);
+        self.depth_target = create_depth_target(&self.device, &self.config, self.sample_count);
+    }
+
+    /// The z-indices of the node widgets in the same layer as `id`, other
+    /// than it.
+    fn sibling_z_indices(&self, id: DrawableId) -> impl Iterator<Item = i32> + '_ {
+        let layer = self
+            .node_widgets
+            .iter()
+            .find(|widget| widget.id == id)
+            .map(|widget| widget.order.layer);
+
+        self.node_widgets
+            .iter()
+            .filter(move |widget| widget.id != id && Some(widget.order.layer) == layer)
+            .map(|widget| widget.order.z_index)
+    }
+
+    /// Move the node widget `id` to the z-index `z_index` gives for its
+    /// current one, taking the ports on it along.
+    fn restack(&mut self, id: DrawableId, z_index: impl FnOnce(i32) -> i32) -> bool {
+        let Some(widget) = self.node_widgets.iter_mut().find(|widget| widget.id == id) else {
+            return false;
+        };
+
+        let z_index = z_index(widget.order.z_index);
+        widget.order.z_index = z_index;
+
+        for anchor in &mut self.port_anchors {
+            if anchor.node == Some(id) {
+                anchor.order.z_index = z_index;
+            }
+        }
+        self.dirty = true;
+
+        true
+    }
+
+    /// Whether a node widget, port anchor, or edge `id` is in the scene.
+    fn has_pickable(&self, id: DrawableId) -> bool {
+        self.node_widgets.iter().any(|widget| widget.id == id)
+            || self.port_anchors.iter().any(|anchor| anchor.id == id)
+            || self.edges.iter().any(|edge| edge.id == id)
+    }
+
+    fn port_anchor(&self, id: DrawableId) -> Option<&PortAnchor> {
+        self.port_anchors.iter().find(|anchor| anchor.id == id)
+    }
+
+    /// The curve `edge` runs along, between where its ends are now, if the
+    /// ports it's on are in the scene. It leaves its source port away from
+    /// the node, or else arrives at its target port that way, or else runs
+    /// left to right.
+    fn edge_curve(&self, edge: &Edge) -> Option<EdgeCurve> {
+        let end = |end: EdgeEnd| match end {
+            EdgeEnd::Port(id) => self.port_anchor(id).map(|anchor| {
+                (
+                    Vec2::new(anchor.position[0], anchor.position[1]),
+                    Some(anchor.direction),
+                )
+            }),
+            EdgeEnd::Point(point) => Some((point, None)),
+        };
+        let (start, source_direction) = end(edge.source)?;
+        let (target, target_direction) = end(edge.target)?;
+
+        let direction = source_direction
+            .or(target_direction.map(|direction| match direction {
+                PortDirection::Input => PortDirection::Output,
+                PortDirection::Output => PortDirection::Input,
+            }))
+            .unwrap_or(PortDirection::Output);
+
+        Some(EdgeCurve::from_port(start, direction, target))
+    }
+
+    /// The dashed curve from the connection's source port to the port it's
+    /// snapped to, or else the pointer, in the source port's color. Nothing
+    /// is drawn once the source port is gone from the scene.
+    fn pending_edge_segments(&self, edge: &PendingEdge, instances: &mut Vec<EdgeInstanceRaw>) {
+        let Some(source) = self.port_anchor(edge.source) else {
+            return;
+        };
+
+        let end = edge
+            .target
+            .and_then(|id| self.port_anchor(id))
+            .map_or(edge.pointer, |target| {
+                Vec2::new(target.position[0], target.position[1])
+            });
+        let start = Vec2::new(source.position[0], source.position[1]);
+        let curve = EdgeCurve::from_port(start, source.direction, end);
+
+        let (_, height) = self.camera.viewport_size();
+        // World units per logical pixel
+        let pixel = 2.0 / self.camera.zoom() / height as f32;
+
+        curve_segments(
+            &curve,
+            PENDING_EDGE_WIDTH * pixel,
+            source.color,
+            [PENDING_EDGE_DASH * pixel, PENDING_EDGE_GAP * pixel],
+            instances,
+        );
+    }
+
+    /// The title of every node widget that has one, in the left end of its
+    /// header and stacked with it.
+    fn node_title_labels(&self) -> impl Iterator<Item = TextLabel> + '_ {
+        self.node_widgets.iter().filter_map(|widget| {
+            let title = self.node_titles.get(&widget.id)?;
+            let [x, y, z] = widget.position;
+            let [width, height] = widget.size;
+            let header_height = widget.header_height.clamp(0.0, height);
+            let size = header_height * NODE_TITLE_SCALE;
+
+            Some(TextLabel {
+                text: title.clone(),
+                position: [
+                    x - width / 2.0 + size * NODE_TITLE_INDENT,
+                    y + height / 2.0 - header_height / 2.0,
+                    z,
+                ],
+                size,
+                color: NODE_TITLE_COLOR,
+                align: TextAlign::Left,
+                order: widget.order,
+            })
+        })
+    }
+
+    /// The last frame's stats as a label pinned to the top-left corner of
+    /// the view, at a fixed size on screen.
+    fn stats_label(&self) -> TextLabel {
+        let corner = self.camera.screen_to_world(0.0, 0.0);
+        let (_, height) = self.camera.viewport_size();
+        // World units per logical pixel
+        let pixel = 2.0 / self.camera.zoom() / height as f32;
+
+        TextLabel {
+            text: self.stats.summary(),
+            position: [
+                corner.x + STATS_OVERLAY_MARGIN * pixel,
+                // Labels are positioned by the middle of their first line
+                corner.y - (STATS_OVERLAY_MARGIN + STATS_OVERLAY_FONT_SIZE / 2.0) * pixel,
+                0.0,
+            ],
+            size: STATS_OVERLAY_FONT_SIZE * pixel,
+            color: STATS_OVERLAY_COLOR,
+            align: TextAlign::Left,
+            order: DrawOrder::new(Layer::Overlay, i32::MAX),
+        }
+    }
+
+    /// Write the instance data of every quad, edge segment, node widget, port
+    /// anchor, sprite, glyph, and gizmo into their instance buffers, in draw
+    /// order and at the depth of their stacking. Returns where each layer's
+    /// instances are.
+    fn upload_instances(&mut self) -> Result<SceneRanges, RendererError> {
+        // Node titles and the stats overlay, on top, are laid out with the
+        // rest of the text
+        let extra_labels: Vec<_> = self
+            .node_title_labels()
+            .chain(self.stats_overlay_visible.then(|| self.stats_label()))
+            .collect();
+        let with_extra;
+        let labels = if extra_labels.is_empty() {
+            &self.text_labels
+        } else {
+            with_extra = [self.text_labels.as_slice(), &extra_labels].concat();
+            &with_extra
+        };
+
+        let depths = StackingDepths::new(
+            self.quads
+                .iter()
+                .map(|quad| quad.order)
+                .chain(self.edges.iter().map(|edge| edge.order))
+                .chain(self.pending_edge.iter().map(|_| PENDING_EDGE_ORDER))
+                .chain(self.node_widgets.iter().map(|widget| widget.order))
+                .chain(self.port_anchors.iter().map(|anchor| anchor.order))
+                .chain(self.sprites.iter().map(|sprite| sprite.order))
+                .chain(labels.iter().map(|label| label.order)),
+        );
+
+        let quads = LayeredBatch::build(
+            &self.quads,
+            |quad| quad.order,
+            &depths,
+            |quad, instances| {
+                instances.push(InstanceRaw::from_quad(quad));
+                Ok(())
+            },
+        )?;
+        self.instance_buffer
+            .upload(&self.device, &self.queue, &quads.instances);
+
+        // Edges whose ports aren't in the scene are left out
+        let curves: Vec<_> = self
+            .edges
+            .iter()
+            .map(EdgeDrawing::Connected)
+            .chain(self.pending_edge.iter().map(EdgeDrawing::Pending))
+            .collect();
+        let edges = LayeredBatch::build(
+            &curves,
+            |curve| match curve {
+                EdgeDrawing::Connected(edge) => edge.order,
+                EdgeDrawing::Pending(_) => PENDING_EDGE_ORDER,
+            },
+            &depths,
+            |curve, instances| {
+                match curve {
+                    EdgeDrawing::Connected(edge) => {
+                        if let Some(curve) = self.edge_curve(edge) {
+                            let dash = if edge.dashed {
+                                [EDGE_DASH * edge.width, EDGE_GAP * edge.width]
+                            } else {
+                                [0.0, 0.0]
+                            };
+                            curve_segments(&curve, edge.width, edge.color, dash, instances);
+                        }
+                    }
+                    EdgeDrawing::Pending(edge) => self.pending_edge_segments(edge, instances),
+                }
+                Ok(())
+            },
+        )?;
+        self.edge_instance_buffer
+            .upload(&self.device, &self.queue, &edges.instances);
+
+        let widgets = LayeredBatch::build(
+            &self.node_widgets,
+            |widget| widget.order,
+            &depths,
+            |widget, instances| {
+                instances.push(NodeInstanceRaw::from_node_widget(widget));
+                Ok(())
+            },
+        )?;
+        self.node_instance_buffer
+            .upload(&self.device, &self.queue, &widgets.instances);
+
+        let ports = LayeredBatch::build(
+            &self.port_anchors,
+            |anchor| anchor.order,
+            &depths,
+            |anchor, instances| {
+                instances.push(PortInstanceRaw::from_port_anchor(anchor));
+                Ok(())
+            },
+        )?;
+        self.port_instance_buffer
+            .upload(&self.device, &self.queue, &ports.instances);
+
+        // Sprites whose icon isn't uploaded (yet) are left out
+        let sprites = LayeredBatch::build(
+            &self.sprites,
+            |sprite| sprite.order,
+            &depths,
+            |sprite, instances| {
+                if let Some(region) = self.icon_atlas.region(&sprite.icon) {
+                    instances.push(SpriteInstanceRaw {
+                        position: sprite.position,
+                        size: sprite.size,
+                        uv_min: region.uv_min,
+                        uv_max: region.uv_max,
+                        tint: sprite.tint,
+                    });
+                }
+                Ok(())
+            },
+        )?;
+        self.sprite_instance_buffer
+            .upload(&self.device, &self.queue, &sprites.instances);
+
+        let glyphs = LayeredBatch::build(
+            labels,
+            |label| label.order,
+            &depths,
+            |label, instances| self.glyph_atlas.layout(&self.queue, label, instances),
+        )?;
+        self.glyph_instance_buffer
+            .upload(&self.device, &self.queue, &glyphs.instances);
+
+        let gizmos: Vec<_> = self
+            .marquee
+            .iter()
+            .map(|marquee| {
+                OverlayInstanceRaw::rect(
+                    marquee.start,
+                    marquee.end,
+                    MARQUEE_FILL_COLOR,
+                    MARQUEE_BORDER_COLOR,
+                    MARQUEE_BORDER_WIDTH,
+                )
+            })
+            .collect();
+        self.gizmo_instance_buffer
+            .upload(&self.device, &self.queue, &gizmos);
+
+        Ok(SceneRanges {
+            quads: quads.ranges,
+            edges: edges.ranges,
+            widgets: widgets.ranges,
+            ports: ports.ranges,
+            sprites: sprites.ranges,
+            glyphs: glyphs.ranges,
+            gizmos: 0..gizmos.len() as u32,
+        })
+    }
+}
+
+/// The segments `curve` is drawn with, `width` wide and dashed with `dash`
+/// and gap lengths, or solid when they're zero.
+fn curve_segments(
+    curve: &EdgeCurve,
+    width: f32,
+    color: [f32; 4],
+    [dash, gap]: [f32; 2],
+    instances: &mut Vec<EdgeInstanceRaw>,
+) {
+    let points = curve.flatten(EDGE_CURVE_SEGMENTS);
+    let mut distance = 0.0;
+    for segment in points.windows(2) {
+        instances.push(EdgeInstanceRaw {
+            start: [segment[0].x, segment[0].y, 0.0],
+            end: segment[1].to_array(),
+            width,
+            color,
+            dash: [distance, dash, gap],
+        });
+        distance += segment[0].distance(segment[1]);
+    }
+}
+
+/// Milliseconds since the page loaded, for timing animations.
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or_else(js_sys::Date::now)
+}
+
+/// The camera's viewport in physical pixels, clamped to the largest texture
+/// the device supports.
+fn surface_size(camera: &Camera2D, device: &Device) -> (u32, u32) {
+    let max_dimension = device.limits().max_texture_dimension_2d;
+    let (width, height) = camera.physical_viewport_size();
+
+    (width.min(max_dimension), height.min(max_dimension))
+}
+
+/// A texture of the surface's size and format to draw frames into and read
+/// them back from.
+fn create_color_texture(device: &Device, config: &SurfaceConfiguration, label: &str) -> Texture {
+    device.create_texture(&TextureDescriptor {
+        label: Some(label),
+        size: Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: config.format,
+        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+/// A surface on `canvas` and an adapter that can draw to it: a WebGPU one
+/// if there is one, otherwise a WebGL2 one.
+async fn request_canvas_adapter(
+    canvas: HtmlCanvasElement,
+) -> Result<(Surface<'static>, Adapter), RendererError> {
+    let webgpu = Instance::new(&InstanceDescriptor {
+        backends: Backends::BROWSER_WEBGPU,
+        ..Default::default()
+    });
+
+    // Look for an adapter before creating the surface: a canvas only ever
+    // gets one kind of context, so WebGL2 can't have it once WebGPU does
+    let adapter = webgpu
+        .request_adapter(&RequestAdapterOptions {
+            power_preference: HighPerformance,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        })
+        .await;
+
+    if let Ok(adapter) = adapter {
+        let surface = create_canvas_surface(&webgpu, canvas)?;
+        return Ok((surface, adapter));
+    }
+
+    web_sys::console::warn_1(&JsValue::from_str(
+        "WebGPU is not available, falling back to WebGL2",
+    ));
+
+    let webgl = Instance::new(&InstanceDescriptor {
+        backends: Backends::GL,
+        ..Default::default()
+    });
+    let surface = create_canvas_surface(&webgl, canvas)?;
+
+    let adapter = webgl
+        .request_adapter(&RequestAdapterOptions {
+            power_preference: HighPerformance,
+            compatible_surface: Some(&surface),
+            force_fallback_adapter: false,
+        })
+        .await
+        .map_err(|e| RendererError::WgpuError {
+            message: format!("No suitable GPU adapter found for WebGPU or WebGL2: {e}"),
+            location: ErrorLocation::from(PanicLocation::caller()),
+        })?;
+
+    Ok((surface, adapter))
+}
+
+fn create_canvas_surface(
+    instance: &Instance,
+    canvas: HtmlCanvasElement,
+) -> Result<Surface<'static>, RendererError> {
+    instance
+        .create_surface(wgpu::SurfaceTarget::Canvas(canvas))
+        .map_err(|e| RendererError::WgpuError {
+            message: format!("Failed to create surface from canvas: {}", e),
+            location: ErrorLocation::from(PanicLocation::caller()),
+        })
+}
+
+/// A device with the limits of the adapter's backend: WebGPU's defaults, or
+/// the lower ones WebGL2 guarantees, raised to the adapter's texture size.
+async fn request_device(adapter: &Adapter) -> Result<(Device, Queue), RendererError> {
+    let required_limits = match adapter.get_info().backend {
+        Backend::Gl => Limits::downlevel_webgl2_defaults().using_resolution(adapter.limits()),
+        _ => Limits::default(),
+    };
+
+    adapter
+        .request_device(&DeviceDescriptor {
+            label: Some("Cognexus Device"),
+            required_features: Features::empty(),
+            required_limits,
+            memory_hints: MemoryHints::default(),
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| RendererError::WgpuError {
+            message: format!("Failed to create device: {}", e),
+            location: ErrorLocation::from(PanicLocation::caller()),
+        })
+}
+
+/// A depth target the size of the surface, with `sample_count` samples per
+/// pixel.
+fn create_depth_target(
+    device: &Device,
+    config: &SurfaceConfiguration,
+    sample_count: u32,
+) -> TextureView {
+    let texture = device.create_texture(&TextureDescriptor {
+        label: Some("Depth Target"),
+        size: Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: TextureDimension::D2,
+        format: DEPTH_FORMAT,
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+
+    texture.create_view(&TextureViewDescriptor::default())
+}
+
+/// The multisampled color target the scene is drawn into before it's
+/// resolved to the surface, or nothing when `sample_count` is 1.
+fn create_msaa_target(
+    device: &Device,
+    config: &SurfaceConfiguration,
+    sample_count: u32,
+) -> Option<TextureView> {
+    if sample_count <= 1 {
+        return None;
+    }
+
+    let texture = device.create_texture(&TextureDescriptor {
+        label: Some("Multisampled Color Target"),
+        size: Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: TextureDimension::D2,
+        format: config.format,
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+
+    Some(texture.create_view(&TextureViewDescriptor::default()))
 }
@@ -0,0 +1,99 @@
+// Camera uniform
+struct Camera {
+    view_proj: mat4x4<f32>,
+    // Takes clip space back to world space
+    view_proj_inv: mat4x4<f32>,
+    zoom: f32,
+    // Physical pixels per logical pixel
+    pixel_ratio: f32,
+    // Width and height of the view, in logical pixels
+    viewport_size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+// Vertex Shader
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct InstanceInput {
+    @location(1) start: vec3<f32>,
+    @location(2) end: vec2<f32>,
+    @location(3) width: f32,
+    @location(4) color: vec4<f32>,
+    // x: distance along the edge at the start, y: dash length, z: gap length
+    @location(5) dash: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Distance along the whole edge, in world units
+    @location(0) distance: f32,
+    // Offset from the segment's center line, in world units
+    @location(1) across: f32,
+    @location(2) half_width: f32,
+    @location(3) color: vec4<f32>,
+    @location(4) dash: vec2<f32>,
+};
+
+@vertex
+fn vs_main(
+    model: VertexInput,
+    instance: InstanceInput,
+) -> VertexOutput {
+    var out: VertexOutput;
+
+    let delta = instance.end - instance.start.xy;
+    let length = length(delta);
+    var direction = vec2<f32>(1.0, 0.0);
+    if length > 0.0 {
+        direction = delta / length;
+    }
+    let normal = vec2<f32>(-direction.y, direction.x);
+
+    // The unit quad spans -0.5..0.5: stretch it along the segment, with half
+    // the width to spare at each end so neighbouring segments overlap at the
+    // joints, and across it by the width
+    let half_width = instance.width * 0.5;
+    let along = mix(-half_width, length + half_width, model.position.x + 0.5);
+    let across = model.position.y * instance.width;
+    let world = instance.start.xy + direction * along + normal * across;
+    out.clip_position = camera.view_proj * vec4<f32>(world, instance.start.z, 1.0);
+
+    out.distance = instance.dash.x + along;
+    out.across = across;
+    out.half_width = half_width;
+    out.color = instance.color;
+    out.dash = instance.dash.yz;
+
+    return out;
+}
+
+// Fragment Shader
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // About one pixel, in world units, so edges stay smooth at any zoom
+    let aa = max(fwidth(in.across), 1e-6);
+    var coverage = clamp((in.half_width - abs(in.across)) / aa + 0.5, 0.0, 1.0);
+
+    let dash = in.dash.x;
+    let gap = in.dash.y;
+    if gap > 0.0 && dash > 0.0 {
+        // Where in its dash and gap this point is, fading over a pixel at
+        // either end of the dash
+        let phase = in.distance - floor(in.distance / (dash + gap)) * (dash + gap);
+        let dash_aa = max(fwidth(in.distance), 1e-6);
+        coverage *= clamp(min(phase, dash - phase) / dash_aa + 0.5, 0.0, 1.0);
+    }
+
+    // Leave the depth to what's below where nothing is drawn
+    if in.color.a * coverage <= 0.0 {
+        discard;
+    }
+
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
@@ -0,0 +1,45 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::{VertexAttribute, VertexBufferLayout, VertexStepMode};
+
+pub const LABEL: &str = "Edge Shader";
+pub const SHADER_SOURCE: &str = include_str!("edge.wgsl");
+
+// -----------------------------------------------------------------------------
+// The Instance
+// One straight segment of a flattened edge curve, drawn on the quad vertices
+// (location 0) stretched from its start to its end.
+// Matches:
+// @location(1) start (vec3, with the z of the whole edge)
+// @location(2) end (vec2)
+// @location(3) width (f32)
+// @location(4) color (vec4)
+// @location(5) dash (vec3: distance along the edge at the start, dash length,
+//              gap length; no gap draws a solid line)
+// -----------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct InstanceRaw {
+    pub start: [f32; 3],
+    pub end: [f32; 2],
+    pub width: f32,
+    pub color: [f32; 4],
+    pub dash: [f32; 3],
+}
+
+impl InstanceRaw {
+    const ATTRIBUTES: [VertexAttribute; 5] = wgpu::vertex_attr_array![
+        1 => Float32x3,
+        2 => Float32x2,
+        3 => Float32,
+        4 => Float32x4,
+        5 => Float32x3,
+    ];
+
+    pub fn desc() -> VertexBufferLayout<'static> {
+        VertexBufferLayout {
+            array_stride: size_of::<InstanceRaw>() as wgpu::BufferAddress,
+            step_mode: VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// Camera uniform
+struct Camera {
+    view_proj: mat4x4<f32>,
+    // Takes clip space back to world space
+    view_proj_inv: mat4x4<f32>,
+    zoom: f32,
+    // Physical pixels per logical pixel
+    pixel_ratio: f32,
+    // Width and height of the view, in logical pixels
+    viewport_size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+// Line colors, from the theme
+struct Grid {
+    minor_color: vec4<f32>,
+    major_color: vec4<f32>,
+};
+
+@group(1) @binding(0)
+var<uniform> grid: Grid;
+
+// Grid spacing, in world units
+const MINOR_SPACING: f32 = 0.1;
+const MAJOR_SPACING: f32 = 1.0;
+
+// Line widths, in logical pixels, so lines stay as thick at every zoom level
+const MINOR_LINE_WIDTH: f32 = 1.0;
+const MAJOR_LINE_WIDTH: f32 = 1.5;
+
+// A set of lines fades out as its spacing shrinks from FADE_END to FADE_START
+// logical pixels, instead of crowding into a solid fill when zoomed out
+const FADE_START: f32 = 4.0;
+const FADE_END: f32 = 16.0;
+
+
+// Vertex Shader
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec2<f32>,
+};
+
+@vertex
+fn vs_main(model: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Stretch the unit quad over the whole viewport
+    let clip = vec4<f32>(model.position.xy * 2.0, 0.0, 1.0);
+    out.clip_position = clip;
+
+    // The projection is orthographic, so world positions interpolate linearly
+    let world = camera.view_proj_inv * clip;
+    out.world_position = world.xy / world.w;
+
+    return out;
+}
+
+// Fragment Shader
+
+// Coverage of the lines `spacing` apart nearest to `world`, given the world
+// units per pixel
+fn grid_lines(world: vec2<f32>, spacing: f32, pixel: f32, width: f32) -> f32 {
+    let offset = abs(fract(world / spacing + 0.5) - 0.5) * spacing / pixel;
+    let distance = min(offset.x, offset.y);
+    return clamp(width * 0.5 + 0.5 - distance, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // World units per physical pixel, and per logical one
+    let pixel = max(fwidth(in.world_position.x), fwidth(in.world_position.y));
+    let logical_pixel = pixel * camera.pixel_ratio;
+
+    let minor_fade = smoothstep(FADE_START, FADE_END, MINOR_SPACING / logical_pixel);
+    let major_fade = smoothstep(FADE_START, FADE_END, MAJOR_SPACING / logical_pixel);
+
+    let minor_width = MINOR_LINE_WIDTH * camera.pixel_ratio;
+    let major_width = MAJOR_LINE_WIDTH * camera.pixel_ratio;
+    let minor = grid_lines(in.world_position, MINOR_SPACING, pixel, minor_width) * minor_fade;
+    let major = grid_lines(in.world_position, MAJOR_SPACING, pixel, major_width) * major_fade;
+
+    // Major lines are drawn over minor ones
+    let color = mix(grid.minor_color.rgb, grid.major_color.rgb, major);
+    let alpha = max(minor * grid.minor_color.a, major * grid.major_color.a);
+    return vec4<f32>(color, alpha);
+}
@@ -0,0 +1,27 @@
+use crate::theme::Theme;
+use bytemuck::{Pod, Zeroable};
+
+pub const LABEL: &str = "Grid Shader";
+pub const SHADER_SOURCE: &str = include_str!("grid.wgsl");
+
+// -----------------------------------------------------------------------------
+// The Grid Uniform
+// Matches `Grid` at @group(1) @binding(0):
+// minor_color (vec4)
+// major_color (vec4)
+// -----------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct GridUniform {
+    pub minor_color: [f32; 4],
+    pub major_color: [f32; 4],
+}
+
+impl GridUniform {
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            minor_color: theme.grid_minor,
+            major_color: theme.grid_major,
+        }
+    }
+}
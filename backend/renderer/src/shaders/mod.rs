@@ -1 +1,8 @@
+pub mod edge;
+pub mod grid;
+pub mod node;
+pub mod overlay;
+pub mod port;
 pub mod quad;
+pub mod sprite;
+pub mod text;
@@ -0,0 +1,81 @@
+use bytemuck::{Pod, Zeroable};
+use cognexus_model::geometry::node_widget::NodeWidget;
+use wgpu::{VertexAttribute, VertexBufferLayout, VertexStepMode};
+
+pub const LABEL: &str = "Node Shader";
+pub const SHADER_SOURCE: &str = include_str!("node.wgsl");
+
+/// Instance state bit set while the node is selected.
+pub const STATE_SELECTED: u32 = 1;
+/// Instance state bit set while the pointer is over the node.
+pub const STATE_HOVERED: u32 = 2;
+
+// -----------------------------------------------------------------------------
+// The Instance
+// Node widgets are drawn on the quad vertices (location 0), so the instance data
+// starts at location 1.
+// Matches:
+// @location(1) position (vec3)
+// @location(2) size (vec2)
+// @location(3) fill_color (vec4)
+// @location(4) border_color (vec4)
+// @location(5) header_color (vec4)
+// @location(6) shape (vec4: border width, corner radius, header height, unused)
+// @location(7) state (u32 flags)
+// -----------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct InstanceRaw {
+    pub position: [f32; 3],
+    pub size: [f32; 2],
+    pub fill_color: [f32; 4],
+    pub border_color: [f32; 4],
+    pub header_color: [f32; 4],
+    pub shape: [f32; 4],
+    pub state: u32,
+}
+
+impl InstanceRaw {
+    const ATTRIBUTES: [VertexAttribute; 7] = wgpu::vertex_attr_array![
+        1 => Float32x3,
+        2 => Float32x2,
+        3 => Float32x4,
+        4 => Float32x4,
+        5 => Float32x4,
+        6 => Float32x4,
+        7 => Uint32,
+    ];
+
+    pub fn desc() -> VertexBufferLayout<'static> {
+        VertexBufferLayout {
+            array_stride: size_of::<InstanceRaw>() as wgpu::BufferAddress,
+            step_mode: VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+
+    pub fn from_node_widget(widget: &NodeWidget) -> Self {
+        let mut state = 0;
+        if widget.selected {
+            state |= STATE_SELECTED;
+        }
+        if widget.hovered {
+            state |= STATE_HOVERED;
+        }
+
+        Self {
+            position: widget.position,
+            size: widget.size,
+            fill_color: widget.fill_color,
+            border_color: widget.border_color,
+            header_color: widget.header_color,
+            shape: [
+                widget.border_width,
+                widget.corner_radius,
+                widget.header_height,
+                0.0,
+            ],
+            state,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// Camera uniform
+struct Camera {
+    view_proj: mat4x4<f32>,
+    // Takes clip space back to world space
+    view_proj_inv: mat4x4<f32>,
+    zoom: f32,
+    // Physical pixels per logical pixel
+    pixel_ratio: f32,
+    // Width and height of the view, in logical pixels
+    viewport_size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+// Instance state flags, matching STATE_SELECTED / STATE_HOVERED in mod.rs
+const STATE_SELECTED: u32 = 1u;
+const STATE_HOVERED: u32 = 2u;
+
+const SELECTED_BORDER_COLOR: vec4<f32> = vec4<f32>(0.98, 0.75, 0.2, 1.0);
+// How far toward white a hovered node's body is lightened
+const HOVER_LIGHTEN: f32 = 0.1;
+
+// Vertex Shader
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct InstanceInput {
+    @location(1) position: vec3<f32>,
+    @location(2) size: vec2<f32>,
+    @location(3) fill_color: vec4<f32>,
+    @location(4) border_color: vec4<f32>,
+    @location(5) header_color: vec4<f32>,
+    // x: border width, y: corner radius, z: header height
+    @location(6) shape: vec4<f32>,
+    @location(7) state: u32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Position relative to the node's center, in world units
+    @location(0) local: vec2<f32>,
+    @location(1) half_size: vec2<f32>,
+    @location(2) fill_color: vec4<f32>,
+    @location(3) border_color: vec4<f32>,
+    @location(4) header_color: vec4<f32>,
+    @location(5) shape: vec4<f32>,
+    @location(6) @interpolate(flat) state: u32,
+};
+
+@vertex
+fn vs_main(
+    model: VertexInput,
+    instance: InstanceInput,
+) -> VertexOutput {
+    var out: VertexOutput;
+
+    // The unit quad spans -0.5..0.5, so scaling it by the size gives the
+    // offset from the center
+    let local = model.position.xy * instance.size;
+    let world_position = vec4<f32>(instance.position.xy + local, instance.position.z, 1.0);
+    out.clip_position = camera.view_proj * world_position;
+
+    out.local = local;
+    out.half_size = instance.size * 0.5;
+    out.fill_color = instance.fill_color;
+    out.border_color = instance.border_color;
+    out.header_color = instance.header_color;
+    out.shape = instance.shape;
+    out.state = instance.state;
+
+    return out;
+}
+
+// Fragment Shader
+
+// Signed distance from `p` to a rectangle centered on the origin with rounded
+// corners: negative inside, positive outside
+fn rounded_rect_sdf(p: vec2<f32>, half_size: vec2<f32>, radius: f32) -> f32 {
+    let q = abs(p) - half_size + vec2<f32>(radius);
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - radius;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let radius = clamp(in.shape.y, 0.0, min(in.half_size.x, in.half_size.y));
+    let distance = rounded_rect_sdf(in.local, in.half_size, radius);
+
+    // About one pixel, in world units, so edges stay smooth at any zoom
+    let aa = max(fwidth(distance), 1e-6);
+    let header_aa = max(fwidth(in.local.y), 1e-6);
+
+    let selected = (in.state & STATE_SELECTED) != 0u;
+    let hovered = (in.state & STATE_HOVERED) != 0u;
+
+    var border_width = in.shape.x;
+    var border_color = in.border_color;
+    if selected {
+        border_width = max(border_width * 2.0, aa * 2.0);
+        border_color = SELECTED_BORDER_COLOR;
+    }
+
+    // The header band runs along the top edge (world Y points up)
+    let header_top = in.half_size.y - in.shape.z;
+    let header = clamp((in.local.y - header_top) / header_aa + 0.5, 0.0, 1.0);
+    var body = mix(in.fill_color, in.header_color, header * select(0.0, 1.0, in.shape.z > 0.0));
+    if hovered {
+        body = vec4<f32>(mix(body.rgb, vec3<f32>(1.0), HOVER_LIGHTEN), body.a);
+    }
+
+    var border = 0.0;
+    if border_width > 0.0 {
+        border = clamp((distance + border_width) / aa + 0.5, 0.0, 1.0);
+    }
+    let color = mix(body, border_color, border);
+
+    let coverage = clamp(0.5 - distance / aa, 0.0, 1.0);
+    // Leave the depth to what's below where nothing is drawn
+    if color.a * coverage <= 0.0 {
+        discard;
+    }
+
+    return vec4<f32>(color.rgb, color.a * coverage);
+}
@@ -0,0 +1,65 @@
+use bytemuck::{Pod, Zeroable};
+use glam::Vec2;
+use wgpu::{VertexAttribute, VertexBufferLayout, VertexStepMode};
+
+pub const LABEL: &str = "Overlay Shader";
+pub const SHADER_SOURCE: &str = include_str!("overlay.wgsl");
+
+// -----------------------------------------------------------------------------
+// The Instance
+// A rectangle placed in screen space, over the scene, like the selection
+// marquee. Drawn on the quad vertices (location 0).
+// Matches:
+// @location(1) position (vec2, top-left corner in logical pixels)
+// @location(2) size (vec2, in logical pixels)
+// @location(3) fill_color (vec4)
+// @location(4) border_color (vec4)
+// @location(5) shape (vec2: border width, corner radius, in logical pixels)
+// -----------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct InstanceRaw {
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+    pub fill_color: [f32; 4],
+    pub border_color: [f32; 4],
+    pub shape: [f32; 2],
+}
+
+impl InstanceRaw {
+    const ATTRIBUTES: [VertexAttribute; 5] = wgpu::vertex_attr_array![
+        1 => Float32x2,
+        2 => Float32x2,
+        3 => Float32x4,
+        4 => Float32x4,
+        5 => Float32x2,
+    ];
+
+    pub fn desc() -> VertexBufferLayout<'static> {
+        VertexBufferLayout {
+            array_stride: size_of::<InstanceRaw>() as wgpu::BufferAddress,
+            step_mode: VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+
+    /// A square-cornered rectangle between two opposite corners, in either
+    /// order.
+    pub fn rect(
+        corner: Vec2,
+        opposite: Vec2,
+        fill_color: [f32; 4],
+        border_color: [f32; 4],
+        border_width: f32,
+    ) -> Self {
+        let min = corner.min(opposite);
+
+        Self {
+            position: min.to_array(),
+            size: (corner.max(opposite) - min).to_array(),
+            fill_color,
+            border_color,
+            shape: [border_width, 0.0],
+        }
+    }
+}
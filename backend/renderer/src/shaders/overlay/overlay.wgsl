@@ -0,0 +1,88 @@
+// Camera uniform
+struct Camera {
+    view_proj: mat4x4<f32>,
+    // Takes clip space back to world space
+    view_proj_inv: mat4x4<f32>,
+    zoom: f32,
+    // Physical pixels per logical pixel
+    pixel_ratio: f32,
+    // Width and height of the view, in logical pixels
+    viewport_size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+// Vertex Shader
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct InstanceInput {
+    @location(1) position: vec2<f32>,
+    @location(2) size: vec2<f32>,
+    @location(3) fill_color: vec4<f32>,
+    @location(4) border_color: vec4<f32>,
+    // x: border width, y: corner radius
+    @location(5) shape: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Position relative to the rectangle's center, in logical pixels
+    @location(0) local: vec2<f32>,
+    @location(1) half_size: vec2<f32>,
+    @location(2) fill_color: vec4<f32>,
+    @location(3) border_color: vec4<f32>,
+    @location(4) shape: vec2<f32>,
+};
+
+@vertex
+fn vs_main(
+    model: VertexInput,
+    instance: InstanceInput,
+) -> VertexOutput {
+    var out: VertexOutput;
+
+    // The unit quad spans -0.5..0.5 with Y up, and screen Y points down
+    let corner = vec2<f32>(model.position.x + 0.5, 0.5 - model.position.y);
+    let screen = instance.position + corner * instance.size;
+    let ndc = screen / camera.viewport_size * 2.0 - vec2<f32>(1.0);
+    out.clip_position = vec4<f32>(ndc.x, -ndc.y, 0.0, 1.0);
+
+    out.local = model.position.xy * instance.size;
+    out.half_size = instance.size * 0.5;
+    out.fill_color = instance.fill_color;
+    out.border_color = instance.border_color;
+    out.shape = instance.shape;
+
+    return out;
+}
+
+// Fragment Shader
+
+// Signed distance from `p` to a rectangle centered on the origin with rounded
+// corners: negative inside, positive outside
+fn rounded_rect_sdf(p: vec2<f32>, half_size: vec2<f32>, radius: f32) -> f32 {
+    let q = abs(p) - half_size + vec2<f32>(radius);
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - radius;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let radius = clamp(in.shape.y, 0.0, min(in.half_size.x, in.half_size.y));
+    let distance = rounded_rect_sdf(in.local, in.half_size, radius);
+
+    // About one pixel, in logical pixels
+    let aa = max(fwidth(distance), 1e-6);
+
+    var border = 0.0;
+    if in.shape.x > 0.0 {
+        border = clamp((distance + in.shape.x) / aa + 0.5, 0.0, 1.0);
+    }
+    let color = mix(in.fill_color, in.border_color, border);
+
+    let coverage = clamp(0.5 - distance / aa, 0.0, 1.0);
+    return vec4<f32>(color.rgb, color.a * coverage);
+}
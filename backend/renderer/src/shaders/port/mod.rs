@@ -0,0 +1,64 @@
+use bytemuck::{Pod, Zeroable};
+use cognexus_model::geometry::port_anchor::PortAnchor;
+use wgpu::{VertexAttribute, VertexBufferLayout, VertexStepMode};
+
+pub const LABEL: &str = "Port Shader";
+pub const SHADER_SOURCE: &str = include_str!("port.wgsl");
+
+/// Instance state bit set while a connection is attached to the port.
+pub const STATE_CONNECTED: u32 = 1;
+/// Instance state bit set while the pointer is over the port.
+pub const STATE_HOVERED: u32 = 2;
+
+// -----------------------------------------------------------------------------
+// The Instance
+// Port anchors are drawn on the quad vertices (location 0), so the instance
+// data starts at location 1.
+// Matches:
+// @location(1) position (vec3, center of the port)
+// @location(2) size (vec2)
+// @location(3) color (vec4)
+// @location(4) state (u32 flags)
+// -----------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct InstanceRaw {
+    pub position: [f32; 3],
+    pub size: [f32; 2],
+    pub color: [f32; 4],
+    pub state: u32,
+}
+
+impl InstanceRaw {
+    const ATTRIBUTES: [VertexAttribute; 4] = wgpu::vertex_attr_array![
+        1 => Float32x3,
+        2 => Float32x2,
+        3 => Float32x4,
+        4 => Uint32,
+    ];
+
+    pub fn desc() -> VertexBufferLayout<'static> {
+        VertexBufferLayout {
+            array_stride: size_of::<InstanceRaw>() as wgpu::BufferAddress,
+            step_mode: VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+
+    pub fn from_port_anchor(anchor: &PortAnchor) -> Self {
+        let mut state = 0;
+        if anchor.connected {
+            state |= STATE_CONNECTED;
+        }
+        if anchor.hovered {
+            state |= STATE_HOVERED;
+        }
+
+        Self {
+            position: anchor.position,
+            size: anchor.size,
+            color: anchor.color,
+            state,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// Camera uniform
+struct Camera {
+    view_proj: mat4x4<f32>,
+    // Takes clip space back to world space
+    view_proj_inv: mat4x4<f32>,
+    zoom: f32,
+    // Physical pixels per logical pixel
+    pixel_ratio: f32,
+    // Width and height of the view, in logical pixels
+    viewport_size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+// Instance state flags, matching STATE_CONNECTED / STATE_HOVERED in mod.rs
+const STATE_CONNECTED: u32 = 1u;
+const STATE_HOVERED: u32 = 2u;
+
+// Thickness of an unconnected port's ring, relative to its radius
+const RING_WIDTH: f32 = 0.35;
+// How far toward white a hovered port is lightened
+const HOVER_LIGHTEN: f32 = 0.3;
+
+// Vertex Shader
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct InstanceInput {
+    @location(1) position: vec3<f32>,
+    @location(2) size: vec2<f32>,
+    @location(3) color: vec4<f32>,
+    @location(4) state: u32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Position relative to the port's center, in world units
+    @location(0) local: vec2<f32>,
+    @location(1) half_size: vec2<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) @interpolate(flat) state: u32,
+};
+
+@vertex
+fn vs_main(
+    model: VertexInput,
+    instance: InstanceInput,
+) -> VertexOutput {
+    var out: VertexOutput;
+
+    // The unit quad spans -0.5..0.5, so scaling it by the size gives the
+    // offset from the center
+    let local = model.position.xy * instance.size;
+    let world_position = vec4<f32>(instance.position.xy + local, instance.position.z, 1.0);
+    out.clip_position = camera.view_proj * world_position;
+
+    out.local = local;
+    out.half_size = abs(instance.size) * 0.5;
+    out.color = instance.color;
+    out.state = instance.state;
+
+    return out;
+}
+
+// Fragment Shader
+
+// Signed distance from `p` to a pill centered on the origin, rounded fully
+// along its shorter side: negative inside, positive outside
+fn pill_sdf(p: vec2<f32>, half_size: vec2<f32>) -> f32 {
+    let radius = min(half_size.x, half_size.y);
+    let q = abs(p) - half_size + vec2<f32>(radius);
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - radius;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let distance = pill_sdf(in.local, in.half_size);
+
+    // About one pixel, in world units, so edges stay smooth at any zoom
+    let aa = max(fwidth(distance), 1e-6);
+
+    let connected = (in.state & STATE_CONNECTED) != 0u;
+    let hovered = (in.state & STATE_HOVERED) != 0u;
+
+    var color = in.color;
+    if hovered {
+        color = vec4<f32>(mix(color.rgb, vec3<f32>(1.0), HOVER_LIGHTEN), color.a);
+    }
+
+    var coverage = clamp(0.5 - distance / aa, 0.0, 1.0);
+    if !connected {
+        // Hollow out the middle, leaving a ring at least a pixel wide
+        let ring_width = max(min(in.half_size.x, in.half_size.y) * RING_WIDTH, aa);
+        coverage *= clamp((distance + ring_width) / aa + 0.5, 0.0, 1.0);
+    }
+
+    // Leave the depth to what's below where nothing is drawn
+    if color.a * coverage <= 0.0 {
+        discard;
+    }
+
+    return vec4<f32>(color.rgb, color.a * coverage);
+}
@@ -1,6 +1,13 @@
 // Camera uniform
 struct Camera {
     view_proj: mat4x4<f32>,
+    // Takes clip space back to world space
+    view_proj_inv: mat4x4<f32>,
+    zoom: f32,
+    // Physical pixels per logical pixel
+    pixel_ratio: f32,
+    // Width and height of the view, in logical pixels
+    viewport_size: vec2<f32>,
 };
 
 @group(0) @binding(0)
@@ -55,5 +62,10 @@ fn vs_main(
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Leave the depth to what's below where nothing is drawn
+    if in.color.a <= 0.0 {
+        discard;
+    }
+
     return in.color;
 }
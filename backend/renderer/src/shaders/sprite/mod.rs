@@ -0,0 +1,44 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::{VertexAttribute, VertexBufferLayout, VertexStepMode};
+
+pub const LABEL: &str = "Sprite Shader";
+pub const SHADER_SOURCE: &str = include_str!("sprite.wgsl");
+
+// -----------------------------------------------------------------------------
+// The Instance
+// One sprite, drawn on the quad vertices (location 0) and textured from its
+// region of the icon atlas.
+// Matches:
+// @location(1) position (vec3, center of the sprite)
+// @location(2) size (vec2)
+// @location(3) uv_min (vec2, atlas top-left)
+// @location(4) uv_max (vec2, atlas bottom-right)
+// @location(5) tint (vec4)
+// -----------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct InstanceRaw {
+    pub position: [f32; 3],
+    pub size: [f32; 2],
+    pub uv_min: [f32; 2],
+    pub uv_max: [f32; 2],
+    pub tint: [f32; 4],
+}
+
+impl InstanceRaw {
+    const ATTRIBUTES: [VertexAttribute; 5] = wgpu::vertex_attr_array![
+        1 => Float32x3,
+        2 => Float32x2,
+        3 => Float32x2,
+        4 => Float32x2,
+        5 => Float32x4,
+    ];
+
+    pub fn desc() -> VertexBufferLayout<'static> {
+        VertexBufferLayout {
+            array_stride: size_of::<InstanceRaw>() as wgpu::BufferAddress,
+            step_mode: VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// Camera uniform
+struct Camera {
+    view_proj: mat4x4<f32>,
+    // Takes clip space back to world space
+    view_proj_inv: mat4x4<f32>,
+    zoom: f32,
+    // Physical pixels per logical pixel
+    pixel_ratio: f32,
+    // Width and height of the view, in logical pixels
+    viewport_size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+// Glyph atlas: coverage of every rasterized glyph in the red channel
+@group(1) @binding(0)
+var atlas_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var atlas_sampler: sampler;
+
+// Vertex Shader
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct InstanceInput {
+    @location(1) position: vec3<f32>,
+    @location(2) size: vec2<f32>,
+    @location(3) uv_min: vec2<f32>,
+    @location(4) uv_max: vec2<f32>,
+    @location(5) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(
+    model: VertexInput,
+    instance: InstanceInput,
+) -> VertexOutput {
+    var out: VertexOutput;
+
+    let world_position = vec4<f32>(
+        instance.position.xy + model.position.xy * instance.size,
+        instance.position.z,
+        1.0,
+    );
+    out.clip_position = camera.view_proj * world_position;
+
+    // World Y points up but texture V points down
+    let t = vec2<f32>(model.position.x + 0.5, 0.5 - model.position.y);
+    out.uv = mix(instance.uv_min, instance.uv_max, t);
+    out.color = instance.color;
+
+    return out;
+}
+
+// Fragment Shader
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coverage = textureSample(atlas_texture, atlas_sampler, in.uv).r;
+    // Leave the depth to what's below where nothing is drawn
+    if in.color.a * coverage <= 0.0 {
+        discard;
+    }
+
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
@@ -0,0 +1,52 @@
+/// What the last frame drew and what it cost, to help tell where the time
+/// goes as the scene grows.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    /// Time spent preparing, encoding, and submitting the frame on the CPU,
+    /// in milliseconds.
+    pub frame_time_ms: f32,
+    /// Time since the frame before it started, in milliseconds, or 0 for the
+    /// first frame.
+    pub frame_interval_ms: f32,
+    pub draw_calls: u32,
+    pub quad_instances: u32,
+    /// Straight segments of edge curves.
+    pub edge_instances: u32,
+    pub node_instances: u32,
+    pub port_instances: u32,
+    pub sprite_instances: u32,
+    pub glyph_instances: u32,
+    /// Bytes allocated for instance buffers, used or not.
+    pub instance_buffer_bytes: u64,
+    /// Buffers created since the frame before, rather than reused from the
+    /// pool.
+    pub buffer_allocations: u32,
+    /// Bytes of free buffers kept in the pool.
+    pub pooled_buffer_bytes: u64,
+}
+
+impl FrameStats {
+    /// The stats as lines of text, for the on-canvas overlay.
+    pub fn summary(&self) -> String {
+        let fps = if self.frame_interval_ms > 0.0 {
+            1000.0 / self.frame_interval_ms
+        } else {
+            0.0
+        };
+
+        format!(
+            "{:.2} ms ({fps:.0} fps)\n{} draw calls\n{} quads, {} edge segments, {} nodes, {} ports, {} sprites, {} glyphs\n{:.1} KiB instances, {:.1} KiB pooled\n{} buffers allocated",
+            self.frame_time_ms,
+            self.draw_calls,
+            self.quad_instances,
+            self.edge_instances,
+            self.node_instances,
+            self.port_instances,
+            self.sprite_instances,
+            self.glyph_instances,
+            self.instance_buffer_bytes as f64 / 1024.0,
+            self.pooled_buffer_bytes as f64 / 1024.0,
+            self.buffer_allocations,
+        )
+    }
+}
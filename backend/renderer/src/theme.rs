@@ -0,0 +1,28 @@
+/// The colors the renderer draws with where the scene doesn't set its own:
+/// the canvas behind everything, the grid, and nodes and edges created
+/// without colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub background: [f32; 4],
+    pub grid_minor: [f32; 4],
+    pub grid_major: [f32; 4],
+    pub node_fill: [f32; 4],
+    pub node_border: [f32; 4],
+    pub node_header: [f32; 4],
+    pub edge: [f32; 4],
+}
+
+impl Default for Theme {
+    /// A dark theme.
+    fn default() -> Self {
+        Self {
+            background: [0.1, 0.1, 0.1, 1.0],
+            grid_minor: [0.16, 0.16, 0.16, 1.0],
+            grid_major: [0.24, 0.24, 0.24, 1.0],
+            node_fill: [0.18, 0.18, 0.2, 1.0],
+            node_border: [0.35, 0.35, 0.4, 1.0],
+            node_header: [0.25, 0.3, 0.45, 1.0],
+            edge: [0.6, 0.6, 0.65, 1.0],
+        }
+    }
+}
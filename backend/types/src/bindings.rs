@@ -12,12 +12,33 @@ pub mod exports {
                 #[doc(hidden)]
                 static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
                 use super::super::super::super::_rt;
+                /// One named field of a structured type.
+                #[derive(Clone)]
+                pub struct FieldInfo {
+                    pub name: _rt::String,
+                    pub type_id: _rt::String,
+                }
+                impl ::core::fmt::Debug for FieldInfo {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("FieldInfo")
+                            .field("name", &self.name)
+                            .field("type-id", &self.type_id)
+                            .finish()
+                    }
+                }
                 #[derive(Clone)]
                 pub struct TypeInfo {
                     pub id: _rt::String,
                     pub name: _rt::String,
                     pub description: _rt::String,
                     pub version: _rt::String,
+                    /// Fields of a structured type, in order; empty for other types. A
+                    /// structured value is its fields' serialized values in order, each
+                    /// prefixed by its length as a little-endian u32.
+                    pub fields: _rt::Vec<FieldInfo>,
                 }
                 impl ::core::fmt::Debug for TypeInfo {
                     fn fmt(
@@ -29,6 +50,7 @@ pub mod exports {
                             .field("name", &self.name)
                             .field("description", &self.description)
                             .field("version", &self.version)
+                            .field("fields", &self.fields)
                             .finish()
                     }
                 }
@@ -38,30 +60,31 @@ pub mod exports {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
                     let result0 = T::list_types();
                     let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
-                    let vec7 = result0;
-                    let len7 = vec7.len();
-                    let layout7 = _rt::alloc::Layout::from_size_align_unchecked(
-                        vec7.len() * (8 * ::core::mem::size_of::<*const u8>()),
+                    let vec11 = result0;
+                    let len11 = vec11.len();
+                    let layout11 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec11.len() * (10 * ::core::mem::size_of::<*const u8>()),
                         ::core::mem::size_of::<*const u8>(),
                     );
-                    let result7 = if layout7.size() != 0 {
-                        let ptr = _rt::alloc::alloc(layout7).cast::<u8>();
+                    let result11 = if layout11.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
                         if ptr.is_null() {
-                            _rt::alloc::handle_alloc_error(layout7);
+                            _rt::alloc::handle_alloc_error(layout11);
                         }
                         ptr
                     } else {
                         ::core::ptr::null_mut()
                     };
-                    for (i, e) in vec7.into_iter().enumerate() {
-                        let base = result7
-                            .add(i * (8 * ::core::mem::size_of::<*const u8>()));
+                    for (i, e) in vec11.into_iter().enumerate() {
+                        let base = result11
+                            .add(i * (10 * ::core::mem::size_of::<*const u8>()));
                         {
                             let TypeInfo {
                                 id: id2,
                                 name: name2,
                                 description: description2,
                                 version: version2,
+                                fields: fields2,
                             } = e;
                             let vec3 = (id2.into_bytes()).into_boxed_slice();
                             let ptr3 = vec3.as_ptr().cast::<u8>();
@@ -101,10 +124,56 @@ pub mod exports {
                             *base
                                 .add(6 * ::core::mem::size_of::<*const u8>())
                                 .cast::<*mut u8>() = ptr6.cast_mut();
+                            let vec10 = fields2;
+                            let len10 = vec10.len();
+                            let layout10 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec10.len() * (4 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let result10 = if layout10.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout10).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout10);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec10.into_iter().enumerate() {
+                                let base = result10
+                                    .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let FieldInfo { name: name7, type_id: type_id7 } = e;
+                                    let vec8 = (name7.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *base.add(0).cast::<*mut u8>() = ptr8.cast_mut();
+                                    let vec9 = (type_id7.into_bytes()).into_boxed_slice();
+                                    let ptr9 = vec9.as_ptr().cast::<u8>();
+                                    let len9 = vec9.len();
+                                    ::core::mem::forget(vec9);
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len9;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr9.cast_mut();
+                                }
+                            }
+                            *base
+                                .add(9 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len10;
+                            *base
+                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result10;
                         }
                     }
-                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len7;
-                    *ptr1.add(0).cast::<*mut u8>() = result7;
+                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len11;
+                    *ptr1.add(0).cast::<*mut u8>() = result11;
                     ptr1
                 }
                 #[doc(hidden)]
@@ -114,11 +183,11 @@ pub mod exports {
                     let l1 = *arg0
                         .add(::core::mem::size_of::<*const u8>())
                         .cast::<usize>();
-                    let base10 = l0;
-                    let len10 = l1;
-                    for i in 0..len10 {
-                        let base = base10
-                            .add(i * (8 * ::core::mem::size_of::<*const u8>()));
+                    let base17 = l0;
+                    let len17 = l1;
+                    for i in 0..len17 {
+                        let base = base17
+                            .add(i * (10 * ::core::mem::size_of::<*const u8>()));
                         {
                             let l2 = *base.add(0).cast::<*mut u8>();
                             let l3 = *base
@@ -146,16 +215,107 @@ pub mod exports {
                                 .add(7 * ::core::mem::size_of::<*const u8>())
                                 .cast::<usize>();
                             _rt::cabi_dealloc(l8, l9, 1);
+                            let l10 = *base
+                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l11 = *base
+                                .add(9 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base16 = l10;
+                            let len16 = l11;
+                            for i in 0..len16 {
+                                let base = base16
+                                    .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let l12 = *base.add(0).cast::<*mut u8>();
+                                    let l13 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l12, l13, 1);
+                                    let l14 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l15 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l14, l15, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base16,
+                                len16 * (4 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
                         }
                     }
                     _rt::cabi_dealloc(
-                        base10,
-                        len10 * (8 * ::core::mem::size_of::<*const u8>()),
+                        base17,
+                        len17 * (10 * ::core::mem::size_of::<*const u8>()),
                         ::core::mem::size_of::<*const u8>(),
                     );
                 }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_validate_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let len1 = arg3;
+                    let result2 = T::validate(
+                        _rt::string_lift(bytes0),
+                        _rt::Vec::from_raw_parts(arg2.cast(), len1, len1),
+                    );
+                    let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result2 {
+                        Ok(_) => {
+                            *ptr3.add(0).cast::<u8>() = (0i32) as u8;
+                        }
+                        Err(e) => {
+                            *ptr3.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec4 = (e.into_bytes()).into_boxed_slice();
+                            let ptr4 = vec4.as_ptr().cast::<u8>();
+                            let len4 = vec4.len();
+                            ::core::mem::forget(vec4);
+                            *ptr3
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len4;
+                            *ptr3
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr4.cast_mut();
+                        }
+                    };
+                    ptr3
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_validate<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l1, l2, 1);
+                        }
+                    }
+                }
                 pub trait Guest {
                     fn list_types() -> _rt::Vec<TypeInfo>;
+                    /// Check that `value` is a well-formed serialized value of the type
+                    /// `type-id`. Returns why it isn't.
+                    fn validate(
+                        type_id: _rt::String,
+                        value: _rt::Vec<u8>,
+                    ) -> Result<(), _rt::String>;
                 }
                 #[doc(hidden)]
                 macro_rules! __export_cognexus_plugin_types_cabi {
@@ -167,7 +327,14 @@ pub mod exports {
                         "cabi_post_cognexus:plugin/types#list-types")] unsafe extern "C"
                         fn _post_return_list_types(arg0 : * mut u8,) { unsafe {
                         $($path_to_types)*:: __post_return_list_types::<$ty > (arg0) } }
-                        };
+                        #[unsafe (export_name = "cognexus:plugin/types#validate")] unsafe
+                        extern "C" fn export_validate(arg0 : * mut u8, arg1 : usize, arg2
+                        : * mut u8, arg3 : usize,) -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_validate_cabi::<$ty > (arg0, arg1,
+                        arg2, arg3) } } #[unsafe (export_name =
+                        "cabi_post_cognexus:plugin/types#validate")] unsafe extern "C" fn
+                        _post_return_validate(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_validate::<$ty > (arg0) } } };
                     };
                 }
                 #[doc(hidden)]
@@ -177,10 +344,10 @@ pub mod exports {
                 struct _RetArea(
                     [::core::mem::MaybeUninit<
                         u8,
-                    >; 2 * ::core::mem::size_of::<*const u8>()],
+                    >; 3 * ::core::mem::size_of::<*const u8>()],
                 );
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 2
+                    [::core::mem::MaybeUninit::uninit(); 3
                         * ::core::mem::size_of::<*const u8>()],
                 );
             }
@@ -191,6 +358,7 @@ pub mod exports {
 mod _rt {
     #![allow(dead_code, clippy::all)]
     pub use alloc_crate::string::String;
+    pub use alloc_crate::vec::Vec;
     #[cfg(target_arch = "wasm32")]
     pub fn run_ctors_once() {
         wit_bindgen_rt::run_ctors_once();
@@ -203,7 +371,13 @@ mod _rt {
         let layout = alloc::Layout::from_size_align_unchecked(size, align);
         alloc::dealloc(ptr, layout);
     }
-    pub use alloc_crate::vec::Vec;
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            String::from_utf8_unchecked(bytes)
+        }
+    }
     extern crate alloc as alloc_crate;
 }
 /// Generates `#[unsafe(no_mangle)]` functions to export the specified type as
@@ -240,13 +414,15 @@ pub(crate) use __export_types_plugin_impl as export;
 #[unsafe(link_section = "component-type:wit-bindgen:0.41.0:cognexus:plugin:types-plugin:encoded world")]
 #[doc(hidden)]
 #[allow(clippy::octal_escapes)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 272] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x8d\x01\x01A\x02\x01\
-A\x02\x01B\x05\x01r\x04\x02ids\x04names\x0bdescriptions\x07versions\x04\0\x09typ\
-e-info\x03\0\0\x01p\x01\x01@\0\0\x02\x04\0\x0alist-types\x01\x03\x04\0\x15cognex\
-us:plugin/types\x05\0\x04\0\x1ccognexus:plugin/types-plugin\x04\0\x0b\x12\x01\0\x0c\
-types-plugin\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x07\
-0.227.1\x10wit-bindgen-rust\x060.41.0";
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 359] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xe4\x01\x01A\x02\x01\
+A\x02\x01B\x0c\x01r\x02\x04names\x07type-ids\x04\0\x0afield-info\x03\0\0\x01p\x01\
+\x01r\x05\x02ids\x04names\x0bdescriptions\x07versions\x06fields\x02\x04\0\x09typ\
+e-info\x03\0\x03\x01p\x04\x01@\0\0\x05\x04\0\x0alist-types\x01\x06\x01p}\x01j\0\x01\
+s\x01@\x02\x07type-ids\x05value\x07\0\x08\x04\0\x08validate\x01\x09\x04\0\x15cog\
+nexus:plugin/types\x05\0\x04\0\x1ccognexus:plugin/types-plugin\x04\0\x0b\x12\x01\
+\0\x0ctypes-plugin\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-compon\
+ent\x070.227.1\x10wit-bindgen-rust\x060.41.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
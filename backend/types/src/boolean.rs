@@ -0,0 +1,65 @@
+use crate::error::TypeError;
+
+use cognexus_model::graph::{DataType, DataTypeInfo};
+
+use common::error::error_location::ErrorLocation;
+
+use std::any::Any;
+use std::panic::Location;
+
+use semver::Version;
+use uuid::Uuid;
+
+pub struct BooleanType;
+
+const ID: &str = "3220634a-f4a8-481e-8d48-674a5dc03ee6";
+const NAME: &str = "Boolean";
+const DESCRIPTION: &str = "A true or false value";
+
+impl DataTypeInfo for BooleanType {
+    fn type_id(&self) -> Uuid {
+        Uuid::parse_str(ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> &str {
+        DESCRIPTION
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+}
+
+impl DataType for BooleanType {
+    type Error = TypeError;
+
+    #[track_caller]
+    fn serialize(&self, value: Box<dyn Any>) -> Result<Vec<u8>, TypeError> {
+        let value = value
+            .downcast::<bool>()
+            .map_err(|_| TypeError::TypeMismatch {
+                expected: String::from("bool"),
+                got: String::from("a value of another type"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(vec![u8::from(*value)])
+    }
+
+    #[track_caller]
+    fn deserialize(&self, bytes: &[u8]) -> Result<Box<dyn Any>, TypeError> {
+        // Anything but exactly 0 or 1 is rejected rather than read as truthy
+        match bytes {
+            [0] => Ok(Box::new(false)),
+            [1] => Ok(Box::new(true)),
+            _ => Err(TypeError::DeserializationError {
+                message: format!("Boolean type expects a single 0 or 1 byte, got {bytes:?}."),
+                location: ErrorLocation::from(Location::caller()),
+            }),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::error::TypeError;
+
+use cognexus_model::graph::{DataType, DataTypeInfo};
+
+use common::error::error_location::ErrorLocation;
+
+use std::any::Any;
+use std::panic::Location;
+
+use semver::Version;
+use uuid::Uuid;
+
+/// Opaque binary payloads, like file contents or images, passed through
+/// unchanged.
+pub struct BytesType;
+
+const ID: &str = "08d35523-40f4-40c8-827b-f73371bd108d";
+const NAME: &str = "Bytes";
+const DESCRIPTION: &str = "Raw binary data";
+
+impl DataTypeInfo for BytesType {
+    fn type_id(&self) -> Uuid {
+        Uuid::parse_str(ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> &str {
+        DESCRIPTION
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+}
+
+impl DataType for BytesType {
+    type Error = TypeError;
+
+    #[track_caller]
+    fn serialize(&self, value: Box<dyn Any>) -> Result<Vec<u8>, TypeError> {
+        let value = value
+            .downcast::<Vec<u8>>()
+            .map_err(|_| TypeError::TypeMismatch {
+                expected: String::from("Vec<u8>"),
+                got: String::from("a value of another type"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(*value)
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> Result<Box<dyn Any>, TypeError> {
+        Ok(Box::new(bytes.to_vec()))
+    }
+}
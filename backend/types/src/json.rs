@@ -0,0 +1,88 @@
+use crate::error::TypeError;
+
+use cognexus_model::graph::{DataType, DataTypeInfo};
+
+use common::error::error_location::ErrorLocation;
+
+use std::any::Any;
+use std::panic::Location;
+
+use semver::Version;
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+pub struct JsonType;
+
+const ID: &str = "764af96b-a0c4-4dc7-8f1b-a0990fb0e19b";
+const NAME: &str = "JSON";
+const DESCRIPTION: &str = "Arbitrary structured data as a JSON value";
+
+impl DataTypeInfo for JsonType {
+    fn type_id(&self) -> Uuid {
+        Uuid::parse_str(ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> &str {
+        DESCRIPTION
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+}
+
+impl DataType for JsonType {
+    type Error = TypeError;
+
+    /// Serialize to compact JSON with object keys sorted, so equal values
+    /// always produce the same bytes (and hit the same cache entries).
+    #[track_caller]
+    fn serialize(&self, value: Box<dyn Any>) -> Result<Vec<u8>, TypeError> {
+        let value = value
+            .downcast::<Value>()
+            .map_err(|_| TypeError::TypeMismatch {
+                expected: String::from("serde_json::Value"),
+                got: String::from("a value of another type"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        serde_json::to_vec(&canonicalize(*value)).map_err(|e| TypeError::SerializationError {
+            message: format!("JSON type could not serialize the value: {e}"),
+            location: ErrorLocation::from(Location::caller()),
+        })
+    }
+
+    #[track_caller]
+    fn deserialize(&self, bytes: &[u8]) -> Result<Box<dyn Any>, TypeError> {
+        let value: Value =
+            serde_json::from_slice(bytes).map_err(|e| TypeError::DeserializationError {
+                message: format!("JSON type expects a JSON document: {e}"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(Box::new(value))
+    }
+}
+
+/// Rebuild `value` with every object's keys in sorted order, whichever map
+/// ordering serde_json was built with.
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(String, Value)> = object.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            let sorted: Map<String, Value> = entries
+                .into_iter()
+                .map(|(key, value)| (key, canonicalize(value)))
+                .collect();
+            Value::Object(sorted)
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize).collect()),
+        other => other,
+    }
+}
@@ -1,26 +1,78 @@
+mod boolean;
+mod bytes;
 pub mod error;
+mod json;
+mod list;
+mod number;
 mod signal;
+mod string;
 
+pub use boolean::BooleanType;
+pub use bytes::BytesType;
+pub use json::JsonType;
+pub use list::ListType;
+pub use number::NumberType;
 pub use signal::SignalType;
+pub use string::StringType;
 
 // -------------------------------------------------------------------------- //
 
 // Component Model bindings
-use cognexus_model::graph::DataTypeInfo;
+use cognexus_model::graph::DataType;
+use error::TypeError;
 mod bindings;
 
-use bindings::exports::cognexus::plugin::types::{Guest, TypeInfo};
+use bindings::exports::cognexus::plugin::types::{FieldInfo, Guest, TypeInfo};
 
 struct Component;
 
+/// Every type this plugin provides.
+fn builtin_types() -> Vec<Box<dyn DataType<Error = TypeError>>> {
+    let scalars: Vec<Box<dyn DataType<Error = TypeError>>> = vec![
+        Box::new(SignalType),
+        Box::new(NumberType),
+        Box::new(StringType),
+        Box::new(BooleanType),
+        Box::new(JsonType),
+        Box::new(BytesType),
+    ];
+    // Signals carry no data, so a list of them would only count
+    let lists: Vec<Box<dyn DataType<Error = TypeError>>> = scalars[1..]
+        .iter()
+        .map(|t| Box::new(ListType::of(t.as_ref())) as Box<dyn DataType<Error = TypeError>>)
+        .collect();
+
+    scalars.into_iter().chain(lists).collect()
+}
+
 impl Guest for Component {
     fn list_types() -> Vec<TypeInfo> {
-        vec![TypeInfo {
-            id: SignalType.type_id().to_string(),
-            name: String::from(SignalType.name()),
-            description: String::from(SignalType.description()),
-            version: SignalType.model_version().to_string(),
-        }]
+        builtin_types()
+            .into_iter()
+            .map(|data_type| TypeInfo {
+                id: data_type.type_id().to_string(),
+                name: String::from(data_type.name()),
+                description: String::from(data_type.description()),
+                version: data_type.model_version().to_string(),
+                fields: data_type
+                    .fields()
+                    .into_iter()
+                    .map(|field| FieldInfo {
+                        name: String::from(field.name()),
+                        type_id: field.type_id().to_string(),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn validate(type_id: String, value: Vec<u8>) -> Result<(), String> {
+        let data_type = builtin_types()
+            .into_iter()
+            .find(|data_type| data_type.type_id().to_string() == type_id)
+            .ok_or_else(|| format!("Unknown data type {type_id}"))?;
+
+        data_type.validate(&value).map_err(|e| e.to_string())
     }
 }
 
@@ -0,0 +1,125 @@
+use crate::error::TypeError;
+
+use cognexus_model::graph::{DataType, DataTypeInfo};
+
+use common::error::error_location::ErrorLocation;
+
+use std::any::Any;
+use std::panic::Location;
+
+use semver::Version;
+use uuid::Uuid;
+
+/// An ordered list of values of one element type, e.g. `List<Number>`.
+///
+/// Each instantiation is its own data type, with an id derived from the
+/// element type's, so `List<Number>` and `List<String>` never connect. Values
+/// are the elements already serialized by the element type; on the wire each
+/// element is prefixed by its length as a little-endian `u32`.
+pub struct ListType {
+    id: Uuid,
+    element_type_id: Uuid,
+    name: String,
+    description: String,
+}
+
+/// Namespace the ids of list instantiations are derived in.
+const NAMESPACE: &str = "d1572582-bb05-4c86-a288-55e9d3414f2f";
+
+impl ListType {
+    /// The list type whose elements are values of `element`.
+    pub fn of(element: &dyn DataTypeInfo) -> Self {
+        let namespace = Uuid::parse_str(NAMESPACE).unwrap();
+
+        Self {
+            id: Uuid::new_v5(&namespace, element.type_id().as_bytes()),
+            element_type_id: element.type_id(),
+            name: format!("List<{}>", element.name()),
+            description: format!("An ordered list of {} values", element.name()),
+        }
+    }
+}
+
+impl DataTypeInfo for ListType {
+    fn type_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn element_type_id(&self) -> Option<Uuid> {
+        Some(self.element_type_id)
+    }
+}
+
+impl DataType for ListType {
+    type Error = TypeError;
+
+    /// Serialize a `Vec<Vec<u8>>` of serialized elements.
+    #[track_caller]
+    fn serialize(&self, value: Box<dyn Any>) -> Result<Vec<u8>, TypeError> {
+        let elements = value
+            .downcast::<Vec<Vec<u8>>>()
+            .map_err(|_| TypeError::TypeMismatch {
+                expected: String::from("Vec<Vec<u8>>"),
+                got: String::from("a value of another type"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        let mut bytes = Vec::with_capacity(elements.iter().map(|e| e.len() + 4).sum());
+        for element in elements.iter() {
+            let len = u32::try_from(element.len()).map_err(|_| TypeError::SerializationError {
+                message: format!(
+                    "{} element of {} bytes is too large.",
+                    self.name,
+                    element.len()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+            bytes.extend_from_slice(&len.to_le_bytes());
+            bytes.extend_from_slice(element);
+        }
+
+        Ok(bytes)
+    }
+
+    #[track_caller]
+    fn deserialize(&self, bytes: &[u8]) -> Result<Box<dyn Any>, TypeError> {
+        let mut elements = Vec::new();
+        let mut rest = bytes;
+
+        while !rest.is_empty() {
+            let truncated = || TypeError::DeserializationError {
+                message: format!(
+                    "{} is truncated after {} element(s).",
+                    self.name,
+                    elements.len()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            };
+
+            let (len, tail) = rest.split_first_chunk::<4>().ok_or_else(truncated)?;
+            let len = u32::from_le_bytes(*len) as usize;
+            if tail.len() < len {
+                return Err(truncated());
+            }
+
+            let (element, tail) = tail.split_at(len);
+            elements.push(element.to_vec());
+            rest = tail;
+        }
+
+        Ok(Box::new(elements))
+    }
+}
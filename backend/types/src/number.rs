@@ -0,0 +1,65 @@
+use crate::error::TypeError;
+
+use cognexus_model::graph::{DataType, DataTypeInfo};
+
+use common::error::error_location::ErrorLocation;
+
+use std::any::Any;
+use std::panic::Location;
+
+use semver::Version;
+use uuid::Uuid;
+
+pub struct NumberType;
+
+const ID: &str = "86d62f83-c9db-47b4-b564-3a1248e33fde";
+const NAME: &str = "Number";
+const DESCRIPTION: &str = "A 64-bit floating point number";
+
+impl DataTypeInfo for NumberType {
+    fn type_id(&self) -> Uuid {
+        Uuid::parse_str(ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> &str {
+        DESCRIPTION
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+}
+
+impl DataType for NumberType {
+    type Error = TypeError;
+
+    #[track_caller]
+    fn serialize(&self, value: Box<dyn Any>) -> Result<Vec<u8>, TypeError> {
+        let value = value
+            .downcast::<f64>()
+            .map_err(|_| TypeError::TypeMismatch {
+                expected: String::from("f64"),
+                got: String::from("a value of another type"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        // Little-endian IEEE 754, the same on every host and in WASM
+        Ok(value.to_le_bytes().to_vec())
+    }
+
+    #[track_caller]
+    fn deserialize(&self, bytes: &[u8]) -> Result<Box<dyn Any>, TypeError> {
+        let bytes: [u8; 8] = bytes
+            .try_into()
+            .map_err(|_| TypeError::DeserializationError {
+                message: format!("Number type expects 8 bytes, got {}.", bytes.len()),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(Box::new(f64::from_le_bytes(bytes)))
+    }
+}
@@ -0,0 +1,66 @@
+use crate::error::TypeError;
+
+use cognexus_model::graph::{DataType, DataTypeInfo};
+
+use common::error::error_location::ErrorLocation;
+
+use std::any::Any;
+use std::panic::Location;
+
+use semver::Version;
+use uuid::Uuid;
+
+pub struct StringType;
+
+const ID: &str = "37f1d33e-1e41-4842-8e1b-331d51dfec94";
+const NAME: &str = "String";
+const DESCRIPTION: &str = "UTF-8 text";
+
+impl DataTypeInfo for StringType {
+    fn type_id(&self) -> Uuid {
+        Uuid::parse_str(ID).unwrap()
+    }
+
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn description(&self) -> &str {
+        DESCRIPTION
+    }
+
+    fn model_version(&self) -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+}
+
+impl DataType for StringType {
+    type Error = TypeError;
+
+    #[track_caller]
+    fn serialize(&self, value: Box<dyn Any>) -> Result<Vec<u8>, TypeError> {
+        let value = value
+            .downcast::<String>()
+            .map_err(|_| TypeError::TypeMismatch {
+                expected: String::from("String"),
+                got: String::from("a value of another type"),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(value.into_bytes())
+    }
+
+    #[track_caller]
+    fn deserialize(&self, bytes: &[u8]) -> Result<Box<dyn Any>, TypeError> {
+        let value =
+            String::from_utf8(bytes.to_vec()).map_err(|e| TypeError::DeserializationError {
+                message: format!(
+                    "String type expects UTF-8, but byte {} is invalid.",
+                    e.utf8_error().valid_up_to()
+                ),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+
+        Ok(Box::new(value))
+    }
+}
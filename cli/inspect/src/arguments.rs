@@ -1,19 +1,175 @@
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(
     name = "cognexus-inspect",
     about = "Inspect Cognexus WASM plugins",
-    long_about = None)]
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true)]
 pub(crate) struct Arguments {
+    #[command(subcommand)]
+    pub(crate) command: Option<Command>,
+
+    /// Plugin to list, when no subcommand is given.
+    #[command(flatten)]
+    pub(crate) plugin: PluginArguments,
+
+    #[arg(long, help = "List the plugin again every time its file changes")]
+    pub(crate) watch: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t = Format::Text,
+        help = "Output format: text or json"
+    )]
+    pub(crate) format: Format,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum Command {
+    /// Check a plugin's exports and metadata, exiting non-zero if any check fails
+    Validate(PluginArguments),
+    /// Execute one node of a nodes plugin on inputs read from files
+    Exec(ExecArguments),
+    /// Compare two builds of a plugin, exiting non-zero if a definition's
+    /// version doesn't match how it changed
+    Diff(DiffArguments),
+    /// Print every interface and function a component imports and exports
+    Wit(WitArguments),
+    /// Time compiling, instantiating and listing a plugin over repeated loads
+    Bench(BenchArguments),
+    /// Bundle a plugin with its manifest, checksum and signature for installing
+    Package(PackageArguments),
+}
+
+#[derive(Args)]
+pub(crate) struct PluginArguments {
+    /// Plugin to inspect; a directory lists every plugin in it, as the
+    /// desktop app would load them.
+    #[arg(value_name = "FILE", required = true)]
+    pub(crate) wasm_file: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "KIND",
+        help = "Kind of plugin: types or nodes; detected from its exports if left out"
+    )]
+    pub(crate) kind: Option<String>,
+}
+
+#[derive(Args)]
+pub(crate) struct ExecArguments {
     #[arg(value_name = "FILE")]
     pub(crate) wasm_file: String,
 
+    #[arg(long, value_name = "ID", help = "Id of the node definition to execute")]
+    pub(crate) node: String,
+
+    #[arg(
+        long,
+        value_name = "[PORT=]FILE",
+        help = "Serialized value of an input port, by port id or name; the port may be left out for a node with one input"
+    )]
+    pub(crate) input: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "NAME=FILE",
+        help = "Serialized value of a configuration parameter"
+    )]
+    pub(crate) config: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Save each output to <DIR>/<port name>.bin instead of printing it"
+    )]
+    pub(crate) output_dir: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub(crate) struct DiffArguments {
+    #[arg(value_name = "OLD")]
+    pub(crate) old_wasm_file: String,
+
+    #[arg(value_name = "NEW")]
+    pub(crate) new_wasm_file: String,
+
     #[arg(
         long,
         value_name = "KIND",
-        default_value = "types",
-        help = "Kind of plugin: types or nodes"
+        help = "Kind of plugin to compare: types or nodes; every kind either build exports if left out"
     )]
-    pub(crate) kind: String,
+    pub(crate) kind: Option<String>,
+}
+
+#[derive(Args)]
+pub(crate) struct WitArguments {
+    #[arg(value_name = "FILE")]
+    pub(crate) wasm_file: PathBuf,
+}
+
+#[derive(Args)]
+pub(crate) struct BenchArguments {
+    #[arg(value_name = "FILE")]
+    pub(crate) wasm_file: PathBuf,
+
+    #[arg(
+        short = 'n',
+        long,
+        value_name = "N",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Number of times to load the plugin"
+    )]
+    pub(crate) iterations: u32,
+
+    #[arg(
+        long,
+        value_name = "KIND",
+        help = "Kind of plugin: types or nodes; detected from its exports if left out"
+    )]
+    pub(crate) kind: Option<String>,
+}
+
+#[derive(Args)]
+pub(crate) struct PackageArguments {
+    #[arg(value_name = "FILE")]
+    pub(crate) wasm_file: String,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "The plugin's manifest, such as plugin.toml"
+    )]
+    pub(crate) manifest: PathBuf,
+
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        help = "Where to write the package, such as plugin.cxp"
+    )]
+    pub(crate) output: PathBuf,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Detached signature of the component to include"
+    )]
+    pub(crate) signature: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Format {
+    /// Human-readable listing.
+    Text,
+    /// One JSON document on stdout, for scripts and CI.
+    Json,
 }
@@ -0,0 +1,165 @@
+//! How long a plugin takes to load: compiling it, instantiating it, and
+//! the discovery call, each timed on its own.
+//!
+//! Timings are taken with the inspector's engine, which doesn't meter fuel
+//! or check epochs like the desktop app's, so they compare one build with
+//! another rather than predict the app's load time.
+
+use crate::arguments::{BenchArguments, Format};
+use crate::error::CliError;
+use crate::nodes_world::NodesPlugin;
+use crate::plugin::{new_engine, new_linker, plugin_kind};
+use crate::plugin_state::PluginState;
+use crate::types_world::TypesPlugin;
+
+use cognexus_plugin_manager::{NODES_KIND, TYPES_KIND, component_kinds};
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use wasmtime::Store;
+use wasmtime::component::Component;
+
+/// What `bench --format json` prints.
+#[derive(Serialize)]
+struct BenchReport {
+    iterations: u32,
+    phases: Vec<PhaseReport>,
+}
+
+#[derive(Serialize)]
+struct PhaseReport {
+    phase: String,
+    min_ms: f64,
+    avg_ms: f64,
+    max_ms: f64,
+}
+
+/// Times of one phase across the iterations.
+struct Phase {
+    name: String,
+    times: Vec<Duration>,
+}
+
+impl Phase {
+    fn new(name: String, iterations: u32) -> Self {
+        Self {
+            name,
+            times: Vec::with_capacity(iterations as usize),
+        }
+    }
+
+    fn report(&self) -> PhaseReport {
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let total: Duration = self.times.iter().sum();
+
+        PhaseReport {
+            phase: self.name.clone(),
+            min_ms: self.times.iter().min().copied().map(ms).unwrap_or_default(),
+            avg_ms: ms(total) / self.times.len().max(1) as f64,
+            max_ms: self.times.iter().max().copied().map(ms).unwrap_or_default(),
+        }
+    }
+}
+
+/// Load the plugin `args.iterations` times, from compiling it to listing
+/// what it provides as each kind it exports, and print the min, average
+/// and max time of each phase.
+pub(crate) fn bench(args: &BenchArguments, format: Format) -> Result<(), CliError> {
+    let engine = new_engine()?;
+    let linker = new_linker(&engine)?;
+
+    let mut compile = Phase::new(String::from("compile"), args.iterations);
+    let mut kind_phases = Vec::new();
+
+    for iteration in 0..args.iterations {
+        let start = Instant::now();
+        let component = Component::from_file(&engine, &args.wasm_file)?;
+        compile.times.push(start.elapsed());
+
+        if iteration == 0 {
+            let kinds = match &args.kind {
+                Some(kind) => vec![plugin_kind(kind)?],
+                None => component_kinds(&engine, &component),
+            };
+            if kinds.is_empty() {
+                return Err(CliError::InspectError {
+                    message: String::from(
+                        "Component exports neither a types nor a nodes interface",
+                    ),
+                    location: ErrorLocation::from(Location::caller()),
+                });
+            }
+
+            for kind in kinds {
+                let call = match kind {
+                    TYPES_KIND => "list-types",
+                    _ => "list-nodes",
+                };
+                kind_phases.push((
+                    kind,
+                    Phase::new(format!("instantiate ({kind})"), args.iterations),
+                    Phase::new(String::from(call), args.iterations),
+                ));
+            }
+        }
+
+        for (kind, instantiate, discover) in &mut kind_phases {
+            // A fresh store each time, as the desktop app uses for every call
+            let mut store = Store::new(&engine, PluginState::new());
+
+            match *kind {
+                TYPES_KIND => {
+                    let start = Instant::now();
+                    let plugin = TypesPlugin::instantiate(&mut store, &component, &linker)?;
+                    instantiate.times.push(start.elapsed());
+
+                    let start = Instant::now();
+                    plugin.cognexus_plugin_types().call_list_types(&mut store)?;
+                    discover.times.push(start.elapsed());
+                }
+                NODES_KIND => {
+                    let start = Instant::now();
+                    let plugin = NodesPlugin::instantiate(&mut store, &component, &linker)?;
+                    instantiate.times.push(start.elapsed());
+
+                    let start = Instant::now();
+                    plugin.cognexus_plugin_nodes().call_list_nodes(&mut store)?;
+                    discover.times.push(start.elapsed());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut phases = vec![compile.report()];
+    for (_, instantiate, discover) in &kind_phases {
+        phases.push(instantiate.report());
+        phases.push(discover.report());
+    }
+    let report = BenchReport {
+        iterations: args.iterations,
+        phases,
+    };
+
+    match format {
+        Format::Text => {
+            println!("\nRan {} iteration(s):", report.iterations);
+            println!(
+                "  {:<20} {:>12} {:>12} {:>12}",
+                "Phase", "Min", "Avg", "Max"
+            );
+            for phase in &report.phases {
+                println!(
+                    "  {:<20} {:>10.3}ms {:>10.3}ms {:>10.3}ms",
+                    phase.phase, phase.min_ms, phase.avg_ms, phase.max_ms
+                );
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
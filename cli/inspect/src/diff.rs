@@ -0,0 +1,491 @@
+//! What changed between two builds of a plugin, and whether their versions
+//! say so.
+
+use crate::arguments::{DiffArguments, Format};
+use crate::error::CliError;
+use crate::nodes_world::exports::cognexus::plugin::nodes::{NodeInfo, ParameterSpec, PortSpec};
+use crate::plugin::{Plugin, plugin_kind};
+use crate::types_world::exports::cognexus::plugin::types::TypeInfo;
+
+use cognexus_plugin_manager::{NODES_KIND, TYPES_KIND};
+
+use std::collections::{HashMap, HashSet};
+
+use semver::Version;
+use serde::Serialize;
+
+/// What `diff` found, printed as text or as JSON.
+#[derive(Default, Serialize)]
+struct DiffReport {
+    added: Vec<Definition>,
+    removed: Vec<Definition>,
+    changed: Vec<ChangedDefinition>,
+    /// Definitions whose version doesn't match how they changed.
+    version_problems: Vec<String>,
+}
+
+/// A type or node, by the kind of definition it is.
+#[derive(Serialize)]
+struct Definition {
+    kind: &'static str,
+    id: String,
+    name: String,
+    version: String,
+}
+
+#[derive(Serialize)]
+struct ChangedDefinition {
+    #[serde(flatten)]
+    definition: Definition,
+    old_version: String,
+    breaking: bool,
+    differences: Vec<Difference>,
+}
+
+#[derive(Serialize)]
+struct Difference {
+    description: String,
+    /// Whether graphs or values made for the old build may not work with
+    /// the new one.
+    breaking: bool,
+}
+
+/// What either build provides of one kind.
+#[derive(Default)]
+struct Listing {
+    types: Vec<TypeInfo>,
+    nodes: Vec<NodeInfo>,
+}
+
+/// Compare what the old and new builds provide as each kind of plugin
+/// either exports, or as `args.kind` alone, and print the report. Returns
+/// whether every changed definition's version matches its changes.
+pub(crate) fn diff(
+    old: &mut Plugin,
+    new: &mut Plugin,
+    args: &DiffArguments,
+    format: Format,
+) -> Result<bool, CliError> {
+    let old = listing(old, args.kind.as_deref())?;
+    let new = listing(new, args.kind.as_deref())?;
+
+    let mut report = DiffReport::default();
+    compare(
+        "type",
+        &old.types,
+        &new.types,
+        |t| (&t.id, &t.name, &t.version),
+        type_differences,
+        &mut report,
+    );
+    compare(
+        "node",
+        &old.nodes,
+        &new.nodes,
+        |n| (&n.id, &n.name, &n.version),
+        node_differences,
+        &mut report,
+    );
+
+    match format {
+        Format::Text => print_report(&report),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(report.version_problems.is_empty())
+}
+
+fn listing(plugin: &mut Plugin, kind: Option<&str>) -> Result<Listing, CliError> {
+    let kinds = match kind {
+        Some(kind) => vec![plugin_kind(kind)?],
+        None => plugin.exported_kinds(),
+    };
+
+    // A build that stopped exporting a kind lists nothing of it
+    let exported = plugin.exported_kinds();
+    let mut listing = Listing::default();
+    for kind in kinds.into_iter().filter(|kind| exported.contains(kind)) {
+        match kind {
+            TYPES_KIND => listing.types = plugin.list_types()?,
+            NODES_KIND => listing.nodes = plugin.list_nodes()?,
+            _ => {}
+        }
+    }
+
+    Ok(listing)
+}
+
+/// Match definitions by id, adding those only one build has and the
+/// differences between those both have to the report.
+fn compare<T>(
+    kind: &'static str,
+    old: &[T],
+    new: &[T],
+    metadata: impl Fn(&T) -> (&String, &String, &String),
+    differences: impl Fn(&T, &T) -> Vec<Difference>,
+    report: &mut DiffReport,
+) {
+    let definition = |item: &T| {
+        let (id, name, version) = metadata(item);
+        Definition {
+            kind,
+            id: id.clone(),
+            name: name.clone(),
+            version: version.clone(),
+        }
+    };
+    let old_by_id: HashMap<&String, &T> = old.iter().map(|item| (metadata(item).0, item)).collect();
+    let new_ids: HashSet<&String> = new.iter().map(|item| metadata(item).0).collect();
+
+    for old_item in old {
+        if !new_ids.contains(metadata(old_item).0) {
+            report.removed.push(definition(old_item));
+        }
+    }
+
+    for new_item in new {
+        let Some(old_item) = old_by_id.get(metadata(new_item).0) else {
+            report.added.push(definition(new_item));
+            continue;
+        };
+
+        let old_version = metadata(old_item).2;
+        let differences = differences(old_item, new_item);
+        let breaking = differences.iter().any(|difference| difference.breaking);
+        let new_definition = definition(new_item);
+
+        if let Some(problem) = version_problem(
+            old_version,
+            &new_definition.version,
+            !differences.is_empty(),
+            breaking,
+        ) {
+            report.version_problems.push(format!(
+                "{kind} {} ({}): {problem}",
+                new_definition.name, new_definition.id
+            ));
+        }
+
+        if !differences.is_empty() {
+            report.changed.push(ChangedDefinition {
+                definition: new_definition,
+                old_version: old_version.clone(),
+                breaking,
+                differences,
+            });
+        }
+    }
+}
+
+/// Why a definition's new version doesn't fit how it changed, if it
+/// doesn't. Breaking changes need a major version bump, or a minor one
+/// before 1.0.0, and any other change at least a patch one.
+fn version_problem(old: &str, new: &str, changed: bool, breaking: bool) -> Option<String> {
+    let (Ok(old_version), Ok(new_version)) = (Version::parse(old), Version::parse(new)) else {
+        return Some(format!("can't compare versions '{old}' and '{new}'"));
+    };
+
+    if new_version < old_version {
+        return Some(format!("version went down from {old} to {new}"));
+    }
+    if !changed {
+        return None;
+    }
+    if new_version == old_version {
+        return Some(format!("changed without a version bump from {old}"));
+    }
+
+    let major_bump = if old_version.major == 0 {
+        new_version.major > 0 || new_version.minor > old_version.minor
+    } else {
+        new_version.major > old_version.major
+    };
+    if breaking && !major_bump {
+        return Some(format!(
+            "has breaking changes, but {old} to {new} is not a major version bump"
+        ));
+    }
+
+    None
+}
+
+fn print_report(report: &DiffReport) {
+    if report.added.is_empty() && report.removed.is_empty() && report.changed.is_empty() {
+        println!("\nNo differences found");
+    }
+
+    if !report.added.is_empty() {
+        println!("\nAdded {} definition(s):", report.added.len());
+        for definition in &report.added {
+            println!(
+                "  + {} {} ({}) {}",
+                definition.kind, definition.name, definition.id, definition.version
+            );
+        }
+    }
+
+    if !report.removed.is_empty() {
+        println!("\nRemoved {} definition(s):", report.removed.len());
+        for definition in &report.removed {
+            println!(
+                "  - {} {} ({}) {} [breaking]",
+                definition.kind, definition.name, definition.id, definition.version
+            );
+        }
+    }
+
+    if !report.changed.is_empty() {
+        println!("\nChanged {} definition(s):", report.changed.len());
+        for changed in &report.changed {
+            let definition = &changed.definition;
+            println!(
+                "  ~ {} {} ({}) {} -> {}",
+                definition.kind,
+                definition.name,
+                definition.id,
+                changed.old_version,
+                definition.version
+            );
+            for difference in &changed.differences {
+                if difference.breaking {
+                    println!("      {} [breaking]", difference.description);
+                } else {
+                    println!("      {}", difference.description);
+                }
+            }
+        }
+    }
+
+    if !report.version_problems.is_empty() {
+        println!(
+            "\nFound {} version problem(s):",
+            report.version_problems.len()
+        );
+        for problem in &report.version_problems {
+            println!("  ! {problem}");
+        }
+    }
+}
+
+/// Records differences between two builds of one definition.
+#[derive(Default)]
+struct Differences(Vec<Difference>);
+
+impl Differences {
+    fn add(&mut self, breaking: bool, description: String) {
+        self.0.push(Difference {
+            description,
+            breaking,
+        });
+    }
+
+    /// Note a change to a field that doesn't affect compatibility.
+    fn field<T: PartialEq + std::fmt::Debug>(&mut self, name: &str, old: &T, new: &T) {
+        if old != new {
+            self.add(false, format!("{name} changed from {old:?} to {new:?}"));
+        }
+    }
+
+    /// Note a change to a flag that changes how the engine runs the node.
+    fn flag(&mut self, name: &str, old: bool, new: bool) {
+        if old != new {
+            self.add(true, format!("{name} changed from {old} to {new}"));
+        }
+    }
+}
+
+fn type_differences(old: &TypeInfo, new: &TypeInfo) -> Vec<Difference> {
+    let mut differences = Differences::default();
+
+    differences.field("name", &old.name, &new.name);
+    differences.field("description", &old.description, &new.description);
+
+    // A structured value is its fields in order, so any change to them
+    // changes the wire format
+    let old_fields: Vec<_> = old.fields.iter().map(|f| (&f.name, &f.type_id)).collect();
+    let new_fields: Vec<_> = new.fields.iter().map(|f| (&f.name, &f.type_id)).collect();
+    if old_fields != new_fields {
+        let old_by_name: HashMap<_, _> = old_fields.iter().copied().collect();
+        let new_by_name: HashMap<_, _> = new_fields.iter().copied().collect();
+
+        for (name, type_id) in &old_fields {
+            match new_by_name.get(name) {
+                None => differences.add(true, format!("field '{name}' removed")),
+                Some(new_type_id) if new_type_id != type_id => differences.add(
+                    true,
+                    format!("field '{name}' changed type from {type_id} to {new_type_id}"),
+                ),
+                Some(_) => {}
+            }
+        }
+        for (name, _) in &new_fields {
+            if !old_by_name.contains_key(name) {
+                differences.add(true, format!("field '{name}' added"));
+            }
+        }
+        if differences.0.iter().all(|difference| !difference.breaking) {
+            differences.add(true, String::from("fields reordered"));
+        }
+    }
+
+    differences.0
+}
+
+fn node_differences(old: &NodeInfo, new: &NodeInfo) -> Vec<Difference> {
+    let mut differences = Differences::default();
+
+    differences.field("name", &old.name, &new.name);
+    differences.field("description", &old.description, &new.description);
+    differences.field("category", &old.category, &new.category);
+    differences.field("tags", &old.tags, &new.tags);
+    if old.icon != new.icon {
+        differences.add(false, String::from("icon changed"));
+    }
+
+    // Existing graphs don't connect new inputs, but do connect every output
+    port_differences(
+        "input",
+        &old.input_ports,
+        &new.input_ports,
+        true,
+        &mut differences,
+    );
+    port_differences(
+        "output",
+        &old.output_ports,
+        &new.output_ports,
+        false,
+        &mut differences,
+    );
+    parameter_differences(&old.parameters, &new.parameters, &mut differences);
+
+    differences.flag("branching", old.branching, new.branching);
+    differences.flag("merging", old.merging, new.merging);
+    differences.flag(
+        "configured ports",
+        old.configured_ports,
+        new.configured_ports,
+    );
+    // Only decides whether outputs may be cached, which graphs can't tell
+    if old.deterministic != new.deterministic {
+        differences.add(
+            false,
+            format!(
+                "deterministic changed from {} to {}",
+                old.deterministic, new.deterministic
+            ),
+        );
+    }
+
+    differences.0
+}
+
+/// Compare ports by id, as edges refer to them.
+fn port_differences(
+    side: &str,
+    old: &[PortSpec],
+    new: &[PortSpec],
+    added_breaks: bool,
+    differences: &mut Differences,
+) {
+    let new_by_id: HashMap<_, _> = new.iter().map(|port| (&port.id, port)).collect();
+    let old_ids: HashSet<_> = old.iter().map(|port| &port.id).collect();
+
+    for old_port in old {
+        let Some(new_port) = new_by_id.get(&old_port.id) else {
+            differences.add(true, format!("{side} port '{}' removed", old_port.name));
+            continue;
+        };
+
+        if new_port.name != old_port.name {
+            differences.add(
+                false,
+                format!(
+                    "{side} port '{}' renamed to '{}'",
+                    old_port.name, new_port.name
+                ),
+            );
+        }
+        if new_port.data_type_id != old_port.data_type_id {
+            differences.add(
+                true,
+                format!(
+                    "{side} port '{}' changed type from {} to {}",
+                    new_port.name, old_port.data_type_id, new_port.data_type_id
+                ),
+            );
+        }
+    }
+
+    for new_port in new {
+        if !old_ids.contains(&new_port.id) {
+            differences.add(
+                added_breaks,
+                format!("{side} port '{}' added", new_port.name),
+            );
+        }
+    }
+}
+
+/// Compare parameters by name, as configurations refer to them.
+fn parameter_differences(
+    old: &[ParameterSpec],
+    new: &[ParameterSpec],
+    differences: &mut Differences,
+) {
+    let new_by_name: HashMap<_, _> = new.iter().map(|p| (&p.name, p)).collect();
+    let old_names: HashSet<_> = old.iter().map(|p| &p.name).collect();
+
+    for old_parameter in old {
+        let name = &old_parameter.name;
+        let Some(new_parameter) = new_by_name.get(name) else {
+            differences.add(true, format!("parameter '{name}' removed"));
+            continue;
+        };
+
+        if new_parameter.data_type_id != old_parameter.data_type_id {
+            differences.add(
+                true,
+                format!(
+                    "parameter '{name}' changed type from {} to {}",
+                    old_parameter.data_type_id, new_parameter.data_type_id
+                ),
+            );
+        }
+        if new_parameter.required != old_parameter.required {
+            differences.add(
+                new_parameter.required,
+                format!(
+                    "parameter '{name}' is {} required",
+                    if new_parameter.required {
+                        "now"
+                    } else {
+                        "no longer"
+                    }
+                ),
+            );
+        }
+        if new_parameter.default_value != old_parameter.default_value {
+            differences.add(
+                false,
+                format!("parameter '{name}' changed its default value"),
+            );
+        }
+        // Constraints that got tighter reject configurations that were valid
+        if new_parameter.constraints != old_parameter.constraints {
+            differences.add(true, format!("parameter '{name}' changed its constraints"));
+        }
+        if new_parameter.description != old_parameter.description {
+            differences.add(false, format!("parameter '{name}' changed its description"));
+        }
+    }
+
+    for new_parameter in new {
+        if !old_names.contains(&new_parameter.name) {
+            differences.add(
+                new_parameter.required && new_parameter.default_value.is_none(),
+                format!("parameter '{}' added", new_parameter.name),
+            );
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! A dry run of what the desktop app would load from a plugin directory:
+//! the same scan, load and discovery as `PluginManager`, carrying on past
+//! files that fail.
+
+use crate::arguments::Format;
+use crate::error::CliError;
+
+use cognexus_plugin_manager::{Loader, NODES_KIND, PluginManagerError, TYPES_KIND, scan_directory};
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+/// What `inspect <directory>` found, printed as text or as JSON.
+#[derive(Default, Serialize)]
+struct Catalogue {
+    directory: String,
+    plugins: Vec<PluginStatus>,
+    types: Vec<CatalogueEntry>,
+    nodes: Vec<CatalogueEntry>,
+    /// Definitions a later plugin replaced, as the registry would.
+    warnings: Vec<String>,
+}
+
+/// How loading one file went.
+#[derive(Serialize)]
+struct PluginStatus {
+    file: String,
+    /// The plugin's name: its file name without the extension.
+    plugin: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<&'static str>,
+    /// Number of definitions it provides.
+    definitions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CatalogueEntry {
+    id: String,
+    name: String,
+    version: String,
+    plugin: String,
+}
+
+/// Load every plugin in `dir` and print the combined catalogue. Returns
+/// whether every plugin loaded, as the desktop app stops at the first one
+/// that doesn't.
+pub(crate) fn inspect_directory(dir: &Path, format: Format) -> Result<bool, CliError> {
+    let loader = Loader::new()?;
+    let paths = scan_directory(dir)?;
+
+    if format == Format::Text {
+        println!("Scanning plugin directory: {}", dir.display());
+        println!("Found {} component(s)\n", paths.len());
+    }
+
+    let mut catalogue = Catalogue {
+        directory: dir.display().to_string(),
+        ..Catalogue::default()
+    };
+    // Where each definition id was last registered from
+    let mut type_plugins = HashMap::new();
+    let mut node_plugins = HashMap::new();
+
+    for path in paths {
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let plugin = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let outcome = discover(&loader, &path);
+        let mut status = PluginStatus {
+            file,
+            plugin: plugin.clone(),
+            kind: None,
+            definitions: 0,
+            error: None,
+        };
+
+        match outcome {
+            Ok(Discovered::Types(types)) => {
+                status.kind = Some(TYPES_KIND);
+                status.definitions = types.len();
+                for entry in types {
+                    register(
+                        "type",
+                        entry,
+                        &plugin,
+                        &mut type_plugins,
+                        &mut catalogue.types,
+                        &mut catalogue.warnings,
+                    );
+                }
+            }
+            Ok(Discovered::Nodes(nodes)) => {
+                status.kind = Some(NODES_KIND);
+                status.definitions = nodes.len();
+                for entry in nodes {
+                    register(
+                        "node",
+                        entry,
+                        &plugin,
+                        &mut node_plugins,
+                        &mut catalogue.nodes,
+                        &mut catalogue.warnings,
+                    );
+                }
+            }
+            Err(error) => status.error = Some(error.to_string()),
+        }
+
+        catalogue.plugins.push(status);
+    }
+
+    let loaded = catalogue
+        .plugins
+        .iter()
+        .all(|status| status.error.is_none());
+
+    match format {
+        Format::Text => print_catalogue(&catalogue),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&catalogue)?),
+    }
+
+    Ok(loaded)
+}
+
+enum Discovered {
+    Types(Vec<(String, String, String)>),
+    Nodes(Vec<(String, String, String)>),
+}
+
+/// Load the component at `path` and list what it provides as the one kind
+/// of plugin the manager would take it as, as (id, name, version).
+fn discover(loader: &Loader, path: &Path) -> Result<Discovered, PluginManagerError> {
+    let component = loader.load_component(path)?;
+
+    match loader.determine_component_kind(&component)? {
+        TYPES_KIND => Ok(Discovered::Types(
+            loader
+                .discover_types(&component)?
+                .into_iter()
+                .map(|t| (t.id, t.name, t.version))
+                .collect(),
+        )),
+        _ => Ok(Discovered::Nodes(
+            loader
+                .discover_nodes(&component)?
+                .into_iter()
+                .map(|n| (n.id, n.name, n.version))
+                .collect(),
+        )),
+    }
+}
+
+/// Add a definition to the catalogue, replacing one with the same id from
+/// an earlier plugin.
+fn register(
+    kind: &str,
+    (id, name, version): (String, String, String),
+    plugin: &str,
+    plugins: &mut HashMap<String, String>,
+    entries: &mut Vec<CatalogueEntry>,
+    warnings: &mut Vec<String>,
+) {
+    if let Some(earlier) = plugins.insert(id.clone(), String::from(plugin)) {
+        warnings.push(format!(
+            "{kind} {name} ({id}) from {plugin} replaces the one from {earlier}"
+        ));
+        entries.retain(|entry| entry.id != id);
+    }
+
+    entries.push(CatalogueEntry {
+        id,
+        name,
+        version,
+        plugin: String::from(plugin),
+    });
+}
+
+fn print_catalogue(catalogue: &Catalogue) {
+    for status in &catalogue.plugins {
+        match (&status.error, status.kind) {
+            (Some(error), _) => println!("  FAILED {}: {error}", status.file),
+            (None, Some(kind)) => println!(
+                "  OK     {}: {kind} plugin, {} definition(s)",
+                status.file, status.definitions
+            ),
+            (None, None) => println!("  OK     {}", status.file),
+        }
+    }
+
+    println!(
+        "\nCatalogue: {} data type(s), {} node(s)",
+        catalogue.types.len(),
+        catalogue.nodes.len()
+    );
+    for (heading, entries) in [("Types", &catalogue.types), ("Nodes", &catalogue.nodes)] {
+        if entries.is_empty() {
+            continue;
+        }
+        println!("  {heading}:");
+        for entry in entries {
+            println!(
+                "    - {} ({}) {} [{}]",
+                entry.name, entry.id, entry.version, entry.plugin
+            );
+        }
+    }
+
+    if !catalogue.warnings.is_empty() {
+        println!("\nFound {} warning(s):", catalogue.warnings.len());
+        for warning in &catalogue.warnings {
+            println!("  - {warning}");
+        }
+    }
+}
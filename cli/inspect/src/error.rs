@@ -6,7 +6,6 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum CliError {
-    #[allow(dead_code)]
     #[error("Common Error: {message} {location}")]
     InspectError {
         message: String,
@@ -18,6 +17,24 @@ pub enum CliError {
         message: String,
         location: ErrorLocation,
     },
+
+    #[error("JSON Error: {message} {location}")]
+    Json {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("IO Error: {message} {location}")]
+    Io {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("Plugin Manager Error: {message} {location}")]
+    PluginManager {
+        message: String,
+        location: ErrorLocation,
+    },
 }
 
 impl CliError {
@@ -30,6 +47,36 @@ impl CliError {
     }
 }
 
+impl From<std::io::Error> for CliError {
+    #[track_caller]
+    fn from(error: std::io::Error) -> Self {
+        CliError::Io {
+            message: error.to_string(),
+            location: ErrorLocation::from(Location::caller()),
+        }
+    }
+}
+
+impl From<cognexus_plugin_manager::PluginManagerError> for CliError {
+    #[track_caller]
+    fn from(error: cognexus_plugin_manager::PluginManagerError) -> Self {
+        CliError::PluginManager {
+            message: error.to_string(),
+            location: ErrorLocation::from(Location::caller()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    #[track_caller]
+    fn from(error: serde_json::Error) -> Self {
+        CliError::Json {
+            message: error.to_string(),
+            location: ErrorLocation::from(Location::caller()),
+        }
+    }
+}
+
 impl From<wasmtime::Error> for CliError {
     #[track_caller]
     fn from(error: wasmtime::Error) -> Self {
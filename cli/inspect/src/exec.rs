@@ -0,0 +1,184 @@
+use crate::arguments::{ExecArguments, Format};
+use crate::error::CliError;
+use crate::nodes_world::exports::cognexus::plugin::nodes::{ConfigValue, PortSpec, PortValue};
+use crate::plugin::Plugin;
+
+use common::error::error_location::ErrorLocation;
+
+use std::fs;
+use std::panic::Location;
+
+use serde::Serialize;
+
+/// Most bytes of an output value printed, in hex.
+const PREVIEW_BYTES: usize = 64;
+
+/// What `exec --format json` prints: every output value, in the order the
+/// node returned them.
+#[derive(Serialize)]
+struct ExecReport {
+    node: String,
+    outputs: Vec<OutputReport>,
+}
+
+#[derive(Serialize)]
+struct OutputReport {
+    port_id: String,
+    port_name: String,
+    /// The whole value, in hex.
+    value: String,
+    /// Where the value was saved, with `--output-dir`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+}
+
+/// Execute the node `args.node` on the inputs and configuration the
+/// arguments name, then print its outputs or save them to files.
+pub(crate) fn exec(
+    plugin: &mut Plugin,
+    args: &ExecArguments,
+    format: Format,
+) -> Result<(), CliError> {
+    let node = plugin
+        .list_nodes()?
+        .into_iter()
+        .find(|node| node.id == args.node)
+        .ok_or_else(|| CliError::InspectError {
+            message: format!("Plugin has no node {}", args.node),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+    let mut config = Vec::with_capacity(args.config.len());
+    for arg in &args.config {
+        let (name, file) = arg.split_once('=').ok_or_else(|| CliError::InspectError {
+            message: format!("Expected NAME=FILE for --config, got '{arg}'"),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+        config.push(ConfigValue {
+            name: String::from(name),
+            value: fs::read(file)?,
+        });
+    }
+
+    // A configured node's ports depend on its configuration
+    let (input_ports, output_ports) = if node.configured_ports {
+        let ports = plugin
+            .resolve_ports(&node.id, &config)?
+            .map_err(|message| CliError::InspectError {
+                message: format!("Node {} rejected its configuration: {message}", node.name),
+                location: ErrorLocation::from(Location::caller()),
+            })?;
+        (ports.input_ports, ports.output_ports)
+    } else {
+        (node.input_ports, node.output_ports)
+    };
+
+    let mut inputs = Vec::with_capacity(args.input.len());
+    for arg in &args.input {
+        let (port, file) = match arg.split_once('=') {
+            Some((port, file)) => (find_port(&input_ports, port)?, file),
+            None => match input_ports.as_slice() {
+                [port] => (port, arg.as_str()),
+                _ => {
+                    return Err(CliError::InspectError {
+                        message: format!(
+                            "Node {} has {} input ports; use PORT=FILE to say which one '{arg}' is for",
+                            node.name,
+                            input_ports.len()
+                        ),
+                        location: ErrorLocation::from(Location::caller()),
+                    });
+                }
+            },
+        };
+        inputs.push(PortValue {
+            port_id: port.id.clone(),
+            value: fs::read(file)?,
+        });
+    }
+
+    let outputs = plugin
+        .execute(&node.id, &config, &inputs)?
+        .map_err(|message| CliError::InspectError {
+            message: format!("Node {} failed: {message}", node.name),
+            location: ErrorLocation::from(Location::caller()),
+        })?;
+
+    if let Some(dir) = &args.output_dir {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut report = ExecReport {
+        node: node.id,
+        outputs: Vec::with_capacity(outputs.len()),
+    };
+    for output in outputs {
+        // Plugins may return values for ports they don't declare
+        let port_name = output_ports
+            .iter()
+            .find(|port| port.id == output.port_id)
+            .map(|port| port.name.clone())
+            .unwrap_or_else(|| output.port_id.clone());
+
+        let path = match &args.output_dir {
+            Some(dir) => {
+                let path = dir.join(format!("{port_name}.bin"));
+                fs::write(&path, &output.value)?;
+                Some(path.display().to_string())
+            }
+            None => None,
+        };
+
+        report.outputs.push(OutputReport {
+            port_id: output.port_id,
+            port_name,
+            value: hex(&output.value),
+            path,
+        });
+    }
+
+    match format {
+        Format::Text => {
+            println!(
+                "\nNode {} produced {} output(s):",
+                node.name,
+                report.outputs.len()
+            );
+            for output in &report.outputs {
+                let bytes = output.value.len() / 2;
+                println!(
+                    "  - {} ({}): {bytes} byte(s)",
+                    output.port_name, output.port_id
+                );
+                match &output.path {
+                    Some(path) => println!("    Saved to {path}"),
+                    None if bytes > PREVIEW_BYTES => {
+                        println!("    {}...", &output.value[..PREVIEW_BYTES * 2])
+                    }
+                    None if bytes > 0 => println!("    {}", output.value),
+                    None => {}
+                }
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
+
+/// The port `port` names, by id or by name.
+#[track_caller]
+fn find_port<'a>(ports: &'a [PortSpec], port: &str) -> Result<&'a PortSpec, CliError> {
+    ports
+        .iter()
+        .find(|spec| spec.id == port)
+        .or_else(|| ports.iter().find(|spec| spec.name == port))
+        .ok_or_else(|| CliError::InspectError {
+            message: format!("Node has no input port {port}"),
+            location: ErrorLocation::from(Location::caller()),
+        })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
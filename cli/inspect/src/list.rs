@@ -0,0 +1,67 @@
+use crate::arguments::Format;
+use crate::error::CliError;
+use crate::nodes_world::exports::cognexus::plugin::nodes::Icon;
+use crate::plugin::Plugin;
+use crate::report::{NodeReport, Report, TypeReport};
+
+use cognexus_plugin_manager::{NODES_KIND, TYPES_KIND};
+
+/// List what the component provides as each of `kinds`, as text or as one
+/// JSON report.
+pub(crate) fn list(plugin: &mut Plugin, kinds: &[&str], format: Format) -> Result<(), CliError> {
+    let mut report = Report::default();
+
+    for &kind in kinds {
+        match kind {
+            TYPES_KIND => {
+                let types = plugin.list_types()?;
+
+                if format == Format::Json {
+                    report.types = Some(types.into_iter().map(TypeReport::from).collect());
+                    continue;
+                }
+
+                println!("\nFound {} data type(s):", types.len());
+                for type_info in types {
+                    println!("  - {} ({})", type_info.name, type_info.id);
+                    println!("    Description: {}", type_info.description);
+                    println!("    Version: {}", type_info.version);
+                }
+            }
+            NODES_KIND => {
+                let nodes = plugin.list_nodes()?;
+
+                if format == Format::Json {
+                    report.nodes = Some(nodes.into_iter().map(NodeReport::from).collect());
+                    continue;
+                }
+
+                println!("\nFound {} node(s):", nodes.len());
+                for node_info in nodes {
+                    println!("  - {} ({})", node_info.name, node_info.id);
+                    println!("    Description: {}", node_info.description);
+                    println!("    Category: {}", node_info.category);
+                    if !node_info.tags.is_empty() {
+                        println!("    Tags: {}", node_info.tags.join(", "));
+                    }
+                    match &node_info.icon {
+                        Some(Icon::Glyph(name)) => println!("    Icon: {name}"),
+                        Some(Icon::Svg(svg)) => println!("    Icon: SVG ({} bytes)", svg.len()),
+                        None => {}
+                    }
+                    println!("    Version: {}", node_info.version);
+                    println!("    Input ports: {}", node_info.input_ports.len());
+                    println!("    Output ports: {}", node_info.output_ports.len());
+                }
+            }
+            // Plugin::kinds only gives the two kinds above
+            _ => {}
+        }
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    }
+
+    Ok(())
+}
@@ -1,14 +1,37 @@
 pub(crate) mod arguments;
+mod bench;
+mod diff;
+mod directory;
 pub(crate) mod error;
+mod exec;
+mod list;
+mod package;
+mod plugin;
 mod plugin_state;
+mod report;
+mod validate;
+mod watch;
+mod wit;
 
-use crate::arguments::Arguments;
+use crate::arguments::{Arguments, Command, Format, PluginArguments};
+use crate::bench::bench;
+use crate::diff::diff;
+use crate::directory::inspect_directory;
 use crate::error::CliError;
+use crate::exec::exec;
+use crate::list::list;
+use crate::package::package;
+use crate::plugin::Plugin;
+use crate::validate::validate;
+use crate::watch::watch;
+use crate::wit::wit;
+
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+use std::path::Path;
 
 use clap::Parser;
-use wasmtime::Engine;
-use wasmtime::component::Component;
-use wasmtime_wasi::p2;
 
 mod types_world {
     wasmtime::component::bindgen!({
@@ -21,60 +44,99 @@ mod nodes_world {
     wasmtime::component::bindgen!({
         path: "../../wit",
         world: "nodes-plugin",
+        // So diff can compare icons and parameter constraints
+        additional_derives: [PartialEq],
     });
 }
 
 fn main() -> Result<(), CliError> {
     let args = Arguments::parse();
 
-    println!("Loading WASM component: {}", args.wasm_file);
-
-    // Set up engine with component model support
-    let mut config = wasmtime::Config::new();
-    config.wasm_component_model(true);
-    let engine = Engine::new(&config)?;
-
-    // Load the component
-    let component = Component::from_file(&engine, &args.wasm_file)?;
-
-    // Create linker and add WASI support
-    let mut linker = wasmtime::component::Linker::new(&engine);
-    p2::add_to_linker_sync(&mut linker)?;
+    match &args.command {
+        None => {
+            let wasm_file = wasm_file(&args.plugin)?;
 
-    // Create store with our state
-    let state = plugin_state::PluginState::new();
-    let mut store = wasmtime::Store::new(&engine, state);
+            if Path::new(wasm_file).is_dir() {
+                if args.watch {
+                    return Err(CliError::InspectError {
+                        message: format!(
+                            "Cannot watch {wasm_file}: it is a directory, not a WASM file"
+                        ),
+                        location: ErrorLocation::from(Location::caller()),
+                    });
+                }
 
-    match args.kind.as_str() {
-        "types" => {
-            let plugin = types_world::TypesPlugin::instantiate(&mut store, &component, &linker)?;
-            let types = plugin.cognexus_plugin_types().call_list_types(&mut store)?;
+                if !inspect_directory(Path::new(wasm_file), args.format)? {
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
 
-            println!("\nFound {} data type(s):", types.len());
-            for type_info in types {
-                println!("  - {} ({})", type_info.name, type_info.id);
-                println!("    Description: {}", type_info.description);
-                println!("    Version: {}", type_info.version);
+            if args.watch {
+                watch(wasm_file, &args.plugin, args.format);
             }
+
+            let mut plugin = load_file(wasm_file, args.format)?;
+            let kinds = plugin.kinds(args.plugin.kind.as_deref())?;
+            list(&mut plugin, &kinds, args.format)?;
         }
-        "nodes" => {
-            let plugin = nodes_world::NodesPlugin::instantiate(&mut store, &component, &linker)?;
-            let nodes = plugin.cognexus_plugin_nodes().call_list_nodes(&mut store)?;
-
-            println!("\nFound {} node(s):", nodes.len());
-            for node_info in nodes {
-                println!("  - {} ({})", node_info.name, node_info.id);
-                println!("    Description: {}", node_info.description);
-                println!("    Version: {}", node_info.version);
-                println!("    Input ports: {}", node_info.input_ports.len());
-                println!("    Output ports: {}", node_info.output_ports.len());
+        Some(Command::Diff(diff_args)) => {
+            let mut old = load_file(&diff_args.old_wasm_file, args.format)?;
+            let mut new = load_file(&diff_args.new_wasm_file, args.format)?;
+            if !diff(&mut old, &mut new, diff_args, args.format)? {
+                std::process::exit(1);
             }
         }
-        _ => {
-            eprintln!("Unknown plugin kind: {}. Use 'types' or 'nodes'", args.kind);
-            std::process::exit(1);
+        Some(Command::Exec(exec_args)) => {
+            let mut plugin = load_file(&exec_args.wasm_file, args.format)?;
+            exec(&mut plugin, exec_args, args.format)?;
+        }
+        Some(Command::Package(package_args)) => {
+            let mut plugin = load_file(&package_args.wasm_file, args.format)?;
+            package(&mut plugin, package_args, args.format)?;
+        }
+        Some(Command::Bench(bench_args)) => {
+            loading(&bench_args.wasm_file.display().to_string(), args.format);
+            bench(bench_args, args.format)?;
+        }
+        Some(Command::Wit(wit_args)) => {
+            loading(&wit_args.wasm_file.display().to_string(), args.format);
+            wit(&wit_args.wasm_file, args.format)?;
+        }
+        Some(Command::Validate(plugin_args)) => {
+            let mut plugin = load_file(wasm_file(plugin_args)?, args.format)?;
+            if !validate(&mut plugin, plugin_args.kind.as_deref(), args.format)? {
+                std::process::exit(1);
+            }
         }
     }
 
     Ok(())
 }
+
+/// The file of the plugin the arguments name.
+#[track_caller]
+fn wasm_file(args: &PluginArguments) -> Result<&str, CliError> {
+    // Clap only leaves the file out when a subcommand that doesn't take
+    // one is given
+    args.wasm_file
+        .as_deref()
+        .ok_or_else(|| CliError::InspectError {
+            message: String::from("No WASM file given"),
+            location: ErrorLocation::from(Location::caller()),
+        })
+}
+
+fn load_file(wasm_file: &str, format: Format) -> Result<Plugin, CliError> {
+    loading(wasm_file, format);
+
+    Plugin::load(wasm_file)
+}
+
+fn loading(wasm_file: &str, format: Format) {
+    // Keep stdout to the JSON document alone
+    match format {
+        Format::Text => println!("Loading WASM component: {wasm_file}"),
+        Format::Json => eprintln!("Loading WASM component: {wasm_file}"),
+    }
+}
@@ -0,0 +1,86 @@
+use crate::arguments::{Format, PackageArguments};
+use crate::error::CliError;
+use crate::plugin::Plugin;
+use crate::validate::check;
+
+use cognexus_plugin_manager::PluginPackage;
+use common::error::error_location::ErrorLocation;
+
+use std::fs;
+use std::panic::Location;
+
+use serde::Serialize;
+
+/// What `package --format json` prints.
+#[derive(Serialize)]
+struct PackageReport {
+    name: String,
+    version: String,
+    output: String,
+    /// SHA-256 of the component, in hex.
+    checksum: String,
+    /// Size of the package, in bytes.
+    bytes: usize,
+    signed: bool,
+}
+
+/// Bundle the plugin with its manifest, checksum and signature into a
+/// `.cxp` package, once the manifest is valid and the plugin passes every
+/// `validate` check.
+pub(crate) fn package(
+    plugin: &mut Plugin,
+    args: &PackageArguments,
+    format: Format,
+) -> Result<(), CliError> {
+    let manifest = fs::read_to_string(&args.manifest)?;
+    let wasm = fs::read(&args.wasm_file)?;
+    let signature = args.signature.as_ref().map(fs::read).transpose()?;
+
+    let package = PluginPackage::new(manifest, wasm, signature)?;
+
+    let report = check(plugin, None)?;
+    if !report.valid {
+        let problems: Vec<String> = report
+            .problems
+            .iter()
+            .map(|problem| format!("  - {}: {}", problem.subject, problem.message))
+            .collect();
+        return Err(CliError::InspectError {
+            message: format!(
+                "Not packaging a plugin that fails validation:\n{}",
+                problems.join("\n")
+            ),
+            location: ErrorLocation::from(Location::caller()),
+        });
+    }
+
+    let bytes = package.to_bytes()?;
+    fs::write(&args.output, &bytes)?;
+
+    let report = PackageReport {
+        name: package.manifest.name,
+        version: package.manifest.version,
+        output: args.output.display().to_string(),
+        checksum: package.checksum,
+        bytes: bytes.len(),
+        signed: package.signature.is_some(),
+    };
+
+    match format {
+        Format::Text => {
+            println!(
+                "\nPackaged {} {} to {} ({} bytes)",
+                report.name, report.version, report.output, report.bytes
+            );
+            println!("  SHA-256: {}", report.checksum);
+            if report.signed {
+                println!("  Signature included");
+            } else {
+                println!("  Not signed");
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use crate::error::CliError;
+use crate::nodes_world::NodesPlugin;
+use crate::nodes_world::exports::cognexus::plugin::nodes::{
+    ConfigValue, InstancePorts, NodeInfo, PortValue,
+};
+use crate::plugin_state::PluginState;
+use crate::types_world::TypesPlugin;
+use crate::types_world::exports::cognexus::plugin::types::TypeInfo;
+
+use cognexus_plugin_manager::{NODES_KIND, TYPES_KIND, component_kinds};
+use common::error::error_location::ErrorLocation;
+
+use std::panic::Location;
+
+use wasmtime::component::{Component, HasSelf, Linker};
+use wasmtime::{Config, Engine, Store};
+use wasmtime_wasi::p2;
+
+/// A plugin component loaded for inspection, with a store to call it in.
+pub(crate) struct Plugin {
+    engine: Engine,
+    component: Component,
+    linker: Linker<PluginState>,
+    store: Store<PluginState>,
+}
+
+impl Plugin {
+    pub(crate) fn load(wasm_file: &str) -> Result<Self, CliError> {
+        let engine = new_engine()?;
+
+        // Load the component
+        let component = Component::from_file(&engine, wasm_file)?;
+
+        let linker = new_linker(&engine)?;
+
+        // Create store with our state
+        let store = Store::new(&engine, PluginState::new());
+
+        Ok(Self {
+            engine,
+            component,
+            linker,
+            store,
+        })
+    }
+
+    /// Every kind of plugin the component exports an interface for.
+    pub(crate) fn exported_kinds(&self) -> Vec<&'static str> {
+        component_kinds(&self.engine, &self.component)
+    }
+
+    /// The kinds to inspect the component as: `kind` if given, otherwise
+    /// every kind it exports.
+    #[track_caller]
+    pub(crate) fn kinds(&self, kind: Option<&str>) -> Result<Vec<&'static str>, CliError> {
+        if let Some(kind) = kind {
+            return Ok(vec![plugin_kind(kind)?]);
+        }
+
+        let kinds = self.exported_kinds();
+        if kinds.is_empty() {
+            return Err(CliError::InspectError {
+                message: String::from("Component exports neither a types nor a nodes interface"),
+                location: ErrorLocation::from(Location::caller()),
+            });
+        }
+
+        Ok(kinds)
+    }
+
+    pub(crate) fn list_types(&mut self) -> Result<Vec<TypeInfo>, CliError> {
+        let plugin = TypesPlugin::instantiate(&mut self.store, &self.component, &self.linker)?;
+
+        Ok(plugin
+            .cognexus_plugin_types()
+            .call_list_types(&mut self.store)?)
+    }
+
+    pub(crate) fn list_nodes(&mut self) -> Result<Vec<NodeInfo>, CliError> {
+        let plugin = NodesPlugin::instantiate(&mut self.store, &self.component, &self.linker)?;
+
+        Ok(plugin
+            .cognexus_plugin_nodes()
+            .call_list_nodes(&mut self.store)?)
+    }
+
+    /// The ports of an instance of `definition_id` with `config`, or why the
+    /// plugin rejected the configuration.
+    pub(crate) fn resolve_ports(
+        &mut self,
+        definition_id: &str,
+        config: &[ConfigValue],
+    ) -> Result<Result<InstancePorts, String>, CliError> {
+        let plugin = NodesPlugin::instantiate(&mut self.store, &self.component, &self.linker)?;
+
+        Ok(plugin.cognexus_plugin_nodes().call_resolve_ports(
+            &mut self.store,
+            definition_id,
+            config,
+        )?)
+    }
+
+    /// Run `definition_id` in a fresh instance, returning its outputs or why
+    /// the node failed.
+    pub(crate) fn execute(
+        &mut self,
+        definition_id: &str,
+        config: &[ConfigValue],
+        inputs: &[PortValue],
+    ) -> Result<Result<Vec<PortValue>, String>, CliError> {
+        let plugin = NodesPlugin::instantiate(&mut self.store, &self.component, &self.linker)?;
+
+        Ok(plugin.cognexus_plugin_nodes().call_execute(
+            &mut self.store,
+            definition_id,
+            config,
+            inputs,
+        )?)
+    }
+}
+
+/// An engine with component model support.
+pub(crate) fn new_engine() -> Result<Engine, CliError> {
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+
+    Ok(Engine::new(&config)?)
+}
+
+/// A linker with WASI support and the host interfaces plugins import.
+pub(crate) fn new_linker(engine: &Engine) -> Result<Linker<PluginState>, CliError> {
+    let mut linker = Linker::new(engine);
+    p2::add_to_linker_sync(&mut linker)?;
+    NodesPlugin::add_to_linker::<_, HasSelf<_>>(&mut linker, |state| state)?;
+
+    Ok(linker)
+}
+
+/// The plugin kind named `kind`.
+#[track_caller]
+pub(crate) fn plugin_kind(kind: &str) -> Result<&'static str, CliError> {
+    match kind {
+        TYPES_KIND => Ok(TYPES_KIND),
+        NODES_KIND => Ok(NODES_KIND),
+        _ => Err(CliError::InspectError {
+            message: format!("Unknown plugin kind: {kind}. Use 'types' or 'nodes'"),
+            location: ErrorLocation::from(Location::caller()),
+        }),
+    }
+}
@@ -1,3 +1,5 @@
+use crate::nodes_world::cognexus::plugin::{clock, logging, random};
+
 use wasmtime::component::ResourceTable;
 use wasmtime_wasi::{WasiCtx, WasiCtxView, WasiView};
 
@@ -23,3 +25,34 @@ impl WasiView for PluginState {
         }
     }
 }
+
+// Inspecting only lists a plugin's nodes, so the host interfaces node
+// executions use do nothing.
+impl clock::Host for PluginState {
+    fn sleep(&mut self, _nanoseconds: u64) {}
+
+    fn defer(&mut self, _nanoseconds: u64) {}
+}
+
+impl random::Host for PluginState {
+    fn random_bytes(&mut self, len: u32) -> Vec<u8> {
+        vec![0; len as usize]
+    }
+
+    fn random_u64(&mut self) -> u64 {
+        0
+    }
+}
+
+impl logging::Host for PluginState {
+    fn log(&mut self, _level: logging::Level, _message: String) {}
+
+    fn log_value(
+        &mut self,
+        _level: logging::Level,
+        _message: String,
+        _type_id: String,
+        _value: Vec<u8>,
+    ) {
+    }
+}
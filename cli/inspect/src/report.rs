@@ -0,0 +1,161 @@
+use crate::nodes_world::exports::cognexus::plugin::nodes::{
+    Direction, Icon, NodeInfo, ParameterSpec, PortSpec,
+};
+use crate::types_world::exports::cognexus::plugin::types::{FieldInfo, TypeInfo};
+
+use serde::Serialize;
+
+/// What `--format json` prints: everything the plugin lists, in the order
+/// it lists it, for each kind of plugin it is.
+#[derive(Default, Serialize)]
+pub(crate) struct Report {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) types: Option<Vec<TypeReport>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) nodes: Option<Vec<NodeReport>>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct TypeReport {
+    id: String,
+    name: String,
+    description: String,
+    version: String,
+    fields: Vec<FieldReport>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct FieldReport {
+    name: String,
+    type_id: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct NodeReport {
+    id: String,
+    name: String,
+    description: String,
+    category: String,
+    tags: Vec<String>,
+    icon: Option<IconReport>,
+    version: String,
+    input_ports: Vec<PortReport>,
+    output_ports: Vec<PortReport>,
+    parameters: Vec<ParameterReport>,
+    branching: bool,
+    merging: bool,
+    configured_ports: bool,
+    deterministic: bool,
+}
+
+/// An icon, with an SVG given only by its size, as in the text output.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum IconReport {
+    Glyph(String),
+    Svg { bytes: usize },
+}
+
+#[derive(Serialize)]
+pub(crate) struct PortReport {
+    id: String,
+    name: String,
+    direction: &'static str,
+    data_type_id: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ParameterReport {
+    name: String,
+    description: String,
+    data_type_id: String,
+    required: bool,
+    has_default: bool,
+}
+
+impl From<TypeInfo> for TypeReport {
+    fn from(type_info: TypeInfo) -> Self {
+        Self {
+            id: type_info.id,
+            name: type_info.name,
+            description: type_info.description,
+            version: type_info.version,
+            fields: type_info
+                .fields
+                .into_iter()
+                .map(FieldReport::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<FieldInfo> for FieldReport {
+    fn from(field: FieldInfo) -> Self {
+        Self {
+            name: field.name,
+            type_id: field.type_id,
+        }
+    }
+}
+
+impl From<NodeInfo> for NodeReport {
+    fn from(node_info: NodeInfo) -> Self {
+        Self {
+            id: node_info.id,
+            name: node_info.name,
+            description: node_info.description,
+            category: node_info.category,
+            tags: node_info.tags,
+            icon: node_info.icon.map(|icon| match icon {
+                Icon::Glyph(name) => IconReport::Glyph(name),
+                Icon::Svg(svg) => IconReport::Svg { bytes: svg.len() },
+            }),
+            version: node_info.version,
+            input_ports: node_info
+                .input_ports
+                .into_iter()
+                .map(PortReport::from)
+                .collect(),
+            output_ports: node_info
+                .output_ports
+                .into_iter()
+                .map(PortReport::from)
+                .collect(),
+            parameters: node_info
+                .parameters
+                .into_iter()
+                .map(ParameterReport::from)
+                .collect(),
+            branching: node_info.branching,
+            merging: node_info.merging,
+            configured_ports: node_info.configured_ports,
+            deterministic: node_info.deterministic,
+        }
+    }
+}
+
+impl From<PortSpec> for PortReport {
+    fn from(port: PortSpec) -> Self {
+        Self {
+            id: port.id,
+            name: port.name,
+            direction: match port.direction {
+                Direction::Input => "input",
+                Direction::Output => "output",
+            },
+            data_type_id: port.data_type_id,
+        }
+    }
+}
+
+impl From<ParameterSpec> for ParameterReport {
+    fn from(parameter: ParameterSpec) -> Self {
+        Self {
+            name: parameter.name,
+            description: parameter.description,
+            data_type_id: parameter.data_type_id,
+            required: parameter.required,
+            has_default: parameter.default_value.is_some(),
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! The checks a plugin has to pass before it's released: that it exports a
+//! plugin interface, and that everything it lists is well-formed.
+
+use crate::arguments::Format;
+use crate::error::CliError;
+use crate::nodes_world::exports::cognexus::plugin::nodes::{
+    Direction, NodeInfo, ParameterConstraint, PortSpec,
+};
+use crate::plugin::{Plugin, plugin_kind};
+use crate::types_world::exports::cognexus::plugin::types::TypeInfo;
+
+use cognexus_plugin_manager::{NODES_KIND, TYPES_KIND};
+
+use std::collections::HashSet;
+
+use semver::Version;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// What `validate` found, printed as text or as JSON.
+#[derive(Default, Serialize)]
+pub(crate) struct ValidationReport {
+    pub(crate) valid: bool,
+    /// Number of types and nodes checked.
+    pub(crate) types: usize,
+    pub(crate) nodes: usize,
+    pub(crate) problems: Vec<Problem>,
+}
+
+/// One failed check.
+#[derive(Serialize)]
+pub(crate) struct Problem {
+    /// What the check was on, such as `node Add (<id>)`.
+    pub(crate) subject: String,
+    pub(crate) message: String,
+}
+
+/// Check the component as each kind of plugin it exports, or as `kind`
+/// alone, and print the report. Returns whether every check passed.
+pub(crate) fn validate(
+    plugin: &mut Plugin,
+    kind: Option<&str>,
+    format: Format,
+) -> Result<bool, CliError> {
+    let report = check(plugin, kind)?;
+
+    match format {
+        Format::Text => print_report(&report),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(report.valid)
+}
+
+/// Run every check on the component as each kind of plugin it exports, or
+/// as `kind` alone.
+pub(crate) fn check(plugin: &mut Plugin, kind: Option<&str>) -> Result<ValidationReport, CliError> {
+    let mut report = ValidationReport::default();
+    let mut problems = Problems::default();
+
+    let exported = plugin.exported_kinds();
+    let kinds = match kind {
+        Some(kind) => {
+            let kind = plugin_kind(kind)?;
+            if !exported.contains(&kind) {
+                problems.add("component", format!("does not export a {kind} interface"));
+            }
+            exported.into_iter().filter(|&k| k == kind).collect()
+        }
+        None => {
+            if exported.is_empty() {
+                problems.add("component", "exports neither a types nor a nodes interface");
+            }
+            exported
+        }
+    };
+
+    for kind in kinds {
+        match kind {
+            TYPES_KIND => {
+                let types = plugin.list_types()?;
+                report.types = types.len();
+                check_types(&types, &mut problems);
+            }
+            NODES_KIND => {
+                let nodes = plugin.list_nodes()?;
+                report.nodes = nodes.len();
+                check_nodes(&nodes, &mut problems);
+            }
+            _ => {}
+        }
+    }
+
+    report.problems = problems.0;
+    report.valid = report.problems.is_empty();
+
+    Ok(report)
+}
+
+fn print_report(report: &ValidationReport) {
+    println!(
+        "\nChecked {} data type(s) and {} node(s)",
+        report.types, report.nodes
+    );
+
+    if report.valid {
+        println!("No problems found");
+        return;
+    }
+
+    println!("\nFound {} problem(s):", report.problems.len());
+    for problem in &report.problems {
+        println!("  - {}: {}", problem.subject, problem.message);
+    }
+}
+
+#[derive(Default)]
+struct Problems(Vec<Problem>);
+
+impl Problems {
+    fn add(&mut self, subject: impl Into<String>, message: impl Into<String>) {
+        self.0.push(Problem {
+            subject: subject.into(),
+            message: message.into(),
+        });
+    }
+}
+
+fn check_types(types: &[TypeInfo], problems: &mut Problems) {
+    let mut ids = HashSet::new();
+
+    for type_info in types {
+        let subject = format!("type {} ({})", type_info.name, type_info.id);
+
+        check_metadata(
+            &subject,
+            &type_info.id,
+            &type_info.name,
+            &type_info.version,
+            problems,
+        );
+        if !ids.insert(type_info.id.as_str()) {
+            problems.add(&subject, "id is used by another type");
+        }
+
+        let mut names = HashSet::new();
+        for field in &type_info.fields {
+            if field.name.is_empty() {
+                problems.add(&subject, "has a field with no name");
+            } else if !names.insert(field.name.as_str()) {
+                problems.add(
+                    &subject,
+                    format!("declares field '{}' more than once", field.name),
+                );
+            }
+            if !is_uuid(&field.type_id) {
+                problems.add(
+                    &subject,
+                    format!(
+                        "field '{}' refers to type '{}', which is not a UUID",
+                        field.name, field.type_id
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn check_nodes(nodes: &[NodeInfo], problems: &mut Problems) {
+    let mut ids = HashSet::new();
+
+    for node_info in nodes {
+        let subject = format!("node {} ({})", node_info.name, node_info.id);
+
+        check_metadata(
+            &subject,
+            &node_info.id,
+            &node_info.name,
+            &node_info.version,
+            problems,
+        );
+        if !ids.insert(node_info.id.as_str()) {
+            problems.add(&subject, "id is used by another node");
+        }
+
+        // Port ids are unique across both sides, as edges name them alone
+        let mut port_ids = HashSet::new();
+        for (ports, direction) in [
+            (&node_info.input_ports, Direction::Input),
+            (&node_info.output_ports, Direction::Output),
+        ] {
+            for port in ports {
+                check_port(&subject, port, direction, &mut port_ids, problems);
+            }
+        }
+
+        let mut names = HashSet::new();
+        for parameter in &node_info.parameters {
+            if parameter.name.is_empty() {
+                problems.add(&subject, "has a parameter with no name");
+            } else if !names.insert(parameter.name.as_str()) {
+                problems.add(
+                    &subject,
+                    format!("declares parameter '{}' more than once", parameter.name),
+                );
+            }
+            if !is_uuid(&parameter.data_type_id) {
+                problems.add(
+                    &subject,
+                    format!(
+                        "parameter '{}' refers to type '{}', which is not a UUID",
+                        parameter.name, parameter.data_type_id
+                    ),
+                );
+            }
+
+            let minimum = parameter.constraints.iter().find_map(|c| match c {
+                ParameterConstraint::Minimum(minimum) => Some(*minimum),
+                _ => None,
+            });
+            let maximum = parameter.constraints.iter().find_map(|c| match c {
+                ParameterConstraint::Maximum(maximum) => Some(*maximum),
+                _ => None,
+            });
+            if let (Some(minimum), Some(maximum)) = (minimum, maximum)
+                && minimum > maximum
+            {
+                problems.add(
+                    &subject,
+                    format!(
+                        "parameter '{}' has a minimum of {minimum} above its maximum of {maximum}",
+                        parameter.name
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Check what types and nodes both have: a UUID, a name and a semantic
+/// version.
+fn check_metadata(subject: &str, id: &str, name: &str, version: &str, problems: &mut Problems) {
+    if !is_uuid(id) {
+        problems.add(subject, format!("id '{id}' is not a UUID"));
+    }
+    if name.is_empty() {
+        problems.add(subject, "has no name");
+    }
+    if Version::parse(version).is_err() {
+        problems.add(
+            subject,
+            format!("version '{version}' is not a semantic version"),
+        );
+    }
+}
+
+fn check_port<'a>(
+    subject: &str,
+    port: &'a PortSpec,
+    direction: Direction,
+    port_ids: &mut HashSet<&'a str>,
+    problems: &mut Problems,
+) {
+    let side = match direction {
+        Direction::Input => "input",
+        Direction::Output => "output",
+    };
+
+    if !is_uuid(&port.id) {
+        problems.add(
+            subject,
+            format!(
+                "{side} port '{}' has id '{}', which is not a UUID",
+                port.name, port.id
+            ),
+        );
+    } else if !port_ids.insert(port.id.as_str()) {
+        problems.add(
+            subject,
+            format!("{side} port '{}' has the id of another port", port.name),
+        );
+    }
+    if port.name.is_empty() {
+        problems.add(subject, format!("{side} port {} has no name", port.id));
+    }
+    if port.direction != direction {
+        problems.add(
+            subject,
+            format!(
+                "{side} port '{}' is declared with the other direction",
+                port.name
+            ),
+        );
+    }
+    if !is_uuid(&port.data_type_id) {
+        problems.add(
+            subject,
+            format!(
+                "{side} port '{}' refers to type '{}', which is not a UUID",
+                port.name, port.data_type_id
+            ),
+        );
+    }
+}
+
+fn is_uuid(id: &str) -> bool {
+    Uuid::parse_str(id).is_ok()
+}
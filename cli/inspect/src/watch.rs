@@ -0,0 +1,60 @@
+use crate::arguments::{Format, PluginArguments};
+use crate::error::CliError;
+use crate::list::list;
+use crate::plugin::Plugin;
+
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// List the plugin, then list it again every time its file changes, until
+/// interrupted.
+///
+/// A build may leave the file half written or broken, so errors are printed
+/// and watching carries on.
+pub(crate) fn watch(wasm_file: &str, args: &PluginArguments, format: Format) -> ! {
+    let mut last_seen = None;
+
+    loop {
+        let current = fingerprint(wasm_file);
+        if current.is_some() && current != last_seen {
+            // Wait for the build to finish writing it
+            thread::sleep(POLL_INTERVAL);
+            if fingerprint(wasm_file) != current {
+                continue;
+            }
+
+            if last_seen.is_some() {
+                match format {
+                    Format::Text => println!("\n--- {wasm_file} changed ---"),
+                    Format::Json => eprintln!("--- {wasm_file} changed ---"),
+                }
+            }
+            last_seen = current;
+
+            if let Err(error) = inspect(wasm_file, args, format) {
+                eprintln!("Error: {error}");
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn inspect(wasm_file: &str, args: &PluginArguments, format: Format) -> Result<(), CliError> {
+    let mut plugin = Plugin::load(wasm_file)?;
+    let kinds = plugin.kinds(args.kind.as_deref())?;
+
+    list(&mut plugin, &kinds, format)
+}
+
+/// When the file was last modified and how long it is, or nothing while it
+/// doesn't exist.
+fn fingerprint(wasm_file: &str) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(wasm_file).ok()?;
+
+    Some((metadata.modified().ok()?, metadata.len()))
+}
@@ -0,0 +1,38 @@
+use crate::arguments::Format;
+use crate::error::CliError;
+
+use cognexus_plugin_manager::{ComponentInterface, Loader};
+
+use std::path::Path;
+
+/// Print every interface and function the component at `wasm_file`
+/// imports and exports.
+pub(crate) fn wit(wasm_file: &Path, format: Format) -> Result<(), CliError> {
+    let loader = Loader::new()?;
+    let component = loader.load_component(wasm_file)?;
+    let interfaces = loader.component_interfaces(&component);
+
+    match format {
+        Format::Text => {
+            print_interfaces("Imports", &interfaces.imports);
+            print_interfaces("Exports", &interfaces.exports);
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&interfaces)?),
+    }
+
+    Ok(())
+}
+
+fn print_interfaces(heading: &str, interfaces: &[ComponentInterface]) {
+    println!("\n{heading} ({}):", interfaces.len());
+
+    for interface in interfaces {
+        println!("  - {} ({})", interface.name, interface.kind);
+        for ty in &interface.types {
+            println!("      type {ty}");
+        }
+        for function in &interface.functions {
+            println!("      {function}");
+        }
+    }
+}
@@ -0,0 +1,34 @@
+using Google.Protobuf;
+
+namespace CognexusBlazor.Services;
+
+// Packs commands back to back into one stream for handle_frames_command:
+// each is a little-endian uint32 length of the message, a little-endian
+// uint16 tag, its field number in the Command oneof, and the message itself
+public static class CommandFraming
+{
+    public static void WriteFrame(Stream stream, Cognexus.Commands.Command command)
+    {
+        if (command.CommandCase == Cognexus.Commands.Command.CommandOneofCase.None)
+            throw new ArgumentException("Command has nothing set", nameof(command));
+
+        var field = Cognexus.Commands.Command.Descriptor.Oneofs[0].Accessor.GetCaseFieldDescriptor(command);
+        var message = (IMessage)field.Accessor.GetValue(command);
+        var body = message.ToByteArray();
+
+        Span<byte> header = stackalloc byte[6];
+        System.Buffers.Binary.BinaryPrimitives.WriteUInt32LittleEndian(header, (uint)body.Length);
+        System.Buffers.Binary.BinaryPrimitives.WriteUInt16LittleEndian(header[4..], (ushort)command.CommandCase);
+        stream.Write(header);
+        stream.Write(body);
+    }
+
+    public static byte[] Pack(IEnumerable<Cognexus.Commands.Command> commands)
+    {
+        using var stream = new MemoryStream();
+        foreach (var command in commands)
+            WriteFrame(stream, command);
+
+        return stream.ToArray();
+    }
+}
@@ -0,0 +1,15 @@
+namespace CognexusBlazor.Services;
+
+// A command the renderer answered with a failed CommandResult
+public class RendererCommandException : Exception
+{
+    public uint RequestId { get; }
+    public string ErrorCode { get; }
+
+    public RendererCommandException(uint requestId, string errorCode, string message)
+        : base(message)
+    {
+        RequestId = requestId;
+        ErrorCode = errorCode;
+    }
+}
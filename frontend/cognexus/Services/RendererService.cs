@@ -1,12 +1,23 @@
+using System.IO.Compression;
+using Google.Protobuf;
 using Microsoft.JSInterop;
 
 namespace CognexusBlazor.Services;
 
 public class RendererService
 {
+    // The protocol and schema versions this frontend speaks, matching
+    // PROTOCOL_VERSION and SCHEMA_VERSION in the proto crate
+    private const uint ProtocolVersion = 1;
+    private const uint SchemaVersion = 1;
+    // Commands smaller than this aren't worth compressing
+    private const int CompressionThreshold = 64 * 1024;
+
     private readonly IJSRuntime _jsRuntime;
     private IJSObjectReference? _renderer;
     private bool _isInitialized;
+    private uint _lastRequestId;
+    private Cognexus.Commands.Compression _compression = Cognexus.Commands.Compression.None;
     
     public RendererService(IJSRuntime jsRuntime)
     {
@@ -34,40 +45,239 @@ public class RendererService
             width,
             height
         );
+
+        var hello = new Cognexus.Commands.HelloCommand
+        {
+            ProtocolVersion = ProtocolVersion,
+            SchemaVersion = SchemaVersion,
+            Compressions = { Cognexus.Commands.Compression.Zlib }
+        };
+        var ackBytes = await SendCommandAsync("handle_hello_command", hello.ToByteArray());
+        var ack = Cognexus.Events.HelloAckEvent.Parser.ParseFrom(ackBytes);
+        if (!ack.Accepted)
+            throw new InvalidOperationException($"Renderer rejected the protocol: {ack.Message}");
+
+        _compression = (Cognexus.Commands.Compression)ack.Compression;
     
         _isInitialized = true;
     }
 
+    public async Task<byte[]> HandleDescribeProtocolCommandAsync(byte[] bytes)
+    {
+        return await SendCommandAsync("handle_describe_protocol_command", bytes);
+    }
+
     public async Task HandleDrawQuadCommandAsync(byte[] bytes)
     {
-        if(_renderer == null)
-            throw new InvalidOperationException("Renderer is not initialized");
-        
-        await _renderer.InvokeVoidAsync("handle_draw_quad_command", bytes);
+        await SendCommandAsync("handle_draw_quad_command", bytes);
     }
     
+    public async Task HandleDrawNodeCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_draw_node_command", bytes);
+    }
+
+    public async Task HandleDrawEdgeCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_draw_edge_command", bytes);
+    }
+
+    public async Task HandleUpdateDrawableTransformCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_update_drawable_transform_command", bytes);
+    }
+
+    public async Task HandleUpdateDrawableColorCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_update_drawable_color_command", bytes);
+    }
+
+    public async Task HandleUpdateDrawableStateCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_update_drawable_state_command", bytes);
+    }
+
+    public async Task HandleSceneSnapshotCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_scene_snapshot_command", bytes);
+    }
+
+    public async Task HandleSetSelectionCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_set_selection_command", bytes);
+    }
+
+    public async Task HandleRemoveDrawableCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_remove_drawable_command", bytes);
+    }
+
+    public async Task HandleClearSceneCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_clear_scene_command", bytes);
+    }
+
     public async Task HandlePanCameraCommandAsync(byte[] bytes)
     {
-        if (_renderer == null)
-            throw new InvalidOperationException("Renderer not initialized");
-        
-        await _renderer.InvokeVoidAsync("handle_pan_camera_command", bytes);
+        await SendCommandAsync("handle_pan_camera_command", bytes);
     }
 
     public async Task HandleZoomCameraCommandAsync(byte[] bytes)
     {
-        if (_renderer == null)
-            throw new InvalidOperationException("Renderer not initialized");
-        
-        await _renderer.InvokeVoidAsync("handle_zoom_camera_command", bytes);
+        await SendCommandAsync("handle_zoom_camera_command", bytes);
+    }
+
+    public async Task<byte[]> HandleGetCameraStateCommandAsync(byte[] bytes)
+    {
+        return await SendCommandAsync("handle_get_camera_state_command", bytes);
+    }
+
+    public async Task HandleSetCameraCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_set_camera_command", bytes);
+    }
+
+    public async Task HandleAnimateCameraCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_animate_camera_command", bytes);
+    }
+
+    public async Task HandleAnimateDrawableCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_animate_drawable_command", bytes);
     }
 
     public async Task HandleResizeViewportCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_resize_viewport_command", bytes);
+    }
+
+    public async Task HandleFitViewCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_fit_view_command", bytes);
+    }
+
+    public async Task HandleUploadIconCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_upload_icon_command", bytes);
+    }
+
+    public async Task HandleSetThemeCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_set_theme_command", bytes);
+    }
+
+    public async Task<byte[]> HandleGetRenderStatsCommandAsync(byte[] bytes)
+    {
+        return await SendCommandAsync("handle_get_render_stats_command", bytes);
+    }
+
+    public async Task HandleBeginMarqueeCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_begin_marquee_command", bytes);
+    }
+
+    public async Task HandleUpdateMarqueeCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_update_marquee_command", bytes);
+    }
+
+    public async Task<byte[]> HandleEndMarqueeCommandAsync(byte[] bytes)
+    {
+        return await SendCommandAsync("handle_end_marquee_command", bytes);
+    }
+
+    public async Task HandleUpdatePendingEdgeCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_update_pending_edge_command", bytes);
+    }
+
+    public async Task<byte[]> HandleDropPendingEdgeCommandAsync(byte[] bytes)
+    {
+        return await SendCommandAsync("handle_drop_pending_edge_command", bytes);
+    }
+
+    public async Task HandleChangeStackingCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_change_stacking_command", bytes);
+    }
+
+    public async Task HandleSetZIndexCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_set_z_index_command", bytes);
+    }
+
+    public async Task HandlePickCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_pick_command", bytes);
+    }
+
+    public async Task HandlePointerMoveCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_pointer_move_command", bytes);
+    }
+
+    public async Task HandlePointerClickCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_pointer_click_command", bytes);
+    }
+
+    public async Task<byte[]> HandlePollEventsCommandAsync(byte[] bytes)
+    {
+        return await SendCommandAsync("handle_poll_events_command", bytes);
+    }
+
+    public async Task HandleBatchCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_batch_command", bytes);
+    }
+
+    public async Task HandleFramesCommandAsync(byte[] bytes)
+    {
+        await SendCommandAsync("handle_frames_command", bytes);
+    }
+
+    public async Task<byte[]> HandleCaptureFrameCommandAsync(byte[] bytes)
+    {
+        return await SendCommandAsync("handle_capture_frame_command", bytes);
+    }
+
+    // Send a command under a new request id and return the event the renderer
+    // replied with, empty for commands without one
+    private async Task<byte[]> SendCommandAsync(string method, byte[] bytes)
     {
         if (_renderer == null)
             throw new InvalidOperationException("Renderer not initialized");
         
-        await _renderer.InvokeVoidAsync("handle_resize_viewport_command", bytes);
+        var requestId = ++_lastRequestId;
+        var resultBytes = await _renderer.InvokeAsync<byte[]>(method, requestId, Compress(bytes));
+        var result = Cognexus.Events.CommandResult.Parser.ParseFrom(resultBytes);
+        if (result.Status != Cognexus.Events.CommandStatus.Ok)
+            throw new RendererCommandException(requestId, result.Error?.ErrorCode ?? "", result.Error?.Message ?? "");
+
+        return result.Payload.ToByteArray();
+    }
+
+    // Once the hello agrees on a compression, every command starts with a byte
+    // saying whether the rest is compressed with it
+    private byte[] Compress(byte[] bytes)
+    {
+        if (_compression == Cognexus.Commands.Compression.None)
+            return bytes;
+
+        using var output = new MemoryStream();
+        if (bytes.Length < CompressionThreshold)
+        {
+            output.WriteByte((byte)Cognexus.Commands.Compression.None);
+            output.Write(bytes);
+            return output.ToArray();
+        }
+
+        output.WriteByte((byte)_compression);
+        using (var zlib = new ZLibStream(output, CompressionLevel.Fastest, leaveOpen: true))
+            zlib.Write(bytes);
+
+        return output.ToArray();
     }
 
     public async Task RenderAsync()
@@ -7,5 +7,23 @@ export async function createRenderer(canvas, width, height) {
         await init();
         initialized = true;
     }
-    return await Renderer.new(canvas, width, height);
+    const renderer = await Renderer.new(canvas, width, height);
+    startFrameLoop(renderer);
+    return renderer;
+}
+
+// Render on every animation frame that has something new to draw, so camera
+// animations keep moving between commands
+function startFrameLoop(renderer) {
+    const frame = () => {
+        if (renderer.needs_frame()) {
+            try {
+                renderer.render();
+            } catch (error) {
+                console.error('Render failed:', error);
+            }
+        }
+        requestAnimationFrame(frame);
+    };
+    requestAnimationFrame(frame);
 }